@@ -551,6 +551,7 @@ impl_runtime_apis! {
 	}
 
 	#[cfg(feature = "try-runtime")]
+	#[api_version(2)]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
 			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
@@ -570,5 +571,14 @@ impl_runtime_apis! {
 			// have a backtrace here.
 			Executive::try_execute_block(block, state_root_check, signature_check, select).expect("execute-block failed")
 		}
+
+		fn execute_call(
+			call: Vec<u8>,
+			origin: frame_try_runtime::CallOrigin,
+		) -> Result<frame_try_runtime::CallOutcome, sp_runtime::TryRuntimeError> {
+			Executive::try_execute_encoded_call(&call, origin, |call, len| {
+				TransactionPayment::query_call_info(call.clone(), len).partial_fee.into()
+			})
+		}
 	}
 }
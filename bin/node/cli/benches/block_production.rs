@@ -81,6 +81,8 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		rpc_max_response_size: Default::default(),
 		rpc_id_provider: Default::default(),
 		rpc_max_subs_per_conn: Default::default(),
+		rpc_execution_limits: Default::default(),
+		rpc_health: Default::default(),
 		rpc_port: 9944,
 		prometheus_config: None,
 		telemetry_endpoints: None,
@@ -40,6 +40,10 @@ pub struct Cli {
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub storage_monitor: sc_storage_monitor::StorageMonitorParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub block_stats: frame_block_stats::BlockStatsParams,
 }
 
 /// Possible subcommands of the main binary.
@@ -87,6 +91,12 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Compile a runtime ahead of time.
+	PrecompileRuntime(sc_cli::PrecompileRuntimeCmd),
+
+	/// Re-execute a block and record a trace of its execution.
+	TraceBlock(sc_cli::TraceBlockCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
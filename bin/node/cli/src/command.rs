@@ -196,6 +196,22 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		},
+		Some(Subcommand::PrecompileRuntime(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents { client, task_manager, .. } = new_partial(&config)?;
+				let executor =
+					sc_service::new_wasm_executor::<sp_io::SubstrateHostFunctions>(&config);
+				Ok((cmd.run(client, executor), task_manager))
+			})
+		},
+		Some(Subcommand::TraceBlock(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents { client, task_manager, .. } = new_partial(&config)?;
+				Ok((cmd.run(client), task_manager))
+			})
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
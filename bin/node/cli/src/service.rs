@@ -233,6 +233,10 @@ pub fn new_partial(
 			registry: config.prometheus_registry(),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool.clone()),
+			concurrent_verification: Some((
+				std::thread::available_parallelism().map_or(1, |n| n.get()),
+				Box::new(task_manager.spawn_handle()),
+			)),
 		})?;
 
 	let import_setup = (block_import, grandpa_link, babe_link);
@@ -624,8 +628,9 @@ pub fn new_full_base(
 /// Builds a new service for a full client.
 pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceError> {
 	let database_source = config.database.clone();
-	let task_manager = new_full_base(config, cli.no_hardware_benchmarks, |_, _| ())
-		.map(|NewFullBase { task_manager, .. }| task_manager)?;
+	let prometheus_registry = config.prometheus_registry().cloned();
+	let NewFullBase { task_manager, client, .. } =
+		new_full_base(config, cli.no_hardware_benchmarks, |_, _| ())?;
 
 	sc_storage_monitor::StorageMonitorService::try_spawn(
 		cli.storage_monitor,
@@ -634,6 +639,13 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 	)
 	.map_err(|e| ServiceError::Application(e.into()))?;
 
+	frame_block_stats::spawn(
+		cli.block_stats,
+		client,
+		prometheus_registry.as_ref(),
+		&task_manager.spawn_handle(),
+	)?;
+
 	Ok(task_manager)
 }
 
@@ -857,3 +857,54 @@ fn should_import_block_with_test_client() {
 
 	futures::executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
 }
+
+#[test]
+fn block_stats_are_computed_by_the_runtime() {
+	use frame_block_stats_runtime_api::BlockStats;
+
+	let time = 42 * 1000;
+	let block = construct_block(
+		&mut new_test_ext(compact_code_unwrap()),
+		1,
+		GENESIS_HASH.into(),
+		vec![
+			CheckedExtrinsic {
+				signed: None,
+				function: RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: time }),
+			},
+			CheckedExtrinsic {
+				signed: Some((alice(), signed_extra(0, 0))),
+				function: RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+					dest: bob().into(),
+					value: 69 * DOLLARS,
+				}),
+			},
+			// Alice can't afford this one anymore, but still pays its fee.
+			CheckedExtrinsic {
+				signed: Some((alice(), signed_extra(1, 0))),
+				function: RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+					dest: bob().into(),
+					value: 69 * DOLLARS,
+				}),
+			},
+		],
+		(time / SLOT_DURATION).into(),
+	);
+
+	let mut t = new_test_ext(compact_code_unwrap());
+	executor_call(&mut t, "Core_execute_block", &block.0, true).0.unwrap();
+
+	let r = executor_call(&mut t, "BlockStatsApi_block_stats", &block.0, true).0.unwrap();
+	let stats = BlockStats::decode(&mut &r[..]).unwrap();
+
+	assert_eq!(stats.length, block.0.len() as u64);
+	assert_eq!(
+		stats.extrinsics,
+		[(b"Timestamp".to_vec(), 1), (b"Balances".to_vec(), 2)].into_iter().collect(),
+	);
+	assert_eq!(stats.failed_dispatches, [(b"Balances".to_vec(), 1)].into_iter().collect());
+	assert!(stats.fees > 0);
+	assert_eq!(stats.tips, 0);
+	assert!(stats.weight.get(DispatchClass::Normal).ref_time() > 0);
+	assert!(stats.weight.get(DispatchClass::Mandatory).ref_time() > 0);
+}
@@ -29,7 +29,7 @@ use frame_election_provider_support::{
 };
 use frame_support::{
 	construct_runtime,
-	dispatch::DispatchClass,
+	dispatch::{DispatchClass, DispatchResultWithPostInfo, PostDispatchInfo},
 	instances::{Instance1, Instance2},
 	ord_parameter_types,
 	pallet_prelude::Get,
@@ -79,8 +79,8 @@ use sp_runtime::{
 		OpaqueKeys, SaturatedConversion, StaticLookup,
 	},
 	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, FixedPointNumber, FixedU128, Perbill, Percent, Permill, Perquintill,
-	RuntimeDebug,
+	ApplyExtrinsicResult, ApplyExtrinsicResultWithInfo, FixedPointNumber, FixedU128, Perbill,
+	Percent, Permill, Perquintill, RuntimeDebug,
 };
 use sp_std::prelude::*;
 #[cfg(any(feature = "std", test))]
@@ -254,6 +254,46 @@ impl pallet_tx_pause::Config for Runtime {
 	type WeightInfo = pallet_tx_pause::weights::SubstrateWeight<Runtime>;
 }
 
+/// Calls that are still applied while multi-block migrations are ongoing.
+pub struct MbmWhitelistedCalls;
+/// Whitelist the governance calls, so that a stuck migration can be fixed.
+impl Contains<RuntimeCall> for MbmWhitelistedCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::Sudo(_) |
+			RuntimeCall::Democracy(_) |
+			RuntimeCall::Council(_) |
+			RuntimeCall::TechnicalCommittee(_) |
+			RuntimeCall::MultiBlockMigrations(_) => true,
+			_ => false,
+		}
+	}
+}
+
+parameter_types! {
+	pub MbmServiceWeight: Weight = Perbill::from_percent(80) * RuntimeBlockWeights::get().max_block;
+}
+
+impl pallet_migrations::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	// Add the multi-block migrations of the next upgrade here.
+	type Migrations = ();
+	type CursorMaxLen = ConstU32<65_536>;
+	type IdentifierMaxLen = ConstU32<256>;
+	type MigrationStatusHandler = ();
+	type FailedMigrationHandler = frame_support::migrations::FreezeChainOnFailedMigration;
+	type MaxServiceWeight = MbmServiceWeight;
+}
+
+impl pallet_tasks::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeTask = RuntimeTask;
+	type MaxTaskScan = ConstU32<64>;
+	type WeightInfo = pallet_tasks::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
 parameter_types! {
 	pub const EnterDuration: BlockNumber = 4 * HOURS;
 	pub const EnterDepositAmount: Balance = 2_000_000 * DOLLARS;
@@ -2085,6 +2125,8 @@ construct_runtime!(
 		SafeMode: pallet_safe_mode,
 		Statement: pallet_statement,
 		Broker: pallet_broker,
+		MultiBlockMigrations: pallet_migrations,
+		Tasks: pallet_tasks,
 	}
 );
 
@@ -2129,6 +2171,8 @@ pub type Executive = frame_executive::Executive<
 	Runtime,
 	AllPalletsWithSystem,
 	Migrations,
+	MultiBlockMigrations,
+	MbmWhitelistedCalls,
 >;
 
 // All migrations executed on runtime upgrade as a nested tuple of types implementing
@@ -2203,6 +2247,7 @@ mod benches {
 		[pallet_staking, Staking]
 		[pallet_state_trie_migration, StateTrieMigration]
 		[pallet_sudo, Sudo]
+		[pallet_tasks, Tasks]
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_timestamp, Timestamp]
 		[pallet_tips, Tips]
@@ -2409,6 +2454,41 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl frame_system_rpc_runtime_api::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller>
+		for Runtime
+	{
+		fn dry_run_extrinsic(
+			extrinsic: <Block as BlockT>::Extrinsic,
+		) -> frame_system_rpc_runtime_api::DryRunEffects<
+			ApplyExtrinsicResultWithInfo<PostDispatchInfo>,
+			RuntimeEvent,
+		> {
+			Executive::dry_run_extrinsic::<()>(extrinsic)
+		}
+
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: RuntimeCall,
+		) -> frame_system_rpc_runtime_api::DryRunEffects<DispatchResultWithPostInfo, RuntimeEvent> {
+			Executive::dry_run_call::<()>(origin.into(), call)
+		}
+	}
+
+	impl frame_block_stats_runtime_api::BlockStatsApi<Block> for Runtime {
+		fn block_stats(block: Block) -> frame_block_stats_runtime_api::BlockStats {
+			frame_block_stats_runtime_api::block_stats::<Runtime, _>(&block)
+		}
+	}
+
+	impl frame_support::view_functions::runtime_api::RuntimeViewFunction<Block> for Runtime {
+		fn execute_view_function(
+			id: frame_support::view_functions::ViewFunctionId,
+			input: Vec<u8>,
+		) -> Result<Vec<u8>, frame_support::view_functions::ViewFunctionDispatchError> {
+			Runtime::execute_view_function(id, input)
+		}
+	}
+
 	impl assets_api::AssetsApi<
 		Block,
 		AccountId,
@@ -2657,6 +2737,7 @@ impl_runtime_apis! {
 	}
 
 	#[cfg(feature = "try-runtime")]
+	#[api_version(2)]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
 			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
@@ -2676,6 +2757,15 @@ impl_runtime_apis! {
 			// have a backtrace here.
 			Executive::try_execute_block(block, state_root_check, signature_check, select).unwrap()
 		}
+
+		fn execute_call(
+			call: Vec<u8>,
+			origin: frame_try_runtime::CallOrigin,
+		) -> Result<frame_try_runtime::CallOutcome, sp_runtime::TryRuntimeError> {
+			Executive::try_execute_encoded_call(&call, origin, |call, len| {
+				TransactionPayment::query_call_info(call.clone(), len).partial_fee.into()
+			})
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -17,13 +17,29 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use chain_spec_builder::{
-	generate_authority_keys_and_store, generate_chain_spec, print_seeds, ChainSpecBuilder,
+	generate_authority_keys_and_store, generate_chain_spec, print_seeds, raw_genesis,
+	runtime_genesis::{self, GenesisConfigBuilder},
+	ChainSpecBuilder,
 };
 use clap::Parser;
 use node_cli::chain_spec;
 use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
 use sp_core::{crypto::Ss58Codec, sr25519};
-use std::fs;
+use std::{fs, path::Path};
+
+fn read_runtime(runtime_wasm_path: &Path) -> Result<GenesisConfigBuilder, String> {
+	let code = fs::read(runtime_wasm_path).map_err(|err| {
+		format!("Failed to read runtime at `{}`: {}", runtime_wasm_path.display(), err)
+	})?;
+	Ok(GenesisConfigBuilder::new(code))
+}
+
+fn read_chain_spec(chain_spec_path: &Path) -> Result<raw_genesis::DecodedGenesis, String> {
+	let json = fs::read(chain_spec_path).map_err(|err| {
+		format!("Failed to read chain spec at `{}`: {}", chain_spec_path.display(), err)
+	})?;
+	raw_genesis::decode_chain_spec(&json)
+}
 
 fn main() -> Result<(), String> {
 	#[cfg(build_type = "debug")]
@@ -34,7 +50,7 @@ fn main() -> Result<(), String> {
 	);
 
 	let builder = ChainSpecBuilder::parse();
-	let chain_spec_path = builder.chain_spec_path().to_path_buf();
+	let chain_spec_path = builder.chain_spec_path().map(Path::to_path_buf);
 
 	let (authority_seeds, nominator_accounts, endowed_accounts, sudo_account) = match builder {
 		ChainSpecBuilder::Generate { authorities, nominators, endowed, keystore_path, .. } => {
@@ -80,10 +96,71 @@ fn main() -> Result<(), String> {
 			sudo_account,
 			..
 		} => (authority_seeds, nominator_accounts, endowed_accounts, sudo_account),
+		ChainSpecBuilder::Default { runtime_wasm_path } => {
+			let config = read_runtime(&runtime_wasm_path)?.default_config()?;
+			let json = serde_json::to_string_pretty(&config).map_err(|err| err.to_string())?;
+			println!("{}", json);
+			return Ok(())
+		},
+		ChainSpecBuilder::Create {
+			runtime_wasm_path,
+			patch_path,
+			chain_name,
+			chain_id,
+			chain_type,
+			raw,
+			chain_spec_path,
+		} => {
+			let builder = read_runtime(&runtime_wasm_path)?;
+			let patch = match patch_path {
+				Some(patch_path) => {
+					let patch = fs::read(&patch_path).map_err(|err| {
+						format!("Failed to read patch at `{}`: {}", patch_path.display(), err)
+					})?;
+					serde_json::from_slice(&patch)
+						.map_err(|err| format!("Failed to parse patch: {}", err))?
+				},
+				None => serde_json::Value::Object(Default::default()),
+			};
+			let config = builder.patched_config(patch)?;
+			let json = runtime_genesis::create_chain_spec(
+				&builder,
+				config,
+				&chain_name,
+				&chain_id,
+				chain_type,
+				raw,
+			)?;
+			return fs::write(chain_spec_path, json).map_err(|err| err.to_string())
+		},
+		ChainSpecBuilder::Verify { runtime_wasm_path, chain_spec_path } => {
+			let builder = read_runtime(&runtime_wasm_path)?;
+			let json = fs::read(&chain_spec_path).map_err(|err| {
+				format!("Failed to read chain spec at `{}`: {}", chain_spec_path.display(), err)
+			})?;
+			println!("{}", runtime_genesis::verify_chain_spec(&builder, &json)?);
+			return Ok(())
+		},
+		ChainSpecBuilder::Decode { chain_spec_path } => {
+			for entry in read_chain_spec(&chain_spec_path)?.entries() {
+				println!("{}", entry);
+			}
+			return Ok(())
+		},
+		ChainSpecBuilder::Diff { old_chain_spec_path, new_chain_spec_path } => {
+			let old = read_chain_spec(&old_chain_spec_path)?;
+			let new = read_chain_spec(&new_chain_spec_path)?;
+			for change in raw_genesis::diff(&old, &new) {
+				println!("{}", change);
+			}
+			return Ok(())
+		},
 	};
 
 	let json =
 		generate_chain_spec(authority_seeds, nominator_accounts, endowed_accounts, sudo_account)?;
 
+	let chain_spec_path =
+		chain_spec_path.expect("`New` and `Generate` have a chain spec path; qed");
 	fs::write(chain_spec_path, json).map_err(|err| err.to_string())
 }
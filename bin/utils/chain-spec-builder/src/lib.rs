@@ -24,6 +24,10 @@
 //! substrate-based nodes. This particular binary is capable of building a more sophisticated chain
 //! specification that can be used with the substrate-node, ie. [`node-cli`].
 //!
+//! The `default`, `create` and `verify` commands work with any runtime wasm blob that implements
+//! the `GenesisBuilder` runtime API, see [`runtime_genesis`]. The `decode` and `diff` commands
+//! make raw chain specs readable through the metadata of their runtime, see [`raw_genesis`].
+//!
 //! See [`ChainSpecBuilder`] for a list of available commands.
 //!
 //! [`sc-chain-spec`]: ../sc_chain_spec/index.html
@@ -35,10 +39,14 @@ use ansi_term::Style;
 use clap::Parser;
 
 use node_cli::chain_spec::{self, AccountId};
+use sc_chain_spec::ChainType;
 use sc_keystore::LocalKeystore;
 use sp_core::crypto::{ByteArray, Ss58Codec};
 use sp_keystore::KeystorePtr;
 
+pub mod raw_genesis;
+pub mod runtime_genesis;
+
 /// A utility to easily create a testnet chain spec definition with a given set
 /// of authorities and endowed accounts and/or generate random accounts.
 #[derive(Parser)]
@@ -89,18 +97,84 @@ pub enum ChainSpecBuilder {
 		#[arg(long, short)]
 		keystore_path: Option<PathBuf>,
 	},
+	/// Print the default genesis config of the given runtime.
+	Default {
+		/// The path to the runtime wasm blob.
+		#[arg(long, short)]
+		runtime_wasm_path: PathBuf,
+	},
+	/// Create a chain spec for the given runtime, with its default genesis config patched by the
+	/// given JSON.
+	Create {
+		/// The path to the runtime wasm blob.
+		#[arg(long, short)]
+		runtime_wasm_path: PathBuf,
+		/// The path to a JSON merge patch (RFC 7386) applied to the default genesis config.
+		#[arg(long, short)]
+		patch_path: Option<PathBuf>,
+		/// The name of the chain.
+		#[arg(long, default_value = "Custom")]
+		chain_name: String,
+		/// The id of the chain.
+		#[arg(long, default_value = "custom")]
+		chain_id: String,
+		/// The type of the chain: `development`, `local`, `live` or any custom type.
+		#[arg(long, default_value = "live", value_parser = parse_chain_type)]
+		chain_type: ChainType,
+		/// Emit the genesis storage instead of the genesis config.
+		#[arg(long)]
+		raw: bool,
+		/// The path where the chain spec should be saved.
+		#[arg(long, short, default_value = "./chain_spec.json")]
+		chain_spec_path: PathBuf,
+	},
+	/// Verify that the given chain spec can be used with the given runtime.
+	Verify {
+		/// The path to the runtime wasm blob.
+		#[arg(long, short)]
+		runtime_wasm_path: PathBuf,
+		/// The path of the chain spec to verify.
+		#[arg(long, short, default_value = "./chain_spec.json")]
+		chain_spec_path: PathBuf,
+	},
+	/// Decode the storage of a raw chain spec using the metadata of its runtime.
+	Decode {
+		/// The path of the raw chain spec to decode.
+		#[arg(long, short, default_value = "./chain_spec.json")]
+		chain_spec_path: PathBuf,
+	},
+	/// Show the decoded storage changes between two raw chain specs.
+	Diff {
+		/// The path of the old raw chain spec.
+		old_chain_spec_path: PathBuf,
+		/// The path of the new raw chain spec.
+		new_chain_spec_path: PathBuf,
+	},
 }
 
 impl ChainSpecBuilder {
-	/// Returns the path where the chain spec should be saved.
-	pub fn chain_spec_path(&self) -> &Path {
+	/// Returns the path of the chain spec, if the command works with one.
+	pub fn chain_spec_path(&self) -> Option<&Path> {
 		match self {
-			ChainSpecBuilder::New { chain_spec_path, .. } => chain_spec_path.as_path(),
-			ChainSpecBuilder::Generate { chain_spec_path, .. } => chain_spec_path.as_path(),
+			ChainSpecBuilder::New { chain_spec_path, .. } |
+			ChainSpecBuilder::Generate { chain_spec_path, .. } |
+			ChainSpecBuilder::Create { chain_spec_path, .. } |
+			ChainSpecBuilder::Verify { chain_spec_path, .. } |
+			ChainSpecBuilder::Decode { chain_spec_path } => Some(chain_spec_path.as_path()),
+			ChainSpecBuilder::Default { .. } | ChainSpecBuilder::Diff { .. } => None,
 		}
 	}
 }
 
+fn parse_chain_type(chain_type: &str) -> Result<ChainType, String> {
+	Ok(match chain_type {
+		"development" => ChainType::Development,
+		"local" => ChainType::Local,
+		"live" => ChainType::Live,
+		custom => ChainType::Custom(custom.into()),
+	})
+}
+
 fn genesis_constructor(
 	authority_seeds: &[String],
 	nominator_accounts: &[AccountId],
@@ -0,0 +1,211 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Decoding and diffing of raw chain specs, using the metadata of the runtime in their `:code`.
+
+use crate::runtime_genesis::call_runtime;
+use frame_storage_decoder::{diff_values, StorageDecoder};
+use sc_chain_spec::RawGenesis;
+use serde_json::Value;
+use sp_core::{
+	bytes::to_hex,
+	storage::{well_known_keys, StorageKey},
+};
+use sp_state_machine::BasicExternalities;
+use std::{collections::BTreeMap, fmt};
+
+/// Decode the top-level storage entry with `key` and `value` through the metadata of `decoder`.
+///
+/// Entries that can not be decoded are returned with their key and value as hex.
+fn decode_entry(decoder: &StorageDecoder, key: &[u8], value: &[u8]) -> DecodedEntry {
+	if key == well_known_keys::CODE {
+		let value = format!("<{} bytes of runtime code>", value.len());
+		return DecodedEntry { key: ":code".into(), value: value.into(), known: true }
+	}
+
+	let decoded = decoder.decode_key(key).and_then(|decoded| {
+		let value = decoder.decode_value(&decoded, value)?;
+		Ok((decoded.name(), value))
+	});
+	match decoded {
+		Ok((key, value)) => DecodedEntry { key, value, known: true },
+		Err(_) => DecodedEntry::unknown(to_hex(key, false), value),
+	}
+}
+
+/// A decoded storage entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedEntry {
+	/// The key, e.g. `System.Account[5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY]`.
+	pub key: String,
+	/// The value, or the raw value as hex if it could not be decoded.
+	pub value: Value,
+	/// Whether the entry was decoded through the runtime metadata.
+	pub known: bool,
+}
+
+impl DecodedEntry {
+	fn unknown(key: String, value: &[u8]) -> Self {
+		Self { key, value: to_hex(value, false).into(), known: false }
+	}
+}
+
+impl fmt::Display for DecodedEntry {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.known {
+			write!(f, "{}: {}", self.key, self.value)
+		} else {
+			write!(f, "{} (unknown): {}", self.key, self.value)
+		}
+	}
+}
+
+/// The decoded raw genesis of a chain spec.
+pub struct DecodedGenesis {
+	/// The top-level storage, keyed by the raw storage key.
+	pub top: BTreeMap<Vec<u8>, DecodedEntry>,
+	/// The default child tries, keyed by the raw storage key of the trie and the entry.
+	///
+	/// Child tries are not described by the metadata, so these entries are never decoded.
+	pub children_default: BTreeMap<(Vec<u8>, Vec<u8>), DecodedEntry>,
+}
+
+impl DecodedGenesis {
+	/// All entries, top-level storage first.
+	pub fn entries(&self) -> impl Iterator<Item = &DecodedEntry> {
+		self.top.values().chain(self.children_default.values())
+	}
+}
+
+/// Decode the raw genesis of the chain spec `json`, using the metadata of its runtime.
+pub fn decode_chain_spec(json: &[u8]) -> Result<DecodedGenesis, String> {
+	let spec: Value =
+		serde_json::from_slice(json).map_err(|e| format!("Error parsing spec file: {}", e))?;
+	let raw = spec
+		.pointer("/genesis/raw")
+		.cloned()
+		.ok_or("The chain spec has no raw genesis, build it with `--raw` first")?;
+	let raw: RawGenesis =
+		serde_json::from_value(raw).map_err(|e| format!("Invalid raw genesis: {}", e))?;
+
+	let code = raw
+		.top
+		.get(&StorageKey(well_known_keys::CODE.to_vec()))
+		.ok_or("The raw genesis has no runtime code")?;
+	let mut ext = BasicExternalities::new_empty();
+	let metadata = call_runtime(&code.0, &mut ext, "Metadata_metadata", &[])?;
+	let decoder = StorageDecoder::new(&metadata)?;
+
+	let top = raw
+		.top
+		.iter()
+		.map(|(key, value)| (key.0.clone(), decode_entry(&decoder, &key.0, &value.0)))
+		.collect();
+
+	let children_default = raw
+		.children_default
+		.iter()
+		.flat_map(|(child, storage)| {
+			storage.iter().map(move |(key, value)| {
+				let name = format!("{}[{}]", to_hex(&child.0, false), to_hex(&key.0, false));
+				((child.0.clone(), key.0.clone()), DecodedEntry::unknown(name, &value.0))
+			})
+		})
+		.collect();
+
+	Ok(DecodedGenesis { top, children_default })
+}
+
+/// Compare the storage of two decoded chain specs.
+///
+/// Returns one line per change: `+` for added entries, `-` for removed entries and `~` for each
+/// changed field of an entry, e.g. `~ System.Account[..]: data.free 10 → 20`.
+pub fn diff(old: &DecodedGenesis, new: &DecodedGenesis) -> Vec<String> {
+	let mut changes = Vec::new();
+	diff_storage(&old.top, &new.top, &mut changes);
+	diff_storage(&old.children_default, &new.children_default, &mut changes);
+	changes
+}
+
+fn diff_storage<K: Ord>(
+	old: &BTreeMap<K, DecodedEntry>,
+	new: &BTreeMap<K, DecodedEntry>,
+	changes: &mut Vec<String>,
+) {
+	for (key, old_entry) in old {
+		match new.get(key) {
+			None => changes.push(format!("- {}", old_entry)),
+			Some(new_entry) if new_entry.value != old_entry.value => {
+				let mut fields = Vec::new();
+				diff_values("", &old_entry.value, &new_entry.value, &mut fields);
+				let unknown = if new_entry.known { "" } else { " (unknown)" };
+				changes.extend(
+					fields
+						.into_iter()
+						.map(|field| format!("~ {}{}: {}", new_entry.key, unknown, field)),
+				);
+			},
+			Some(_) => {},
+		}
+	}
+
+	changes.extend(
+		new.iter()
+			.filter(|(key, _)| !old.contains_key(*key))
+			.map(|(_, entry)| format!("+ {}", entry)),
+	);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn diff_lists_changed_fields() {
+		let entry =
+			|value| DecodedEntry { key: "System.Account[alice]".into(), value, known: true };
+		let old = DecodedGenesis {
+			top: [
+				(vec![1], entry(json!({ "nonce": 0, "data": { "free": 10, "reserved": 0 } }))),
+				(vec![2], entry(json!(1))),
+			]
+			.into_iter()
+			.collect(),
+			children_default: Default::default(),
+		};
+		let new = DecodedGenesis {
+			top: [
+				(vec![1], entry(json!({ "nonce": 0, "data": { "free": 20, "reserved": 0 } }))),
+				(vec![3], DecodedEntry { key: "0x03".into(), value: json!("0x00"), known: false }),
+			]
+			.into_iter()
+			.collect(),
+			children_default: Default::default(),
+		};
+
+		assert_eq!(
+			diff(&old, &new),
+			vec![
+				"~ System.Account[alice]: data.free 10 → 20".to_string(),
+				"- System.Account[alice]: 1".to_string(),
+				"+ 0x03 (unknown): \"0x00\"".to_string(),
+			]
+		);
+	}
+}
@@ -0,0 +1,243 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Chain specs for any runtime, built through its `GenesisBuilder` runtime API.
+
+use codec::{Decode, Encode};
+use sc_chain_spec::ChainType;
+use sc_executor::WasmExecutor;
+use sc_executor_common::runtime_blob::RuntimeBlob;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sp_core::{
+	storage::{well_known_keys, Storage},
+	traits::Externalities,
+};
+use sp_runtime::BuildStorage;
+use sp_state_machine::BasicExternalities;
+use std::sync::Arc;
+
+/// Calls the `GenesisBuilder` API of a runtime wasm blob.
+#[derive(Clone)]
+pub struct GenesisConfigBuilder {
+	code: Arc<Vec<u8>>,
+}
+
+impl GenesisConfigBuilder {
+	/// Create a new instance calling the runtime `code`.
+	pub fn new(code: Vec<u8>) -> Self {
+		Self { code: Arc::new(code) }
+	}
+
+	/// The code of the runtime.
+	pub fn code(&self) -> &[u8] {
+		&self.code
+	}
+
+	fn call(
+		&self,
+		ext: &mut dyn Externalities,
+		method: &str,
+		data: &[u8],
+	) -> Result<Vec<u8>, String> {
+		call_runtime(&self.code, ext, method, data)
+	}
+
+	/// Returns the default genesis config of the runtime.
+	pub fn default_config(&self) -> Result<Value, String> {
+		let mut ext = BasicExternalities::new_empty();
+		let result = self.call(&mut ext, "GenesisBuilder_create_default_config", &[])?;
+		let json = Vec::<u8>::decode(&mut &result[..])
+			.map_err(|e| format!("Invalid default genesis config: {}", e))?;
+		serde_json::from_slice(&json).map_err(|e| format!("Invalid default genesis config: {}", e))
+	}
+
+	/// Returns the default genesis config of the runtime with `patch` applied, see [`merge`].
+	///
+	/// If the config has a `system.code` entry, it is set to the code of the runtime.
+	pub fn patched_config(&self, patch: Value) -> Result<Value, String> {
+		let mut config = self.default_config()?;
+		merge(&mut config, patch);
+
+		if let Some(code) = config.pointer_mut("/system/code") {
+			*code = Value::String(sp_core::bytes::to_hex(&self.code, false));
+		}
+
+		Ok(config)
+	}
+
+	/// Build the genesis storage for the full genesis `config`.
+	pub fn build_storage(&self, config: &Value) -> Result<Storage, String> {
+		let mut ext = BasicExternalities::new_empty();
+		let json = serde_json::to_vec(config).map_err(|e| e.to_string())?;
+		let result = self.call(&mut ext, "GenesisBuilder_build_config", &json.encode())?;
+		sp_genesis_builder::Result::decode(&mut &result[..])
+			.map_err(|e| format!("Invalid result of building the genesis config: {}", e))?
+			.map_err(|e| format!("Invalid genesis config: {}", e))?;
+
+		let mut storage = ext.into_storages();
+		storage.top.insert(well_known_keys::CODE.to_vec(), self.code.to_vec());
+		Ok(storage)
+	}
+}
+
+/// Call `method` of the runtime `code` with `data`.
+pub(crate) fn call_runtime(
+	code: &[u8],
+	ext: &mut dyn Externalities,
+	method: &str,
+	data: &[u8],
+) -> Result<Vec<u8>, String> {
+	let blob = RuntimeBlob::uncompress_if_needed(code)
+		.map_err(|e| format!("Invalid runtime code: {}", e))?;
+	WasmExecutor::<sp_io::SubstrateHostFunctions>::builder()
+		.build()
+		.uncached_call(blob, ext, true, method, data)
+		.map_err(|e| format!("Calling `{}` failed: {}", method, e))
+}
+
+/// Apply the JSON merge `patch` to `config`, as described in RFC 7386.
+///
+/// Objects are merged recursively, `null` removes an entry and any other value replaces the
+/// existing one.
+pub fn merge(config: &mut Value, patch: Value) {
+	match patch {
+		Value::Object(patch) => {
+			if !config.is_object() {
+				*config = Value::Object(Default::default());
+			}
+			let config = config.as_object_mut().expect("`config` was made an object above; qed");
+			for (key, value) in patch {
+				if value.is_null() {
+					config.remove(&key);
+				} else {
+					merge(config.entry(key).or_insert(Value::Null), value);
+				}
+			}
+		},
+		patch => *config = patch,
+	}
+}
+
+/// A genesis config in JSON, turned into storage by the runtime.
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct RuntimeGenesisConfig {
+	config: Value,
+	#[serde(skip)]
+	builder: Option<GenesisConfigBuilder>,
+}
+
+impl BuildStorage for RuntimeGenesisConfig {
+	fn assimilate_storage(&self, storage: &mut Storage) -> Result<(), String> {
+		let builder = self.builder.as_ref().ok_or("No runtime to build the genesis config")?;
+		let built = builder.build_storage(&self.config)?;
+		storage.top.extend(built.top);
+		storage.children_default.extend(built.children_default);
+		Ok(())
+	}
+}
+
+/// Create a chain spec for the runtime of `builder` with the genesis `config`.
+///
+/// Returns the chain spec as JSON, with the genesis storage if `raw` is set.
+pub fn create_chain_spec(
+	builder: &GenesisConfigBuilder,
+	config: Value,
+	name: &str,
+	id: &str,
+	chain_type: ChainType,
+	raw: bool,
+) -> Result<String, String> {
+	let builder = builder.clone();
+	let chain_spec = sc_chain_spec::GenericChainSpec::<RuntimeGenesisConfig>::from_genesis(
+		name,
+		id,
+		chain_type,
+		move || RuntimeGenesisConfig { config: config.clone(), builder: Some(builder.clone()) },
+		vec![],
+		None,
+		None,
+		None,
+		None,
+		Default::default(),
+	);
+
+	chain_spec.as_json(raw)
+}
+
+/// Verify the chain spec `json` against the runtime of `builder`.
+///
+/// The genesis config of a plain chain spec has to be accepted by the runtime. The code of a raw
+/// chain spec has to be the code of the runtime. Returns a description of the spec on success.
+pub fn verify_chain_spec(builder: &GenesisConfigBuilder, json: &[u8]) -> Result<String, String> {
+	let spec: Value =
+		serde_json::from_slice(json).map_err(|e| format!("Error parsing spec file: {}", e))?;
+	let genesis = spec.get("genesis").ok_or("The chain spec has no genesis")?;
+
+	if let Some(config) = genesis.get("runtime") {
+		let storage = builder.build_storage(config)?;
+		Ok(format!("Plain genesis config builds {} storage entries", storage.top.len()))
+	} else if let Some(raw) = genesis.get("raw") {
+		let code_key = sp_core::bytes::to_hex(well_known_keys::CODE, false);
+		let code = raw
+			.pointer(&format!("/top/{}", code_key))
+			.and_then(Value::as_str)
+			.ok_or("The raw genesis has no runtime code")?;
+		let code =
+			sp_core::bytes::from_hex(code).map_err(|e| format!("Invalid runtime code: {}", e))?;
+		if code != builder.code() {
+			return Err("The runtime code of the raw genesis differs from the given runtime".into())
+		}
+		Ok("Raw genesis contains the given runtime".into())
+	} else {
+		Err("Unsupported genesis format".into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn merge_applies_patch() {
+		let mut config = json!({
+			"balances": { "balances": [["alice", 1]] },
+			"sudo": { "key": "alice" },
+			"system": { "code": "0x" },
+		});
+		merge(
+			&mut config,
+			json!({
+				"balances": { "balances": [["bob", 2]] },
+				"sudo": null,
+				"staking": { "validatorCount": 3 },
+			}),
+		);
+
+		assert_eq!(
+			config,
+			json!({
+				"balances": { "balances": [["bob", 2]] },
+				"staking": { "validatorCount": 3 },
+				"system": { "code": "0x" },
+			})
+		);
+	}
+}
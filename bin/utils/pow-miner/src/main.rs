@@ -0,0 +1,138 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reference CPU miner.
+//!
+//! Mines on the work served by a node through `pow_getWork` with the SHA3 proof of work of
+//! `sc_consensus_pow::sha3_pow`, and submits the seals it finds through `pow_submitWork`.
+
+use clap::Parser;
+use codec::Encode;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use sc_consensus_pow::{
+	rpc::{PowApiClient, Work},
+	sha3_pow::{self, Sha3Seal},
+};
+use sp_core::{H256, U256};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long to wait before asking the node for work again, if it has none.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Parser)]
+#[command(rename_all = "kebab-case", version, about)]
+struct Cli {
+	/// HTTP RPC endpoint of the node.
+	#[arg(long, value_name = "URL", default_value = "http://127.0.0.1:9944")]
+	url: String,
+
+	/// Number of mining threads.
+	#[arg(long, default_value_t = 1)]
+	threads: u64,
+
+	/// Number of nonces each thread tries before checking for new work.
+	#[arg(long, default_value_t = 100_000)]
+	batch_size: u64,
+}
+
+/// Try `threads * batch_size` nonces starting at `start` on `work`.
+async fn mine_batch(
+	work: &Work<H256, U256>,
+	start: u64,
+	threads: u64,
+	batch_size: u64,
+) -> Option<Sha3Seal> {
+	let tasks = (0..threads).map(|thread| {
+		let (pre_hash, difficulty) = (work.pre_hash, work.difficulty);
+		let first = start.wrapping_add(thread.wrapping_mul(batch_size));
+		tokio::task::spawn_blocking(move || {
+			sha3_pow::mine(
+				pre_hash.as_bytes(),
+				difficulty,
+				(0..batch_size).map(|i| first.wrapping_add(i)),
+			)
+		})
+	});
+
+	for task in tasks.collect::<Vec<_>>() {
+		if let Ok(Some(seal)) = task.await {
+			return Some(seal)
+		}
+	}
+	None
+}
+
+async fn submit(client: &HttpClient, work: &Work<H256, U256>, seal: Sha3Seal) {
+	let nonce = seal.nonce;
+	match PowApiClient::<H256, U256>::submit_work(client, work.pre_hash, seal.encode().into()).await
+	{
+		Ok(_) => log::info!("Found seal with nonce {} on top of {}", nonce, work.best_hash),
+		Err(e) => log::warn!("Seal with nonce {} was rejected: {}", nonce, e),
+	}
+}
+
+#[tokio::main]
+async fn main() -> Result<(), String> {
+	sp_tracing::try_init_simple();
+
+	let cli = Cli::parse();
+	if cli.threads == 0 || cli.batch_size == 0 {
+		return Err("The number of threads and the batch size must not be zero".into())
+	}
+
+	let client = HttpClientBuilder::default()
+		.build(&cli.url)
+		.map_err(|e| format!("Failed to connect to {}: {}", cli.url, e))?;
+
+	// Start at a different nonce every run, so that several miners don't repeat the same work.
+	let mut nonce = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_nanos() as u64)
+		.unwrap_or_default();
+	let mut version = None;
+
+	log::info!("Mining with {} threads on {}", cli.threads, cli.url);
+
+	loop {
+		let work = match PowApiClient::<H256, U256>::get_work(&client).await {
+			Ok(Some(work)) => work,
+			Ok(None) => {
+				log::debug!("No work available");
+				tokio::time::sleep(RETRY_DELAY).await;
+				continue
+			},
+			Err(e) => {
+				log::warn!("Failed to get work: {}", e);
+				tokio::time::sleep(RETRY_DELAY).await;
+				continue
+			},
+		};
+
+		if version != Some(work.version) {
+			log::info!("Mining on top of {} with difficulty {}", work.best_hash, work.difficulty);
+			version = Some(work.version);
+		}
+
+		let seal = mine_batch(&work, nonce, cli.threads, cli.batch_size).await;
+		nonce = nonce.wrapping_add(cli.threads.wrapping_mul(cli.batch_size));
+
+		if let Some(seal) = seal {
+			submit(&client, &work, seal).await;
+		}
+	}
+}
@@ -0,0 +1,84 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reference remote signer.
+//!
+//! Serves the keys of a local keystore directory to nodes started with
+//! `--keystore-uri <URI> --keystore-secret-filename <PATH>`.
+
+use clap::Parser;
+use sc_keystore::{
+	remote::{Endpoint, DEFAULT_MAX_CONNECTIONS},
+	LocalKeystore, RemoteSigner,
+};
+use sp_core::crypto::SecretString;
+use std::{fs, path::PathBuf, sync::Arc};
+
+#[derive(Debug, Parser)]
+#[command(rename_all = "kebab-case", version, about)]
+struct Cli {
+	/// Where to listen for nodes, either `unix://<path>` or `tcp://<host>:<port>`.
+	#[arg(long, value_name = "URI")]
+	listen: Endpoint,
+
+	/// Path of the keystore directory holding the keys.
+	#[arg(long, value_name = "PATH")]
+	keystore_path: PathBuf,
+
+	/// File that contains the password of the keystore.
+	#[arg(long, value_name = "PATH")]
+	password_filename: Option<PathBuf>,
+
+	/// File that contains the secret shared with the nodes.
+	#[arg(long, value_name = "PATH")]
+	secret_filename: PathBuf,
+
+	/// Maximum number of nodes served at the same time.
+	#[arg(long, value_name = "COUNT", default_value_t = DEFAULT_MAX_CONNECTIONS)]
+	max_connections: usize,
+}
+
+fn read_trimmed(path: &PathBuf) -> Result<String, String> {
+	fs::read_to_string(path)
+		.map(|s| s.trim_end().to_string())
+		.map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+fn main() -> Result<(), String> {
+	sp_tracing::try_init_simple();
+
+	let cli = Cli::parse();
+
+	let password = cli.password_filename.as_ref().map(read_trimmed).transpose()?;
+	let secret = read_trimmed(&cli.secret_filename)?;
+	if secret.is_empty() {
+		return Err("The shared secret must not be empty".into())
+	}
+
+	let keystore = LocalKeystore::open(&cli.keystore_path, password.map(SecretString::new))
+		.map_err(|e| format!("Failed to open keystore: {}", e))?;
+	let listener =
+		cli.listen.bind().map_err(|e| format!("Failed to bind {}: {}", cli.listen, e))?;
+
+	log::info!("Serving keystore {} on {}", cli.keystore_path.display(), cli.listen);
+
+	RemoteSigner::new(Arc::new(keystore), secret.into_bytes())
+		.with_max_connections(cli.max_connections)
+		.serve(listener)
+		.map_err(|e| format!("Signer stopped: {}", e))
+}
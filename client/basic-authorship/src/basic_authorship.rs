@@ -41,11 +41,14 @@ use sp_runtime::{
 	traits::{BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT},
 	Digest, Percent, SaturatedConversion,
 };
-use std::{marker::PhantomData, pin::Pin, sync::Arc, time};
+use std::{marker::PhantomData, ops::ControlFlow, pin::Pin, sync::Arc, time};
+use tracing::Instrument;
 
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_proposer_metrics::{EndProposingReason, MetricsLink as PrometheusMetrics};
 
+use crate::selection::{PriorityOrder, PushOutcome, SelectionContext, SelectionPolicy};
+
 /// Default block size limit in bytes used by [`Proposer`].
 ///
 /// Can be overwritten by [`ProposerFactory::set_default_block_size_limit`].
@@ -60,7 +63,7 @@ const DEFAULT_SOFT_DEADLINE_PERCENT: Percent = Percent::from_percent(50);
 const LOG_TARGET: &'static str = "basic-authorship";
 
 /// [`Proposer`] factory.
-pub struct ProposerFactory<A, B, C, PR> {
+pub struct ProposerFactory<A, B, C, PR, S = PriorityOrder> {
 	spawn_handle: Box<dyn SpawnNamed>,
 	/// The client instance.
 	client: Arc<C>,
@@ -84,6 +87,8 @@ pub struct ProposerFactory<A, B, C, PR> {
 	telemetry: Option<TelemetryHandle>,
 	/// When estimating the block size, should the proof be included?
 	include_proof_in_block_size_estimation: bool,
+	/// The policy deciding which transactions are tried for a block.
+	selection_policy: Arc<S>,
 	/// phantom member to pin the `Backend`/`ProofRecording` type.
 	_phantom: PhantomData<(B, PR)>,
 }
@@ -109,6 +114,7 @@ impl<A, B, C> ProposerFactory<A, B, C, DisableProofRecording> {
 			telemetry,
 			client,
 			include_proof_in_block_size_estimation: false,
+			selection_policy: Arc::new(PriorityOrder),
 			_phantom: PhantomData,
 		}
 	}
@@ -137,17 +143,20 @@ impl<A, B, C> ProposerFactory<A, B, C, EnableProofRecording> {
 			soft_deadline_percent: DEFAULT_SOFT_DEADLINE_PERCENT,
 			telemetry,
 			include_proof_in_block_size_estimation: true,
+			selection_policy: Arc::new(PriorityOrder),
 			_phantom: PhantomData,
 		}
 	}
+}
 
+impl<A, B, C, S> ProposerFactory<A, B, C, EnableProofRecording, S> {
 	/// Disable the proof inclusion when estimating the block size.
 	pub fn disable_proof_in_block_size_estimation(&mut self) {
 		self.include_proof_in_block_size_estimation = false;
 	}
 }
 
-impl<A, B, C, PR> ProposerFactory<A, B, C, PR> {
+impl<A, B, C, PR, S> ProposerFactory<A, B, C, PR, S> {
 	/// Set the default block size limit in bytes.
 	///
 	/// The default value for the block size limit is:
@@ -174,9 +183,27 @@ impl<A, B, C, PR> ProposerFactory<A, B, C, PR> {
 	pub fn set_soft_deadline(&mut self, percent: Percent) {
 		self.soft_deadline_percent = percent;
 	}
+
+	/// Use `policy` to decide which ready transactions are tried for a block and when to stop.
+	///
+	/// The default policy is [`PriorityOrder`].
+	pub fn with_selection_policy<S2>(self, policy: S2) -> ProposerFactory<A, B, C, PR, S2> {
+		ProposerFactory {
+			spawn_handle: self.spawn_handle,
+			client: self.client,
+			transaction_pool: self.transaction_pool,
+			metrics: self.metrics,
+			default_block_size_limit: self.default_block_size_limit,
+			soft_deadline_percent: self.soft_deadline_percent,
+			telemetry: self.telemetry,
+			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+			selection_policy: Arc::new(policy),
+			_phantom: PhantomData,
+		}
+	}
 }
 
-impl<B, Block, C, A, PR> ProposerFactory<A, B, C, PR>
+impl<B, Block, C, A, PR, S> ProposerFactory<A, B, C, PR, S>
 where
 	A: TransactionPool<Block = Block> + 'static,
 	B: backend::Backend<Block> + Send + Sync + 'static,
@@ -193,12 +220,12 @@ where
 		&mut self,
 		parent_header: &<Block as BlockT>::Header,
 		now: Box<dyn Fn() -> time::Instant + Send + Sync>,
-	) -> Proposer<B, Block, C, A, PR> {
+	) -> Proposer<B, Block, C, A, PR, S> {
 		let parent_hash = parent_header.hash();
 
 		info!("🙌 Starting consensus session on top of parent {:?}", parent_hash);
 
-		let proposer = Proposer::<_, _, _, _, PR> {
+		let proposer = Proposer::<_, _, _, _, PR, S> {
 			spawn_handle: self.spawn_handle.clone(),
 			client: self.client.clone(),
 			parent_hash,
@@ -211,13 +238,14 @@ where
 			telemetry: self.telemetry.clone(),
 			_phantom: PhantomData,
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+			selection_policy: self.selection_policy.clone(),
 		};
 
 		proposer
 	}
 }
 
-impl<A, B, Block, C, PR> sp_consensus::Environment<Block> for ProposerFactory<A, B, C, PR>
+impl<A, B, Block, C, PR, S> sp_consensus::Environment<Block> for ProposerFactory<A, B, C, PR, S>
 where
 	A: TransactionPool<Block = Block> + 'static,
 	B: backend::Backend<Block> + Send + Sync + 'static,
@@ -230,9 +258,10 @@ where
 		+ 'static,
 	C::Api: ApiExt<Block> + BlockBuilderApi<Block>,
 	PR: ProofRecording,
+	S: SelectionPolicy<A::InPoolTransaction>,
 {
 	type CreateProposer = future::Ready<Result<Self::Proposer, Self::Error>>;
-	type Proposer = Proposer<B, Block, C, A, PR>;
+	type Proposer = Proposer<B, Block, C, A, PR, S>;
 	type Error = sp_blockchain::Error;
 
 	fn init(&mut self, parent_header: &<Block as BlockT>::Header) -> Self::CreateProposer {
@@ -241,7 +270,7 @@ where
 }
 
 /// The proposer logic.
-pub struct Proposer<B, Block: BlockT, C, A: TransactionPool, PR> {
+pub struct Proposer<B, Block: BlockT, C, A: TransactionPool, PR, S = PriorityOrder> {
 	spawn_handle: Box<dyn SpawnNamed>,
 	client: Arc<C>,
 	parent_hash: Block::Hash,
@@ -253,10 +282,11 @@ pub struct Proposer<B, Block: BlockT, C, A: TransactionPool, PR> {
 	include_proof_in_block_size_estimation: bool,
 	soft_deadline_percent: Percent,
 	telemetry: Option<TelemetryHandle>,
+	selection_policy: Arc<S>,
 	_phantom: PhantomData<(B, PR)>,
 }
 
-impl<A, B, Block, C, PR> sp_consensus::Proposer<Block> for Proposer<B, Block, C, A, PR>
+impl<A, B, Block, C, PR, S> sp_consensus::Proposer<Block> for Proposer<B, Block, C, A, PR, S>
 where
 	A: TransactionPool<Block = Block> + 'static,
 	B: backend::Backend<Block> + Send + Sync + 'static,
@@ -269,6 +299,7 @@ where
 		+ 'static,
 	C::Api: ApiExt<Block> + BlockBuilderApi<Block>,
 	PR: ProofRecording,
+	S: SelectionPolicy<A::InPoolTransaction>,
 {
 	type Proposal =
 		Pin<Box<dyn Future<Output = Result<Proposal<Block, PR::Proof>, Self::Error>> + Send>>;
@@ -285,35 +316,39 @@ where
 	) -> Self::Proposal {
 		let (tx, rx) = oneshot::channel();
 		let spawn_handle = self.spawn_handle.clone();
+		let span = tracing::debug_span!(
+			target: LOG_TARGET,
+			"propose",
+			parent_hash = %self.parent_hash,
+			parent_number = %self.parent_number,
+		);
 
 		spawn_handle.spawn_blocking(
 			"basic-authorship-proposer",
 			None,
-			Box::pin(async move {
-				// leave some time for evaluation and block finalization (33%)
-				let deadline = (self.now)() + max_duration - max_duration / 3;
-				let res = self
-					.propose_with(inherent_data, inherent_digests, deadline, block_size_limit)
-					.await;
-				if tx.send(res).is_err() {
-					trace!(
-						target: LOG_TARGET,
-						"Could not send block production result to proposer!"
-					);
+			Box::pin(
+				async move {
+					// leave some time for evaluation and block finalization (33%)
+					let deadline = (self.now)() + max_duration - max_duration / 3;
+					let res = self
+						.propose_with(inherent_data, inherent_digests, deadline, block_size_limit)
+						.await;
+					if tx.send(res).is_err() {
+						trace!(
+							target: LOG_TARGET,
+							"Could not send block production result to proposer!"
+						);
+					}
 				}
-			}),
+				.instrument(span),
+			),
 		);
 
 		async move { rx.await? }.boxed()
 	}
 }
 
-/// If the block is full we will attempt to push at most
-/// this number of transactions before quitting for real.
-/// It allows us to increase block utilization.
-const MAX_SKIPPED_TRANSACTIONS: usize = 8;
-
-impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
+impl<A, B, Block, C, PR, S> Proposer<B, Block, C, A, PR, S>
 where
 	A: TransactionPool<Block = Block>,
 	B: backend::Backend<Block> + Send + Sync + 'static,
@@ -326,6 +361,7 @@ where
 		+ 'static,
 	C::Api: ApiExt<Block> + BlockBuilderApi<Block>,
 	PR: ProofRecording,
+	S: SelectionPolicy<A::InPoolTransaction>,
 {
 	async fn propose_with(
 		self,
@@ -338,14 +374,17 @@ where
 		let mut block_builder =
 			self.client.new_block_at(self.parent_hash, inherent_digests, PR::ENABLED)?;
 
-		self.apply_inherents(&mut block_builder, inherent_data)?;
+		tracing::debug_span!(target: LOG_TARGET, "apply_inherents")
+			.in_scope(|| self.apply_inherents(&mut block_builder, inherent_data))?;
 
 		// TODO call `after_inherents` and check if we should apply extrinsincs here
 		// <https://github.com/paritytech/substrate/pull/14275/>
 
 		let block_timer = time::Instant::now();
-		let end_reason =
-			self.apply_extrinsics(&mut block_builder, deadline, block_size_limit).await?;
+		let end_reason = self
+			.apply_extrinsics(&mut block_builder, deadline, block_size_limit)
+			.instrument(tracing::debug_span!(target: LOG_TARGET, "apply_extrinsics"))
+			.await?;
 		let (block, storage_changes, proof) = block_builder.build()?.into_inner();
 		let block_took = block_timer.elapsed();
 
@@ -414,14 +453,13 @@ where
 		let left_micros: u64 = left.as_micros().saturated_into();
 		let soft_deadline =
 			now + time::Duration::from_micros(self.soft_deadline_percent.mul_floor(left_micros));
-		let mut skipped = 0;
 		let mut unqueue_invalid = Vec::new();
 
 		let mut t1 = self.transaction_pool.ready_at(self.parent_number).fuse();
 		let mut t2 =
 			futures_timer::Delay::new(deadline.saturating_duration_since((self.now)()) / 8).fuse();
 
-		let mut pending_iterator = select! {
+		let pending_iterator = select! {
 			res = t1 => res,
 			_ = t2 => {
 				warn!(target: LOG_TARGET,
@@ -434,13 +472,17 @@ where
 		};
 
 		let block_size_limit = block_size_limit.unwrap_or(self.default_block_size_limit);
+		let mut selector = self.selection_policy.start(
+			pending_iterator,
+			SelectionContext { deadline, soft_deadline, block_size_limit },
+		);
 
 		debug!(target: LOG_TARGET, "Attempting to push transactions from the pool.");
 		debug!(target: LOG_TARGET, "Pool status: {:?}", self.transaction_pool.status());
 		let mut transaction_pushed = false;
 
 		let end_reason = loop {
-			let pending_tx = if let Some(pending_tx) = pending_iterator.next() {
+			let pending_tx = if let Some(pending_tx) = selector.next() {
 				pending_tx
 			} else {
 				break EndProposingReason::NoMoreTransactions
@@ -462,68 +504,34 @@ where
 			let block_size =
 				block_builder.estimate_block_size(self.include_proof_in_block_size_estimation);
 			if block_size + pending_tx_data.encoded_size() > block_size_limit {
-				pending_iterator.report_invalid(&pending_tx);
-				if skipped < MAX_SKIPPED_TRANSACTIONS {
-					skipped += 1;
-					debug!(
-						target: LOG_TARGET,
-						"Transaction would overflow the block size limit, \
-					 but will try {} more transactions before quitting.",
-						MAX_SKIPPED_TRANSACTIONS - skipped,
-					);
-					continue
-				} else if now < soft_deadline {
-					debug!(
-						target: LOG_TARGET,
-						"Transaction would overflow the block size limit, \
-					 but we still have time before the soft deadline, so \
-					 we will try a bit more."
-					);
-					continue
-				} else {
-					debug!(
-						target: LOG_TARGET,
-						"Reached block size limit, proceeding with proposing."
-					);
-					break EndProposingReason::HitBlockSizeLimit
+				match selector.report(&pending_tx, PushOutcome::ExceedsBlockSize, now) {
+					ControlFlow::Continue(()) => continue,
+					ControlFlow::Break(end_reason) => break end_reason,
 				}
 			}
 
 			trace!(target: LOG_TARGET, "[{:?}] Pushing to the block.", pending_tx_hash);
-			match sc_block_builder::BlockBuilder::push(block_builder, pending_tx_data) {
+			let outcome = match sc_block_builder::BlockBuilder::push(block_builder, pending_tx_data)
+			{
 				Ok(()) => {
 					transaction_pushed = true;
 					debug!(target: LOG_TARGET, "[{:?}] Pushed to the block.", pending_tx_hash);
+					PushOutcome::Pushed
 				},
-				Err(ApplyExtrinsicFailed(Validity(e))) if e.exhausted_resources() => {
-					pending_iterator.report_invalid(&pending_tx);
-					if skipped < MAX_SKIPPED_TRANSACTIONS {
-						skipped += 1;
-						debug!(target: LOG_TARGET,
-							"Block seems full, but will try {} more transactions before quitting.",
-							MAX_SKIPPED_TRANSACTIONS - skipped,
-						);
-					} else if (self.now)() < soft_deadline {
-						debug!(target: LOG_TARGET,
-							"Block seems full, but we still have time before the soft deadline, \
-							 so we will try a bit more before quitting."
-						);
-					} else {
-						debug!(
-							target: LOG_TARGET,
-							"Reached block weight limit, proceeding with proposing."
-						);
-						break EndProposingReason::HitBlockWeightLimit
-					}
-				},
+				Err(ApplyExtrinsicFailed(Validity(e))) if e.exhausted_resources() =>
+					PushOutcome::ExhaustedResources,
 				Err(e) => {
-					pending_iterator.report_invalid(&pending_tx);
 					debug!(
 						target: LOG_TARGET,
 						"[{:?}] Invalid transaction: {}", pending_tx_hash, e
 					);
 					unqueue_invalid.push(pending_tx_hash);
+					PushOutcome::Invalid
 				},
+			};
+
+			if let ControlFlow::Break(end_reason) = selector.report(&pending_tx, outcome, now) {
+				break end_reason
 			}
 		};
 
@@ -588,6 +596,7 @@ where
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::selection::{ReadyIterator, TransactionSelector, MAX_SKIPPED_TRANSACTIONS};
 
 	use futures::executor::block_on;
 	use parking_lot::Mutex;
@@ -1148,4 +1157,95 @@ mod tests {
 			"Not enough calls to current time, which indicates the test might have ended because of deadline, not soft deadline"
 		);
 	}
+
+	/// Includes at most `limit` transactions into a block.
+	struct AtMost(usize);
+
+	struct AtMostSelector<Tx> {
+		ready: ReadyIterator<Tx>,
+		remaining: usize,
+	}
+
+	impl<Tx: Send + Sync + 'static> SelectionPolicy<Tx> for AtMost {
+		fn start(
+			&self,
+			ready: ReadyIterator<Tx>,
+			_: SelectionContext,
+		) -> Box<dyn TransactionSelector<Tx>> {
+			Box::new(AtMostSelector { ready, remaining: self.0 })
+		}
+	}
+
+	impl<Tx: Send + Sync> TransactionSelector<Tx> for AtMostSelector<Tx> {
+		fn next(&mut self) -> Option<Arc<Tx>> {
+			self.ready.next()
+		}
+
+		fn report(
+			&mut self,
+			tx: &Arc<Tx>,
+			outcome: PushOutcome,
+			_: time::Instant,
+		) -> ControlFlow<EndProposingReason> {
+			if outcome != PushOutcome::Pushed {
+				self.ready.report_invalid(tx);
+				return ControlFlow::Continue(())
+			}
+			self.remaining -= 1;
+			if self.remaining == 0 {
+				ControlFlow::Break(EndProposingReason::HitBlockWeightLimit)
+			} else {
+				ControlFlow::Continue(())
+			}
+		}
+	}
+
+	#[test]
+	fn should_respect_selection_policy() {
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		);
+
+		block_on(txpool.submit_at(
+			&BlockId::number(0),
+			SOURCE,
+			vec![extrinsic(0), extrinsic(1), extrinsic(2)],
+		))
+		.unwrap();
+
+		block_on(
+			txpool.maintain(chain_event(
+				client
+					.expect_header(client.info().genesis_hash)
+					.expect("there should be header"),
+			)),
+		);
+		assert_eq!(txpool.ready().count(), 3);
+
+		let mut proposer_factory =
+			ProposerFactory::new(spawner.clone(), client.clone(), txpool.clone(), None, None)
+				.with_selection_policy(AtMost(2));
+
+		// when
+		let proposer = block_on(
+			proposer_factory.init(&client.expect_header(client.info().genesis_hash).unwrap()),
+		)
+		.unwrap();
+		let deadline = time::Duration::from_secs(600);
+		let block =
+			block_on(proposer.propose(Default::default(), Default::default(), deadline, None))
+				.map(|r| r.block)
+				.unwrap();
+
+		// then
+		assert_eq!(block.extrinsics().len(), 2);
+		assert_eq!(txpool.ready().count(), 3);
+	}
 }
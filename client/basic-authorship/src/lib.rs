@@ -71,5 +71,6 @@
 //! ```
 
 mod basic_authorship;
+pub mod selection;
 
 pub use crate::basic_authorship::{Proposer, ProposerFactory, DEFAULT_BLOCK_SIZE_LIMIT};
@@ -0,0 +1,171 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Policies deciding which ready transactions the [`Proposer`](crate::Proposer) tries to include.
+//!
+//! For every block the proposer asks its [`SelectionPolicy`] for a [`TransactionSelector`]. The
+//! selector hands out the transactions to try one by one and is told the outcome of every attempt,
+//! so it can decide when the block is full. The hard deadline is always enforced by the proposer.
+//!
+//! The default policy, [`PriorityOrder`], includes the transactions in the order of the pool.
+
+use log::debug;
+use sc_proposer_metrics::EndProposingReason;
+use sc_transaction_pool_api::ReadyTransactions;
+use std::{ops::ControlFlow, sync::Arc, time};
+
+const LOG_TARGET: &'static str = "basic-authorship";
+
+/// If the block is full we will attempt to push at most
+/// this number of transactions before quitting for real.
+/// It allows us to increase block utilization.
+pub const MAX_SKIPPED_TRANSACTIONS: usize = 8;
+
+/// The ready transactions of the pool, as passed to [`SelectionPolicy::start`].
+pub type ReadyIterator<Tx> = Box<dyn ReadyTransactions<Item = Arc<Tx>> + Send>;
+
+/// Information about the block being built, passed to [`SelectionPolicy::start`].
+#[derive(Debug, Clone)]
+pub struct SelectionContext {
+	/// The time at which the proposer stops adding transactions.
+	pub deadline: time::Instant,
+	/// The soft deadline, see
+	/// [`ProposerFactory::set_soft_deadline`](crate::ProposerFactory::set_soft_deadline).
+	pub soft_deadline: time::Instant,
+	/// The block size limit in bytes.
+	pub block_size_limit: usize,
+}
+
+/// The outcome of trying to push a transaction into the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+	/// The transaction was included.
+	Pushed,
+	/// The transaction was not tried, as it would overflow the block size limit.
+	ExceedsBlockSize,
+	/// The transaction exhausted the block resources, usually the weight.
+	ExhaustedResources,
+	/// The transaction is invalid and will be removed from the pool.
+	Invalid,
+}
+
+/// Picks the transactions to include into a single block.
+pub trait TransactionSelector<Tx>: Send {
+	/// Returns the next transaction to try, or `None` if there are no more transactions.
+	fn next(&mut self) -> Option<Arc<Tx>>;
+
+	/// Report the `outcome` of pushing `tx`, returned by the last call to
+	/// [`next`](Self::next), at time `now`.
+	///
+	/// Returns [`ControlFlow::Break`] with the reason to finish the block, or
+	/// [`ControlFlow::Continue`] to try the next transaction. Transactions which were not pushed
+	/// should be reported to the [`ReadyTransactions`] iterator, so transactions depending on them
+	/// are skipped.
+	fn report(
+		&mut self,
+		tx: &Arc<Tx>,
+		outcome: PushOutcome,
+		now: time::Instant,
+	) -> ControlFlow<EndProposingReason>;
+}
+
+/// Decides which ready transactions are tried for a block and when to stop.
+///
+/// Set with
+/// [`ProposerFactory::with_selection_policy`](crate::ProposerFactory::with_selection_policy).
+pub trait SelectionPolicy<Tx>: Send + Sync + 'static {
+	/// Start selecting transactions for a new block out of the `ready` transactions.
+	fn start(
+		&self,
+		ready: ReadyIterator<Tx>,
+		context: SelectionContext,
+	) -> Box<dyn TransactionSelector<Tx>>;
+}
+
+/// The default [`SelectionPolicy`].
+///
+/// Tries the ready transactions in the order of the pool, i.e. by priority. When a transaction
+/// doesn't fit into the block, up to [`MAX_SKIPPED_TRANSACTIONS`] further transactions are tried,
+/// and arbitrarily many until the soft deadline is reached.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PriorityOrder;
+
+impl<Tx: Send + Sync + 'static> SelectionPolicy<Tx> for PriorityOrder {
+	fn start(
+		&self,
+		ready: ReadyIterator<Tx>,
+		context: SelectionContext,
+	) -> Box<dyn TransactionSelector<Tx>> {
+		Box::new(PriorityOrderSelector { ready, soft_deadline: context.soft_deadline, skipped: 0 })
+	}
+}
+
+struct PriorityOrderSelector<Tx> {
+	ready: ReadyIterator<Tx>,
+	soft_deadline: time::Instant,
+	skipped: usize,
+}
+
+impl<Tx: Send + Sync> TransactionSelector<Tx> for PriorityOrderSelector<Tx> {
+	fn next(&mut self) -> Option<Arc<Tx>> {
+		self.ready.next()
+	}
+
+	fn report(
+		&mut self,
+		tx: &Arc<Tx>,
+		outcome: PushOutcome,
+		now: time::Instant,
+	) -> ControlFlow<EndProposingReason> {
+		if outcome == PushOutcome::Pushed {
+			return ControlFlow::Continue(())
+		}
+		self.ready.report_invalid(tx);
+
+		let (what, end_reason) = match outcome {
+			PushOutcome::ExceedsBlockSize => (
+				"Transaction would overflow the block size limit",
+				EndProposingReason::HitBlockSizeLimit,
+			),
+			PushOutcome::ExhaustedResources =>
+				("Block seems full", EndProposingReason::HitBlockWeightLimit),
+			PushOutcome::Pushed | PushOutcome::Invalid => return ControlFlow::Continue(()),
+		};
+
+		if self.skipped < MAX_SKIPPED_TRANSACTIONS {
+			self.skipped += 1;
+			debug!(
+				target: LOG_TARGET,
+				"{}, but will try {} more transactions before quitting.",
+				what,
+				MAX_SKIPPED_TRANSACTIONS - self.skipped,
+			);
+			ControlFlow::Continue(())
+		} else if now < self.soft_deadline {
+			debug!(
+				target: LOG_TARGET,
+				"{}, but we still have time before the soft deadline, so we will try a bit more.",
+				what,
+			);
+			ControlFlow::Continue(())
+		} else {
+			debug!(target: LOG_TARGET, "{}, proceeding with proposing.", what);
+			ControlFlow::Break(end_reason)
+		}
+	}
+}
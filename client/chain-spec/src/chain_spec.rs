@@ -140,7 +140,9 @@ pub type GenesisStorage = BTreeMap<StorageKey, StorageData>;
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
 pub struct RawGenesis {
+	/// The top-level storage.
 	pub top: GenesisStorage,
+	/// The default child tries, keyed by their storage key.
 	pub children_default: BTreeMap<StorageKey, GenesisStorage>,
 }
 
@@ -192,7 +192,7 @@ mod extension;
 mod genesis;
 
 pub use self::{
-	chain_spec::{ChainSpec as GenericChainSpec, NoExtension},
+	chain_spec::{ChainSpec as GenericChainSpec, GenesisStorage, NoExtension, RawGenesis},
 	extension::{get_extension, get_extension_mut, Extension, Fork, Forks, GetExtension, Group},
 	genesis::{
 		construct_genesis_block, resolve_state_version_from_wasm, BuildGenesisBlock,
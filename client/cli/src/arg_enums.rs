@@ -117,6 +117,28 @@ impl Into<sc_tracing::TracingReceiver> for TracingReceiver {
 	}
 }
 
+/// The output format of the `trace-block` command.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum TraceBlockFormat {
+	/// All recorded spans and events as JSON.
+	Json,
+	/// The spans in the Chrome trace-event format, e.g. for `chrome://tracing` or Perfetto.
+	ChromeTrace,
+	/// The spans as folded stacks, e.g. for `inferno-flamegraph`.
+	FoldedStacks,
+}
+
+impl From<TraceBlockFormat> for sp_rpc::tracing::TraceBlockFormat {
+	fn from(format: TraceBlockFormat) -> Self {
+		match format {
+			TraceBlockFormat::Json => Self::Json,
+			TraceBlockFormat::ChromeTrace => Self::ChromeTrace,
+			TraceBlockFormat::FoldedStacks => Self::FoldedStacks,
+		}
+	}
+}
+
 /// The type of the node key.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
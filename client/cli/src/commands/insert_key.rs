@@ -21,9 +21,9 @@ use crate::{
 	utils, with_crypto_scheme, CryptoScheme, Error, KeystoreParams, SharedParams, SubstrateCli,
 };
 use clap::Parser;
-use sc_keystore::LocalKeystore;
+use sc_keystore::{remote::Endpoint, LocalKeystore, RemoteKeystore};
 use sc_service::config::{BasePath, KeystoreConfig};
-use sp_core::crypto::{KeyTypeId, SecretString};
+use sp_core::crypto::{ExposeSecret, KeyTypeId, SecretString};
 use sp_keystore::KeystorePtr;
 
 /// The `insert` command
@@ -71,7 +71,16 @@ impl InsertKeyCmd {
 				let keystore: KeystorePtr = LocalKeystore::open(path, password)?.into();
 				(keystore, public)
 			},
-			_ => unreachable!("keystore_config always returns path and password; qed"),
+			KeystoreConfig::Remote { uri, secret } => {
+				let password = self.keystore_params.read_password()?;
+				let public = with_crypto_scheme!(self.scheme, to_vec(&suri, password))?;
+				let endpoint = uri.parse::<Endpoint>()?;
+				let secret = secret.expose_secret().as_bytes().to_vec();
+				let keystore: KeystorePtr = RemoteKeystore::connect(endpoint, secret)?.into();
+				(keystore, public)
+			},
+			KeystoreConfig::InMemory =>
+				unreachable!("keystore_config never returns an in-memory keystore; qed"),
 		};
 
 		let key_type =
@@ -93,54 +102,11 @@ fn to_vec<P: sp_core::Pair>(uri: &str, pass: Option<SecretString>) -> Result<Vec
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use sc_service::{ChainSpec, ChainType, GenericChainSpec, NoExtension};
+	use crate::commands::test::cli::Cli;
 	use sp_core::{sr25519::Pair, ByteArray, Pair as _};
 	use sp_keystore::Keystore;
 	use tempfile::TempDir;
 
-	struct Cli;
-
-	impl SubstrateCli for Cli {
-		fn impl_name() -> String {
-			"test".into()
-		}
-
-		fn impl_version() -> String {
-			"2.0".into()
-		}
-
-		fn description() -> String {
-			"test".into()
-		}
-
-		fn support_url() -> String {
-			"test.test".into()
-		}
-
-		fn copyright_start_year() -> i32 {
-			2021
-		}
-
-		fn author() -> String {
-			"test".into()
-		}
-
-		fn load_spec(&self, _: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
-			Ok(Box::new(GenericChainSpec::from_genesis(
-				"test",
-				"test_id",
-				ChainType::Development,
-				|| unimplemented!("Not required in tests"),
-				Vec::new(),
-				None,
-				None,
-				None,
-				None,
-				NoExtension::None,
-			)))
-		}
-	}
-
 	#[test]
 	fn insert_with_custom_base_path() {
 		let path = TempDir::new().unwrap();
@@ -19,7 +19,8 @@
 
 use super::{
 	generate::GenerateCmd, generate_node_key::GenerateNodeKeyCmd, insert_key::InsertKeyCmd,
-	inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
+	inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd, list_keys::ListKeysCmd,
+	remove_key::RemoveKeyCmd,
 };
 use crate::{Error, SubstrateCli};
 
@@ -41,6 +42,12 @@ pub enum KeySubcommand {
 
 	/// Insert a key to the keystore of a node.
 	Insert(InsertKeyCmd),
+
+	/// List the public keys in the keystore of a node.
+	List(ListKeysCmd),
+
+	/// Remove a key from the keystore of a node.
+	Remove(RemoveKeyCmd),
 }
 
 impl KeySubcommand {
@@ -51,6 +58,8 @@ impl KeySubcommand {
 			KeySubcommand::Generate(cmd) => cmd.run(),
 			KeySubcommand::Inspect(cmd) => cmd.run(),
 			KeySubcommand::Insert(cmd) => cmd.run(cli),
+			KeySubcommand::List(cmd) => cmd.run(cli),
+			KeySubcommand::Remove(cmd) => cmd.run(cli),
 			KeySubcommand::InspectNodeKey(cmd) => cmd.run(),
 		}
 	}
@@ -0,0 +1,121 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `list` subcommand
+
+use crate::{Error, KeystoreParams, SharedParams, SubstrateCli};
+use clap::Parser;
+use sc_service::config::BasePath;
+use sp_core::crypto::KeyTypeId;
+use sp_keystore::Keystore;
+
+/// The `list` command
+#[derive(Debug, Clone, Parser)]
+#[command(name = "list", about = "List the public keys in the keystore of a node.")]
+pub struct ListKeysCmd {
+	/// Only list keys of this type, examples: "gran", or "imon".
+	#[arg(long)]
+	key_type: Option<String>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl ListKeysCmd {
+	/// Run the command
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<(), Error> {
+		let base_path = self
+			.shared_params
+			.base_path()?
+			.unwrap_or_else(|| BasePath::from_project("", "", &C::executable_name()));
+		let chain_id = self.shared_params.chain_id(self.shared_params.is_dev());
+		let chain_spec = cli.load_spec(&chain_id)?;
+		let config_dir = base_path.config_dir(chain_spec.id());
+
+		let keystore = self.keystore_params.open_keystore(&config_dir)?;
+
+		for (key_type, public) in self.keys(&*keystore)? {
+			println!(
+				"{} {}",
+				String::from_utf8_lossy(&key_type.0),
+				array_bytes::bytes2hex("0x", &public)
+			);
+		}
+
+		Ok(())
+	}
+
+	/// The keys of `keystore` to list, sorted by key type and public key.
+	fn keys(&self, keystore: &dyn Keystore) -> Result<Vec<(KeyTypeId, Vec<u8>)>, Error> {
+		let mut keys = match &self.key_type {
+			Some(key_type) => {
+				let key_type =
+					KeyTypeId::try_from(key_type.as_str()).map_err(|_| Error::KeyTypeInvalid)?;
+				let keys = keystore.keys(key_type).map_err(|_| Error::KeystoreOperation)?;
+				keys.into_iter().map(|public| (key_type, public)).collect()
+			},
+			None => keystore.all_keys().map_err(|_| Error::KeystoreOperation)?,
+		};
+		keys.sort();
+		Ok(keys)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::commands::test::cli::Cli;
+	use sc_keystore::LocalKeystore;
+	use sp_core::crypto::ByteArray;
+	use tempfile::TempDir;
+
+	#[test]
+	fn list_with_custom_base_path() {
+		let path = TempDir::new().unwrap();
+		let path_str = format!("{}", path.path().display());
+		let keystore_path = path.path().join("chains").join("test_id").join("keystore");
+
+		let keystore = LocalKeystore::open(&keystore_path, None).unwrap();
+		let babe = keystore.sr25519_generate_new(KeyTypeId(*b"babe"), None).unwrap();
+		let gran = keystore.ed25519_generate_new(KeyTypeId(*b"gran"), None).unwrap();
+
+		let all = ListKeysCmd::parse_from(&["list", "-d", &path_str]);
+		assert!(all.run(&Cli).is_ok());
+		assert_eq!(
+			all.keys(&keystore).unwrap(),
+			vec![
+				(KeyTypeId(*b"babe"), babe.to_raw_vec()),
+				(KeyTypeId(*b"gran"), gran.to_raw_vec()),
+			],
+		);
+
+		let filtered = ListKeysCmd::parse_from(&["list", "-d", &path_str, "--key-type", "gran"]);
+		assert!(filtered.run(&Cli).is_ok());
+		assert_eq!(
+			filtered.keys(&keystore).unwrap(),
+			vec![(KeyTypeId(*b"gran"), gran.to_raw_vec())],
+		);
+
+		let invalid = ListKeysCmd::parse_from(&["list", "-d", &path_str, "--key-type", "toolong"]);
+		assert!(matches!(invalid.run(&Cli), Err(Error::KeyTypeInvalid)));
+	}
+}
@@ -30,11 +30,15 @@ mod insert_key;
 mod inspect_key;
 mod inspect_node_key;
 mod key;
+mod list_keys;
+mod precompile_runtime_cmd;
 mod purge_chain_cmd;
+mod remove_key;
 mod revert_cmd;
 mod run_cmd;
 mod sign;
 mod test;
+mod trace_block_cmd;
 pub mod utils;
 mod vanity;
 mod verify;
@@ -44,6 +48,8 @@ pub use self::{
 	export_blocks_cmd::ExportBlocksCmd, export_state_cmd::ExportStateCmd, generate::GenerateCmd,
 	generate_node_key::GenerateNodeKeyCmd, import_blocks_cmd::ImportBlocksCmd,
 	insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
-	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
-	sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	key::KeySubcommand, list_keys::ListKeysCmd, precompile_runtime_cmd::PrecompileRuntimeCmd,
+	purge_chain_cmd::PurgeChainCmd, remove_key::RemoveKeyCmd, revert_cmd::RevertCmd,
+	run_cmd::RunCmd, sign::SignCmd, trace_block_cmd::TraceBlockCmd, vanity::VanityCmd,
+	verify::VerifyCmd,
 };
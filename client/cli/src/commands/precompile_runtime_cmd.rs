@@ -0,0 +1,144 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::info;
+use parity_scale_codec::Decode;
+use sc_client_api::{HeaderBackend, StorageProvider, UsageProvider};
+use sc_executor::WasmExecutor;
+use sp_core::{
+	storage::{well_known_keys, StorageKey},
+	traits::{RuntimeCode, WrappedRuntimeCode},
+};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{fmt::Debug, path::PathBuf, str::FromStr, sync::Arc};
+
+/// The `precompile-runtime` command used to compile a runtime ahead of time.
+///
+/// The artifacts are written to the directory the node loads precompiled runtimes from, so the
+/// node doesn't need to compile the runtime when it starts or when the runtime is upgraded.
+#[derive(Debug, Clone, Parser)]
+pub struct PrecompileRuntimeCmd {
+	/// Block hash or number of the block whose runtime is compiled.
+	///
+	/// Defaults to the best block.
+	#[arg(value_name = "HASH or NUMBER", conflicts_with = "wasm_file")]
+	pub input: Option<BlockNumberOrHash>,
+
+	/// Compile the given wasm file instead of an on-chain runtime, e.g. ahead of a runtime
+	/// upgrade.
+	///
+	/// The artifact is tied to the `blake2_256` hash of the file, which matches the code hash the
+	/// node sees once the file is set as `:code` on a chain hashing its storage with
+	/// `blake2_256`. The default number of heap pages is assumed.
+	#[arg(long, value_name = "PATH")]
+	pub wasm_file: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl PrecompileRuntimeCmd {
+	/// Run the `precompile-runtime` command
+	///
+	/// `executor` has to be configured like the executor of the node, see
+	/// `sc_service::new_wasm_executor`.
+	pub async fn run<B, BA, C, H>(
+		&self,
+		client: Arc<C>,
+		executor: WasmExecutor<H>,
+	) -> error::Result<()>
+	where
+		B: BlockT,
+		C: UsageProvider<B> + StorageProvider<B, BA> + HeaderBackend<B>,
+		BA: sc_client_api::backend::Backend<B>,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let (code, hash, heap_pages) = match &self.wasm_file {
+			Some(path) => {
+				info!("Compiling {}...", path.display());
+				let code = std::fs::read(path)?;
+				let hash = sp_core::blake2_256(&code).to_vec();
+				(code, hash, None)
+			},
+			None => {
+				let block_id = self.input.as_ref().map(|b| b.parse()).transpose()?;
+				let block_hash = match block_id {
+					Some(id) => client.expect_block_hash_from_id(&id)?,
+					None => client.usage_info().chain.best_hash,
+				};
+				info!("Compiling the runtime of block {}...", block_hash);
+
+				let code_key = StorageKey(well_known_keys::CODE.to_vec());
+				let code = client
+					.storage(block_hash, &code_key)?
+					.ok_or("The block doesn't have a runtime")?
+					.0;
+				let hash = client
+					.storage_hash(block_hash, &code_key)?
+					.ok_or("The block doesn't have a runtime")?
+					.as_ref()
+					.to_vec();
+				let heap_pages = client
+					.storage(block_hash, &StorageKey(well_known_keys::HEAP_PAGES.to_vec()))?
+					.and_then(|heap_pages| u64::decode(&mut &heap_pages.0[..]).ok());
+				(code, hash, heap_pages)
+			},
+		};
+
+		let runtime_code =
+			RuntimeCode { code_fetcher: &WrappedRuntimeCode(code.into()), hash, heap_pages };
+		let paths = executor
+			.precompile_runtime(&runtime_code)
+			.map_err(|e| format!("Failed to precompile the runtime: {}", e))?;
+		for path in paths {
+			info!("Wrote {}", path.display());
+		}
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for PrecompileRuntimeCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,110 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `remove` subcommand
+
+use crate::{Error, KeystoreParams, SharedParams, SubstrateCli};
+use clap::Parser;
+use sc_service::config::BasePath;
+use sp_core::crypto::KeyTypeId;
+
+/// The `remove` command
+#[derive(Debug, Clone, Parser)]
+#[command(name = "remove", about = "Remove a key from the keystore of a node.")]
+pub struct RemoveKeyCmd {
+	/// Key type, examples: "gran", or "imon".
+	#[arg(long)]
+	key_type: String,
+
+	/// The hex encoded public key of the key to remove.
+	#[arg(long, value_name = "PUBLIC")]
+	public: String,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl RemoveKeyCmd {
+	/// Run the command
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<(), Error> {
+		let base_path = self
+			.shared_params
+			.base_path()?
+			.unwrap_or_else(|| BasePath::from_project("", "", &C::executable_name()));
+		let chain_id = self.shared_params.chain_id(self.shared_params.is_dev());
+		let chain_spec = cli.load_spec(&chain_id)?;
+		let config_dir = base_path.config_dir(chain_spec.id());
+
+		let key_type =
+			KeyTypeId::try_from(self.key_type.as_str()).map_err(|_| Error::KeyTypeInvalid)?;
+		let public = array_bytes::hex2bytes(&self.public).map_err(Error::HexDataConversion)?;
+
+		let keystore = self.keystore_params.open_keystore(&config_dir)?;
+		let removed = keystore.remove(key_type, &public).map_err(|_| Error::KeystoreOperation)?;
+		if !removed {
+			return Err(Error::Input(format!(
+				"No key of type `{}` with public key {} found",
+				self.key_type, self.public
+			)))
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::commands::test::cli::Cli;
+	use sc_keystore::LocalKeystore;
+	use sp_core::crypto::ByteArray;
+	use sp_keystore::Keystore;
+	use tempfile::TempDir;
+
+	#[test]
+	fn remove_with_custom_base_path() {
+		let path = TempDir::new().unwrap();
+		let path_str = format!("{}", path.path().display());
+		let keystore_path = path.path().join("chains").join("test_id").join("keystore");
+
+		let public = LocalKeystore::open(&keystore_path, None)
+			.unwrap()
+			.sr25519_generate_new(KeyTypeId(*b"test"), None)
+			.unwrap();
+		let public_hex = array_bytes::bytes2hex("0x", public.as_slice());
+
+		let remove = RemoveKeyCmd::parse_from(&[
+			"remove",
+			"-d",
+			&path_str,
+			"--key-type",
+			"test",
+			"--public",
+			&public_hex,
+		]);
+		assert!(remove.run(&Cli).is_ok());
+		assert!(remove.run(&Cli).is_err());
+
+		let keystore = LocalKeystore::open(&keystore_path, None).unwrap();
+		assert!(keystore.all_keys().unwrap().is_empty());
+	}
+}
@@ -30,11 +30,16 @@ use crate::{
 use clap::Parser;
 use regex::Regex;
 use sc_service::{
-	config::{BasePath, PrometheusConfig, TransactionPoolOptions},
+	config::{
+		BasePath, ExecutionLimits, PrometheusConfig, RpcHealthConfig, TransactionPoolOptions,
+	},
 	ChainSpec, Role,
 };
 use sc_telemetry::TelemetryEndpoints;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+	net::{IpAddr, Ipv4Addr, SocketAddr},
+	time::Duration,
+};
 
 /// The `run` command used to run a node.
 #[derive(Debug, Clone, Parser)]
@@ -107,6 +112,26 @@ pub struct RunCmd {
 	#[arg(long, value_name = "ORIGINS", value_parser = parse_cors)]
 	pub rpc_cors: Option<Cors>,
 
+	/// Minimum number of connected peers for `GET /health/readiness` to succeed.
+	///
+	/// Default is `1`, or `0` when running in --dev mode.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_health_min_peers: Option<usize>,
+
+	/// Let `GET /health/readiness` succeed while the node is major syncing.
+	#[arg(long)]
+	pub rpc_health_ready_while_syncing: bool,
+
+	/// Maximum time in seconds since the best block last changed for `GET /health/readiness`
+	/// and `GET /health/liveness` to succeed.
+	#[arg(long, value_name = "SECS")]
+	pub rpc_health_max_best_block_age: Option<u64>,
+
+	/// Maximum number of blocks finality may lag behind the best block for
+	/// `GET /health/readiness` to succeed.
+	#[arg(long, value_name = "BLOCKS")]
+	pub rpc_health_max_finality_lag: Option<u64>,
+
 	/// The human-readable name for this node.
 	/// It's used as network node name.
 	#[arg(long, value_name = "NAME")]
@@ -377,6 +402,20 @@ impl CliConfiguration for RunCmd {
 		Ok(self.runtime_params.runtime_cache_size)
 	}
 
+	fn rpc_execution_limits(&self) -> Result<ExecutionLimits> {
+		Ok(self.runtime_params.rpc_execution_limits())
+	}
+
+	fn rpc_health(&self) -> Result<RpcHealthConfig> {
+		let default_min_peers = if self.shared_params().is_dev() { 0 } else { 1 };
+		Ok(RpcHealthConfig {
+			min_peers: self.rpc_health_min_peers.unwrap_or(default_min_peers),
+			ready_while_syncing: self.rpc_health_ready_while_syncing,
+			max_best_block_age: self.rpc_health_max_best_block_age.map(Duration::from_secs),
+			max_finality_lag: self.rpc_health_max_finality_lag,
+		})
+	}
+
 	fn base_path(&self) -> Result<Option<BasePath>> {
 		Ok(if self.tmp {
 			Some(BasePath::new_temp_dir()?)
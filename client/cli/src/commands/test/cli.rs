@@ -0,0 +1,68 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+//! Minimal [`SubstrateCli`] for the tests of commands operating on a node's base path.
+
+use crate::SubstrateCli;
+use sc_service::{ChainSpec, ChainType, GenericChainSpec, NoExtension};
+
+/// CLI whose only chain spec has the id `test_id`.
+pub struct Cli;
+
+impl SubstrateCli for Cli {
+	fn impl_name() -> String {
+		"test".into()
+	}
+
+	fn impl_version() -> String {
+		"2.0".into()
+	}
+
+	fn description() -> String {
+		"test".into()
+	}
+
+	fn support_url() -> String {
+		"test.test".into()
+	}
+
+	fn copyright_start_year() -> i32 {
+		2021
+	}
+
+	fn author() -> String {
+		"test".into()
+	}
+
+	fn load_spec(&self, _: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
+		Ok(Box::new(GenericChainSpec::from_genesis(
+			"test",
+			"test_id",
+			ChainType::Development,
+			|| unimplemented!("Not required in tests"),
+			Vec::new(),
+			None,
+			None,
+			None,
+			None,
+			NoExtension::None,
+		)))
+	}
+}
@@ -18,4 +18,5 @@
 
 //! Integration tests for subkey commands.
 
+pub(crate) mod cli;
 mod sig_verify;
@@ -0,0 +1,133 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	arg_enums::TraceBlockFormat,
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::info;
+use sc_client_api::{BlockBackend, HeaderBackend};
+use sc_tracing::block::BlockExecutor;
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_rpc::tracing::TraceBlockResponse;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{fmt::Debug, io::Write, path::PathBuf, str::FromStr, sync::Arc};
+
+/// The `trace-block` command used to re-execute a block and record the spans of its execution.
+#[derive(Debug, Clone, Parser)]
+pub struct TraceBlockCmd {
+	/// Block hash or number.
+	#[arg(value_name = "HASH or NUMBER")]
+	pub input: BlockNumberOrHash,
+
+	/// Comma separated list of targets of the spans to record.
+	///
+	/// Defaults to `pallet,frame,state`. Spans of the runtime are only recorded if it was built
+	/// with the `with-tracing` feature.
+	#[arg(long, value_name = "TARGETS")]
+	pub targets: Option<String>,
+
+	/// The format of the recorded trace.
+	#[arg(long, value_enum, ignore_case = true, default_value_t = TraceBlockFormat::ChromeTrace)]
+	pub format: TraceBlockFormat,
+
+	/// Profile the host functions called while executing the block.
+	///
+	/// The statistics are only part of the `json` format. Profiling slows down the execution,
+	/// which also shows in the timings of the spans.
+	#[arg(long)]
+	pub profile_host_functions: bool,
+
+	/// Write the trace to the given file instead of stdout.
+	#[arg(long, short, value_name = "PATH")]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl TraceBlockCmd {
+	/// Run the `trace-block` command
+	pub async fn run<B, C>(&self, client: Arc<C>) -> error::Result<()>
+	where
+		B: BlockT + 'static,
+		C: HeaderBackend<B> + BlockBackend<B> + ProvideRuntimeApi<B> + Send + Sync + 'static,
+		C::Api: Metadata<B>,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let block_id = self.input.parse()?;
+		let hash = client.expect_block_hash_from_id(&block_id)?;
+
+		info!("Tracing block {}...", hash);
+		// Empty filters keep all events, to match the spans recorded for `targets`.
+		let response = BlockExecutor::new(
+			client,
+			hash,
+			self.targets.clone(),
+			Some(String::new()),
+			Some(String::new()),
+		)
+		.with_format(self.format.into())
+		.with_host_function_profiling(self.profile_host_functions)
+		.trace_block()
+		.map_err(|e| error::Error::Application(Box::new(e)))?;
+
+		let output = match response {
+			TraceBlockResponse::TraceError(e) => return Err(e.error.into()),
+			TraceBlockResponse::FoldedStacks(stacks) =>
+				stacks.into_iter().map(|stack| stack + "\n").collect(),
+			TraceBlockResponse::ChromeTrace(trace) =>
+				serde_json::to_string(&trace).map_err(|e| error::Error::Application(Box::new(e)))?,
+			response => serde_json::to_string_pretty(&response)
+				.map_err(|e| error::Error::Application(Box::new(e)))?,
+		};
+
+		match &self.output {
+			Some(path) => std::fs::write(path, output)?,
+			None => std::io::stdout().write_all(output.as_bytes())?,
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for TraceBlockCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
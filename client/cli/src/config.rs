@@ -26,13 +26,14 @@ use log::warn;
 use names::{Generator, Name};
 use sc_service::{
 	config::{
-		BasePath, Configuration, DatabaseSource, KeystoreConfig, NetworkConfiguration,
-		NodeKeyConfig, OffchainWorkerConfig, PrometheusConfig, PruningMode, Role, RpcMethods,
-		TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
+		BasePath, Configuration, DatabaseSource, ExecutionLimits, KeystoreConfig,
+		NetworkConfiguration, NodeKeyConfig, OffchainWorkerConfig, PrometheusConfig, PruningMode,
+		Role, RpcHealthConfig, RpcMethods, TelemetryEndpoints, TransactionPoolOptions,
+		WasmExecutionMethod,
 	},
 	BlocksPruning, ChainSpec, TracingReceiver,
 };
-use sc_tracing::logging::LoggerBuilder;
+use sc_tracing::logging::{LoggerBuilder, OtlpConfig};
 use std::{net::SocketAddr, path::PathBuf};
 
 /// The maximum number of characters for a node name.
@@ -428,6 +429,20 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(2)
 	}
 
+	/// Get the execution limits for runtime calls issued through RPC
+	///
+	/// By default calls are not limited.
+	fn rpc_execution_limits(&self) -> Result<ExecutionLimits> {
+		Ok(Default::default())
+	}
+
+	/// Get the thresholds of the RPC readiness and liveness endpoints
+	///
+	/// By default the node is ready once it is synced and has a peer.
+	fn rpc_health(&self) -> Result<RpcHealthConfig> {
+		Ok(Default::default())
+	}
+
 	/// Activate or not the automatic announcing of blocks after import
 	///
 	/// By default this is `false`.
@@ -500,6 +515,8 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			rpc_max_response_size: self.rpc_max_response_size()?,
 			rpc_id_provider: None,
 			rpc_max_subs_per_conn: self.rpc_max_subscriptions_per_connection()?,
+			rpc_execution_limits: self.rpc_execution_limits()?,
+			rpc_health: self.rpc_health()?,
 			rpc_port: DCV::rpc_listen_port(),
 			prometheus_config: self
 				.prometheus_config(DCV::prometheus_listen_port(), &chain_spec)?,
@@ -528,7 +545,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 	///
 	/// By default this is retrieved from `SharedParams`.
 	fn log_filters(&self) -> Result<String> {
-		Ok(self.shared_params().log_filters().join(","))
+		let shared_params = self.shared_params();
+		let mut filters = shared_params.log_filters().to_vec();
+		if shared_params.runtime_profile() {
+			filters.push(format!("{}=info", sc_executor::profiling::LOG_TARGET));
+		}
+
+		Ok(filters.join(","))
 	}
 
 	/// Should the detailed log output be enabled.
@@ -596,6 +619,14 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			logger.with_profiling(tracing_receiver, tracing_targets);
 		}
 
+		if let Some(endpoint) = self.shared_params().otlp_endpoint() {
+			logger.with_otlp(OtlpConfig {
+				endpoint: endpoint.into(),
+				sampling_ratio: self.shared_params().otlp_sampling_ratio(),
+				service_name: config.network.node_name.clone(),
+			});
+		}
+
 		if self.disable_log_color()? {
 			logger.with_colors(false);
 		}
@@ -605,6 +636,10 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 
 		logger.init()?;
 
+		if self.shared_params().runtime_profile() {
+			sc_executor::profiling::set_enabled(true);
+		}
+
 		if let Some(new_limit) = fdlimit::raise_fd_limit() {
 			if new_limit < RECOMMENDED_OPEN_FILE_DESCRIPTOR_LIMIT {
 				warn!(
@@ -18,8 +18,10 @@
 
 use crate::{error, error::Result};
 use clap::Args;
+use sc_keystore::{remote::Endpoint, LocalKeystore, RemoteKeystore};
 use sc_service::config::KeystoreConfig;
-use sp_core::crypto::SecretString;
+use sp_core::crypto::{ExposeSecret, SecretString};
+use sp_keystore::KeystorePtr;
 use std::{
 	fs,
 	path::{Path, PathBuf},
@@ -31,10 +33,22 @@ const DEFAULT_KEYSTORE_CONFIG_PATH: &str = "keystore";
 /// Parameters of the keystore
 #[derive(Debug, Clone, Args)]
 pub struct KeystoreParams {
-	/// Specify custom URIs to connect to for keystore-services
-	#[arg(long)]
+	/// Use a remote signer instead of a local keystore.
+	///
+	/// Either `unix://<path>` or `tcp://<host>:<port>`. Requires `--keystore-secret-filename`.
+	/// The password options then refer to the keystore of the signer.
+	#[arg(
+		long,
+		value_name = "URI",
+		conflicts_with = "keystore_path",
+		requires = "keystore_secret_filename"
+	)]
 	pub keystore_uri: Option<String>,
 
+	/// File that contains the secret shared with the remote signer set by `--keystore-uri`.
+	#[arg(long, value_name = "PATH", requires = "keystore_uri")]
+	pub keystore_secret_filename: Option<PathBuf>,
+
 	/// Specify custom keystore path.
 	#[arg(long, value_name = "PATH")]
 	pub keystore_path: Option<PathBuf>,
@@ -69,6 +83,18 @@ pub fn secret_string_from_str(s: &str) -> std::result::Result<SecretString, Stri
 impl KeystoreParams {
 	/// Get the keystore configuration for the parameters
 	pub fn keystore_config(&self, config_dir: &Path) -> Result<KeystoreConfig> {
+		if let Some(ref uri) = self.keystore_uri {
+			let file = self
+				.keystore_secret_filename
+				.as_ref()
+				.ok_or("`--keystore-uri` requires `--keystore-secret-filename`")?;
+			let secret = fs::read_to_string(file).map_err(|e| format!("{}", e))?;
+			return Ok(KeystoreConfig::Remote {
+				uri: uri.clone(),
+				secret: SecretString::new(secret.trim_end().to_string()),
+			})
+		}
+
 		let password = if self.password_interactive {
 			Some(SecretString::new(input_keystore_password()?))
 		} else if let Some(ref file) = self.password_filename {
@@ -86,6 +112,20 @@ impl KeystoreParams {
 		Ok(KeystoreConfig::Path { path, password })
 	}
 
+	/// Open the keystore configured by the parameters.
+	pub fn open_keystore(&self, config_dir: &Path) -> Result<KeystorePtr> {
+		match self.keystore_config(config_dir)? {
+			KeystoreConfig::Path { path, password } =>
+				Ok(LocalKeystore::open(path, password)?.into()),
+			KeystoreConfig::Remote { uri, secret } => {
+				let endpoint = uri.parse::<Endpoint>()?;
+				let secret = secret.expose_secret().as_bytes().to_vec();
+				Ok(RemoteKeystore::connect(endpoint, secret)?.into())
+			},
+			KeystoreConfig::InMemory => Ok(LocalKeystore::in_memory().into()),
+		}
+	}
+
 	/// helper method to fetch password from `KeyParams` or read from stdin
 	pub fn read_password(&self) -> error::Result<Option<SecretString>> {
 		let (password_interactive, password) = (self.password_interactive, self.password.clone());
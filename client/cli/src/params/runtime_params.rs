@@ -17,7 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use clap::Args;
-use std::str::FromStr;
+use sc_service::config::ExecutionLimits;
+use std::{str::FromStr, time::Duration};
 
 /// Parameters used to config runtime.
 #[derive(Debug, Clone, Args)]
@@ -29,6 +30,29 @@ pub struct RuntimeParams {
 	/// Maximum number of different runtimes that can be cached.
 	#[arg(long, default_value_t = 2)]
 	pub runtime_cache_size: u8,
+
+	/// Maximum time in milliseconds a runtime call issued through RPC may run for.
+	///
+	/// Applies to `state_call` and `chainHead_unstable_call`. Calls running longer are aborted.
+	#[arg(long, value_name = "MS")]
+	pub rpc_call_max_duration: Option<u64>,
+
+	/// Maximum size in MiB of the linear memory of a runtime call issued through RPC.
+	///
+	/// Applies to `state_call` and `chainHead_unstable_call`. The limit must leave room for the
+	/// heap pages of the runtime, otherwise every such call will fail.
+	#[arg(long, value_name = "MIB")]
+	pub rpc_call_max_memory: Option<usize>,
+}
+
+impl RuntimeParams {
+	/// The execution limits for runtime calls issued through RPC.
+	pub fn rpc_execution_limits(&self) -> ExecutionLimits {
+		ExecutionLimits {
+			max_duration: self.rpc_call_max_duration.map(Duration::from_millis),
+			max_memory: self.rpc_call_max_memory.map(|mib| mib.saturating_mul(1024 * 1024)),
+		}
+	}
 }
 
 fn parse_max_runtime_instances(s: &str) -> Result<usize, String> {
@@ -19,7 +19,7 @@
 use crate::arg_enums::TracingReceiver;
 use clap::Args;
 use sc_service::config::BasePath;
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr};
 
 /// Shared parameters used by all `CoreParams`.
 #[derive(Debug, Clone, Args)]
@@ -64,6 +64,14 @@ pub struct SharedParams {
 	#[arg(long)]
 	pub enable_log_reloading: bool,
 
+	/// Log the host functions called by every runtime call.
+	///
+	/// For every runtime call the number of calls, the time spent and the bytes moved are logged
+	/// per host function. This slows down runtime execution and should only be enabled while
+	/// profiling.
+	#[arg(long)]
+	pub runtime_profile: bool,
+
 	/// Sets a custom profiling filter. Syntax is the same as for logging: `<target>=<level>`.
 	#[arg(long, value_name = "TARGETS")]
 	pub tracing_targets: Option<String>,
@@ -71,6 +79,18 @@ pub struct SharedParams {
 	/// Receiver to process tracing messages.
 	#[arg(long, value_name = "RECEIVER", value_enum, ignore_case = true, default_value_t = TracingReceiver::Log)]
 	pub tracing_receiver: TracingReceiver,
+
+	/// Export tracing spans to the OpenTelemetry (OTLP) collector at the given gRPC endpoint.
+	///
+	/// For example `http://localhost:4317`. The exported spans are subject to the log filters:
+	/// client spans such as block import, proposing and network requests are emitted at the
+	/// `debug` level, runtime spans additionally require `--tracing-targets`.
+	#[arg(long, value_name = "URL")]
+	pub otlp_endpoint: Option<String>,
+
+	/// The ratio of traces exported to the OTLP collector, between `0.0` and `1.0`.
+	#[arg(long, value_name = "RATIO", default_value_t = 1.0, value_parser = parse_sampling_ratio)]
+	pub otlp_sampling_ratio: f64,
 }
 
 impl SharedParams {
@@ -107,6 +127,11 @@ impl SharedParams {
 		&self.log
 	}
 
+	/// Should the host functions called by the runtime be profiled and logged.
+	pub fn runtime_profile(&self) -> bool {
+		self.runtime_profile
+	}
+
 	/// Should the detailed log output be enabled.
 	pub fn detailed_log_output(&self) -> bool {
 		self.detailed_log_output
@@ -131,4 +156,27 @@ impl SharedParams {
 	pub fn tracing_targets(&self) -> Option<String> {
 		self.tracing_targets.clone()
 	}
+
+	/// The endpoint of the OTLP collector to export spans to.
+	pub fn otlp_endpoint(&self) -> Option<&str> {
+		self.otlp_endpoint.as_deref()
+	}
+
+	/// The ratio of traces exported to the OTLP collector.
+	pub fn otlp_sampling_ratio(&self) -> f64 {
+		self.otlp_sampling_ratio
+	}
+}
+
+fn parse_sampling_ratio(s: &str) -> Result<f64, String> {
+	let ratio =
+		f64::from_str(s).map_err(|_err| format!("Illegal `--otlp-sampling-ratio` value: {s}"))?;
+
+	if (0.0..=1.0).contains(&ratio) {
+		Ok(ratio)
+	} else {
+		Err(format!(
+			"Illegal `--otlp-sampling-ratio` value: {ratio} is not between `0.0` and `1.0`"
+		))
+	}
 }
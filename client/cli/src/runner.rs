@@ -43,9 +43,22 @@ pub struct Runner<C: SubstrateCli> {
 	config: Configuration,
 	tokio_runtime: tokio::runtime::Runtime,
 	signals: Signals,
+	otlp: FlushOtlpOnDrop,
 	phantom: PhantomData<C>,
 }
 
+/// Flushes the spans not yet exported to the OTLP collector when dropped.
+///
+/// Held by the [`Runner`] so the exporter is stopped on every exit path, after the tokio runtime
+/// was shut down.
+struct FlushOtlpOnDrop;
+
+impl Drop for FlushOtlpOnDrop {
+	fn drop(&mut self) {
+		sc_tracing::logging::shutdown_otlp();
+	}
+}
+
 impl<C: SubstrateCli> Runner<C> {
 	/// Create a new runtime with the command provided in argument
 	pub fn new(
@@ -53,7 +66,7 @@ impl<C: SubstrateCli> Runner<C> {
 		tokio_runtime: tokio::runtime::Runtime,
 		signals: Signals,
 	) -> Result<Runner<C>> {
-		Ok(Runner { config, tokio_runtime, signals, phantom: PhantomData })
+		Ok(Runner { config, tokio_runtime, signals, otlp: FlushOtlpOnDrop, phantom: PhantomData })
 	}
 
 	/// Log information about the node itself.
@@ -100,6 +113,7 @@ impl<C: SubstrateCli> Runner<C> {
 		// Give all futures 60 seconds to shutdown, before tokio "leaks" them.
 		let shutdown_timeout = Duration::from_secs(60);
 		self.tokio_runtime.shutdown_timeout(shutdown_timeout);
+		drop(self.otlp);
 
 		let running_tasks = task_registry.running_tasks();
 
@@ -273,6 +287,8 @@ mod tests {
 				rpc_max_response_size: Default::default(),
 				rpc_id_provider: Default::default(),
 				rpc_max_subs_per_conn: Default::default(),
+				rpc_execution_limits: Default::default(),
+				rpc_health: Default::default(),
 				rpc_port: 9944,
 				prometheus_config: None,
 				telemetry_endpoints: None,
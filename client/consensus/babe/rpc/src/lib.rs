@@ -239,6 +239,7 @@ mod tests {
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
 			),
+			concurrent_verification: None,
 		})
 		.unwrap();
 
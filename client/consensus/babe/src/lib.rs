@@ -96,7 +96,9 @@ use sc_consensus::{
 		BlockCheckParams, BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult,
 		StateAction,
 	},
-	import_queue::{BasicQueue, BoxJustificationImport, DefaultImportQueue, Verifier},
+	import_queue::{
+		BasicQueue, BoxJustificationImport, ConcurrentVerifier, DefaultImportQueue, Verifier,
+	},
 };
 use sc_consensus_epochs::{
 	descendent_query, Epoch as EpochT, EpochChangesFor, SharedEpochChanges, ViableEpochDescriptor,
@@ -117,7 +119,7 @@ use sp_blockchain::{
 use sp_consensus::{BlockOrigin, Environment, Error as ConsensusError, Proposer, SelectChain};
 use sp_consensus_babe::inherents::BabeInherentData;
 use sp_consensus_slots::Slot;
-use sp_core::traits::SpawnEssentialNamed;
+use sp_core::traits::{SpawnEssentialNamed, SpawnNamed};
 use sp_inherents::{CreateInherentDataProviders, InherentData, InherentDataProvider};
 use sp_keystore::KeystorePtr;
 use sp_runtime::{
@@ -378,6 +380,19 @@ pub struct BabeIntermediate<B: BlockT> {
 /// Intermediate key for Babe engine.
 pub static INTERMEDIATE_KEY: &[u8] = b"babe1";
 
+/// A header checked ahead of its import by [`BabeVerifier`].
+struct PreVerifiedHeader<B: BlockT> {
+	/// The epoch the header was checked against.
+	epoch_descriptor: ViableEpochDescriptor<B::Hash, NumberFor<B>, Epoch>,
+	/// The header, without its seal.
+	pre_header: B::Header,
+	/// The outcome of the check.
+	verified_info: verification::VerifiedHeaderInfo,
+}
+
+/// Intermediate key for [`PreVerifiedHeader`].
+static PRE_VERIFIED_KEY: &[u8] = b"babe_pre_verified";
+
 /// Read configuration from the runtime state at current best block.
 pub fn configuration<B: BlockT, C>(client: &C) -> ClientResult<BabeConfiguration>
 where
@@ -998,6 +1013,20 @@ where
 	SelectChain: sp_consensus::SelectChain<Block>,
 	CIDP: CreateInherentDataProviders<Block, ()>,
 {
+	/// Whether the verification of `block` is skipped.
+	///
+	/// Verification for imported blocks is skipped in two cases:
+	/// 1. When importing blocks below the last finalized block during network initial
+	///    synchronization.
+	/// 2. When importing whole state we don't calculate epoch descriptor, but rather read it from
+	///    the state after import. We also skip all verifications because there's no parent state
+	///    and we trust the sync module to verify that the state is correct and finalized.
+	fn skips_verification(&self, block: &BlockImportParams<Block>) -> bool {
+		let number = *block.header.number();
+		let info = self.client.info();
+		info.block_gap.map_or(false, |(s, e)| s <= number && number <= e) || block.with_state()
+	}
+
 	async fn check_inherents(
 		&self,
 		block: Block,
@@ -1113,9 +1142,7 @@ where
 	}
 }
 
-#[async_trait::async_trait]
-impl<Block, Client, SelectChain, CIDP> Verifier<Block>
-	for BabeVerifier<Block, Client, SelectChain, CIDP>
+impl<Block, Client, SelectChain, CIDP> BabeVerifier<Block, Client, SelectChain, CIDP>
 where
 	Block: BlockT,
 	Client: HeaderMetadata<Block, Error = sp_blockchain::Error>
@@ -1129,8 +1156,63 @@ where
 	CIDP: CreateInherentDataProviders<Block, ()> + Send + Sync,
 	CIDP::InherentDataProviders: InherentDataProviderExt + Send + Sync,
 {
-	async fn verify(
-		&mut self,
+	/// Check the seal of the header of `block`, possibly before its parent is imported.
+	///
+	/// The parent may be unknown, so the epoch is looked up from the best block instead. This
+	/// gives the epoch of the block, unless it is on another fork or the blocks in between
+	/// announce an epoch change. [`Self::verify_block`] only reuses the outcome if the epoch
+	/// of the block turns out to be the same.
+	fn pre_verify_block(&self, mut block: BlockImportParams<Block>) -> BlockImportParams<Block> {
+		if self.skips_verification(&block) {
+			return block
+		}
+
+		let Ok(pre_digest) = find_pre_digest::<Block>(&block.header) else { return block };
+
+		let info = self.client.info();
+		let (epoch_descriptor, epoch) = {
+			let epoch_changes = self.epoch_changes.shared_data();
+			let Ok(Some(epoch_descriptor)) = epoch_changes.epoch_descriptor_for_child_of(
+				descendent_query(&*self.client),
+				&info.best_hash,
+				info.best_number,
+				pre_digest.slot(),
+			) else {
+				return block
+			};
+			let Some(viable_epoch) = epoch_changes
+				.viable_epoch(&epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))
+			else {
+				return block
+			};
+
+			// Cloned, to not hold the lock while checking the header.
+			(epoch_descriptor, viable_epoch.as_ref().clone())
+		};
+
+		// The slot is checked against the current time in `verify_block`.
+		let v_params = verification::VerificationParams {
+			header: block.header.clone(),
+			pre_digest: Some(pre_digest),
+			slot_now: u64::MAX.into(),
+			epoch: &epoch,
+		};
+
+		// A failed check is left to `verify_block`, the epoch may not be the right one.
+		if let Ok(CheckedHeader::Checked(pre_header, verified_info)) =
+			verification::check_header::<Block>(v_params)
+		{
+			block.insert_intermediate(
+				PRE_VERIFIED_KEY,
+				PreVerifiedHeader::<Block> { epoch_descriptor, pre_header, verified_info },
+			);
+		}
+
+		block
+	}
+
+	async fn verify_block(
+		&self,
 		mut block: BlockImportParams<Block>,
 	) -> Result<BlockImportParams<Block>, String> {
 		trace!(
@@ -1145,17 +1227,10 @@ where
 		let hash = block.header.hash();
 		let parent_hash = *block.header.parent_hash();
 
-		let info = self.client.info();
-		let number = *block.header.number();
+		let pre_verified =
+			block.remove_intermediate::<PreVerifiedHeader<Block>>(PRE_VERIFIED_KEY).ok();
 
-		if info.block_gap.map_or(false, |(s, e)| s <= number && number <= e) || block.with_state() {
-			// Verification for imported blocks is skipped in two cases:
-			// 1. When importing blocks below the last finalized block during network initial
-			//    synchronization.
-			// 2. When importing whole state we don't calculate epoch descriptor, but rather read it
-			//    from the state after import. We also skip all verifications because there's no
-			//    parent state and we trust the sync module to verify that the state is correct and
-			//    finalized.
+		if self.skips_verification(&block) {
 			return Ok(block)
 		}
 
@@ -1190,20 +1265,35 @@ where
 				)
 				.map_err(|e| Error::<Block>::ForkTree(Box::new(e)))?
 				.ok_or(Error::<Block>::FetchEpoch(parent_hash))?;
-			let viable_epoch = epoch_changes
-				.viable_epoch(&epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))
-				.ok_or(Error::<Block>::FetchEpoch(parent_hash))?;
 
-			// We add one to the current slot to allow for some small drift.
-			// FIXME #1019 in the future, alter this queue to allow deferring of headers
-			let v_params = verification::VerificationParams {
-				header: block.header.clone(),
-				pre_digest: Some(pre_digest),
-				slot_now: slot_now + 1,
-				epoch: viable_epoch.as_ref(),
-			};
+			// The header was already checked against this epoch.
+			if let Some(pre_verified) = pre_verified
+				.filter(|pre_verified| pre_verified.epoch_descriptor == epoch_descriptor)
+			{
+				// We add one to the current slot to allow for some small drift.
+				let check_header = if pre_digest.slot() > slot_now + 1 {
+					CheckedHeader::Deferred(block.header.clone(), pre_digest.slot())
+				} else {
+					CheckedHeader::Checked(pre_verified.pre_header, pre_verified.verified_info)
+				};
+
+				(check_header, epoch_descriptor)
+			} else {
+				let viable_epoch = epoch_changes
+					.viable_epoch(&epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))
+					.ok_or(Error::<Block>::FetchEpoch(parent_hash))?;
+
+				// We add one to the current slot to allow for some small drift.
+				// FIXME #1019 in the future, alter this queue to allow deferring of headers
+				let v_params = verification::VerificationParams {
+					header: block.header.clone(),
+					pre_digest: Some(pre_digest),
+					slot_now: slot_now + 1,
+					epoch: viable_epoch.as_ref(),
+				};
 
-			(verification::check_header::<Block>(v_params)?, epoch_descriptor)
+				(verification::check_header::<Block>(v_params)?, epoch_descriptor)
+			}
 		};
 
 		match check_header {
@@ -1290,6 +1380,61 @@ where
 	}
 }
 
+#[async_trait::async_trait]
+impl<Block, Client, SelectChain, CIDP> Verifier<Block>
+	for BabeVerifier<Block, Client, SelectChain, CIDP>
+where
+	Block: BlockT,
+	Client: HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ Send
+		+ Sync
+		+ AuxStore,
+	Client::Api: BlockBuilderApi<Block> + BabeApi<Block>,
+	SelectChain: sp_consensus::SelectChain<Block>,
+	CIDP: CreateInherentDataProviders<Block, ()> + Send + Sync,
+	CIDP::InherentDataProviders: InherentDataProviderExt + Send + Sync,
+{
+	async fn verify(
+		&mut self,
+		block: BlockImportParams<Block>,
+	) -> Result<BlockImportParams<Block>, String> {
+		self.verify_block(block).await
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block, Client, SelectChain, CIDP> ConcurrentVerifier<Block>
+	for BabeVerifier<Block, Client, SelectChain, CIDP>
+where
+	Block: BlockT,
+	Client: HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ Send
+		+ Sync
+		+ AuxStore,
+	Client::Api: BlockBuilderApi<Block> + BabeApi<Block>,
+	SelectChain: sp_consensus::SelectChain<Block>,
+	CIDP: CreateInherentDataProviders<Block, ()> + Send + Sync,
+	CIDP::InherentDataProviders: InherentDataProviderExt + Send + Sync,
+{
+	async fn pre_verify(
+		&self,
+		block: BlockImportParams<Block>,
+	) -> Result<BlockImportParams<Block>, String> {
+		Ok(self.pre_verify_block(block))
+	}
+
+	async fn verify(
+		&self,
+		block: BlockImportParams<Block>,
+	) -> Result<BlockImportParams<Block>, String> {
+		self.verify_block(block).await
+	}
+}
+
 /// A block-import handler for BABE.
 ///
 /// This scans each imported block for epoch change signals. The signals are
@@ -1796,6 +1941,11 @@ pub struct ImportQueueParams<'a, Block: BlockT, BI, Client, CIDP, SelectChain, S
 	///
 	/// Will be used when sending equivocation reports.
 	pub offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
+	/// Pre-verify up to this many blocks concurrently, ahead of their import, on tasks spawned
+	/// with the given spawner.
+	///
+	/// If `None`, each block is verified right before its import.
+	pub concurrent_verification: Option<(usize, Box<dyn SpawnNamed>)>,
 }
 
 /// Start an import queue for the BABE consensus algorithm.
@@ -1819,6 +1969,7 @@ pub fn import_queue<Block: BlockT, Client, SelectChain, BI, CIDP, Spawn>(
 		registry,
 		telemetry,
 		offchain_tx_pool_factory,
+		concurrent_verification,
 	}: ImportQueueParams<'_, Block, BI, Client, CIDP, SelectChain, Spawn>,
 ) -> ClientResult<(DefaultImportQueue<Block>, BabeWorkerHandle<Block>)>
 where
@@ -1855,10 +2006,26 @@ where
 
 	spawner.spawn_essential("babe-worker", Some("babe"), answer_requests.boxed());
 
-	Ok((
-		BasicQueue::new(verifier, Box::new(block_import), justification_import, spawner, registry),
-		BabeWorkerHandle(worker_tx),
-	))
+	let import_queue = match concurrent_verification {
+		Some((parallelism, verification_spawner)) => BasicQueue::new_with_concurrent_verification(
+			Arc::new(verifier),
+			parallelism,
+			Box::new(block_import),
+			justification_import,
+			spawner,
+			verification_spawner,
+			registry,
+		),
+		None => BasicQueue::new(
+			verifier,
+			Box::new(block_import),
+			justification_import,
+			spawner,
+			registry,
+		),
+	};
+
+	Ok((import_queue, BabeWorkerHandle(worker_tx)))
 }
 
 /// Reverts protocol aux data to at most the last finalized block.
@@ -740,6 +740,64 @@ async fn importing_block_one_sets_genesis_epoch() {
 	assert_eq!(epoch_for_second_block, genesis_epoch);
 }
 
+#[tokio::test]
+async fn pre_verified_header_is_only_reused_for_the_same_epoch() {
+	let net = BabeTestNet::new(1);
+
+	let peer = &net.peers()[0];
+	let data = peer.data.as_ref().expect("babe link set up during initialization");
+	let client = peer.client().as_client();
+	let verifier = net.make_verifier(peer.client().clone(), &peer.data).inner;
+
+	let mut proposer_factory = DummyFactory {
+		client: client.clone(),
+		epoch_changes: data.link.epoch_changes.clone(),
+		mutator: Arc::new(|_, _| ()),
+	};
+
+	let genesis_header = client.header(client.chain_info().genesis_hash).unwrap().unwrap();
+	let mut proposer = proposer_factory.init(&genesis_header).await.unwrap();
+	let pre_digest = sp_runtime::generic::Digest {
+		logs: vec![Item::babe_pre_digest(PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+			authority_index: 0,
+			slot: 999.into(),
+		}))],
+	};
+	// The header is not sealed, so it does not pass any check.
+	let header = proposer.propose_with(pre_digest.clone()).await.unwrap().block.header;
+
+	let import = BlockImportParams::new(BlockOrigin::NetworkInitialSync, header.clone());
+	let import = ConcurrentVerifier::pre_verify(&verifier, import).await.unwrap();
+	assert!(!import.intermediates.contains_key(PRE_VERIFIED_KEY));
+
+	let pre_verified = |epoch_descriptor| {
+		let mut import = BlockImportParams::new(BlockOrigin::NetworkInitialSync, header.clone());
+		import.insert_intermediate(
+			PRE_VERIFIED_KEY,
+			PreVerifiedHeader::<TestBlock> {
+				epoch_descriptor,
+				pre_header: header.clone(),
+				verified_info: verification::VerifiedHeaderInfo {
+					pre_digest: pre_digest.logs[0].clone(),
+					seal: Item::babe_seal(AuthorityPair::from_seed(&[1; 32]).sign(b"")),
+					author: AuthorityPair::from_seed(&[1; 32]).public(),
+				},
+			},
+		);
+		import
+	};
+
+	// Checked against another epoch, so the header is checked again.
+	let import = pre_verified(ViableEpochDescriptor::UnimportedGenesis(1.into()));
+	assert!(ConcurrentVerifier::verify(&verifier, import).await.is_err());
+
+	// Checked against the epoch of the block, so the outcome is reused.
+	let import = pre_verified(ViableEpochDescriptor::UnimportedGenesis(999.into()));
+	let import = ConcurrentVerifier::verify(&verifier, import).await.unwrap();
+	assert!(!import.intermediates.contains_key(PRE_VERIFIED_KEY));
+	assert!(import.intermediates.contains_key(INTERMEDIATE_KEY));
+}
+
 #[tokio::test]
 async fn revert_prunes_epoch_changes_and_removes_weights() {
 	let mut net = BabeTestNet::new(1);
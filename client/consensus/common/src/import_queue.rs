@@ -28,6 +28,7 @@
 //! queues to be instantiated simply.
 
 use log::{debug, trace};
+use std::{sync::Arc, time::Duration};
 
 use sp_consensus::{error::Error as ConsensusError, BlockOrigin};
 use sp_runtime::{
@@ -100,6 +101,44 @@ pub trait Verifier<B: BlockT>: Send {
 		-> Result<BlockImportParams<B>, String>;
 }
 
+/// A [`Verifier`] that can verify several blocks at the same time.
+///
+/// Used by [`BasicQueue::new_with_concurrent_verification`], which pre-verifies the blocks of a
+/// batch on a pool of tasks, ahead of their sequential import, and then verifies each block
+/// right before importing it.
+#[async_trait::async_trait]
+pub trait ConcurrentVerifier<B: BlockT>: Send + Sync {
+	/// Do the expensive part of the verification of the given block ahead of time, e.g. check
+	/// the seal of the header, and return the `BlockImportParams` to pass to [`Self::verify`].
+	///
+	/// The blocks of a batch are pre-verified concurrently, possibly before their parent is
+	/// imported. The outcome can be stored in the intermediates of the block, for
+	/// [`Self::verify`] to reuse once it confirmed that it still applies.
+	async fn pre_verify(
+		&self,
+		block: BlockImportParams<B>,
+	) -> Result<BlockImportParams<B>, String> {
+		Ok(block)
+	}
+
+	/// Verify the given block data and return the `BlockImportParams` to
+	/// continue the block import process.
+	///
+	/// Called once the parent block is imported. The block must be fully verified, whether it
+	/// was pre-verified or not.
+	async fn verify(&self, block: BlockImportParams<B>) -> Result<BlockImportParams<B>, String>;
+}
+
+#[async_trait::async_trait]
+impl<B: BlockT, V: ConcurrentVerifier<B> + ?Sized> Verifier<B> for Arc<V> {
+	async fn verify(
+		&mut self,
+		block: BlockImportParams<B>,
+	) -> Result<BlockImportParams<B>, String> {
+		(**self).verify(block).await
+	}
+}
+
 /// Blocks import queue API.
 ///
 /// The `import_*` methods can be called in order to send elements for the import queue to verify.
@@ -234,93 +273,181 @@ pub(crate) async fn import_single_block_metered<B: BlockT, V: Verifier<B>>(
 	verifier: &mut V,
 	metrics: Option<Metrics>,
 ) -> BlockImportResult<B> {
-	let peer = block.origin;
-
-	let (header, justifications) = match (block.header, block.justifications) {
-		(Some(header), justifications) => (header, justifications),
-		(None, _) => {
-			if let Some(ref peer) = peer {
-				debug!(target: LOG_TARGET, "Header {} was not provided by {} ", block.hash, peer);
-			} else {
-				debug!(target: LOG_TARGET, "Header {} was not provided ", block.hash);
-			}
-			return Err(BlockImportError::IncompleteHeader(peer))
-		},
-	};
+	let (info, import_block) = BlockImportInfo::prepare(block_origin, block)?;
 
-	trace!(target: LOG_TARGET, "Header {} has {:?} logs", block.hash, header.digest().logs().len());
-
-	let number = *header.number();
-	let hash = block.hash;
-	let parent_hash = *header.parent_hash();
-
-	let import_handler = |import| match import {
-		Ok(ImportResult::AlreadyInChain) => {
-			trace!(target: LOG_TARGET, "Block already in chain {}: {:?}", number, hash);
-			Ok(BlockImportStatus::ImportedKnown(number, peer))
-		},
-		Ok(ImportResult::Imported(aux)) =>
-			Ok(BlockImportStatus::ImportedUnknown(number, aux, peer)),
-		Ok(ImportResult::MissingState) => {
-			debug!(
-				target: LOG_TARGET,
-				"Parent state is missing for {}: {:?}, parent: {:?}", number, hash, parent_hash
-			);
-			Err(BlockImportError::MissingState)
-		},
-		Ok(ImportResult::UnknownParent) => {
-			debug!(
-				target: LOG_TARGET,
-				"Block with unknown parent {}: {:?}, parent: {:?}", number, hash, parent_hash
-			);
-			Err(BlockImportError::UnknownParent)
-		},
-		Ok(ImportResult::KnownBad) => {
-			debug!(target: LOG_TARGET, "Peer gave us a bad block {}: {:?}", number, hash);
-			Err(BlockImportError::BadBlock(peer))
-		},
-		Err(e) => {
-			debug!(target: LOG_TARGET, "Error importing block {}: {:?}: {}", number, hash, e);
-			Err(BlockImportError::Other(e))
-		},
+	if let Some(status) = info.check(import_handle, false).await? {
+		// Any other successful result means that the block is already imported.
+		return Ok(status)
+	}
+
+	let started = std::time::Instant::now();
+	let import_block = verifier.verify(import_block).await.map_err(|msg| {
+		if let Some(metrics) = metrics.as_ref() {
+			metrics.report_verification(false, started.elapsed());
+		}
+		info.verification_failed(msg)
+	})?;
+
+	let verification_time = started.elapsed();
+	if let Some(metrics) = metrics.as_ref() {
+		metrics.report_verification(true, verification_time);
+	}
+
+	info.import(import_handle, import_block, verification_time, metrics.as_ref())
+		.await
+}
+
+/// Import a block that was pre-verified ahead of time.
+///
+/// `pre_verification` is the result of pre-verifying the block prepared together with `info`,
+/// which took `pre_verification_time`. If the block isn't known yet, its verification is
+/// completed with `verifier` before importing it.
+pub(crate) async fn import_pre_verified_block<B: BlockT>(
+	import_handle: &mut impl BlockImport<B, Error = ConsensusError>,
+	info: BlockImportInfo<B>,
+	pre_verification: Result<BlockImportParams<B>, String>,
+	pre_verification_time: Duration,
+	verifier: &dyn ConcurrentVerifier<B>,
+	metrics: Option<&Metrics>,
+) -> BlockImportResult<B> {
+	if let Some(status) = info.check(import_handle, false).await? {
+		return Ok(status)
+	}
+
+	let started = std::time::Instant::now();
+	let verification = match pre_verification {
+		Ok(import_block) => verifier.verify(import_block).await,
+		Err(msg) => Err(msg),
 	};
 
-	match import_handler(
-		import_handle
+	let verification_time = pre_verification_time + started.elapsed();
+	if let Some(metrics) = metrics {
+		metrics.report_verification(verification.is_ok(), verification_time);
+	}
+
+	let import_block = verification.map_err(|msg| info.verification_failed(msg))?;
+	info.import(import_handle, import_block, verification_time, metrics).await
+}
+
+/// The details of an [`IncomingBlock`] needed to check and import it after verification.
+pub(crate) struct BlockImportInfo<B: BlockT> {
+	peer: Option<RuntimeOrigin>,
+	hash: B::Hash,
+	number: NumberFor<B>,
+	parent_hash: B::Hash,
+	allow_missing_state: bool,
+	import_existing: bool,
+	allow_missing_parent: bool,
+}
+
+impl<B: BlockT> BlockImportInfo<B> {
+	/// Turn `block` into the [`BlockImportParams`] to verify.
+	pub(crate) fn prepare(
+		block_origin: BlockOrigin,
+		block: IncomingBlock<B>,
+	) -> Result<(Self, BlockImportParams<B>), BlockImportError> {
+		let peer = block.origin;
+
+		let (header, justifications) = match (block.header, block.justifications) {
+			(Some(header), justifications) => (header, justifications),
+			(None, _) => {
+				if let Some(ref peer) = peer {
+					debug!(target: LOG_TARGET, "Header {} was not provided by {} ", block.hash, peer);
+				} else {
+					debug!(target: LOG_TARGET, "Header {} was not provided ", block.hash);
+				}
+				return Err(BlockImportError::IncompleteHeader(peer))
+			},
+		};
+
+		trace!(
+			target: LOG_TARGET,
+			"Header {} has {:?} logs",
+			block.hash,
+			header.digest().logs().len()
+		);
+
+		let info = Self {
+			peer,
+			hash: block.hash,
+			number: *header.number(),
+			parent_hash: *header.parent_hash(),
+			allow_missing_state: block.allow_missing_state,
+			import_existing: block.import_existing,
+			allow_missing_parent: block.state.is_some(),
+		};
+
+		let mut import_block = BlockImportParams::new(block_origin, header);
+		import_block.body = block.body;
+		import_block.justifications = justifications;
+		import_block.post_hash = Some(block.hash);
+		import_block.import_existing = block.import_existing;
+		import_block.indexed_body = block.indexed_body;
+
+		if let Some(state) = block.state {
+			let changes = crate::block_import::StorageChanges::Import(state);
+			import_block.state_action = StateAction::ApplyChanges(changes);
+		} else if block.skip_execution {
+			import_block.state_action = StateAction::Skip;
+		} else if block.allow_missing_state {
+			import_block.state_action = StateAction::ExecuteIfPossible;
+		}
+
+		Ok((info, import_block))
+	}
+
+	/// Check whether the block needs to be imported.
+	///
+	/// Returns the import status if the block doesn't need to be imported. If `parent_pending`,
+	/// the parent block is going to be imported first, so it is fine if it isn't known yet.
+	pub(crate) async fn check(
+		&self,
+		import_handle: &mut impl BlockImport<B, Error = ConsensusError>,
+		parent_pending: bool,
+	) -> Result<Option<BlockImportStatus<NumberFor<B>>>, BlockImportError> {
+		let result = import_handle
 			.check_block(BlockCheckParams {
-				hash,
-				number,
-				parent_hash,
-				allow_missing_state: block.allow_missing_state,
-				import_existing: block.import_existing,
-				allow_missing_parent: block.state.is_some(),
+				hash: self.hash,
+				number: self.number,
+				parent_hash: self.parent_hash,
+				allow_missing_state: self.allow_missing_state,
+				import_existing: self.import_existing,
+				allow_missing_parent: self.allow_missing_parent,
 			})
-			.await,
-	)? {
-		BlockImportStatus::ImportedUnknown { .. } => (),
-		r => return Ok(r), // Any other successful result means that the block is already imported.
-	}
+			.await;
 
-	let started = std::time::Instant::now();
+		if parent_pending && matches!(result, Ok(ImportResult::UnknownParent)) {
+			return Ok(None)
+		}
 
-	let mut import_block = BlockImportParams::new(block_origin, header);
-	import_block.body = block.body;
-	import_block.justifications = justifications;
-	import_block.post_hash = Some(hash);
-	import_block.import_existing = block.import_existing;
-	import_block.indexed_body = block.indexed_body;
-
-	if let Some(state) = block.state {
-		let changes = crate::block_import::StorageChanges::Import(state);
-		import_block.state_action = StateAction::ApplyChanges(changes);
-	} else if block.skip_execution {
-		import_block.state_action = StateAction::Skip;
-	} else if block.allow_missing_state {
-		import_block.state_action = StateAction::ExecuteIfPossible;
+		match self.import_result(result)? {
+			BlockImportStatus::ImportedUnknown { .. } => Ok(None),
+			r => Ok(Some(r)),
+		}
 	}
 
-	let import_block = verifier.verify(import_block).await.map_err(|msg| {
-		if let Some(ref peer) = peer {
+	/// Import the verified `import_block`.
+	async fn import(
+		&self,
+		import_handle: &mut impl BlockImport<B, Error = ConsensusError>,
+		import_block: BlockImportParams<B>,
+		verification_time: Duration,
+		metrics: Option<&Metrics>,
+	) -> BlockImportResult<B> {
+		let started = std::time::Instant::now();
+		let imported = import_handle.import_block(import_block).await;
+		if let Some(metrics) = metrics {
+			let import_time = started.elapsed();
+			metrics.report_import_time(import_time);
+			metrics.report_verification_and_import(verification_time + import_time);
+		}
+		self.import_result(imported)
+	}
+
+	/// Log a failed verification and turn it into an error.
+	fn verification_failed(&self, msg: String) -> BlockImportError {
+		let (number, hash) = (self.number, self.hash);
+		if let Some(ref peer) = self.peer {
 			trace!(
 				target: LOG_TARGET,
 				"Verifying {}({}) from {} failed: {}",
@@ -332,19 +459,41 @@ pub(crate) async fn import_single_block_metered<B: BlockT, V: Verifier<B>>(
 		} else {
 			trace!(target: LOG_TARGET, "Verifying {}({}) failed: {}", number, hash, msg);
 		}
-		if let Some(metrics) = metrics.as_ref() {
-			metrics.report_verification(false, started.elapsed());
-		}
-		BlockImportError::VerificationFailed(peer, msg)
-	})?;
-
-	if let Some(metrics) = metrics.as_ref() {
-		metrics.report_verification(true, started.elapsed());
+		BlockImportError::VerificationFailed(self.peer, msg)
 	}
 
-	let imported = import_handle.import_block(import_block).await;
-	if let Some(metrics) = metrics.as_ref() {
-		metrics.report_verification_and_import(started.elapsed());
+	fn import_result(&self, import: Result<ImportResult, ConsensusError>) -> BlockImportResult<B> {
+		let (peer, number, hash, parent_hash) =
+			(self.peer, self.number, self.hash, self.parent_hash);
+		match import {
+			Ok(ImportResult::AlreadyInChain) => {
+				trace!(target: LOG_TARGET, "Block already in chain {}: {:?}", number, hash);
+				Ok(BlockImportStatus::ImportedKnown(number, peer))
+			},
+			Ok(ImportResult::Imported(aux)) =>
+				Ok(BlockImportStatus::ImportedUnknown(number, aux, peer)),
+			Ok(ImportResult::MissingState) => {
+				debug!(
+					target: LOG_TARGET,
+					"Parent state is missing for {}: {:?}, parent: {:?}", number, hash, parent_hash
+				);
+				Err(BlockImportError::MissingState)
+			},
+			Ok(ImportResult::UnknownParent) => {
+				debug!(
+					target: LOG_TARGET,
+					"Block with unknown parent {}: {:?}, parent: {:?}", number, hash, parent_hash
+				);
+				Err(BlockImportError::UnknownParent)
+			},
+			Ok(ImportResult::KnownBad) => {
+				debug!(target: LOG_TARGET, "Peer gave us a bad block {}: {:?}", number, hash);
+				Err(BlockImportError::BadBlock(peer))
+			},
+			Err(e) => {
+				debug!(target: LOG_TARGET, "Error importing block {}: {:?}: {}", number, hash, e);
+				Err(BlockImportError::Other(e))
+			},
+		}
 	}
-	import_handler(imported)
 }
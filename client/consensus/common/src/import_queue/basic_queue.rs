@@ -16,7 +16,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 use futures::{
+	channel::oneshot,
 	prelude::*,
+	stream,
 	task::{Context, Poll},
 };
 use futures_timer::Delay;
@@ -24,18 +26,26 @@ use log::{debug, trace};
 use prometheus_endpoint::Registry;
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 use sp_consensus::BlockOrigin;
+use sp_core::traits::SpawnNamed;
 use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT, NumberFor},
 	Justification, Justifications,
 };
-use std::{pin::Pin, time::Duration};
+use std::{
+	collections::HashSet,
+	pin::Pin,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
 use crate::{
+	block_import::BlockImportParams,
 	import_queue::{
 		buffered_link::{self, BufferedLinkReceiver, BufferedLinkSender},
-		import_single_block_metered, BlockImportError, BlockImportStatus, BoxBlockImport,
-		BoxJustificationImport, ImportQueue, ImportQueueService, IncomingBlock, Link,
-		RuntimeOrigin, Verifier, LOG_TARGET,
+		import_pre_verified_block, import_single_block_metered, BlockImportError, BlockImportInfo,
+		BlockImportResult, BlockImportStatus, BoxBlockImport, BoxJustificationImport,
+		ConcurrentVerifier, ImportQueue, ImportQueueService, IncomingBlock, Link, RuntimeOrigin,
+		Verifier, LOG_TARGET,
 	},
 	metrics::Metrics,
 };
@@ -67,6 +77,53 @@ impl<B: BlockT> BasicQueue<B> {
 		justification_import: Option<BoxJustificationImport<B>>,
 		spawner: &impl sp_core::traits::SpawnEssentialNamed,
 		prometheus_registry: Option<&Registry>,
+	) -> Self {
+		Self::with_verification(
+			Verification::Sequential(verifier),
+			block_import,
+			justification_import,
+			spawner,
+			prometheus_registry,
+		)
+	}
+
+	/// Instantiate a new basic queue, which verifies up to `parallelism` blocks concurrently.
+	///
+	/// The blocks of a batch are verified on tasks spawned with `verification_spawner`, ahead of
+	/// their import. The blocks are still imported one after the other, in the order they were
+	/// received.
+	///
+	/// This creates a background task, and calls `on_start` on the justification importer.
+	pub fn new_with_concurrent_verification<V: 'static + ConcurrentVerifier<B>>(
+		verifier: Arc<V>,
+		parallelism: usize,
+		block_import: BoxBlockImport<B>,
+		justification_import: Option<BoxJustificationImport<B>>,
+		spawner: &impl sp_core::traits::SpawnEssentialNamed,
+		verification_spawner: impl SpawnNamed + 'static,
+		prometheus_registry: Option<&Registry>,
+	) -> Self {
+		let pipeline = VerificationPipeline {
+			verifier,
+			spawner: Box::new(verification_spawner),
+			parallelism: parallelism.max(1),
+		};
+
+		Self::with_verification(
+			Verification::<B, Arc<V>>::Pipelined(pipeline),
+			block_import,
+			justification_import,
+			spawner,
+			prometheus_registry,
+		)
+	}
+
+	fn with_verification<V: 'static + Verifier<B>>(
+		verification: Verification<B, V>,
+		block_import: BoxBlockImport<B>,
+		justification_import: Option<BoxJustificationImport<B>>,
+		spawner: &impl sp_core::traits::SpawnEssentialNamed,
+		prometheus_registry: Option<&Registry>,
 	) -> Self {
 		let (result_sender, result_port) = buffered_link::buffered_link(100_000);
 
@@ -80,7 +137,7 @@ impl<B: BlockT> BasicQueue<B> {
 
 		let (future, justification_sender, block_import_sender) = BlockImportWorker::new(
 			result_sender,
-			verifier,
+			verification,
 			block_import,
 			justification_import,
 			metrics,
@@ -211,6 +268,61 @@ mod worker_messages {
 	);
 }
 
+/// How the blocks of a batch are verified.
+enum Verification<B: BlockT, V> {
+	/// Each block is verified right before it is imported.
+	Sequential(V),
+	/// The blocks are verified concurrently, ahead of their import.
+	Pipelined(VerificationPipeline<B>),
+}
+
+/// Verifies blocks on a pool of tasks.
+struct VerificationPipeline<B: BlockT> {
+	verifier: Arc<dyn ConcurrentVerifier<B>>,
+	spawner: Box<dyn SpawnNamed>,
+	/// The maximum number of blocks verified at the same time.
+	parallelism: usize,
+}
+
+impl<B: BlockT> VerificationPipeline<B> {
+	/// Pre-verify `block` on a new task, once the returned future is polled.
+	///
+	/// Resolves to the pre-verification result and the time the pre-verification took.
+	fn pre_verify(
+		&self,
+		block: BlockImportParams<B>,
+	) -> impl Future<Output = (Result<BlockImportParams<B>, String>, Duration)> {
+		let verifier = self.verifier.clone();
+		let spawner = self.spawner.clone();
+
+		async move {
+			let (result_sender, result_receiver) = oneshot::channel();
+			spawner.spawn_blocking(
+				"block-verification",
+				Some("block-import"),
+				async move {
+					let started = Instant::now();
+					let result = verifier.pre_verify(block).await;
+					let _ = result_sender.send((result, started.elapsed()));
+				}
+				.boxed(),
+			);
+
+			result_receiver.await.unwrap_or_else(|_| {
+				(Err("Verification task was cancelled".into()), Duration::default())
+			})
+		}
+	}
+}
+
+/// A block of a batch, after checking whether it needs to be imported.
+enum CheckedBlock<B: BlockT> {
+	/// The block needs to be verified and imported.
+	ToImport(BlockImportInfo<B>, BlockImportParams<B>),
+	/// The outcome of the import is already known.
+	Done(BlockImportResult<B>),
+}
+
 /// The process of importing blocks.
 ///
 /// This polls the `block_import_receiver` for new blocks to import and than awaits on
@@ -220,7 +332,7 @@ mod worker_messages {
 /// Returns when `block_import` ended.
 async fn block_import_process<B: BlockT>(
 	mut block_import: BoxBlockImport<B>,
-	mut verifier: impl Verifier<B>,
+	mut verification: Verification<B, impl Verifier<B>>,
 	mut result_sender: BufferedLinkSender<B>,
 	mut block_import_receiver: TracingUnboundedReceiver<worker_messages::ImportBlocks<B>>,
 	metrics: Option<Metrics>,
@@ -239,15 +351,28 @@ async fn block_import_process<B: BlockT>(
 			},
 		};
 
-		let res = import_many_blocks(
-			&mut block_import,
-			origin,
-			blocks,
-			&mut verifier,
-			delay_between_blocks,
-			metrics.clone(),
-		)
-		.await;
+		let res = match &mut verification {
+			Verification::Sequential(verifier) =>
+				import_many_blocks(
+					&mut block_import,
+					origin,
+					blocks,
+					verifier,
+					delay_between_blocks,
+					metrics.clone(),
+				)
+				.await,
+			Verification::Pipelined(pipeline) =>
+				import_many_blocks_pipelined(
+					&mut block_import,
+					origin,
+					blocks,
+					pipeline,
+					delay_between_blocks,
+					metrics.clone(),
+				)
+				.await,
+		};
 
 		result_sender.blocks_processed(res.imported, res.block_count, res.results);
 	}
@@ -262,7 +387,7 @@ struct BlockImportWorker<B: BlockT> {
 impl<B: BlockT> BlockImportWorker<B> {
 	fn new<V: 'static + Verifier<B>>(
 		result_sender: BufferedLinkSender<B>,
-		verifier: V,
+		verification: Verification<B, V>,
 		block_import: BoxBlockImport<B>,
 		justification_import: Option<BoxJustificationImport<B>>,
 		metrics: Option<Metrics>,
@@ -293,7 +418,7 @@ impl<B: BlockT> BlockImportWorker<B> {
 
 			let block_import_process = block_import_process(
 				block_import,
-				verifier,
+				verification,
 				worker.result_sender.clone(),
 				block_import_port,
 				worker.metrics.clone(),
@@ -468,6 +593,122 @@ async fn import_many_blocks<B: BlockT, V: Verifier<B>>(
 	}
 }
 
+/// Import several blocks at once, pre-verifying them concurrently ahead of their import.
+///
+/// Like [`import_many_blocks`], the blocks are imported one after the other and all blocks after
+/// the first failed one are cancelled.
+async fn import_many_blocks_pipelined<B: BlockT>(
+	import_handle: &mut BoxBlockImport<B>,
+	blocks_origin: BlockOrigin,
+	blocks: Vec<IncomingBlock<B>>,
+	pipeline: &VerificationPipeline<B>,
+	delay_between_blocks: Duration,
+	metrics: Option<Metrics>,
+) -> ImportManyBlocksResult<B> {
+	let count = blocks.len();
+	let hashes = blocks.iter().map(|block| block.hash).collect::<Vec<_>>();
+
+	trace!(target: LOG_TARGET, "Starting pipelined import of {} blocks", count);
+
+	// Check the blocks first, so that the known ones aren't verified. A block whose parent is
+	// earlier in the batch is checked again once the parent is imported.
+	let mut checked = Vec::with_capacity(count);
+	let mut pending = HashSet::new();
+	for block in blocks {
+		let block_hash = block.hash;
+		let parent_pending = block
+			.header
+			.as_ref()
+			.map_or(false, |header| pending.contains(header.parent_hash()));
+
+		let checked_block = match BlockImportInfo::prepare(blocks_origin, block) {
+			Ok((info, import_block)) => match info.check(import_handle, parent_pending).await {
+				Ok(None) => {
+					pending.insert(block_hash);
+					CheckedBlock::ToImport(info, import_block)
+				},
+				Ok(Some(status)) => CheckedBlock::Done(Ok(status)),
+				Err(e) => CheckedBlock::Done(Err(e)),
+			},
+			Err(e) => CheckedBlock::Done(Err(e)),
+		};
+
+		let has_error = matches!(checked_block, CheckedBlock::Done(Err(_)));
+		checked.push((block_hash, checked_block));
+		if has_error {
+			break
+		}
+	}
+
+	let mut pre_verified = stream::iter(checked.into_iter().map(|(block_hash, checked_block)| {
+		let pre_verification = match checked_block {
+			CheckedBlock::ToImport(info, import_block) =>
+				Ok((info, pipeline.pre_verify(import_block))),
+			CheckedBlock::Done(result) => Err(result),
+		};
+
+		async move {
+			match pre_verification {
+				Ok((info, pre_verification)) => (block_hash, Ok((info, pre_verification.await))),
+				Err(result) => (block_hash, Err(result)),
+			}
+		}
+	}))
+	.buffered(pipeline.parallelism);
+
+	let mut imported = 0;
+	let mut results = Vec::with_capacity(count);
+
+	while let Some((block_hash, pre_verified)) = pre_verified.next().await {
+		let import_result = match pre_verified {
+			Ok((info, (pre_verification, pre_verification_time))) =>
+				import_pre_verified_block(
+					import_handle,
+					info,
+					pre_verification,
+					pre_verification_time,
+					&*pipeline.verifier,
+					metrics.as_ref(),
+				)
+				.await,
+			Err(result) => result,
+		};
+
+		if let Some(metrics) = metrics.as_ref() {
+			metrics.report_import::<B>(&import_result);
+		}
+
+		let has_error = import_result.is_err();
+		if !has_error {
+			trace!(target: LOG_TARGET, "Block imported successfully ({})", block_hash);
+			imported += 1;
+		}
+
+		results.push((import_result, block_hash));
+
+		if has_error {
+			break
+		} else if delay_between_blocks != Duration::default() {
+			Delay::new(delay_between_blocks).await;
+		} else {
+			Yield::new().await
+		}
+	}
+
+	// Stop pre-verifying the blocks which won't be imported.
+	drop(pre_verified);
+
+	for block_hash in hashes.into_iter().skip(results.len()) {
+		let import_result = Err(BlockImportError::Cancelled);
+		if let Some(metrics) = metrics.as_ref() {
+			metrics.report_import::<B>(&import_result);
+		}
+		results.push((import_result, block_hash));
+	}
+
+	ImportManyBlocksResult { block_count: count, imported, results }
+}
+
 /// A future that will always `yield` on the first call of `poll` but schedules the
 /// current task for re-execution.
 ///
@@ -592,8 +833,13 @@ mod tests {
 	fn prioritizes_finality_work_over_block_import() {
 		let (result_sender, mut result_port) = buffered_link::buffered_link(100_000);
 
-		let (worker, finality_sender, block_import_sender) =
-			BlockImportWorker::new(result_sender, (), Box::new(()), Some(Box::new(())), None);
+		let (worker, finality_sender, block_import_sender) = BlockImportWorker::new(
+			result_sender,
+			Verification::Sequential(()),
+			Box::new(()),
+			Some(Box::new(())),
+			None,
+		);
 		futures::pin_mut!(worker);
 
 		let import_block = |n| {
@@ -685,4 +931,218 @@ mod tests {
 			]
 		);
 	}
+
+	/// Rejects the blocks with the given number.
+	struct RejectNumber(BlockNumber);
+
+	#[async_trait::async_trait]
+	impl ConcurrentVerifier<Block> for RejectNumber {
+		async fn pre_verify(
+			&self,
+			block: BlockImportParams<Block>,
+		) -> Result<BlockImportParams<Block>, String> {
+			if block.header.number == self.0 {
+				Err("rejected".into())
+			} else {
+				Ok(block)
+			}
+		}
+
+		async fn verify(
+			&self,
+			block: BlockImportParams<Block>,
+		) -> Result<BlockImportParams<Block>, String> {
+			Ok(BlockImportParams::new(block.origin, block.header))
+		}
+	}
+
+	/// Records the blocks it pre-verifies.
+	#[derive(Default)]
+	struct RecordPreVerified(std::sync::Mutex<Vec<BlockNumber>>);
+
+	#[async_trait::async_trait]
+	impl ConcurrentVerifier<Block> for RecordPreVerified {
+		async fn pre_verify(
+			&self,
+			block: BlockImportParams<Block>,
+		) -> Result<BlockImportParams<Block>, String> {
+			self.0.lock().unwrap().push(block.header.number);
+			Ok(block)
+		}
+
+		async fn verify(
+			&self,
+			block: BlockImportParams<Block>,
+		) -> Result<BlockImportParams<Block>, String> {
+			Ok(BlockImportParams::new(block.origin, block.header))
+		}
+	}
+
+	/// Imports blocks on top of the `known` ones.
+	struct ChainImport {
+		known: std::collections::HashSet<Hash>,
+	}
+
+	#[async_trait::async_trait]
+	impl BlockImport<Block> for ChainImport {
+		type Error = sp_consensus::Error;
+
+		async fn check_block(
+			&mut self,
+			block: BlockCheckParams<Block>,
+		) -> Result<ImportResult, Self::Error> {
+			Ok(if self.known.contains(&block.hash) {
+				ImportResult::AlreadyInChain
+			} else if !self.known.contains(&block.parent_hash) {
+				ImportResult::UnknownParent
+			} else {
+				ImportResult::imported(false)
+			})
+		}
+
+		async fn import_block(
+			&mut self,
+			block: BlockImportParams<Block>,
+		) -> Result<ImportResult, Self::Error> {
+			self.known.insert(block.post_hash());
+			Ok(ImportResult::imported(true))
+		}
+	}
+
+	fn incoming_block(header: Header) -> IncomingBlock<Block> {
+		IncomingBlock {
+			hash: header.hash(),
+			header: Some(header),
+			body: None,
+			indexed_body: None,
+			justifications: None,
+			origin: None,
+			allow_missing_state: false,
+			import_existing: false,
+			state: None,
+			skip_execution: false,
+		}
+	}
+
+	fn chain(parent_hash: Hash, numbers: std::ops::RangeInclusive<BlockNumber>) -> Vec<Header> {
+		let mut parent_hash = parent_hash;
+		numbers
+			.map(|number| {
+				let header = Header {
+					parent_hash,
+					number,
+					extrinsics_root: Hash::random(),
+					state_root: Default::default(),
+					digest: Default::default(),
+				};
+				parent_hash = header.hash();
+				header
+			})
+			.collect()
+	}
+
+	#[test]
+	fn pipelined_import_cancels_blocks_after_failed_verification() {
+		let pipeline = VerificationPipeline {
+			verifier: Arc::new(RejectNumber(3)),
+			spawner: Box::new(sp_core::testing::TaskExecutor::new()),
+			parallelism: 2,
+		};
+
+		let blocks = (1..=5)
+			.map(|number| {
+				incoming_block(Header {
+					parent_hash: Hash::random(),
+					number,
+					extrinsics_root: Hash::random(),
+					state_root: Default::default(),
+					digest: Default::default(),
+				})
+			})
+			.collect::<Vec<_>>();
+		let hashes = blocks.iter().map(|block| block.hash).collect::<Vec<_>>();
+
+		let mut block_import: BoxBlockImport<Block> = Box::new(());
+		let result = block_on(import_many_blocks_pipelined(
+			&mut block_import,
+			BlockOrigin::NetworkInitialSync,
+			blocks,
+			&pipeline,
+			Duration::default(),
+			None,
+		));
+
+		assert_eq!(result.block_count, 5);
+		assert_eq!(result.imported, 2);
+		assert_eq!(result.results.iter().map(|(_, hash)| *hash).collect::<Vec<_>>(), hashes);
+		assert!(matches!(
+			result.results[2].0,
+			Err(BlockImportError::VerificationFailed(None, ref msg)) if msg == "rejected"
+		));
+		assert!(result.results[3..]
+			.iter()
+			.all(|(r, _)| matches!(r, Err(BlockImportError::Cancelled))));
+	}
+
+	#[test]
+	fn pipelined_import_checks_blocks_before_verifying_them() {
+		let verifier = Arc::new(RecordPreVerified::default());
+		let pipeline = VerificationPipeline {
+			verifier: verifier.clone(),
+			spawner: Box::new(sp_core::testing::TaskExecutor::new()),
+			parallelism: 2,
+		};
+
+		let genesis_hash = Hash::random();
+		let headers = chain(genesis_hash, 1..=5);
+		let known = [genesis_hash, headers[0].hash(), headers[1].hash()].into_iter().collect();
+		let mut block_import: BoxBlockImport<Block> = Box::new(ChainImport { known });
+
+		let result = block_on(import_many_blocks_pipelined(
+			&mut block_import,
+			BlockOrigin::NetworkInitialSync,
+			headers.into_iter().map(incoming_block).collect(),
+			&pipeline,
+			Duration::default(),
+			None,
+		));
+
+		// The known blocks are skipped, the others are imported on top of each other.
+		assert_eq!(result.imported, 5);
+		assert!(matches!(result.results[0].0, Ok(BlockImportStatus::ImportedKnown(1, None))));
+		assert!(matches!(result.results[1].0, Ok(BlockImportStatus::ImportedKnown(2, None))));
+		assert!(result.results[2..]
+			.iter()
+			.all(|(r, _)| matches!(r, Ok(BlockImportStatus::ImportedUnknown(..)))));
+		assert_eq!(*verifier.0.lock().unwrap(), vec![3, 4, 5]);
+	}
+
+	#[test]
+	fn pipelined_import_does_not_verify_blocks_with_unknown_parent() {
+		let verifier = Arc::new(RecordPreVerified::default());
+		let pipeline = VerificationPipeline {
+			verifier: verifier.clone(),
+			spawner: Box::new(sp_core::testing::TaskExecutor::new()),
+			parallelism: 2,
+		};
+
+		let known = [Hash::random()].into_iter().collect();
+		let mut block_import: BoxBlockImport<Block> = Box::new(ChainImport { known });
+
+		let result = block_on(import_many_blocks_pipelined(
+			&mut block_import,
+			BlockOrigin::NetworkInitialSync,
+			chain(Hash::random(), 1..=3).into_iter().map(incoming_block).collect(),
+			&pipeline,
+			Duration::default(),
+			None,
+		));
+
+		assert_eq!(result.imported, 0);
+		assert!(matches!(result.results[0].0, Err(BlockImportError::UnknownParent)));
+		assert!(result.results[1..]
+			.iter()
+			.all(|(r, _)| matches!(r, Err(BlockImportError::Cancelled))));
+		assert!(verifier.0.lock().unwrap().is_empty());
+	}
 }
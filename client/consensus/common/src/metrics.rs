@@ -32,6 +32,7 @@ use crate::import_queue::{BlockImportError, BlockImportStatus};
 pub(crate) struct Metrics {
 	pub import_queue_processed: CounterVec<U64>,
 	pub block_verification_time: HistogramVec,
+	pub block_import_time: Histogram,
 	pub block_verification_and_import_time: Histogram,
 	pub justification_import_time: Histogram,
 }
@@ -59,6 +60,13 @@ impl Metrics {
 				)?,
 				registry,
 			)?,
+			block_import_time: register(
+				Histogram::with_opts(HistogramOpts::new(
+					"substrate_block_import_time",
+					"Time taken to import verified blocks",
+				))?,
+				registry,
+			)?,
 			block_verification_and_import_time: register(
 				Histogram::with_opts(HistogramOpts::new(
 					"substrate_block_verification_and_import_time",
@@ -100,6 +108,10 @@ impl Metrics {
 			.observe(time.as_secs_f64());
 	}
 
+	pub fn report_import_time(&self, time: std::time::Duration) {
+		self.block_import_time.observe(time.as_secs_f64());
+	}
+
 	pub fn report_verification_and_import(&self, time: std::time::Duration) {
 		self.block_verification_and_import_time.observe(time.as_secs_f64());
 	}
@@ -0,0 +1,167 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Development chain controls: time travel, snapshots and impersonation.
+
+use crate::Error;
+use codec::{Decode, Encode};
+use sc_client_api::backend::Backend as ClientBackend;
+use sp_blockchain::HeaderBackend;
+use sp_inherents::{InherentData, InherentIdentifier};
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use sp_timestamp::TimestampInherentData;
+
+/// The inherent identifier of the calls to dispatch with an impersonated origin.
+///
+/// The data is a `Vec<ImpersonatedCall>`. Only runtimes built for development should turn it
+/// into extrinsics, since it allows dispatching any call on behalf of any account.
+pub const IMPERSONATED_CALLS_INHERENT_IDENTIFIER: InherentIdentifier = *b"impcalls";
+
+/// A call to dispatch on behalf of an account, without a signature.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct ImpersonatedCall {
+	/// The SCALE encoded account id of the impersonated origin.
+	pub origin: Vec<u8>,
+	/// The SCALE encoded runtime call.
+	pub call: Vec<u8>,
+}
+
+/// Overrides of the inherent data of the sealed blocks.
+#[derive(Debug, Default)]
+pub struct InherentDataOverrides {
+	/// The timestamp of the next block in milliseconds.
+	next_timestamp: Option<u64>,
+	/// The offset in milliseconds added to the timestamp of every block after a time jump.
+	time_offset: i128,
+	/// The calls to dispatch with an impersonated origin in the next block.
+	impersonated_calls: Vec<ImpersonatedCall>,
+}
+
+impl InherentDataOverrides {
+	/// Use `timestamp`, in milliseconds, for the next block.
+	///
+	/// The following blocks keep the same offset to the timestamps provided by the inherent data
+	/// providers, so time keeps moving forward from there.
+	pub fn set_next_timestamp(&mut self, timestamp: u64) {
+		self.next_timestamp = Some(timestamp);
+	}
+
+	/// Dispatch `call` with an impersonated origin in the next block.
+	pub fn impersonate(&mut self, call: ImpersonatedCall) {
+		self.impersonated_calls.push(call);
+	}
+
+	/// Apply the overrides to the `inherent_data` of the block about to be sealed.
+	pub fn apply(&mut self, inherent_data: &mut InherentData) -> Result<(), Error> {
+		let impersonated_calls = std::mem::take(&mut self.impersonated_calls);
+		if !impersonated_calls.is_empty() {
+			inherent_data.replace_data(IMPERSONATED_CALLS_INHERENT_IDENTIFIER, &impersonated_calls);
+		}
+
+		let provided = inherent_data.timestamp_inherent_data()?.map(|t| *t as i128);
+		let timestamp = match (self.next_timestamp.take(), provided) {
+			(Some(next), provided) => {
+				self.time_offset = provided.map_or(0, |provided| next as i128 - provided);
+				next
+			},
+			(None, Some(provided)) if self.time_offset != 0 =>
+				(provided + self.time_offset).clamp(0, u64::MAX as i128) as u64,
+			(None, _) => return Ok(()),
+		};
+
+		inherent_data.replace_data(
+			sp_timestamp::INHERENT_IDENTIFIER,
+			&sp_timestamp::InherentType::new(timestamp),
+		);
+		Ok(())
+	}
+}
+
+/// Revert the best chain to the snapshot block `hash`.
+///
+/// Only unfinalized blocks are reverted, so the snapshot has to be on the best chain and not
+/// below the last finalized block.
+pub fn revert_to_snapshot<B, C, CB>(client: &C, backend: &CB, hash: B::Hash) -> Result<(), Error>
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+	CB: ClientBackend<B>,
+{
+	let number = client.number(hash)?.ok_or_else(|| Error::BlockNotFound(hash.to_string()))?;
+	if client.hash(number)? != Some(hash) {
+		return Err(Error::StringError(format!("Snapshot block {} is not on the best chain", hash)))
+	}
+
+	let info = client.info();
+	if number < info.finalized_number {
+		return Err(Error::StringError(format!(
+			"Snapshot block {} is below the last finalized block",
+			hash
+		)))
+	}
+
+	let to_revert: NumberFor<B> = info.best_number - number;
+	backend.revert(to_revert, false)?;
+
+	let best_hash = client.info().best_hash;
+	if best_hash != hash {
+		return Err(Error::StringError(format!(
+			"Reverting to snapshot block {} stopped at {}",
+			hash, best_hash
+		)))
+	}
+
+	log::info!(target: crate::LOG_TARGET, "⏪ Reverted to snapshot block {}", hash);
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn inherent_data(timestamp: u64) -> InherentData {
+		let mut inherent_data = InherentData::new();
+		inherent_data
+			.put_data(
+				sp_timestamp::INHERENT_IDENTIFIER,
+				&sp_timestamp::InherentType::new(timestamp),
+			)
+			.unwrap();
+		inherent_data
+	}
+
+	fn apply(overrides: &mut InherentDataOverrides, timestamp: u64) -> u64 {
+		let mut inherent_data = inherent_data(timestamp);
+		overrides.apply(&mut inherent_data).unwrap();
+		*inherent_data.timestamp_inherent_data().unwrap().unwrap()
+	}
+
+	#[test]
+	fn time_offset_is_kept_after_time_travel() {
+		let mut overrides = InherentDataOverrides::default();
+		assert_eq!(apply(&mut overrides, 1_000), 1_000);
+
+		overrides.set_next_timestamp(61_000);
+		assert_eq!(apply(&mut overrides, 2_000), 61_000);
+		assert_eq!(apply(&mut overrides, 3_000), 62_000);
+
+		overrides.set_next_timestamp(10_000);
+		assert_eq!(apply(&mut overrides, 4_000), 10_000);
+		assert_eq!(apply(&mut overrides, 5_000), 11_000);
+	}
+}
@@ -38,6 +38,7 @@ mod codes {
 	pub const CONSENSUS_ERROR: i32 = 14_000;
 	pub const INHERENTS_ERROR: i32 = 15_000;
 	pub const BLOCKCHAIN_ERROR: i32 = 16_000;
+	pub const SNAPSHOT_NOT_FOUND: i32 = 17_000;
 	pub const UNKNOWN_ERROR: i32 = 20_000;
 }
 
@@ -64,6 +65,9 @@ pub enum Error {
 	/// Supplied parent_hash doesn't exist in chain
 	#[error("Supplied parent_hash: {0} doesn't exist in chain")]
 	BlockNotFound(String),
+	/// No snapshot with the supplied name was taken
+	#[error("Snapshot {0} doesn't exist")]
+	SnapshotNotFound(String),
 	/// Some string error
 	#[error("{0}")]
 	StringError(String),
@@ -96,6 +100,7 @@ impl Error {
 		match self {
 			BlockImportError(_) => codes::BLOCK_IMPORT_FAILED,
 			BlockNotFound(_) => codes::BLOCK_NOT_FOUND,
+			SnapshotNotFound(_) => codes::SNAPSHOT_NOT_FOUND,
 			EmptyTransactionPool => codes::EMPTY_TRANSACTION_POOL,
 			ConsensusError(_) => codes::CONSENSUS_ERROR,
 			InherentError(_) => codes::INHERENTS_ERROR,
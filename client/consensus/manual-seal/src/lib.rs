@@ -34,14 +34,18 @@ use sp_blockchain::HeaderBackend;
 use sp_consensus::{Environment, Proposer, SelectChain};
 use sp_core::traits::SpawnNamed;
 use sp_inherents::CreateInherentDataProviders;
-use sp_runtime::{traits::Block as BlockT, ConsensusEngineId};
-use std::{marker::PhantomData, sync::Arc, time::Duration};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT},
+	ConsensusEngineId,
+};
+use std::{collections::HashMap, marker::PhantomData, sync::Arc, time::Duration};
 
 mod error;
 mod finalize_block;
 mod seal_block;
 
 pub mod consensus;
+pub mod dev;
 pub mod rpc;
 
 pub use self::{
@@ -151,7 +155,48 @@ pub struct DelayedFinalizeParams<C, S> {
 }
 
 /// Creates the background authorship task for the manually seal engine.
+///
+/// [`EngineCommand::RevertToSnapshot`] is not supported, see [`run_manual_seal_with_backend`].
 pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
+	params: ManualSealParams<B, BI, E, C, TP, SC, CS, CIDP, P>,
+) where
+	B: BlockT + 'static,
+	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
+	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	CB: ClientBackend<B> + 'static,
+	E: Environment<B> + 'static,
+	E::Proposer: Proposer<B, Proof = P>,
+	CS: Stream<Item = EngineCommand<<B as BlockT>::Hash>> + Unpin + 'static,
+	SC: SelectChain<B> + 'static,
+	TP: TransactionPool<Block = B>,
+	CIDP: CreateInherentDataProviders<B, ()>,
+	P: codec::Encode + Send + Sync + 'static,
+{
+	run_manual_seal_inner(params, None).await
+}
+
+/// Creates the background authorship task for the manually seal engine, using `backend` to
+/// revert the chain to snapshots.
+pub async fn run_manual_seal_with_backend<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
+	params: ManualSealParams<B, BI, E, C, TP, SC, CS, CIDP, P>,
+	backend: Arc<CB>,
+) where
+	B: BlockT + 'static,
+	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
+	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	CB: ClientBackend<B> + 'static,
+	E: Environment<B> + 'static,
+	E::Proposer: Proposer<B, Proof = P>,
+	CS: Stream<Item = EngineCommand<<B as BlockT>::Hash>> + Unpin + 'static,
+	SC: SelectChain<B> + 'static,
+	TP: TransactionPool<Block = B>,
+	CIDP: CreateInherentDataProviders<B, ()>,
+	P: codec::Encode + Send + Sync + 'static,
+{
+	run_manual_seal_inner(params, Some(backend)).await
+}
+
+async fn run_manual_seal_inner<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 	ManualSealParams {
 		mut block_import,
 		mut env,
@@ -162,6 +207,7 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 		consensus_data_provider,
 		create_inherent_data_providers,
 	}: ManualSealParams<B, BI, E, C, TP, SC, CS, CIDP, P>,
+	backend: Option<Arc<CB>>,
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
@@ -175,6 +221,10 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 	CIDP: CreateInherentDataProviders<B, ()>,
 	P: codec::Encode + Send + Sync + 'static,
 {
+	// Set through the dev commands, applied to the sealed blocks.
+	let mut inherent_data_overrides = dev::InherentDataOverrides::default();
+	let mut snapshots = HashMap::new();
+
 	while let Some(command) = commands_stream.next().await {
 		match command {
 			EngineCommand::SealNewBlock { create_empty, finalize, parent_hash, sender } => {
@@ -190,9 +240,71 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 					pool: pool.clone(),
 					client: client.clone(),
 					create_inherent_data_providers: &create_inherent_data_providers,
+					inherent_data_overrides: &mut inherent_data_overrides,
 				})
 				.await;
 			},
+			EngineCommand::SealNewBlocks { count, finalize, mut sender } => {
+				let mut created = Vec::with_capacity(count as usize);
+				let mut result = Ok(());
+				for _ in 0..count {
+					let (block_sender, block_receiver) = futures::channel::oneshot::channel();
+					seal_block(SealBlockParams {
+						sender: Some(block_sender),
+						parent_hash: None,
+						finalize,
+						create_empty: true,
+						env: &mut env,
+						select_chain: &select_chain,
+						block_import: &mut block_import,
+						consensus_data_provider: consensus_data_provider.as_deref(),
+						pool: pool.clone(),
+						client: client.clone(),
+						create_inherent_data_providers: &create_inherent_data_providers,
+						inherent_data_overrides: &mut inherent_data_overrides,
+					})
+					.await;
+
+					match block_receiver.await {
+						Ok(Ok(block)) => created.push(block),
+						Ok(Err(e)) => {
+							result = Err(e);
+							break
+						},
+						Err(e) => {
+							result = Err(e.into());
+							break
+						},
+					}
+				}
+				rpc::send_result(&mut sender, result.map(|()| created));
+			},
+			EngineCommand::SetNextBlockTimestamp { timestamp, mut sender } => {
+				inherent_data_overrides.set_next_timestamp(timestamp);
+				rpc::send_result(&mut sender, Ok(()));
+			},
+			EngineCommand::ImpersonateCall { call, mut sender } => {
+				inherent_data_overrides.impersonate(call);
+				rpc::send_result(&mut sender, Ok(()));
+			},
+			EngineCommand::TakeSnapshot { name, mut sender } => {
+				let result = select_chain.best_chain().await.map_err(Error::from).map(|best| {
+					let hash = best.hash();
+					snapshots.insert(name, hash);
+					hash
+				});
+				rpc::send_result(&mut sender, result);
+			},
+			EngineCommand::RevertToSnapshot { name, mut sender } => {
+				let result = match (snapshots.get(&name), &backend) {
+					(None, _) => Err(Error::SnapshotNotFound(name)),
+					(Some(_), None) =>
+						Err(Error::StringError("Reverting requires the backend".into())),
+					(Some(hash), Some(backend)) =>
+						dev::revert_to_snapshot(&*client, &**backend, *hash).map(|()| *hash),
+				};
+				rpc::send_result(&mut sender, result);
+			},
 			EngineCommand::FinalizeBlock { hash, sender, justification } => {
 				let justification = justification.map(|j| (MANUAL_SEAL_ENGINE_ID, j));
 				finalize_block(FinalizeBlockParams {
@@ -808,4 +920,166 @@ mod tests {
 		let header = client.header(created_block.hash).unwrap().unwrap();
 		assert_eq!(header.number, 1);
 	}
+
+	#[tokio::test]
+	async fn manual_seal_snapshot_and_revert() {
+		let builder = TestClientBuilder::new();
+		let backend = builder.backend();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			api(),
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+		let future = run_manual_seal_with_backend(
+			ManualSealParams {
+				block_import: client.clone(),
+				env,
+				client: client.clone(),
+				pool: pool.clone(),
+				commands_stream,
+				select_chain,
+				consensus_data_provider: None,
+				create_inherent_data_providers: |_, _| async { Ok(()) },
+			},
+			backend,
+		);
+		std::thread::spawn(|| {
+			let rt = tokio::runtime::Runtime::new().unwrap();
+			rt.block_on(future);
+		});
+
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::SealNewBlocks { count: 2, finalize: false, sender: Some(tx) })
+			.await
+			.unwrap();
+		let created_blocks = rx.await.unwrap().unwrap();
+		assert_eq!(created_blocks.len(), 2);
+		assert_eq!(client.info().best_number, 2);
+
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::TakeSnapshot { name: "two".into(), sender: Some(tx) })
+			.await
+			.unwrap();
+		let snapshot = rx.await.unwrap().unwrap();
+		assert_eq!(snapshot, created_blocks[1].hash);
+
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::SealNewBlocks { count: 3, finalize: false, sender: Some(tx) })
+			.await
+			.unwrap();
+		rx.await.unwrap().unwrap();
+		assert_eq!(client.info().best_number, 5);
+
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::RevertToSnapshot { name: "two".into(), sender: Some(tx) })
+			.await
+			.unwrap();
+		assert_eq!(rx.await.unwrap().unwrap(), snapshot);
+		assert_eq!(client.info().best_hash, snapshot);
+
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::RevertToSnapshot { name: "unknown".into(), sender: Some(tx) })
+			.await
+			.unwrap();
+		assert!(matches!(rx.await.unwrap(), Err(Error::SnapshotNotFound(_))));
+	}
+
+	#[tokio::test]
+	async fn manual_seal_impersonated_call() {
+		use codec::Encode;
+		use sc_client_api::BlockBackend;
+		use substrate_test_runtime_client::runtime::{BalancesCall, RuntimeCall, TestAPI};
+
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			api(),
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+		let future = run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+		});
+		std::thread::spawn(|| {
+			let rt = tokio::runtime::Runtime::new().unwrap();
+			rt.block_on(future);
+		});
+
+		let balance_of = |hash| client.runtime_api().balance_of(hash, Bob.public()).unwrap();
+		let initial_balance = balance_of(genesis_hash);
+
+		let call = RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+			dest: Bob.public(),
+			value: 100,
+		});
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::ImpersonateCall {
+			call: dev::ImpersonatedCall { origin: Alice.public().encode(), call: call.encode() },
+			sender: Some(tx),
+		})
+		.await
+		.unwrap();
+		rx.await.unwrap().unwrap();
+
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::SealNewBlock {
+			parent_hash: None,
+			sender: Some(tx),
+			create_empty: true,
+			finalize: false,
+		})
+		.await
+		.unwrap();
+		let created_block = rx.await.unwrap().unwrap();
+
+		// the impersonated transfer is the only extrinsic, injected as an inherent.
+		let block = client.block(created_block.hash).unwrap().unwrap().block;
+		assert_eq!(block.extrinsics.len(), 1);
+		assert_eq!(balance_of(created_block.hash), initial_balance + 100);
+
+		// the impersonated calls are only dispatched in the next block.
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::SealNewBlock {
+			parent_hash: None,
+			sender: Some(tx),
+			create_empty: true,
+			finalize: false,
+		})
+		.await
+		.unwrap();
+		let created_block = rx.await.unwrap().unwrap();
+		let block = client.block(created_block.hash).unwrap().unwrap().block;
+		assert!(block.extrinsics.is_empty());
+		assert_eq!(balance_of(created_block.hash), initial_balance + 100);
+	}
 }
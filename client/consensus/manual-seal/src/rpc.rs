@@ -29,6 +29,7 @@ use jsonrpsee::{
 };
 use sc_consensus::ImportedAux;
 use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
 use sp_runtime::EncodedJustification;
 
 /// Sender passed to the authorship task to report errors or successes.
@@ -62,6 +63,46 @@ pub enum EngineCommand<Hash> {
 		/// finalization justification
 		justification: Option<EncodedJustification>,
 	},
+	/// Tells the engine to seal `count` blocks on top of the best block, creating empty blocks if
+	/// there are no transactions in the transaction pool.
+	SealNewBlocks {
+		/// the number of blocks to seal.
+		count: u32,
+		/// instantly finalize the blocks?
+		finalize: bool,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<Vec<CreatedBlock<Hash>>>,
+	},
+	/// Tells the engine to use the given timestamp for the next block it seals.
+	SetNextBlockTimestamp {
+		/// the timestamp in milliseconds.
+		timestamp: u64,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
+	/// Tells the engine to remember the best block under the given name.
+	TakeSnapshot {
+		/// the name of the snapshot, replacing any previous snapshot with the same name.
+		name: String,
+		/// sender to report the snapshotted block to the rpc.
+		sender: Sender<Hash>,
+	},
+	/// Tells the engine to revert the best chain to the block of a snapshot.
+	RevertToSnapshot {
+		/// the name of the snapshot.
+		name: String,
+		/// sender to report the new best block to the rpc.
+		sender: Sender<Hash>,
+	},
+	/// Tells the engine to dispatch a call on behalf of an account in the next block it seals.
+	///
+	/// See [`crate::dev::IMPERSONATED_CALLS_INHERENT_IDENTIFIER`].
+	ImpersonateCall {
+		/// the call and the impersonated origin.
+		call: crate::dev::ImpersonatedCall,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
 }
 
 /// RPC trait that provides methods for interacting with the manual-seal authorship task over rpc.
@@ -83,6 +124,38 @@ pub trait ManualSealApi<Hash> {
 		hash: Hash,
 		justification: Option<EncodedJustification>,
 	) -> RpcResult<bool>;
+
+	/// Instructs the manual-seal authorship task to create `count` blocks on top of the best
+	/// block.
+	#[method(name = "engine_createBlocks")]
+	async fn create_blocks(&self, count: u32, finalize: bool)
+		-> RpcResult<Vec<CreatedBlock<Hash>>>;
+
+	/// Sets the timestamp, in milliseconds, of the next block created by the manual-seal
+	/// authorship task.
+	///
+	/// Later blocks keep the same offset to the timestamps provided by the inherent data
+	/// providers.
+	#[method(name = "engine_setNextBlockTimestamp")]
+	async fn set_next_block_timestamp(&self, timestamp: u64) -> RpcResult<bool>;
+
+	/// Remembers the best block under `name`, to revert to it later.
+	#[method(name = "engine_snapshot")]
+	async fn snapshot(&self, name: String) -> RpcResult<Hash>;
+
+	/// Reverts the best chain to the block remembered under `name`.
+	///
+	/// Only unfinalized blocks can be reverted.
+	#[method(name = "engine_revert")]
+	async fn revert(&self, name: String) -> RpcResult<Hash>;
+
+	/// Dispatches the SCALE encoded `call` on behalf of the account with the SCALE encoded id
+	/// `origin` in the next block.
+	///
+	/// The runtime has to support impersonation, see
+	/// [`crate::dev::IMPERSONATED_CALLS_INHERENT_IDENTIFIER`].
+	#[method(name = "engine_impersonate")]
+	async fn impersonate(&self, origin: Bytes, call: Bytes) -> RpcResult<bool>;
 }
 
 /// A struct that implements the [`ManualSealApiServer`].
@@ -106,6 +179,22 @@ impl<Hash> ManualSeal<Hash> {
 	pub fn new(import_block_channel: mpsc::Sender<EngineCommand<Hash>>) -> Self {
 		Self { import_block_channel }
 	}
+
+	/// Send the command created by `command` to the authorship task and wait for the result.
+	async fn send_command<T>(
+		&self,
+		command: impl FnOnce(Sender<T>) -> EngineCommand<Hash>,
+	) -> RpcResult<T> {
+		let mut sink = self.import_block_channel.clone();
+		let (sender, receiver) = oneshot::channel();
+		sink.send(command(Some(sender))).await?;
+
+		match receiver.await {
+			Ok(Ok(rx)) => Ok(rx),
+			Ok(Err(e)) => Err(e.into()),
+			Err(e) => Err(JsonRpseeError::to_call_error(e)),
+		}
+	}
 }
 
 #[async_trait]
@@ -146,6 +235,37 @@ impl<Hash: Send + 'static> ManualSealApiServer<Hash> for ManualSeal<Hash> {
 		sink.send(command).await?;
 		receiver.await.map(|_| true).map_err(|e| JsonRpseeError::to_call_error(e))
 	}
+
+	async fn create_blocks(
+		&self,
+		count: u32,
+		finalize: bool,
+	) -> RpcResult<Vec<CreatedBlock<Hash>>> {
+		self.send_command(|sender| EngineCommand::SealNewBlocks { count, finalize, sender })
+			.await
+	}
+
+	async fn set_next_block_timestamp(&self, timestamp: u64) -> RpcResult<bool> {
+		self.send_command(|sender| EngineCommand::SetNextBlockTimestamp { timestamp, sender })
+			.await
+			.map(|_| true)
+	}
+
+	async fn snapshot(&self, name: String) -> RpcResult<Hash> {
+		self.send_command(|sender| EngineCommand::TakeSnapshot { name, sender }).await
+	}
+
+	async fn revert(&self, name: String) -> RpcResult<Hash> {
+		self.send_command(|sender| EngineCommand::RevertToSnapshot { name, sender })
+			.await
+	}
+
+	async fn impersonate(&self, origin: Bytes, call: Bytes) -> RpcResult<bool> {
+		let call = crate::dev::ImpersonatedCall { origin: origin.0, call: call.0 };
+		self.send_command(|sender| EngineCommand::ImpersonateCall { call, sender })
+			.await
+			.map(|_| true)
+	}
 }
 
 /// report any errors or successes encountered by the authorship task back
@@ -38,10 +38,15 @@
 //! for the auxiliary storage. It is also possible to just use the runtime
 //! as the storage, but it is not recommended as it won't work well with light
 //! clients.
+//!
+//! Miners running in a separate process can fetch the work and submit seals
+//! through the RPC methods in [`rpc`], backed by the [`MiningHandle`].
 
+pub mod rpc;
+pub mod sha3_pow;
 mod worker;
 
-pub use crate::worker::{MiningBuild, MiningHandle, MiningMetadata};
+pub use crate::worker::{MiningBuild, MiningHandle, MiningMetadata, SubmitError, Version};
 
 use crate::worker::UntilImportedOrTimeout;
 use codec::{Decode, Encode};
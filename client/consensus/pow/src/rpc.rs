@@ -0,0 +1,298 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for external miners.
+//!
+//! Miners poll `pow_getWork` for the pre-hash and difficulty of the current build and submit
+//! the seals they find with `pow_submitWork`. Seals for a build which is no longer current are
+//! rejected as stale.
+
+use crate::{MiningHandle, PowAlgorithm, SubmitError};
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+/// Error codes of `pow_submitWork`.
+mod codes {
+	pub const NO_WORK: i32 = 10_000;
+	pub const STALE_WORK: i32 = 10_001;
+	pub const INVALID_SEAL: i32 = 10_002;
+	pub const IMPORT_FAILED: i32 = 10_003;
+}
+
+impl From<SubmitError> for JsonRpseeError {
+	fn from(err: SubmitError) -> Self {
+		let code = match err {
+			SubmitError::NoWork => codes::NO_WORK,
+			SubmitError::Stale => codes::STALE_WORK,
+			SubmitError::InvalidSeal | SubmitError::Verification(_) => codes::INVALID_SEAL,
+			SubmitError::Import(_) => codes::IMPORT_FAILED,
+		};
+		CallError::Custom(ErrorObject::owned(code, err.to_string(), None::<()>)).into()
+	}
+}
+
+/// The work to mine on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Work<Hash, Difficulty> {
+	/// The version of the build, which changes whenever the work changes.
+	pub version: u64,
+	/// The best block the build is built on.
+	pub best_hash: Hash,
+	/// The pre-hash to mine on.
+	pub pre_hash: Hash,
+	/// The pre-runtime digest of the build.
+	pub pre_runtime: Option<Bytes>,
+	/// The difficulty the seal has to meet.
+	pub difficulty: Difficulty,
+}
+
+/// RPC trait that provides methods for external miners.
+#[rpc(client, server)]
+pub trait PowApi<Hash, Difficulty> {
+	/// Returns the current work, or `None` if there is nothing to mine on, e.g. because the node
+	/// is syncing.
+	#[method(name = "pow_getWork")]
+	fn get_work(&self) -> RpcResult<Option<Work<Hash, Difficulty>>>;
+
+	/// Submits a seal mined on the work with `pre_hash`, and imports the sealed block.
+	#[method(name = "pow_submitWork")]
+	async fn submit_work(&self, pre_hash: Hash, seal: Bytes) -> RpcResult<bool>;
+}
+
+/// A struct that implements the [`PowApiServer`].
+pub struct Pow<Block: BlockT, Algorithm: PowAlgorithm<Block>, L, Proof>
+where
+	L: sc_consensus::JustificationSyncLink<Block>,
+{
+	handle: MiningHandle<Block, Algorithm, L, Proof>,
+}
+
+impl<Block, Algorithm, L, Proof> Pow<Block, Algorithm, L, Proof>
+where
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block>,
+	L: sc_consensus::JustificationSyncLink<Block>,
+{
+	/// Create a new instance serving the work of `handle`.
+	pub fn new(handle: MiningHandle<Block, Algorithm, L, Proof>) -> Self {
+		Self { handle }
+	}
+}
+
+#[async_trait]
+impl<Block, Algorithm, L, Proof> PowApiServer<Block::Hash, Algorithm::Difficulty>
+	for Pow<Block, Algorithm, L, Proof>
+where
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block> + Send + Sync + 'static,
+	Algorithm::Difficulty: Serialize + DeserializeOwned + Send + Sync + 'static,
+	L: sc_consensus::JustificationSyncLink<Block> + 'static,
+	Proof: Send + 'static,
+{
+	fn get_work(&self) -> RpcResult<Option<Work<Block::Hash, Algorithm::Difficulty>>> {
+		Ok(self.handle.versioned_metadata().map(|(version, metadata)| Work {
+			version: version.0 as u64,
+			best_hash: metadata.best_hash,
+			pre_hash: metadata.pre_hash,
+			pre_runtime: metadata.pre_runtime.map(Into::into),
+			difficulty: metadata.difficulty,
+		}))
+	}
+
+	async fn submit_work(&self, pre_hash: Block::Hash, seal: Bytes) -> RpcResult<bool> {
+		self.handle.submit_work(&pre_hash, seal.0).await?;
+		Ok(true)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		sha3_pow::{mine, Sha3Algorithm},
+		MiningBuild, MiningMetadata,
+	};
+	use codec::Encode;
+	use futures::executor::block_on;
+	use jsonrpsee::{types::EmptyServerParams as EmptyParams, RpcModule};
+	use parking_lot::Mutex;
+	use sc_consensus::{BlockCheckParams, BlockImport, BlockImportParams, ImportResult};
+	use sp_consensus::{Error as ConsensusError, Proposal};
+	use sp_core::{H256, U256};
+	use sp_runtime::{
+		testing::{Block as RawBlock, ExtrinsicWrapper, Header},
+		traits::Header as HeaderT,
+	};
+	use std::sync::Arc;
+
+	type Block = RawBlock<ExtrinsicWrapper<u64>>;
+
+	const DIFFICULTY: u64 = 100;
+
+	/// Records the hashes of the imported blocks.
+	#[derive(Clone, Default)]
+	struct RecordImport(Arc<Mutex<Vec<H256>>>);
+
+	#[async_trait]
+	impl BlockImport<Block> for RecordImport {
+		type Error = ConsensusError;
+
+		async fn check_block(
+			&mut self,
+			_block: BlockCheckParams<Block>,
+		) -> Result<ImportResult, Self::Error> {
+			Ok(ImportResult::imported(false))
+		}
+
+		async fn import_block(
+			&mut self,
+			block: BlockImportParams<Block>,
+		) -> Result<ImportResult, Self::Error> {
+			self.0.lock().push(block.post_hash());
+			Ok(ImportResult::imported(true))
+		}
+	}
+
+	type Handle = MiningHandle<Block, Sha3Algorithm, (), ()>;
+
+	fn setup() -> (Handle, RpcModule<Pow<Block, Sha3Algorithm, (), ()>>, RecordImport) {
+		let block_import = RecordImport::default();
+		let handle = MiningHandle::new(
+			Sha3Algorithm::new(U256::from(DIFFICULTY)),
+			Box::new(block_import.clone()),
+			(),
+		);
+		let api = Pow::new(handle.clone()).into_rpc();
+
+		(handle, api, block_import)
+	}
+
+	/// A build of a block with `number` on top of a block filled with `number`.
+	fn build(number: u64) -> MiningBuild<Block, Sha3Algorithm, ()> {
+		let mut header = Header::new_from_number(number);
+		header.set_parent_hash(H256::repeat_byte(number as u8));
+		let block = Block { header, extrinsics: Vec::new() };
+
+		MiningBuild {
+			metadata: MiningMetadata {
+				best_hash: *block.header.parent_hash(),
+				pre_hash: block.header.hash(),
+				pre_runtime: None,
+				difficulty: U256::from(DIFFICULTY),
+			},
+			proposal: Proposal { block, proof: (), storage_changes: Default::default() },
+		}
+	}
+
+	fn get_work(api: &RpcModule<Pow<Block, Sha3Algorithm, (), ()>>) -> Option<Work<H256, U256>> {
+		block_on(api.call("pow_getWork", EmptyParams::new())).unwrap()
+	}
+
+	fn submit_work(
+		api: &RpcModule<Pow<Block, Sha3Algorithm, (), ()>>,
+		pre_hash: H256,
+		seal: Vec<u8>,
+	) -> Result<bool, JsonRpseeError> {
+		block_on(api.call("pow_submitWork", (pre_hash, Bytes(seal))))
+	}
+
+	fn seal_for(pre_hash: &H256) -> Vec<u8> {
+		mine(pre_hash.as_bytes(), U256::from(DIFFICULTY), 0..).unwrap().encode()
+	}
+
+	fn assert_error_code(result: Result<bool, JsonRpseeError>, code: i32) {
+		match result {
+			Err(JsonRpseeError::Call(CallError::Custom(err))) => assert_eq!(err.code(), code),
+			other => panic!("Expected error code {}, got {:?}", code, other),
+		}
+	}
+
+	#[test]
+	fn get_work_returns_the_current_build() {
+		let (handle, api, _) = setup();
+		assert_eq!(get_work(&api), None);
+
+		let first = build(1);
+		let metadata = first.metadata.clone();
+		handle.on_build(first);
+		let work = get_work(&api).unwrap();
+		assert_eq!(
+			work,
+			Work {
+				version: work.version,
+				best_hash: metadata.best_hash,
+				pre_hash: metadata.pre_hash,
+				pre_runtime: None,
+				difficulty: U256::from(DIFFICULTY),
+			}
+		);
+
+		handle.on_build(build(2));
+		let next = get_work(&api).unwrap();
+		assert_ne!(next.version, work.version);
+		assert_ne!(next.pre_hash, work.pre_hash);
+
+		handle.on_major_syncing();
+		assert_eq!(get_work(&api), None);
+	}
+
+	#[test]
+	fn submit_work_imports_the_sealed_block() {
+		let (handle, api, block_import) = setup();
+		assert_error_code(submit_work(&api, H256::zero(), Vec::new()), codes::NO_WORK);
+
+		handle.on_build(build(1));
+		let work = get_work(&api).unwrap();
+
+		assert_error_code(submit_work(&api, work.pre_hash, Vec::new()), codes::INVALID_SEAL);
+		assert!(block_import.0.lock().is_empty());
+
+		assert!(submit_work(&api, work.pre_hash, seal_for(&work.pre_hash)).unwrap());
+		assert_eq!(block_import.0.lock().len(), 1);
+
+		// The build is consumed by the import.
+		assert_eq!(get_work(&api), None);
+	}
+
+	#[test]
+	fn submit_work_rejects_stale_work() {
+		let (handle, api, block_import) = setup();
+
+		handle.on_build(build(1));
+		let stale = get_work(&api).unwrap();
+		handle.on_build(build(2));
+
+		// The seal is valid, but for the previous build.
+		assert_error_code(
+			submit_work(&api, stale.pre_hash, seal_for(&stale.pre_hash)),
+			codes::STALE_WORK,
+		);
+		assert!(block_import.0.lock().is_empty());
+
+		let work = get_work(&api).unwrap();
+		assert!(submit_work(&api, work.pre_hash, seal_for(&work.pre_hash)).unwrap());
+		assert_eq!(block_import.0.lock().len(), 1);
+	}
+}
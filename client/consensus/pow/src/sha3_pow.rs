@@ -0,0 +1,143 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A reference [`PowAlgorithm`] using SHA3-256 with a fixed difficulty.
+//!
+//! The work for a nonce is `sha3_256(pre_hash ++ nonce)`, which meets the difficulty `d` if
+//! `work * d` doesn't overflow a [`U256`]. It is meant for development chains and for testing
+//! miners, not for securing a production chain.
+
+use crate::{Error, PowAlgorithm};
+use codec::{Decode, Encode};
+use sha3::{Digest, Sha3_256};
+use sp_consensus_pow::Seal;
+use sp_core::{H256, U256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// The seal of [`Sha3Algorithm`].
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode)]
+pub struct Sha3Seal {
+	/// The difficulty the seal was mined for.
+	pub difficulty: U256,
+	/// The work, see [`compute_work`].
+	pub work: H256,
+	/// The nonce.
+	pub nonce: u64,
+}
+
+/// Returns the work for `nonce` on top of `pre_hash`.
+pub fn compute_work(pre_hash: &[u8], nonce: u64) -> H256 {
+	let mut hasher = Sha3_256::new();
+	hasher.update(pre_hash);
+	hasher.update(nonce.encode());
+	H256::from_slice(&hasher.finalize())
+}
+
+/// Returns whether `work` meets `difficulty`.
+pub fn work_meets_difficulty(work: &H256, difficulty: U256) -> bool {
+	let (_, overflowed) = U256::from_big_endian(work.as_bytes()).overflowing_mul(difficulty);
+	!overflowed
+}
+
+/// Try the given `nonces` on top of `pre_hash` and return the seal of the first one meeting
+/// `difficulty`.
+pub fn mine(
+	pre_hash: &[u8],
+	difficulty: U256,
+	nonces: impl IntoIterator<Item = u64>,
+) -> Option<Sha3Seal> {
+	nonces.into_iter().find_map(|nonce| {
+		let work = compute_work(pre_hash, nonce);
+		work_meets_difficulty(&work, difficulty).then(|| Sha3Seal { difficulty, work, nonce })
+	})
+}
+
+/// A [`PowAlgorithm`] using SHA3-256 with a fixed difficulty.
+#[derive(Clone, Copy, Debug)]
+pub struct Sha3Algorithm {
+	difficulty: U256,
+}
+
+impl Sha3Algorithm {
+	/// Create a new algorithm requiring `difficulty` for every block.
+	pub fn new(difficulty: U256) -> Self {
+		Self { difficulty }
+	}
+}
+
+impl<B: BlockT> PowAlgorithm<B> for Sha3Algorithm {
+	type Difficulty = U256;
+
+	fn difficulty(&self, _parent: B::Hash) -> Result<U256, Error<B>> {
+		Ok(self.difficulty)
+	}
+
+	fn verify(
+		&self,
+		_parent: &BlockId<B>,
+		pre_hash: &B::Hash,
+		_pre_digest: Option<&[u8]>,
+		seal: &Seal,
+		difficulty: U256,
+	) -> Result<bool, Error<B>> {
+		let seal = match Sha3Seal::decode(&mut &seal[..]) {
+			Ok(seal) => seal,
+			Err(_) => return Ok(false),
+		};
+
+		Ok(seal.difficulty == difficulty &&
+			compute_work(pre_hash.as_ref(), seal.nonce) == seal.work &&
+			work_meets_difficulty(&seal.work, difficulty))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::testing::{Block as RawBlock, ExtrinsicWrapper};
+
+	type Block = RawBlock<ExtrinsicWrapper<u64>>;
+
+	#[test]
+	fn mined_seal_verifies() {
+		let algorithm = Sha3Algorithm::new(U256::from(100));
+		let pre_hash = H256::repeat_byte(1);
+
+		let seal = mine(pre_hash.as_bytes(), U256::from(100), 0..).unwrap();
+		let verify = |pre_hash: &H256, seal: &Sha3Seal, difficulty| {
+			PowAlgorithm::<Block>::verify(
+				&algorithm,
+				&BlockId::Number(0),
+				pre_hash,
+				None,
+				&seal.encode(),
+				difficulty,
+			)
+			.unwrap()
+		};
+
+		assert!(verify(&pre_hash, &seal, U256::from(100)));
+		assert!(!verify(&H256::repeat_byte(2), &seal, U256::from(100)));
+		assert!(!verify(&pre_hash, &seal, U256::from(101)));
+		assert!(!verify(
+			&pre_hash,
+			&Sha3Seal { nonce: seal.nonce + 1, ..seal.clone() },
+			U256::from(100)
+		));
+	}
+}
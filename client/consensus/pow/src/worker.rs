@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use futures::{
+	lock::Mutex as AsyncMutex,
 	prelude::*,
 	task::{Context, Poll},
 };
@@ -65,7 +66,27 @@ pub struct MiningBuild<Block: BlockT, Algorithm: PowAlgorithm<Block>, Proof> {
 
 /// Version of the mining worker.
 #[derive(Eq, PartialEq, Clone, Copy)]
-pub struct Version(usize);
+pub struct Version(pub(crate) usize);
+
+/// Error returned by [`MiningHandle::submit_work`].
+#[derive(Debug, thiserror::Error)]
+pub enum SubmitError {
+	/// There is no build to submit a seal for, e.g. because the node is syncing.
+	#[error("No work available")]
+	NoWork,
+	/// The seal was mined for a build which is no longer current.
+	#[error("Work is stale")]
+	Stale,
+	/// The seal doesn't meet the difficulty of the build.
+	#[error("Seal is invalid")]
+	InvalidSeal,
+	/// The seal couldn't be verified.
+	#[error("Seal verification failed: {0}")]
+	Verification(String),
+	/// The sealed block couldn't be imported.
+	#[error("Block import failed: {0}")]
+	Import(String),
+}
 
 /// Mining worker that exposes structs to query the current mining build and submit mined blocks.
 pub struct MiningHandle<
@@ -78,7 +99,7 @@ pub struct MiningHandle<
 	algorithm: Arc<Algorithm>,
 	justification_sync_link: Arc<L>,
 	build: Arc<Mutex<Option<MiningBuild<Block, Algorithm, Proof>>>>,
-	block_import: Arc<Mutex<BoxBlockImport<Block>>>,
+	block_import: Arc<AsyncMutex<BoxBlockImport<Block>>>,
 }
 
 impl<Block, Algorithm, L, Proof> MiningHandle<Block, Algorithm, L, Proof>
@@ -102,7 +123,7 @@ where
 			algorithm: Arc::new(algorithm),
 			justification_sync_link: Arc::new(justification_sync_link),
 			build: Arc::new(Mutex::new(None)),
-			block_import: Arc::new(Mutex::new(block_import)),
+			block_import: Arc::new(AsyncMutex::new(block_import)),
 		}
 	}
 
@@ -137,44 +158,82 @@ where
 		self.build.lock().as_ref().map(|b| b.metadata.clone())
 	}
 
+	/// Get a copy of the current mining metadata together with its version, if available.
+	pub fn versioned_metadata(
+		&self,
+	) -> Option<(Version, MiningMetadata<Block::Hash, Algorithm::Difficulty>)> {
+		// The version only changes while the build is locked.
+		let build = self.build.lock();
+		build.as_ref().map(|b| (self.version(), b.metadata.clone()))
+	}
+
 	/// Submit a mined seal. The seal will be validated again. Returns true if the submission is
 	/// successful.
 	pub async fn submit(&self, seal: Seal) -> bool {
-		if let Some(metadata) = self.metadata() {
-			match self.algorithm.verify(
-				&BlockId::Hash(metadata.best_hash),
-				&metadata.pre_hash,
-				metadata.pre_runtime.as_ref().map(|v| &v[..]),
-				&seal,
-				metadata.difficulty,
-			) {
-				Ok(true) => (),
-				Ok(false) => {
-					warn!(target: LOG_TARGET, "Unable to import mined block: seal is invalid",);
-					return false
-				},
-				Err(err) => {
-					warn!(target: LOG_TARGET, "Unable to import mined block: {}", err,);
-					return false
-				},
-			}
+		self.submit_inner(None, seal).await.is_ok()
+	}
+
+	/// Submit a seal mined for the build with `pre_hash`.
+	///
+	/// Unlike [`submit`](Self::submit), the seal is rejected with [`SubmitError::Stale`] if the
+	/// current build has a different pre-hash, e.g. because a new block was imported meanwhile.
+	pub async fn submit_work(&self, pre_hash: &Block::Hash, seal: Seal) -> Result<(), SubmitError> {
+		self.submit_inner(Some(pre_hash), seal).await
+	}
+
+	async fn submit_inner(
+		&self,
+		pre_hash: Option<&Block::Hash>,
+		seal: Seal,
+	) -> Result<(), SubmitError> {
+		let metadata = if let Some(metadata) = self.metadata() {
+			metadata
 		} else {
 			warn!(target: LOG_TARGET, "Unable to import mined block: metadata does not exist",);
-			return false
+			return Err(SubmitError::NoWork)
+		};
+
+		if pre_hash.map_or(false, |pre_hash| *pre_hash != metadata.pre_hash) {
+			debug!(target: LOG_TARGET, "Unable to import mined block: work is stale");
+			return Err(SubmitError::Stale)
 		}
 
-		let build = if let Some(build) = {
+		match self.algorithm.verify(
+			&BlockId::Hash(metadata.best_hash),
+			&metadata.pre_hash,
+			metadata.pre_runtime.as_ref().map(|v| &v[..]),
+			&seal,
+			metadata.difficulty,
+		) {
+			Ok(true) => (),
+			Ok(false) => {
+				warn!(target: LOG_TARGET, "Unable to import mined block: seal is invalid",);
+				return Err(SubmitError::InvalidSeal)
+			},
+			Err(err) => {
+				warn!(target: LOG_TARGET, "Unable to import mined block: {}", err,);
+				return Err(SubmitError::Verification(err.to_string()))
+			},
+		}
+
+		let build = {
 			let mut build = self.build.lock();
-			let value = build.take();
-			if value.is_some() {
-				self.increment_version();
+			match build.take() {
+				// The seal was verified against the metadata of this build.
+				Some(value) if value.metadata.pre_hash == metadata.pre_hash => {
+					self.increment_version();
+					value
+				},
+				Some(value) => {
+					*build = Some(value);
+					warn!(target: LOG_TARGET, "Unable to import mined block: build has changed",);
+					return Err(SubmitError::Stale)
+				},
+				None => {
+					warn!(target: LOG_TARGET, "Unable to import mined block: build does not exist",);
+					return Err(SubmitError::NoWork)
+				},
 			}
-			value
-		} {
-			build
-		} else {
-			warn!(target: LOG_TARGET, "Unable to import mined block: build does not exist",);
-			return false
 		};
 
 		let seal = DigestItem::Seal(POW_ENGINE_ID, seal);
@@ -192,7 +251,7 @@ where
 		import_block.insert_intermediate(INTERMEDIATE_KEY, intermediate);
 
 		let header = import_block.post_header();
-		let mut block_import = self.block_import.lock();
+		let mut block_import = self.block_import.lock().await;
 
 		match block_import.import_block(import_block).await {
 			Ok(res) => {
@@ -206,11 +265,11 @@ where
 					target: LOG_TARGET,
 					"✅ Successfully mined block on top of: {}", build.metadata.best_hash
 				);
-				true
+				Ok(())
 			},
 			Err(err) => {
 				warn!(target: LOG_TARGET, "Unable to import mined block: {}", err,);
-				false
+				Err(SubmitError::Import(err.to_string()))
 			},
 		}
 	}
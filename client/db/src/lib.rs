@@ -1124,7 +1124,23 @@ impl<Block: BlockT> Backend<Block> {
 				Err(as_is) => return Err(as_is.into()),
 			};
 
-		Self::from_database(db as Arc<_>, canonicalization_delay, &db_config, needs_init)
+		Self::from_database(db as Arc<_>, canonicalization_delay, &db_config, needs_init, false)
+	}
+
+	/// Open the existing database of a node read-only, which is possible while the node is
+	/// running.
+	///
+	/// Unlike [`Self::new`], this fails if there is no database at the configured source instead
+	/// of creating a new one. The database is neither upgraded nor written to, so the backend
+	/// can't import blocks.
+	pub fn open_existing(
+		db_config: DatabaseSettings,
+		canonicalization_delay: u64,
+	) -> ClientResult<Self> {
+		let db =
+			crate::utils::open_database_read_only::<Block>(&db_config.source, DatabaseType::Full)?;
+
+		Self::from_database(db as Arc<_>, canonicalization_delay, &db_config, false, true)
 	}
 
 	/// Reset the shared trie cache.
@@ -1185,6 +1201,7 @@ impl<Block: BlockT> Backend<Block> {
 		canonicalization_delay: u64,
 		config: &DatabaseSettings,
 		should_init: bool,
+		read_only: bool,
 	) -> ClientResult<Self> {
 		let mut db_init_transaction = Transaction::new();
 
@@ -1245,7 +1262,10 @@ impl<Block: BlockT> Backend<Block> {
 			});
 		}
 
-		db.commit(db_init_transaction)?;
+		// Nothing is written to a read-only database.
+		if !read_only {
+			db.commit(db_init_transaction)?;
+		}
 
 		Ok(backend)
 	}
@@ -110,6 +110,16 @@ pub fn upgrade_db<Block: BlockT>(db_path: &Path, db_type: DatabaseType) -> Upgra
 	Ok(())
 }
 
+/// Check that the database has the current version, without upgrading it.
+pub fn check_version(db_path: &Path) -> UpgradeResult<()> {
+	match current_version(db_path)? {
+		CURRENT_VERSION => Ok(()),
+		db_version if db_version > CURRENT_VERSION =>
+			Err(UpgradeError::FutureDatabaseVersion(db_version)),
+		db_version => Err(UpgradeError::UnsupportedVersion(db_version)),
+	}
+}
+
 /// Migration from version1 to version2:
 /// 1) the number of columns has changed from 11 to 12;
 /// 2) transactions column is added;
@@ -182,19 +182,32 @@ pub fn open_database<Block: BlockT>(
 	// NOTE: This function can be removed in a few releases
 	maybe_migrate_to_type_subdir::<Block>(db_source, db_type)?;
 
-	open_database_at::<Block>(db_source, db_type, create)
+	open_database_at::<Block>(db_source, db_type, create, false)
+}
+
+/// Opens the configured database read-only, e.g. the database of a running node.
+///
+/// Nothing is written to the database: it is neither migrated nor upgraded, and RocksDB is opened
+/// as a secondary instance. Fails if there is no database at the configured source.
+pub fn open_database_read_only<Block: BlockT>(
+	db_source: &DatabaseSource,
+	db_type: DatabaseType,
+) -> OpenDbResult {
+	open_database_at::<Block>(db_source, db_type, false, true)
 }
 
 fn open_database_at<Block: BlockT>(
 	db_source: &DatabaseSource,
 	db_type: DatabaseType,
 	create: bool,
+	read_only: bool,
 ) -> OpenDbResult {
 	let db: Arc<dyn Database<DbHash>> = match &db_source {
-		DatabaseSource::ParityDb { path } => open_parity_db::<Block>(path, db_type, create)?,
+		DatabaseSource::ParityDb { path } =>
+			open_parity_db::<Block>(path, db_type, create, read_only)?,
 		#[cfg(feature = "rocksdb")]
 		DatabaseSource::RocksDb { path, cache_size } =>
-			open_kvdb_rocksdb::<Block>(path, db_type, create, *cache_size)?,
+			open_kvdb_rocksdb::<Block>(path, db_type, create, read_only, *cache_size)?,
 		DatabaseSource::Custom { db, require_create_flag } => {
 			if *require_create_flag && !create {
 				return Err(OpenDbError::DoesNotExist)
@@ -203,16 +216,25 @@ fn open_database_at<Block: BlockT>(
 		},
 		DatabaseSource::Auto { paritydb_path, rocksdb_path, cache_size } => {
 			// check if rocksdb exists first, if not, open paritydb
-			match open_kvdb_rocksdb::<Block>(rocksdb_path, db_type, false, *cache_size) {
+			match open_kvdb_rocksdb::<Block>(rocksdb_path, db_type, false, read_only, *cache_size) {
 				Ok(db) => db,
 				Err(OpenDbError::NotEnabled(_)) | Err(OpenDbError::DoesNotExist) =>
-					open_parity_db::<Block>(paritydb_path, db_type, create)?,
+					open_parity_db::<Block>(paritydb_path, db_type, create, read_only)?,
 				Err(as_is) => return Err(as_is),
 			}
 		},
 	};
 
-	check_database_type(&*db, db_type)?;
+	if read_only {
+		match db.get(COLUMN_META, meta_keys::TYPE) {
+			Some(stored_type) if db_type.as_str().as_bytes() != &*stored_type =>
+				return Err(OpenDbError::UnexpectedDbType { expected: db_type, found: stored_type }),
+			Some(_) => (),
+			None => return Err(OpenDbError::DoesNotExist),
+		}
+	} else {
+		check_database_type(&*db, db_type)?;
+	}
 	Ok(db)
 }
 
@@ -281,7 +303,16 @@ impl From<io::Error> for OpenDbError {
 	}
 }
 
-fn open_parity_db<Block: BlockT>(path: &Path, db_type: DatabaseType, create: bool) -> OpenDbResult {
+fn open_parity_db<Block: BlockT>(
+	path: &Path,
+	db_type: DatabaseType,
+	create: bool,
+	read_only: bool,
+) -> OpenDbResult {
+	if read_only {
+		return Ok(crate::parity_db::open_read_only(path, db_type)?)
+	}
+
 	match crate::parity_db::open(path, db_type, create, false) {
 		Ok(db) => Ok(db),
 		Err(parity_db::Error::InvalidConfiguration(_)) => {
@@ -298,19 +329,40 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	path: &Path,
 	db_type: DatabaseType,
 	create: bool,
+	read_only: bool,
 	cache_size: usize,
 ) -> OpenDbResult {
-	// first upgrade database to required version
-	match crate::upgrade::upgrade_db::<Block>(path, db_type) {
-		// in case of missing version file, assume that database simply does not exist at given
-		// location
-		Ok(_) | Err(crate::upgrade::UpgradeError::MissingDatabaseVersionFile) => (),
-		Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err.to_string()).into()),
+	if read_only {
+		// a database that is read-only can't be upgraded, so it must have the latest version
+		match crate::upgrade::check_version(path) {
+			Ok(_) => (),
+			Err(crate::upgrade::UpgradeError::MissingDatabaseVersionFile) =>
+				return Err(OpenDbError::DoesNotExist),
+			Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err.to_string()).into()),
+		}
+	} else {
+		// first upgrade database to required version
+		match crate::upgrade::upgrade_db::<Block>(path, db_type) {
+			// in case of missing version file, assume that database simply does not exist at given
+			// location
+			Ok(_) | Err(crate::upgrade::UpgradeError::MissingDatabaseVersionFile) => (),
+			Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err.to_string()).into()),
+		}
 	}
 
 	// and now open database assuming that it has the latest version
 	let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS);
 	db_config.create_if_missing = create;
+	// a secondary instance never writes to the database, and can open it while the node is
+	// running. It only needs a directory of its own for its logs, removed once it is closed.
+	let secondary_dir = if read_only {
+		let dir = tempfile::Builder::new().prefix("substrate-db-secondary-").tempdir()?;
+		db_config.secondary = Some(dir.path().to_path_buf());
+		db_config.max_open_files = -1;
+		Some(dir)
+	} else {
+		None
+	};
 
 	let mut memory_budget = std::collections::HashMap::new();
 	match db_type {
@@ -339,8 +391,54 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 
 	let db = kvdb_rocksdb::Database::open(&db_config, path)?;
 	// write database version only after the database is succesfully opened
-	crate::upgrade::update_version(path)?;
-	Ok(sp_database::as_database(db))
+	if !read_only {
+		crate::upgrade::update_version(path)?;
+	}
+	let db = sp_database::as_database(db);
+	match secondary_dir {
+		Some(dir) => Ok(Arc::new(SecondaryDb { db, _dir: dir })),
+		None => Ok(db),
+	}
+}
+
+/// A secondary RocksDB instance, owning the directory of its own files.
+///
+/// The directory is removed after the database is closed.
+#[cfg(any(feature = "rocksdb", test))]
+struct SecondaryDb {
+	db: Arc<dyn Database<DbHash>>,
+	_dir: tempfile::TempDir,
+}
+
+#[cfg(any(feature = "rocksdb", test))]
+impl Database<DbHash> for SecondaryDb {
+	fn commit(&self, transaction: Transaction<DbHash>) -> sp_database::error::Result<()> {
+		self.db.commit(transaction)
+	}
+
+	fn get(&self, col: sp_database::ColumnId, key: &[u8]) -> Option<Vec<u8>> {
+		self.db.get(col, key)
+	}
+
+	fn contains(&self, col: sp_database::ColumnId, key: &[u8]) -> bool {
+		self.db.contains(col, key)
+	}
+
+	fn value_size(&self, col: sp_database::ColumnId, key: &[u8]) -> Option<usize> {
+		self.db.value_size(col, key)
+	}
+
+	fn with_get(&self, col: sp_database::ColumnId, key: &[u8], f: &mut dyn FnMut(&[u8])) {
+		self.db.with_get(col, key, f)
+	}
+
+	fn supports_ref_counting(&self) -> bool {
+		self.db.supports_ref_counting()
+	}
+
+	fn sanitize_key(&self, key: &mut Vec<u8>) {
+		self.db.sanitize_key(key)
+	}
 }
 
 #[cfg(not(any(feature = "rocksdb", test)))]
@@ -348,6 +446,7 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	_path: &Path,
 	_db_type: DatabaseType,
 	_create: bool,
+	_read_only: bool,
 	_cache_size: usize,
 ) -> OpenDbResult {
 	Err(OpenDbError::NotEnabled("with-kvdb-rocksdb"))
@@ -394,7 +493,7 @@ fn maybe_migrate_to_type_subdir<Block: BlockT>(
 			// database stored in the target directory and close the database on success.
 			let mut old_source = source.clone();
 			old_source.set_path(&basedir);
-			open_database_at::<Block>(&old_source, db_type, false)?;
+			open_database_at::<Block>(&old_source, db_type, false, false)?;
 
 			info!(
 				"Migrating database to a database-type-based subdirectory: '{:?}' -> '{:?}'",
@@ -824,4 +923,41 @@ mod tests {
 			assert!(db_res.is_ok(), "Existing parity database should be reopened");
 		}
 	}
+
+	#[cfg(feature = "rocksdb")]
+	#[test]
+	fn test_open_database_read_only() {
+		let db_dir = tempfile::TempDir::new().unwrap();
+		let paritydb_path = db_dir.path().join("paritydb");
+		let rocksdb_path = db_dir.path().join("rocksdb");
+		let paritydb = DatabaseSource::ParityDb { path: paritydb_path.clone() };
+		let rocksdb = DatabaseSource::RocksDb { path: rocksdb_path.clone(), cache_size: 128 };
+
+		// it should not create a new database
+		for source in [&paritydb, &rocksdb] {
+			assert!(matches!(
+				open_database_read_only::<Block>(source, DatabaseType::Full),
+				Err(OpenDbError::DoesNotExist)
+			));
+		}
+		assert!(!paritydb_path.join("metadata").exists());
+		assert!(!rocksdb_path.join("db_version").exists());
+
+		drop(open_database::<Block>(&paritydb, DatabaseType::Full, true).unwrap());
+		let db = open_database_read_only::<Block>(&paritydb, DatabaseType::Full)
+			.expect("Existing parity database should be opened read-only");
+		assert_eq!(
+			db.get(COLUMN_META, meta_keys::TYPE),
+			Some(DatabaseType::Full.as_str().as_bytes().to_vec())
+		);
+
+		// it should open the rocksdb database while it is open for writing
+		let primary = open_database::<Block>(&rocksdb, DatabaseType::Full, true).unwrap();
+		let db = open_database_read_only::<Block>(&rocksdb, DatabaseType::Full)
+			.expect("Open rocksdb database should be opened as a secondary instance");
+		let mut transaction = Transaction::new();
+		transaction.set(COLUMN_META, b"key", b"value");
+		assert!(db.commit(transaction).is_err(), "Secondary instance should not be writable");
+		drop(primary);
+	}
 }
@@ -99,6 +99,9 @@ pub enum Error {
 
 	#[error("Output exceeds bounds of wasm memory")]
 	OutputExceedsBounds,
+
+	#[error("Execution limit exceeded: {0}")]
+	ExecutionLimitExceeded(String),
 }
 
 impl From<&'static str> for Error {
@@ -0,0 +1,293 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Profiling of the host functions called by the runtime.
+//!
+//! Profiling is opt-in and has to be supported by the execution engine. It is enabled for every
+//! runtime call on the current thread while inside of [`collect`], and for all runtime calls after
+//! [`set_enabled`] was called, e.g. by `--runtime-profile`. In the latter case the profile of every
+//! call is logged under the [`LOG_TARGET`].
+
+use std::{
+	cell::RefCell,
+	collections::BTreeMap,
+	fmt,
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
+
+/// The log target under which the profile of every runtime call is logged.
+pub const LOG_TARGET: &str = "runtime-profile";
+
+/// Aggregated statistics of a single host function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HostFunctionStats {
+	/// The number of times the host function was called.
+	pub calls: u64,
+	/// The total time spent inside of the host function.
+	pub time: Duration,
+	/// The number of bytes the host function read from the linear memory of the runtime.
+	pub bytes_read: u64,
+	/// The number of bytes the host function wrote into the linear memory of the runtime.
+	pub bytes_written: u64,
+}
+
+impl HostFunctionStats {
+	fn merge(&mut self, other: &HostFunctionStats) {
+		self.calls += other.calls;
+		self.time += other.time;
+		self.bytes_read += other.bytes_read;
+		self.bytes_written += other.bytes_written;
+	}
+}
+
+/// The statistics of all host functions called during one or more runtime calls.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostFunctionProfile {
+	functions: BTreeMap<&'static str, HostFunctionStats>,
+}
+
+impl HostFunctionProfile {
+	/// Record a single call of the host function `name`.
+	pub fn record(
+		&mut self,
+		name: &'static str,
+		time: Duration,
+		bytes_read: u64,
+		bytes_written: u64,
+	) {
+		self.functions.entry(name).or_default().merge(&HostFunctionStats {
+			calls: 1,
+			time,
+			bytes_read,
+			bytes_written,
+		});
+	}
+
+	/// Add the statistics of `other` to `self`.
+	pub fn merge(&mut self, other: &HostFunctionProfile) {
+		for (name, stats) in &other.functions {
+			self.functions.entry(name).or_default().merge(stats);
+		}
+	}
+
+	/// Returns `true` if no host function was called.
+	pub fn is_empty(&self) -> bool {
+		self.functions.is_empty()
+	}
+
+	/// Returns the statistics of the host function `name`, if it was called.
+	pub fn get(&self, name: &str) -> Option<&HostFunctionStats> {
+		self.functions.get(name)
+	}
+
+	/// Iterate over the called host functions and their statistics, ordered by name.
+	pub fn iter(&self) -> impl Iterator<Item = (&'static str, &HostFunctionStats)> {
+		self.functions.iter().map(|(name, stats)| (*name, stats))
+	}
+
+	/// The total time spent inside of host functions.
+	pub fn total_time(&self) -> Duration {
+		self.functions.values().map(|stats| stats.time).sum()
+	}
+}
+
+/// Renders the profile as a table, with the most expensive host functions first.
+impl fmt::Display for HostFunctionProfile {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut functions = self.functions.iter().collect::<Vec<_>>();
+		functions.sort_by(|a, b| b.1.time.cmp(&a.1.time).then_with(|| a.0.cmp(b.0)));
+
+		writeln!(
+			f,
+			"{:<48} {:>10} {:>14} {:>14} {:>14}",
+			"host function", "calls", "time (µs)", "read (bytes)", "written (bytes)",
+		)?;
+		for (name, stats) in functions {
+			writeln!(
+				f,
+				"{:<48} {:>10} {:>14} {:>14} {:>14}",
+				name,
+				stats.calls,
+				stats.time.as_micros(),
+				stats.bytes_read,
+				stats.bytes_written,
+			)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Whether every runtime call is profiled and logged.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+	/// The profile collected by the innermost [`collect`] on this thread.
+	static COLLECTED: RefCell<Option<HostFunctionProfile>> = RefCell::new(None);
+}
+
+/// Run `f` and collect the profile of every runtime call made by it on the current thread.
+///
+/// Calls to `collect` may be nested, in which case the outer call also receives the profile
+/// collected by the inner one.
+pub fn collect<R>(f: impl FnOnce() -> R) -> (R, HostFunctionProfile) {
+	let mut guard =
+		RestoreOuter(Some(COLLECTED.with(|collected| collected.replace(Some(Default::default())))));
+	let result = f();
+	let profile = guard.restore();
+	COLLECTED.with(|collected| {
+		if let Some(outer) = collected.borrow_mut().as_mut() {
+			outer.merge(&profile);
+		}
+	});
+
+	(result, profile)
+}
+
+/// Restores the profile of the outer [`collect`] when dropped, also if the profiled closure
+/// panics.
+struct RestoreOuter(Option<Option<HostFunctionProfile>>);
+
+impl RestoreOuter {
+	/// Restore the outer profile and return the one collected since it was replaced.
+	fn restore(&mut self) -> HostFunctionProfile {
+		let outer = self.0.take().expect("only restored once; qed");
+		COLLECTED
+			.with(|collected| collected.replace(outer))
+			.expect("set in `collect` and only replaced by nested calls which restore it; qed")
+	}
+}
+
+impl Drop for RestoreOuter {
+	fn drop(&mut self) {
+		if let Some(outer) = self.0.take() {
+			COLLECTED.with(|collected| collected.replace(outer));
+		}
+	}
+}
+
+/// Profile and log every runtime call made by the process, not only the ones inside of
+/// [`collect`].
+pub fn set_enabled(enabled: bool) {
+	ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns `true` if runtime calls made on the current thread should be profiled.
+pub fn is_enabled() -> bool {
+	ENABLED.load(Ordering::Relaxed) || COLLECTED.with(|collected| collected.borrow().is_some())
+}
+
+/// Hand the profile of the runtime call `method` over to the current collector, and log it if
+/// profiling was enabled with [`set_enabled`].
+///
+/// This is meant to be called by the execution engines.
+pub fn submit(method: &str, profile: HostFunctionProfile) {
+	if ENABLED.load(Ordering::Relaxed) {
+		log::info!(
+			target: LOG_TARGET,
+			"Host functions called by `{}` ({} µs in total):\n{}",
+			method,
+			profile.total_time().as_micros(),
+			profile,
+		);
+	}
+
+	COLLECTED.with(|collected| {
+		if let Some(collected) = collected.borrow_mut().as_mut() {
+			collected.merge(&profile);
+		}
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn nested_collectors_receive_profiles() {
+		let ((_, inner), outer) = collect(|| {
+			submit("outer", {
+				let mut profile = HostFunctionProfile::default();
+				profile.record("ext_storage_get_version_1", Duration::from_micros(5), 32, 64);
+				profile
+			});
+
+			collect(|| {
+				submit("inner", {
+					let mut profile = HostFunctionProfile::default();
+					profile.record("ext_storage_get_version_1", Duration::from_micros(3), 32, 0);
+					profile.record("ext_storage_set_version_1", Duration::from_micros(1), 64, 0);
+					profile
+				});
+			})
+		});
+
+		assert_eq!(inner.get("ext_storage_get_version_1").unwrap().calls, 1);
+		assert_eq!(
+			outer.get("ext_storage_get_version_1"),
+			Some(&HostFunctionStats {
+				calls: 2,
+				time: Duration::from_micros(8),
+				bytes_read: 64,
+				bytes_written: 64,
+			}),
+		);
+		assert_eq!(outer.get("ext_storage_set_version_1").unwrap().calls, 1);
+	}
+
+	#[test]
+	fn panic_while_collecting_restores_outer_profile() {
+		let ((), outer) = collect(|| {
+			let result = std::panic::catch_unwind(|| {
+				collect(|| {
+					submit("inner", {
+						let mut profile = HostFunctionProfile::default();
+						profile.record(
+							"ext_storage_get_version_1",
+							Duration::from_micros(3),
+							32,
+							0,
+						);
+						profile
+					});
+					panic!("runtime call failed");
+				})
+			});
+			assert!(result.is_err());
+			submit("outer", {
+				let mut profile = HostFunctionProfile::default();
+				profile.record("ext_storage_set_version_1", Duration::from_micros(1), 64, 0);
+				profile
+			});
+		});
+
+		// The profile of the panicking collector is discarded instead of leaking into the outer
+		// one.
+		assert!(outer.get("ext_storage_get_version_1").is_none());
+		assert_eq!(outer.get("ext_storage_set_version_1").unwrap().calls, 1);
+		assert!(!is_enabled());
+	}
+
+	#[test]
+	fn only_enabled_explicitly_or_while_collecting() {
+		assert!(!is_enabled());
+		assert!(collect(is_enabled).0);
+		assert!(!is_enabled());
+	}
+}
@@ -20,6 +20,7 @@
 
 use crate::error::Error;
 use sp_wasm_interface::Value;
+use std::time::Duration;
 
 pub use sc_allocator::AllocationStats;
 
@@ -152,3 +153,27 @@ pub enum HeapAllocStrategy {
 		maximum_pages: Option<u32>,
 	},
 }
+
+/// Limits on the resources a single runtime call is allowed to consume.
+///
+/// These are meant for calls that originate from untrusted sources, like JSON-RPC clients. A call
+/// that hits one of the limits is aborted with [`Error::ExecutionLimitExceeded`].
+#[derive(Debug, Copy, Clone, PartialEq, Hash, Eq, Default)]
+pub struct ExecutionLimits {
+	/// The maximum wall-clock time a call is allowed to run for.
+	///
+	/// The deadline is only checked while executing wasm code, so a long running host function
+	/// will only be interrupted after it has returned.
+	pub max_duration: Option<Duration>,
+	/// The maximum size of the linear memory of the instance, in bytes.
+	///
+	/// This includes the memory requested by the module at instantiation.
+	pub max_memory: Option<usize>,
+}
+
+impl ExecutionLimits {
+	/// Returns `true` if no limit is set.
+	pub fn is_unlimited(&self) -> bool {
+		self.max_duration.is_none() && self.max_memory.is_none()
+	}
+}
@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	error::{Error, Result},
+	error::{Error, Result, WasmError},
 	wasm_runtime::{RuntimeCache, WasmExecutionMethod},
 	RuntimeVersionOf,
 };
@@ -33,10 +33,11 @@ use codec::Encode;
 use sc_executor_common::{
 	runtime_blob::RuntimeBlob,
 	wasm_runtime::{
-		AllocationStats, HeapAllocStrategy, WasmInstance, WasmModule, DEFAULT_HEAP_ALLOC_STRATEGY,
+		AllocationStats, ExecutionLimits, HeapAllocStrategy, WasmInstance, WasmModule,
+		DEFAULT_HEAP_ALLOC_STRATEGY,
 	},
 };
-use sp_core::traits::{CallContext, CodeExecutor, Externalities, RuntimeCode};
+use sp_core::traits::{CallContext, CodeExecutor, Externalities, FetchRuntimeCode, RuntimeCode};
 use sp_version::{GetNativeVersion, NativeVersion, RuntimeVersion};
 use sp_wasm_interface::{ExtendedHostFunctions, HostFunctions};
 
@@ -93,6 +94,8 @@ pub struct WasmExecutorBuilder<H> {
 	cache_path: Option<PathBuf>,
 	allow_missing_host_functions: bool,
 	runtime_cache_size: u8,
+	rpc_execution_limits: ExecutionLimits,
+	precompiled_runtimes_path: Option<PathBuf>,
 }
 
 impl<H> WasmExecutorBuilder<H> {
@@ -110,6 +113,8 @@ impl<H> WasmExecutorBuilder<H> {
 			runtime_cache_size: 4,
 			allow_missing_host_functions: false,
 			cache_path: None,
+			rpc_execution_limits: ExecutionLimits::default(),
+			precompiled_runtimes_path: None,
 		}
 	}
 
@@ -193,6 +198,31 @@ impl<H> WasmExecutorBuilder<H> {
 		self
 	}
 
+	/// Create the wasm executor with the given `rpc_execution_limits`.
+	///
+	/// The limits are applied to calls made with [`CallContext::Rpc`]. Such calls are always
+	/// executed in wasm and use the offchain heap allocation strategy.
+	///
+	/// By default RPC calls are not limited.
+	pub fn with_rpc_execution_limits(mut self, rpc_execution_limits: ExecutionLimits) -> Self {
+		self.rpc_execution_limits = rpc_execution_limits;
+		self
+	}
+
+	/// Create the wasm executor with the given `precompiled_runtimes_path`.
+	///
+	/// Before compiling a runtime the executor looks for an artifact of it in this directory, as
+	/// written by [`WasmExecutor::precompile_runtime`]. See [`crate::precompiled`] for details.
+	///
+	/// By default there is no `precompiled_runtimes_path` given.
+	pub fn with_precompiled_runtimes_path(
+		mut self,
+		precompiled_runtimes_path: impl Into<PathBuf>,
+	) -> Self {
+		self.precompiled_runtimes_path = Some(precompiled_runtimes_path.into());
+		self
+	}
+
 	/// Build the configured [`WasmExecutor`].
 	pub fn build(self) -> WasmExecutor<H> {
 		WasmExecutor {
@@ -204,13 +234,18 @@ impl<H> WasmExecutorBuilder<H> {
 				self.onchain_heap_alloc_strategy,
 			),
 			ignore_onchain_heap_pages: self.ignore_onchain_heap_pages,
-			cache: Arc::new(RuntimeCache::new(
-				self.max_runtime_instances,
-				self.cache_path.clone(),
-				self.runtime_cache_size,
-			)),
+			cache: Arc::new(
+				RuntimeCache::new(
+					self.max_runtime_instances,
+					self.cache_path.clone(),
+					self.runtime_cache_size,
+				)
+				.with_precompiled_runtimes_path(self.precompiled_runtimes_path.clone()),
+			),
 			cache_path: self.cache_path,
 			allow_missing_host_functions: self.allow_missing_host_functions,
+			rpc_execution_limits: self.rpc_execution_limits,
+			precompiled_runtimes_path: self.precompiled_runtimes_path,
 			phantom: PhantomData,
 		}
 	}
@@ -234,6 +269,10 @@ pub struct WasmExecutor<H> {
 	cache_path: Option<PathBuf>,
 	/// Ignore missing function imports.
 	allow_missing_host_functions: bool,
+	/// The execution limits for RPC calls.
+	rpc_execution_limits: ExecutionLimits,
+	/// The directory containing ahead-of-time compiled runtimes.
+	precompiled_runtimes_path: Option<PathBuf>,
 	phantom: PhantomData<H>,
 }
 
@@ -247,11 +286,78 @@ impl<H> Clone for WasmExecutor<H> {
 			cache: self.cache.clone(),
 			cache_path: self.cache_path.clone(),
 			allow_missing_host_functions: self.allow_missing_host_functions,
+			rpc_execution_limits: self.rpc_execution_limits,
+			precompiled_runtimes_path: self.precompiled_runtimes_path.clone(),
 			phantom: self.phantom,
 		}
 	}
 }
 
+impl<H> WasmExecutor<H> {
+	/// The heap allocation strategy used for on-chain calls into `runtime_code`.
+	fn on_chain_heap_alloc_strategy(&self, runtime_code: &RuntimeCode) -> HeapAllocStrategy {
+		if self.ignore_onchain_heap_pages {
+			self.default_onchain_heap_alloc_strategy
+		} else {
+			runtime_code
+				.heap_pages
+				.map(|h| HeapAllocStrategy::Static { extra_pages: h as _ })
+				.unwrap_or_else(|| self.default_onchain_heap_alloc_strategy)
+		}
+	}
+
+	/// The heap allocation strategy and the execution limits for a call into `runtime_code` made
+	/// in the given `context`.
+	fn call_config(
+		&self,
+		runtime_code: &RuntimeCode,
+		context: CallContext,
+	) -> (HeapAllocStrategy, ExecutionLimits) {
+		match context {
+			CallContext::Offchain =>
+				(self.default_offchain_heap_alloc_strategy, ExecutionLimits::default()),
+			CallContext::Onchain =>
+				(self.on_chain_heap_alloc_strategy(runtime_code), ExecutionLimits::default()),
+			CallContext::Rpc =>
+				(self.default_offchain_heap_alloc_strategy, self.rpc_execution_limits),
+		}
+	}
+
+	/// Compile `runtime_code` ahead of time into the precompiled runtimes directory.
+	///
+	/// An artifact is written for every heap allocation strategy the executor would use to run
+	/// the code without execution limits, i.e. for on-chain and off-chain calls. Once written,
+	/// the executor and any other executor configured the same way load the artifacts instead
+	/// of compiling the code.
+	///
+	/// Returns the paths of the written artifacts.
+	pub fn precompile_runtime(&self, runtime_code: &RuntimeCode) -> Result<Vec<PathBuf>> {
+		let dir = self.precompiled_runtimes_path.as_deref().ok_or_else(|| {
+			Error::Other("no directory for precompiled runtimes configured".into())
+		})?;
+		let code = runtime_code.fetch_runtime_code().ok_or(WasmError::CodeNotFound)?;
+
+		let mut heap_alloc_strategies = vec![self.on_chain_heap_alloc_strategy(runtime_code)];
+		if !heap_alloc_strategies.contains(&self.default_offchain_heap_alloc_strategy) {
+			heap_alloc_strategies.push(self.default_offchain_heap_alloc_strategy);
+		}
+
+		heap_alloc_strategies
+			.into_iter()
+			.map(|heap_alloc_strategy| {
+				crate::precompiled::precompile_runtime(
+					dir,
+					&code,
+					&runtime_code.hash,
+					self.method,
+					heap_alloc_strategy,
+				)
+				.map_err(Into::into)
+			})
+			.collect()
+	}
+}
+
 impl<H> WasmExecutor<H>
 where
 	H: HostFunctions,
@@ -298,6 +404,8 @@ where
 			)),
 			cache_path,
 			allow_missing_host_functions: false,
+			rpc_execution_limits: ExecutionLimits::default(),
+			precompiled_runtimes_path: None,
 			phantom: PhantomData,
 		}
 	}
@@ -331,6 +439,7 @@ where
 		runtime_code: &RuntimeCode,
 		ext: &mut dyn Externalities,
 		heap_alloc_strategy: HeapAllocStrategy,
+		execution_limits: ExecutionLimits,
 		f: F,
 	) -> Result<R>
 	where
@@ -346,6 +455,7 @@ where
 			ext,
 			self.method,
 			heap_alloc_strategy,
+			execution_limits,
 			self.allow_missing_host_functions,
 			|module, instance, version, ext| {
 				let module = AssertUnwindSafe(module);
@@ -419,6 +529,7 @@ where
 		let module = crate::wasm_runtime::create_wasm_runtime_with_code::<H>(
 			self.method,
 			self.default_onchain_heap_alloc_strategy,
+			ExecutionLimits::default(),
 			runtime_blob,
 			allow_missing_host_functions,
 			self.cache_path.as_deref(),
@@ -501,24 +612,13 @@ where
 			"Executing function",
 		);
 
-		let on_chain_heap_alloc_strategy = if self.ignore_onchain_heap_pages {
-			self.default_onchain_heap_alloc_strategy
-		} else {
-			runtime_code
-				.heap_pages
-				.map(|h| HeapAllocStrategy::Static { extra_pages: h as _ })
-				.unwrap_or_else(|| self.default_onchain_heap_alloc_strategy)
-		};
-
-		let heap_alloc_strategy = match context {
-			CallContext::Offchain => self.default_offchain_heap_alloc_strategy,
-			CallContext::Onchain => on_chain_heap_alloc_strategy,
-		};
+		let (heap_alloc_strategy, execution_limits) = self.call_config(runtime_code, context);
 
 		let result = self.with_instance(
 			runtime_code,
 			ext,
 			heap_alloc_strategy,
+			execution_limits,
 			|_, mut instance, _onchain_version, mut ext| {
 				with_externalities_safe(&mut **ext, move || instance.call_export(method, data))
 			},
@@ -537,19 +637,13 @@ where
 		ext: &mut dyn Externalities,
 		runtime_code: &RuntimeCode,
 	) -> Result<RuntimeVersion> {
-		let on_chain_heap_pages = if self.ignore_onchain_heap_pages {
-			self.default_onchain_heap_alloc_strategy
-		} else {
-			runtime_code
-				.heap_pages
-				.map(|h| HeapAllocStrategy::Static { extra_pages: h as _ })
-				.unwrap_or_else(|| self.default_onchain_heap_alloc_strategy)
-		};
+		let on_chain_heap_pages = self.on_chain_heap_alloc_strategy(runtime_code);
 
 		self.with_instance(
 			runtime_code,
 			ext,
 			on_chain_heap_pages,
+			ExecutionLimits::default(),
 			|_module, _instance, version, _ext| {
 				Ok(version.cloned().ok_or_else(|| Error::ApiError("Unknown version".into())))
 			},
@@ -654,25 +748,17 @@ impl<D: NativeExecutionDispatch + 'static> CodeExecutor for NativeElseWasmExecut
 			"Executing function",
 		);
 
-		let on_chain_heap_alloc_strategy = if self.wasm.ignore_onchain_heap_pages {
-			self.wasm.default_onchain_heap_alloc_strategy
-		} else {
-			runtime_code
-				.heap_pages
-				.map(|h| HeapAllocStrategy::Static { extra_pages: h as _ })
-				.unwrap_or_else(|| self.wasm.default_onchain_heap_alloc_strategy)
-		};
+		let (heap_alloc_strategy, execution_limits) = self.wasm.call_config(runtime_code, context);
 
-		let heap_alloc_strategy = match context {
-			CallContext::Offchain => self.wasm.default_offchain_heap_alloc_strategy,
-			CallContext::Onchain => on_chain_heap_alloc_strategy,
-		};
+		// Native execution can't be interrupted, so limited calls always run in wasm.
+		let use_native = use_native && execution_limits.is_unlimited();
 
 		let mut used_native = false;
 		let result = self.wasm.with_instance(
 			runtime_code,
 			ext,
 			heap_alloc_strategy,
+			execution_limits,
 			|_, mut instance, onchain_version, mut ext| {
 				let onchain_version =
 					onchain_version.ok_or_else(|| Error::ApiError("Unknown version".into()))?;
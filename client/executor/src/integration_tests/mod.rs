@@ -472,6 +472,7 @@ fn mk_test_runtime(
 	crate::wasm_runtime::create_wasm_runtime_with_code::<HostFunctions>(
 		wasm_method,
 		pages,
+		Default::default(),
 		blob,
 		true,
 		None,
@@ -698,6 +699,7 @@ fn memory_is_cleared_between_invocations(wasm_method: WasmExecutionMethod) {
 	let runtime = crate::wasm_runtime::create_wasm_runtime_with_code::<HostFunctions>(
 		wasm_method,
 		HeapAllocStrategy::Dynamic { maximum_pages: Some(1024) },
+		Default::default(),
 		RuntimeBlob::uncompress_if_needed(&binary[..]).unwrap(),
 		true,
 		None,
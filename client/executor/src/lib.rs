@@ -35,6 +35,7 @@
 mod executor;
 #[cfg(test)]
 mod integration_tests;
+pub mod precompiled;
 mod wasm_runtime;
 
 pub use self::{
@@ -51,8 +52,10 @@ pub use sp_version::{NativeVersion, RuntimeVersion};
 pub use sp_wasm_interface;
 
 pub use sc_executor_common::{
-	error,
-	wasm_runtime::{HeapAllocStrategy, DEFAULT_HEAP_ALLOC_PAGES, DEFAULT_HEAP_ALLOC_STRATEGY},
+	error, profiling,
+	wasm_runtime::{
+		ExecutionLimits, HeapAllocStrategy, DEFAULT_HEAP_ALLOC_PAGES, DEFAULT_HEAP_ALLOC_STRATEGY,
+	},
 };
 pub use sc_executor_wasmtime::InstantiationStrategy as WasmtimeInstantiationStrategy;
 
@@ -0,0 +1,278 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ahead-of-time compiled runtimes.
+//!
+//! A runtime can be compiled into a native artifact before it is needed, e.g. ahead of a runtime
+//! upgrade. The artifact is stored in a directory under a name derived from the hash of the
+//! runtime code and the [`Semantics`] it was compiled with. When the executor needs to create a
+//! runtime for a code hash it first looks for a matching artifact and only compiles the code if
+//! there is none, or if the artifact fails any of the integrity checks.
+//!
+//! An artifact file consists of [`MAGIC`], an encoded [`ArtifactHeader`] and the serialized
+//! wasmtime module. The header repeats the code hash and the semantics fingerprint, so a renamed
+//! file is rejected, and contains a checksum of the module to detect corrupted files.
+//!
+//! The artifacts are loaded as native code, so the directory must only be writable by the node
+//! operator.
+
+use crate::{error::WasmError, wasm_runtime::wasmtime_config, WasmExecutionMethod};
+
+use codec::{Decode, Encode};
+use sc_executor_common::{
+	runtime_blob::RuntimeBlob,
+	wasm_runtime::{ExecutionLimits, HeapAllocStrategy, WasmModule},
+};
+use sc_executor_wasmtime::Semantics;
+use sp_core::hexdisplay::HexDisplay;
+use sp_wasm_interface::HostFunctions;
+use std::{
+	fs,
+	io::Write,
+	path::{Path, PathBuf},
+};
+
+/// The prefix of every artifact file.
+const MAGIC: [u8; 8] = *b"scaot\0\0\x01";
+
+/// The file extension of artifact files.
+const EXTENSION: &str = "cwasm";
+
+/// The metadata stored in front of the compiled module.
+#[derive(Encode, Decode)]
+struct ArtifactHeader {
+	/// The hash of the runtime code the artifact was compiled from.
+	code_hash: Vec<u8>,
+	/// The fingerprint of the [`Semantics`] the artifact was compiled with.
+	semantics: [u8; 32],
+	/// The `blake2_256` hash of the compiled module.
+	checksum: [u8; 32],
+}
+
+/// Returns the fingerprint of `semantics`.
+///
+/// Every field of [`Semantics`] can influence the generated code, so all of them are included.
+fn semantics_fingerprint(semantics: &Semantics) -> [u8; 32] {
+	sp_core::blake2_256(format!("{:?}", semantics).as_bytes())
+}
+
+/// Returns the path of the artifact for `code_hash` compiled with `semantics` in `dir`.
+pub fn artifact_path(dir: &Path, code_hash: &[u8], semantics: &Semantics) -> PathBuf {
+	let fingerprint = semantics_fingerprint(semantics);
+	dir.join(format!(
+		"{}-{}.{}",
+		HexDisplay::from(&code_hash),
+		HexDisplay::from(&&fingerprint[..8]),
+		EXTENSION,
+	))
+}
+
+/// Compile `code` and store the resulting artifact in `dir`.
+///
+/// `code_hash` has to be the hash the executor sees for this code, i.e. the storage hash of the
+/// `:code` entry for an on-chain runtime. An existing artifact is replaced atomically.
+///
+/// Returns the path of the written artifact.
+pub fn precompile_runtime(
+	dir: &Path,
+	code: &[u8],
+	code_hash: &[u8],
+	wasm_method: WasmExecutionMethod,
+	heap_alloc_strategy: HeapAllocStrategy,
+) -> Result<PathBuf, WasmError> {
+	let blob = RuntimeBlob::uncompress_if_needed(code)?;
+	let semantics =
+		wasmtime_config(wasm_method, heap_alloc_strategy, ExecutionLimits::default(), false, None)
+			.semantics;
+	let module = sc_executor_wasmtime::prepare_runtime_artifact(blob, &semantics)?;
+
+	let header = ArtifactHeader {
+		code_hash: code_hash.to_vec(),
+		semantics: semantics_fingerprint(&semantics),
+		checksum: sp_core::blake2_256(&module),
+	};
+
+	let io_error = |e: std::io::Error| WasmError::Other(format!("cannot write artifact: {}", e));
+	fs::create_dir_all(dir).map_err(io_error)?;
+
+	let path = artifact_path(dir, code_hash, &semantics);
+	let tmp_path = path.with_extension("tmp");
+	let mut file = fs::File::create(&tmp_path).map_err(io_error)?;
+	file.write_all(&MAGIC).map_err(io_error)?;
+	file.write_all(&header.encode()).map_err(io_error)?;
+	file.write_all(&module).map_err(io_error)?;
+	file.sync_all().map_err(io_error)?;
+	fs::rename(&tmp_path, &path).map_err(io_error)?;
+
+	Ok(path)
+}
+
+/// Read the compiled module for `code_hash` and `semantics` from `dir`.
+///
+/// Returns `Ok(None)` if there is no artifact and an error if the artifact fails to verify.
+fn read_artifact(
+	dir: &Path,
+	code_hash: &[u8],
+	semantics: &Semantics,
+) -> Result<Option<Vec<u8>>, String> {
+	let path = artifact_path(dir, code_hash, semantics);
+	let mut bytes = match fs::read(&path) {
+		Ok(bytes) => bytes,
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+		Err(e) => return Err(format!("cannot read {}: {}", path.display(), e)),
+	};
+
+	let mut input = bytes.strip_prefix(&MAGIC[..]).ok_or("not an artifact file")?;
+	let header = ArtifactHeader::decode(&mut input)
+		.map_err(|e| format!("cannot decode artifact header: {}", e))?;
+	if header.code_hash != code_hash {
+		return Err("artifact was compiled from a different code".into())
+	}
+	if header.semantics != semantics_fingerprint(semantics) {
+		return Err("artifact was compiled with different semantics".into())
+	}
+	if header.checksum != sp_core::blake2_256(input) {
+		return Err("artifact checksum mismatch".into())
+	}
+
+	let module_offset = bytes.len() - input.len();
+	bytes.drain(..module_offset);
+
+	Ok(Some(bytes))
+}
+
+/// Create a runtime from the artifact for `code_hash` in `dir`, if there is a valid one.
+///
+/// Invalid or incompatible artifacts are logged and ignored, so the caller falls back to
+/// compiling the code.
+pub(crate) fn load_runtime<H>(
+	dir: &Path,
+	code_hash: &[u8],
+	wasm_method: WasmExecutionMethod,
+	heap_alloc_strategy: HeapAllocStrategy,
+	execution_limits: ExecutionLimits,
+	allow_missing_func_imports: bool,
+	cache_path: Option<&Path>,
+) -> Option<Box<dyn WasmModule>>
+where
+	H: HostFunctions,
+{
+	let config = wasmtime_config(
+		wasm_method,
+		heap_alloc_strategy,
+		execution_limits,
+		allow_missing_func_imports,
+		cache_path,
+	);
+
+	let module = match read_artifact(dir, code_hash, &config.semantics) {
+		Ok(Some(module)) => module,
+		Ok(None) => return None,
+		Err(e) => {
+			tracing::warn!(
+				target: "wasm-runtime",
+				code_hash = %HexDisplay::from(&code_hash),
+				"Ignoring precompiled runtime: {}",
+				e,
+			);
+			return None
+		},
+	};
+
+	// SAFETY: The module was produced by `prepare_runtime_artifact` in `precompile_runtime` and
+	//         its checksum was verified above. An artifact written by another version of wasmtime
+	//         or with another configuration results in an error.
+	match unsafe { sc_executor_wasmtime::create_runtime_from_artifact_bytes::<H>(&module, config) }
+	{
+		Ok(runtime) => {
+			tracing::debug!(
+				target: "wasm-runtime",
+				code_hash = %HexDisplay::from(&code_hash),
+				"Loaded precompiled runtime",
+			);
+			Some(Box::new(runtime))
+		},
+		Err(e) => {
+			tracing::warn!(
+				target: "wasm-runtime",
+				code_hash = %HexDisplay::from(&code_hash),
+				"Ignoring incompatible precompiled runtime: {}",
+				e,
+			);
+			None
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_executor_common::wasm_runtime::DEFAULT_HEAP_ALLOC_STRATEGY;
+
+	fn write_test_artifact(dir: &Path) -> PathBuf {
+		precompile_runtime(
+			dir,
+			substrate_test_runtime::wasm_binary_unwrap(),
+			b"code hash",
+			WasmExecutionMethod::default(),
+			DEFAULT_HEAP_ALLOC_STRATEGY,
+		)
+		.unwrap()
+	}
+
+	fn test_semantics(heap_alloc_strategy: HeapAllocStrategy) -> Semantics {
+		wasmtime_config(
+			WasmExecutionMethod::default(),
+			heap_alloc_strategy,
+			ExecutionLimits::default(),
+			false,
+			None,
+		)
+		.semantics
+	}
+
+	#[test]
+	fn artifact_roundtrip() {
+		let dir = tempfile::tempdir().unwrap();
+		write_test_artifact(dir.path());
+
+		let semantics = test_semantics(DEFAULT_HEAP_ALLOC_STRATEGY);
+		assert!(read_artifact(dir.path(), b"code hash", &semantics).unwrap().is_some());
+		assert!(read_artifact(dir.path(), b"other hash", &semantics).unwrap().is_none());
+
+		let other_semantics = test_semantics(HeapAllocStrategy::Static { extra_pages: 1 });
+		assert!(read_artifact(dir.path(), b"code hash", &other_semantics).unwrap().is_none());
+	}
+
+	#[test]
+	fn corrupted_artifact_is_rejected() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = write_test_artifact(dir.path());
+
+		let mut bytes = fs::read(&path).unwrap();
+		let last = bytes.len() - 1;
+		bytes[last] ^= 0xff;
+		fs::write(&path, bytes).unwrap();
+
+		let semantics = test_semantics(DEFAULT_HEAP_ALLOC_STRATEGY);
+		assert_eq!(
+			read_artifact(dir.path(), b"code hash", &semantics),
+			Err("artifact checksum mismatch".to_string()),
+		);
+	}
+}
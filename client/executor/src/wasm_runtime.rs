@@ -27,7 +27,7 @@ use codec::Decode;
 use parking_lot::Mutex;
 use sc_executor_common::{
 	runtime_blob::RuntimeBlob,
-	wasm_runtime::{HeapAllocStrategy, WasmInstance, WasmModule},
+	wasm_runtime::{ExecutionLimits, HeapAllocStrategy, WasmInstance, WasmModule},
 };
 use schnellru::{ByLength, LruMap};
 use sp_core::traits::{Externalities, FetchRuntimeCode, RuntimeCode};
@@ -66,6 +66,8 @@ struct VersionedRuntimeId {
 	wasm_method: WasmExecutionMethod,
 	/// The heap allocation strategy this runtime was created with.
 	heap_alloc_strategy: HeapAllocStrategy,
+	/// The execution limits this runtime was created with.
+	execution_limits: ExecutionLimits,
 }
 
 /// A Wasm runtime object along with its cached runtime version.
@@ -158,15 +160,21 @@ impl VersionedRuntime {
 /// the memory reset to the initial memory. So, one runtime instance is reused for every fetch
 /// request.
 ///
-/// The size of cache is configurable via the cli option `--runtime-cache-size`.
+/// The size of cache is configurable via the cli option `--runtime-cache-size`. Runtimes created
+/// with execution limits are kept in a separate cache of the same size, so that runtime calls
+/// made through RPC never evict the runtimes used to import and author blocks.
 pub struct RuntimeCache {
 	/// A cache of runtimes along with metadata.
 	///
 	/// Runtimes sorted by recent usage. The most recently used is at the front.
 	runtimes: Mutex<LruMap<VersionedRuntimeId, Arc<VersionedRuntime>>>,
+	/// Same as `runtimes`, for runtimes created with execution limits.
+	limited_runtimes: Mutex<LruMap<VersionedRuntimeId, Arc<VersionedRuntime>>>,
 	/// The size of the instances cache for each runtime.
 	max_runtime_instances: usize,
 	cache_path: Option<PathBuf>,
+	/// The directory containing ahead-of-time compiled runtimes.
+	precompiled_runtimes_path: Option<PathBuf>,
 }
 
 impl RuntimeCache {
@@ -179,14 +187,33 @@ impl RuntimeCache {
 	/// for caching.
 	///
 	/// `runtime_cache_size` specifies the number of different runtimes versions preserved in an
-	/// in-memory cache, must always be at least 1.
+	/// in-memory cache, must always be at least 1. The same number of runtimes with execution
+	/// limits is cached on top of that.
 	pub fn new(
 		max_runtime_instances: usize,
 		cache_path: Option<PathBuf>,
 		runtime_cache_size: u8,
 	) -> RuntimeCache {
 		let cap = ByLength::new(runtime_cache_size.max(1) as u32);
-		RuntimeCache { runtimes: Mutex::new(LruMap::new(cap)), max_runtime_instances, cache_path }
+		RuntimeCache {
+			runtimes: Mutex::new(LruMap::new(cap)),
+			limited_runtimes: Mutex::new(LruMap::new(cap)),
+			max_runtime_instances,
+			cache_path,
+			precompiled_runtimes_path: None,
+		}
+	}
+
+	/// Look for ahead-of-time compiled runtimes in `precompiled_runtimes_path` before compiling
+	/// a runtime.
+	///
+	/// See [`crate::precompiled`] for more details.
+	pub fn with_precompiled_runtimes_path(
+		mut self,
+		precompiled_runtimes_path: Option<PathBuf>,
+	) -> Self {
+		self.precompiled_runtimes_path = precompiled_runtimes_path;
+		self
 	}
 
 	/// Prepares a WASM module instance and executes given function for it.
@@ -202,6 +229,8 @@ impl RuntimeCache {
 	///
 	/// `heap_alloc_strategy` - The heap allocation strategy to use.
 	///
+	/// `execution_limits` - The limits applied to every call into the runtime.
+	///
 	/// `allow_missing_func_imports` - Ignore missing function imports.
 	///
 	/// `f` - Function to execute.
@@ -221,6 +250,7 @@ impl RuntimeCache {
 		ext: &mut dyn Externalities,
 		wasm_method: WasmExecutionMethod,
 		heap_alloc_strategy: HeapAllocStrategy,
+		execution_limits: ExecutionLimits,
 		allow_missing_func_imports: bool,
 		f: F,
 	) -> Result<Result<R, Error>, Error>
@@ -235,10 +265,16 @@ impl RuntimeCache {
 	{
 		let code_hash = &runtime_code.hash;
 
-		let versioned_runtime_id =
-			VersionedRuntimeId { code_hash: code_hash.clone(), heap_alloc_strategy, wasm_method };
+		let versioned_runtime_id = VersionedRuntimeId {
+			code_hash: code_hash.clone(),
+			heap_alloc_strategy,
+			wasm_method,
+			execution_limits,
+		};
 
-		let mut runtimes = self.runtimes.lock(); // this must be released prior to calling f
+		let runtimes =
+			if execution_limits.is_unlimited() { &self.runtimes } else { &self.limited_runtimes };
+		let mut runtimes = runtimes.lock(); // this must be released prior to calling f
 		let versioned_runtime = if let Some(versioned_runtime) = runtimes.get(&versioned_runtime_id)
 		{
 			versioned_runtime.clone()
@@ -249,12 +285,15 @@ impl RuntimeCache {
 
 			let result = create_versioned_wasm_runtime::<H>(
 				&code,
+				code_hash,
 				ext,
 				wasm_method,
 				heap_alloc_strategy,
+				execution_limits,
 				allow_missing_func_imports,
 				self.max_runtime_instances,
 				self.cache_path.as_deref(),
+				self.precompiled_runtimes_path.as_deref(),
 			);
 
 			match result {
@@ -290,6 +329,7 @@ impl RuntimeCache {
 pub fn create_wasm_runtime_with_code<H>(
 	wasm_method: WasmExecutionMethod,
 	heap_alloc_strategy: HeapAllocStrategy,
+	execution_limits: ExecutionLimits,
 	blob: RuntimeBlob,
 	allow_missing_func_imports: bool,
 	cache_path: Option<&Path>,
@@ -297,27 +337,44 @@ pub fn create_wasm_runtime_with_code<H>(
 where
 	H: HostFunctions,
 {
-	match wasm_method {
-		WasmExecutionMethod::Compiled { instantiation_strategy } =>
-			sc_executor_wasmtime::create_runtime::<H>(
-				blob,
-				sc_executor_wasmtime::Config {
-					allow_missing_func_imports,
-					cache_path: cache_path.map(ToOwned::to_owned),
-					semantics: sc_executor_wasmtime::Semantics {
-						heap_alloc_strategy,
-						instantiation_strategy,
-						deterministic_stack_limit: None,
-						canonicalize_nans: false,
-						parallel_compilation: true,
-						wasm_multi_value: false,
-						wasm_bulk_memory: false,
-						wasm_reference_types: false,
-						wasm_simd: false,
-					},
-				},
-			)
-			.map(|runtime| -> Box<dyn WasmModule> { Box::new(runtime) }),
+	sc_executor_wasmtime::create_runtime::<H>(
+		blob,
+		wasmtime_config(
+			wasm_method,
+			heap_alloc_strategy,
+			execution_limits,
+			allow_missing_func_imports,
+			cache_path,
+		),
+	)
+	.map(|runtime| -> Box<dyn WasmModule> { Box::new(runtime) })
+}
+
+/// The wasmtime configuration used to create runtimes with the given parameters.
+pub(crate) fn wasmtime_config(
+	wasm_method: WasmExecutionMethod,
+	heap_alloc_strategy: HeapAllocStrategy,
+	execution_limits: ExecutionLimits,
+	allow_missing_func_imports: bool,
+	cache_path: Option<&Path>,
+) -> sc_executor_wasmtime::Config {
+	let WasmExecutionMethod::Compiled { instantiation_strategy } = wasm_method;
+
+	sc_executor_wasmtime::Config {
+		allow_missing_func_imports,
+		cache_path: cache_path.map(ToOwned::to_owned),
+		semantics: sc_executor_wasmtime::Semantics {
+			heap_alloc_strategy,
+			instantiation_strategy,
+			deterministic_stack_limit: None,
+			canonicalize_nans: false,
+			parallel_compilation: true,
+			wasm_multi_value: false,
+			wasm_bulk_memory: false,
+			wasm_reference_types: false,
+			wasm_simd: false,
+			execution_limits,
+		},
 	}
 }
 
@@ -379,12 +436,15 @@ pub fn read_embedded_version(blob: &RuntimeBlob) -> Result<Option<RuntimeVersion
 
 fn create_versioned_wasm_runtime<H>(
 	code: &[u8],
+	code_hash: &[u8],
 	ext: &mut dyn Externalities,
 	wasm_method: WasmExecutionMethod,
 	heap_alloc_strategy: HeapAllocStrategy,
+	execution_limits: ExecutionLimits,
 	allow_missing_func_imports: bool,
 	max_instances: usize,
 	cache_path: Option<&Path>,
+	precompiled_runtimes_path: Option<&Path>,
 ) -> Result<VersionedRuntime, WasmError>
 where
 	H: HostFunctions,
@@ -398,13 +458,28 @@ where
 	// runtime.
 	let mut version = read_embedded_version(&blob)?;
 
-	let runtime = create_wasm_runtime_with_code::<H>(
-		wasm_method,
-		heap_alloc_strategy,
-		blob,
-		allow_missing_func_imports,
-		cache_path,
-	)?;
+	let precompiled = precompiled_runtimes_path.and_then(|path| {
+		crate::precompiled::load_runtime::<H>(
+			path,
+			code_hash,
+			wasm_method,
+			heap_alloc_strategy,
+			execution_limits,
+			allow_missing_func_imports,
+			cache_path,
+		)
+	});
+	let runtime = match precompiled {
+		Some(runtime) => runtime,
+		None => create_wasm_runtime_with_code::<H>(
+			wasm_method,
+			heap_alloc_strategy,
+			execution_limits,
+			blob,
+			allow_missing_func_imports,
+			cache_path,
+		)?,
+	};
 
 	// If the runtime blob doesn't embed the runtime version then use the legacy version query
 	// mechanism: call the runtime.
@@ -554,4 +629,45 @@ mod tests {
 
 		assert_eq!(runtime_version, read_version);
 	}
+
+	#[test]
+	fn limited_runtimes_do_not_evict_unlimited_ones() {
+		let code = substrate_test_runtime::wasm_binary_unwrap();
+		let fetcher = sp_core::traits::WrappedRuntimeCode(code.into());
+		let runtime_code =
+			RuntimeCode { code_fetcher: &fetcher, hash: vec![1, 2, 3], heap_pages: None };
+		let mut ext = sp_state_machine::BasicExternalities::default();
+		let cache = RuntimeCache::new(1, None, 1);
+
+		let mut call = |execution_limits| {
+			cache
+				.with_instance::<sp_io::SubstrateHostFunctions, _, _>(
+					&runtime_code,
+					&mut ext,
+					WasmExecutionMethod::default(),
+					HeapAllocStrategy::Static { extra_pages: 2048 },
+					execution_limits,
+					false,
+					|_, _, version, _| Ok(version.cloned()),
+				)
+				.unwrap()
+				.unwrap()
+		};
+
+		call(ExecutionLimits::default());
+		call(ExecutionLimits {
+			max_duration: Some(std::time::Duration::from_secs(10)),
+			max_memory: None,
+		});
+		call(ExecutionLimits { max_duration: None, max_memory: Some(128 * 1024 * 1024) });
+
+		let unlimited = VersionedRuntimeId {
+			code_hash: vec![1, 2, 3],
+			wasm_method: WasmExecutionMethod::default(),
+			heap_alloc_strategy: HeapAllocStrategy::Static { extra_pages: 2048 },
+			execution_limits: ExecutionLimits::default(),
+		};
+		assert!(cache.runtimes.lock().peek(&unlimited).is_some());
+		assert_eq!(cache.limited_runtimes.lock().len(), 1);
+	}
 }
@@ -19,9 +19,11 @@
 //! This module defines `HostState` and `HostContext` structs which provide logic and state
 //! required for execution of host.
 
+use std::{cell::Cell, time::Duration};
 use wasmtime::Caller;
 
 use sc_allocator::{AllocationStats, FreeingBumpHeapAllocator};
+use sc_executor_common::profiling::HostFunctionProfile;
 use sp_wasm_interface::{Pointer, WordSize};
 
 use crate::{instance_wrapper::MemoryWrapper, runtime::StoreData, util};
@@ -37,12 +39,29 @@ pub struct HostState {
 	/// once.
 	allocator: Option<FreeingBumpHeapAllocator>,
 	panic_message: Option<String>,
+	/// The host functions called so far; only set if profiling is enabled.
+	profile: Option<HostFunctionProfile>,
 }
 
 impl HostState {
 	/// Constructs a new `HostState`.
 	pub fn new(allocator: FreeingBumpHeapAllocator) -> Self {
-		HostState { allocator: Some(allocator), panic_message: None }
+		HostState { allocator: Some(allocator), panic_message: None, profile: None }
+	}
+
+	/// Start recording the host functions that are called.
+	pub(crate) fn enable_profiling(&mut self) {
+		self.profile = Some(Default::default());
+	}
+
+	/// Returns `true` if the host functions that are called are recorded.
+	pub(crate) fn is_profiling(&self) -> bool {
+		self.profile.is_some()
+	}
+
+	/// Takes the recorded host function calls out of the host state.
+	pub(crate) fn take_profile(&mut self) -> Option<HostFunctionProfile> {
+		self.profile.take()
 	}
 
 	/// Takes the error message out of the host state, leaving a `None` in its place.
@@ -61,16 +80,37 @@ impl HostState {
 /// runtime. The `HostContext` exists only for the lifetime of the call and borrows state from
 /// a longer-living `HostState`.
 pub(crate) struct HostContext<'a> {
-	pub(crate) caller: Caller<'a, StoreData>,
+	caller: Caller<'a, StoreData>,
+	/// The number of bytes read from the linear memory during this host call.
+	bytes_read: Cell<u64>,
+	/// The number of bytes written into the linear memory during this host call.
+	bytes_written: u64,
 }
 
 impl<'a> HostContext<'a> {
+	pub(crate) fn new(caller: Caller<'a, StoreData>) -> Self {
+		HostContext { caller, bytes_read: Cell::new(0), bytes_written: 0 }
+	}
+
 	fn host_state_mut(&mut self) -> &mut HostState {
 		self.caller
 			.data_mut()
 			.host_state_mut()
 			.expect("host state is not empty when calling a function in wasm; qed")
 	}
+
+	/// Returns `true` if the host state records the host functions that are called.
+	pub(crate) fn is_profiling(&self) -> bool {
+		self.caller.data().host_state.as_ref().map_or(false, HostState::is_profiling)
+	}
+
+	/// Record this context as a call of the host function `name` which took `time`.
+	pub(crate) fn record_host_call(&mut self, name: &'static str, time: Duration) {
+		let (bytes_read, bytes_written) = (self.bytes_read.get(), self.bytes_written);
+		if let Some(profile) = self.host_state_mut().profile.as_mut() {
+			profile.record(name, time, bytes_read, bytes_written);
+		}
+	}
 }
 
 impl<'a> sp_wasm_interface::FunctionContext for HostContext<'a> {
@@ -79,11 +119,15 @@ impl<'a> sp_wasm_interface::FunctionContext for HostContext<'a> {
 		address: Pointer<u8>,
 		dest: &mut [u8],
 	) -> sp_wasm_interface::Result<()> {
-		util::read_memory_into(&self.caller, address, dest).map_err(|e| e.to_string())
+		util::read_memory_into(&self.caller, address, dest).map_err(|e| e.to_string())?;
+		self.bytes_read.set(self.bytes_read.get() + dest.len() as u64);
+		Ok(())
 	}
 
 	fn write_memory(&mut self, address: Pointer<u8>, data: &[u8]) -> sp_wasm_interface::Result<()> {
-		util::write_memory_from(&mut self.caller, address, data).map_err(|e| e.to_string())
+		util::write_memory_from(&mut self.caller, address, data).map_err(|e| e.to_string())?;
+		self.bytes_written += data.len() as u64;
+		Ok(())
 	}
 
 	fn allocate_memory(&mut self, size: WordSize) -> sp_wasm_interface::Result<Pointer<u8>> {
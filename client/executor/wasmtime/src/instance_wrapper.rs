@@ -19,7 +19,10 @@
 //! Defines data and logic needed for interaction with an WebAssembly instance of a substrate
 //! runtime module.
 
-use crate::runtime::{Store, StoreData};
+use crate::{
+	limits::MemoryLimiter,
+	runtime::{Store, StoreData},
+};
 use sc_executor_common::{
 	error::{Backtrace, Error, MessageWithBacktrace, Result, WasmError},
 	wasm_runtime::InvokeMethod,
@@ -81,6 +84,11 @@ impl EntryPoint {
 				Backtrace { backtrace_string: backtrace.to_string() }
 			});
 
+			// Epoch interruption is only enabled if the call has a time limit.
+			if let Some(wasmtime::Trap::Interrupt) = trap.downcast_ref::<wasmtime::Trap>() {
+				return Error::ExecutionLimitExceeded("the call ran out of time".into())
+			}
+
 			if let Some(message) = host_state.take_panic_message() {
 				Error::AbortedDueToPanic(MessageWithBacktrace { message, backtrace })
 			} else {
@@ -162,13 +170,30 @@ pub struct InstanceWrapper {
 }
 
 impl InstanceWrapper {
-	pub(crate) fn new(engine: &Engine, instance_pre: &InstancePre<StoreData>) -> Result<Self> {
+	pub(crate) fn new(
+		engine: &Engine,
+		instance_pre: &InstancePre<StoreData>,
+		max_memory: Option<usize>,
+	) -> Result<Self> {
 		let mut store = Store::new(engine, Default::default());
-		let instance = instance_pre.instantiate(&mut store).map_err(|error| {
+		if let Some(max_memory) = max_memory {
+			store.data_mut().memory_limiter = MemoryLimiter::new(max_memory);
+			store.limiter(|data| &mut data.memory_limiter);
+		}
+
+		let instance = instance_pre.instantiate(&mut store).map_err(|error| -> Error {
+			if store.data().memory_limiter.exceeded() {
+				return Error::ExecutionLimitExceeded(format!(
+					"the initial linear memory exceeds the limit of {} bytes",
+					max_memory.unwrap_or_default(),
+				))
+			}
+
 			WasmError::Other(format!(
 				"failed to instantiate a new WASM module instance: {:#}",
 				error,
 			))
+			.into()
 		})?;
 
 		let memory = get_linear_memory(&instance, &mut store)?;
@@ -397,7 +422,7 @@ fn decommit_works() {
 	let module = wasmtime::Module::new(&engine, code).unwrap();
 	let linker = wasmtime::Linker::new(&engine);
 	let instance_pre = linker.instantiate_pre(&module).unwrap();
-	let mut wrapper = InstanceWrapper::new(&engine, &instance_pre).unwrap();
+	let mut wrapper = InstanceWrapper::new(&engine, &instance_pre, None).unwrap();
 	unsafe { *wrapper.memory.data_ptr(&wrapper.store) = 42 };
 	assert_eq!(unsafe { *wrapper.memory.data_ptr(&wrapper.store) }, 42);
 	wrapper.decommit();
@@ -30,6 +30,7 @@
 mod host;
 mod imports;
 mod instance_wrapper;
+mod limits;
 mod runtime;
 mod util;
 
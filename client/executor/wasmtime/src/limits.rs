@@ -0,0 +1,173 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Enforcement of [`ExecutionLimits`](sc_executor_common::wasm_runtime::ExecutionLimits).
+//!
+//! The time limit is implemented using wasmtime's epoch interruption: a single background thread
+//! advances the epoch of every engine that currently has a limited call in flight, and each call
+//! sets its deadline as a number of epochs from now. The memory limit is implemented with a
+//! [`wasmtime::ResourceLimiter`] installed into the store of the instance.
+
+use std::{
+	sync::{Condvar, Mutex, OnceLock},
+	time::Duration,
+};
+use wasmtime::Engine;
+
+/// The interval at which the epochs of engines with in-flight calls are advanced.
+const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// The largest deadline handed to wasmtime, which adds it to the current epoch of the engine.
+const MAX_DEADLINE_TICKS: u64 = u64::MAX / 2;
+
+/// Returns the epoch deadline, relative to the current epoch, that corresponds to `max_duration`.
+///
+/// The result is rounded up and accounts for the partially elapsed current tick, so a call is
+/// never interrupted before `max_duration` has passed.
+pub(crate) fn deadline_in_ticks(max_duration: Duration) -> u64 {
+	let tick = EPOCH_TICK.as_nanos();
+	let ticks = (max_duration.as_nanos() + tick - 1) / tick;
+	u64::try_from(ticks)
+		.unwrap_or(u64::MAX)
+		.saturating_add(1)
+		.min(MAX_DEADLINE_TICKS)
+}
+
+/// Engines that currently have a time limited call in flight, along with the number of such
+/// calls.
+#[derive(Default)]
+struct EpochTicker {
+	engines: Mutex<Vec<(Engine, usize)>>,
+	wakeup: Condvar,
+}
+
+impl EpochTicker {
+	fn get() -> &'static EpochTicker {
+		static TICKER: OnceLock<EpochTicker> = OnceLock::new();
+
+		TICKER.get_or_init(|| {
+			std::thread::Builder::new()
+				.name("wasmtime-epoch".into())
+				.spawn(|| EpochTicker::get().run())
+				.expect("failed to spawn the wasmtime epoch thread");
+			EpochTicker::default()
+		})
+	}
+
+	fn run(&self) {
+		loop {
+			{
+				let mut engines = self.engines.lock().unwrap_or_else(|e| e.into_inner());
+				while engines.is_empty() {
+					engines = self.wakeup.wait(engines).unwrap_or_else(|e| e.into_inner());
+				}
+			}
+
+			std::thread::sleep(EPOCH_TICK);
+
+			let engines = self.engines.lock().unwrap_or_else(|e| e.into_inner());
+			engines.iter().for_each(|(engine, _)| engine.increment_epoch());
+		}
+	}
+}
+
+/// Keeps the epoch of an engine advancing for as long as it is alive.
+pub(crate) struct EpochGuard {
+	engine: Engine,
+}
+
+impl EpochGuard {
+	/// Start advancing the epoch of `engine`.
+	pub(crate) fn new(engine: &Engine) -> Self {
+		let ticker = EpochTicker::get();
+		let mut engines = ticker.engines.lock().unwrap_or_else(|e| e.into_inner());
+		match engines.iter_mut().find(|(e, _)| Engine::same(e, engine)) {
+			Some((_, calls)) => *calls += 1,
+			None => engines.push((engine.clone(), 1)),
+		}
+		ticker.wakeup.notify_one();
+
+		EpochGuard { engine: engine.clone() }
+	}
+}
+
+impl Drop for EpochGuard {
+	fn drop(&mut self) {
+		let mut engines = EpochTicker::get().engines.lock().unwrap_or_else(|e| e.into_inner());
+		if let Some(index) = engines.iter().position(|(e, _)| Engine::same(e, &self.engine)) {
+			engines[index].1 -= 1;
+			if engines[index].1 == 0 {
+				engines.swap_remove(index);
+			}
+		}
+	}
+}
+
+/// A [`wasmtime::ResourceLimiter`] bounding the size of the linear memory of an instance.
+#[derive(Default)]
+pub(crate) struct MemoryLimiter {
+	max_memory: Option<usize>,
+	exceeded: bool,
+}
+
+impl MemoryLimiter {
+	/// Create a limiter that refuses to grow memories beyond `max_memory` bytes.
+	pub(crate) fn new(max_memory: usize) -> Self {
+		MemoryLimiter { max_memory: Some(max_memory), exceeded: false }
+	}
+
+	/// The configured limit in bytes, if any.
+	pub(crate) fn max_memory(&self) -> Option<usize> {
+		self.max_memory
+	}
+
+	/// Returns `true` if a memory growth was refused since the last [`Self::reset`].
+	pub(crate) fn exceeded(&self) -> bool {
+		self.exceeded
+	}
+
+	/// Forget about previously refused memory growths.
+	pub(crate) fn reset(&mut self) {
+		self.exceeded = false;
+	}
+}
+
+impl wasmtime::ResourceLimiter for MemoryLimiter {
+	fn memory_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> bool {
+		let allowed = self.max_memory.map_or(true, |max_memory| desired <= max_memory);
+		self.exceeded |= !allowed;
+		allowed
+	}
+
+	fn table_growing(&mut self, _current: u32, _desired: u32, _maximum: Option<u32>) -> bool {
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn deadline_is_rounded_up() {
+		assert_eq!(deadline_in_ticks(Duration::ZERO), 1);
+		assert_eq!(deadline_in_ticks(EPOCH_TICK), 2);
+		assert_eq!(deadline_in_ticks(EPOCH_TICK + Duration::from_nanos(1)), 3);
+		assert_eq!(deadline_in_ticks(Duration::MAX), MAX_DEADLINE_TICKS);
+	}
+}
@@ -21,17 +21,19 @@
 use crate::{
 	host::HostState,
 	instance_wrapper::{EntryPoint, InstanceWrapper, MemoryWrapper},
+	limits::{self, EpochGuard, MemoryLimiter},
 	util::{self, replace_strategy_if_broken},
 };
 
 use sc_allocator::{AllocationStats, FreeingBumpHeapAllocator};
 use sc_executor_common::{
 	error::{Error, Result, WasmError},
+	profiling,
 	runtime_blob::{
 		self, DataSegmentsSnapshot, ExposedMutableGlobalsSet, GlobalsSnapshot, RuntimeBlob,
 	},
 	util::checked_range,
-	wasm_runtime::{HeapAllocStrategy, InvokeMethod, WasmInstance, WasmModule},
+	wasm_runtime::{ExecutionLimits, HeapAllocStrategy, InvokeMethod, WasmInstance, WasmModule},
 };
 use sp_runtime_interface::unpack_ptr_and_len;
 use sp_wasm_interface::{HostFunctions, Pointer, Value, WordSize};
@@ -52,6 +54,8 @@ pub(crate) struct StoreData {
 	pub(crate) memory: Option<Memory>,
 	/// This will be set only if the runtime actually contains a table.
 	pub(crate) table: Option<Table>,
+	/// Bounds the linear memory; only installed into the store if a memory limit is configured.
+	pub(crate) memory_limiter: MemoryLimiter,
 }
 
 impl StoreData {
@@ -81,11 +85,12 @@ enum Strategy {
 struct InstanceCreator {
 	engine: wasmtime::Engine,
 	instance_pre: Arc<wasmtime::InstancePre<StoreData>>,
+	max_memory: Option<usize>,
 }
 
 impl InstanceCreator {
 	fn instantiate(&mut self) -> Result<InstanceWrapper> {
-		InstanceWrapper::new(&self.engine, &self.instance_pre)
+		InstanceWrapper::new(&self.engine, &self.instance_pre, self.max_memory)
 	}
 }
 
@@ -125,13 +130,18 @@ pub struct WasmtimeRuntime {
 	engine: wasmtime::Engine,
 	instance_pre: Arc<wasmtime::InstancePre<StoreData>>,
 	instantiation_strategy: InternalInstantiationStrategy,
+	execution_limits: ExecutionLimits,
 }
 
 impl WasmModule for WasmtimeRuntime {
 	fn new_instance(&self) -> Result<Box<dyn WasmInstance>> {
 		let strategy = match self.instantiation_strategy {
 			InternalInstantiationStrategy::LegacyInstanceReuse(ref snapshot_data) => {
-				let mut instance_wrapper = InstanceWrapper::new(&self.engine, &self.instance_pre)?;
+				let mut instance_wrapper = InstanceWrapper::new(
+					&self.engine,
+					&self.instance_pre,
+					self.execution_limits.max_memory,
+				)?;
 				let heap_base = instance_wrapper.extract_heap_base()?;
 
 				// This function panics if the instance was created from a runtime blob different
@@ -153,10 +163,11 @@ impl WasmModule for WasmtimeRuntime {
 			InternalInstantiationStrategy::Builtin => Strategy::RecreateInstance(InstanceCreator {
 				engine: self.engine.clone(),
 				instance_pre: self.instance_pre.clone(),
+				max_memory: self.execution_limits.max_memory,
 			}),
 		};
 
-		Ok(Box::new(WasmtimeInstance { strategy }))
+		Ok(Box::new(WasmtimeInstance { strategy, execution_limits: self.execution_limits }))
 	}
 }
 
@@ -164,6 +175,7 @@ impl WasmModule for WasmtimeRuntime {
 /// to execute the compiled code.
 pub struct WasmtimeInstance {
 	strategy: Strategy,
+	execution_limits: ExecutionLimits,
 }
 
 impl WasmtimeInstance {
@@ -173,6 +185,11 @@ impl WasmtimeInstance {
 		data: &[u8],
 		allocation_stats: &mut Option<AllocationStats>,
 	) -> Result<Vec<u8>> {
+		let method_name = match method {
+			InvokeMethod::Export(name) => name,
+			InvokeMethod::Table(_) | InvokeMethod::TableWithWrapper { .. } => "<table entry>",
+		};
+
 		match &mut self.strategy {
 			Strategy::LegacyInstanceReuse {
 				ref mut instance_wrapper,
@@ -192,8 +209,15 @@ impl WasmtimeInstance {
 				globals_snapshot.apply(&mut InstanceGlobals { instance: instance_wrapper });
 				let allocator = FreeingBumpHeapAllocator::new(*heap_base);
 
-				let result =
-					perform_call(data, instance_wrapper, entrypoint, allocator, allocation_stats);
+				let result = perform_call(
+					method_name,
+					data,
+					instance_wrapper,
+					entrypoint,
+					allocator,
+					allocation_stats,
+					&self.execution_limits,
+				);
 
 				// Signal to the OS that we are done with the linear memory and that it can be
 				// reclaimed.
@@ -207,7 +231,15 @@ impl WasmtimeInstance {
 				let entrypoint = instance_wrapper.resolve_entrypoint(method)?;
 
 				let allocator = FreeingBumpHeapAllocator::new(heap_base);
-				perform_call(data, &mut instance_wrapper, entrypoint, allocator, allocation_stats)
+				perform_call(
+					method_name,
+					data,
+					&mut instance_wrapper,
+					entrypoint,
+					allocator,
+					allocation_stats,
+					&self.execution_limits,
+				)
 			},
 		}
 	}
@@ -323,6 +355,10 @@ fn common_config(semantics: &Semantics) -> std::result::Result<wasmtime::Config,
 
 	config.parallel_compilation(semantics.parallel_compilation);
 
+	// Epoch interruption makes the generated code check for the deadline, so only enable it for
+	// the modules that are actually meant to be time limited.
+	config.epoch_interruption(semantics.execution_limits.max_duration.is_some());
+
 	// Be clear and specific about the extensions we support. If an update brings new features
 	// they should be introduced here as well.
 	config.wasm_reference_types(semantics.wasm_reference_types);
@@ -410,7 +446,7 @@ fn common_config(semantics: &Semantics) -> std::result::Result<wasmtime::Config,
 /// See [here][stack_height] for more details of the instrumentation
 ///
 /// [stack_height]: https://github.com/paritytech/wasm-utils/blob/d9432baf/src/stack_height/mod.rs#L1-L50
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct DeterministicStackLimit {
 	/// A number of logical "values" that can be pushed on the wasm stack. A trap will be triggered
 	/// if exceeded.
@@ -468,7 +504,7 @@ enum InternalInstantiationStrategy {
 	Builtin,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Semantics {
 	/// The instantiation strategy to use.
 	pub instantiation_strategy: InstantiationStrategy,
@@ -516,6 +552,13 @@ pub struct Semantics {
 
 	/// Enables WASM Fixed-Width SIMD proposal
 	pub wasm_simd: bool,
+
+	/// Limits applied to every call into an instance of the runtime.
+	///
+	/// Calls exceeding them are aborted with [`Error::ExecutionLimitExceeded`]. A time limit
+	/// requires instrumenting the compiled code, so a module compiled with a time limit can't be
+	/// used without one and vice versa.
+	pub execution_limits: ExecutionLimits,
 }
 
 #[derive(Clone)]
@@ -719,7 +762,12 @@ where
 		.instantiate_pre(&module)
 		.map_err(|e| WasmError::Other(format!("cannot preinstantiate module: {:#}", e)))?;
 
-	Ok(WasmtimeRuntime { engine, instance_pre: Arc::new(instance_pre), instantiation_strategy })
+	Ok(WasmtimeRuntime {
+		engine,
+		instance_pre: Arc::new(instance_pre),
+		instantiation_strategy,
+		execution_limits: config.semantics.execution_limits,
+	})
 }
 
 fn prepare_blob_for_compilation(
@@ -767,6 +815,49 @@ pub fn prepare_runtime_artifact(
 }
 
 fn perform_call(
+	method_name: &str,
+	data: &[u8],
+	instance_wrapper: &mut InstanceWrapper,
+	entrypoint: EntryPoint,
+	allocator: FreeingBumpHeapAllocator,
+	allocation_stats: &mut Option<AllocationStats>,
+	execution_limits: &ExecutionLimits,
+) -> Result<Vec<u8>> {
+	instance_wrapper.store_mut().data_mut().memory_limiter.reset();
+
+	// Keep the epoch of the engine advancing while the call is running.
+	let _epoch_guard = execution_limits.max_duration.map(|max_duration| {
+		let store = instance_wrapper.store_mut();
+		store.set_epoch_deadline(limits::deadline_in_ticks(max_duration));
+		EpochGuard::new(store.engine())
+	});
+
+	let result = perform_call_impl(
+		method_name,
+		data,
+		instance_wrapper,
+		entrypoint,
+		allocator,
+		allocation_stats,
+	);
+
+	let memory_limiter = &instance_wrapper.store().data().memory_limiter;
+	match (result, memory_limiter.max_memory()) {
+		(Err(_), Some(max_memory)) if memory_limiter.exceeded() =>
+			Err(Error::ExecutionLimitExceeded(format!(
+				"the linear memory can't grow beyond {} bytes",
+				max_memory
+			))),
+		(Err(Error::ExecutionLimitExceeded(_)), _) => Err(Error::ExecutionLimitExceeded(format!(
+			"the call didn't finish within {:?}",
+			execution_limits.max_duration.unwrap_or_default(),
+		))),
+		(result, _) => result,
+	}
+}
+
+fn perform_call_impl(
+	method_name: &str,
 	data: &[u8],
 	instance_wrapper: &mut InstanceWrapper,
 	entrypoint: EntryPoint,
@@ -775,7 +866,10 @@ fn perform_call(
 ) -> Result<Vec<u8>> {
 	let (data_ptr, data_len) = inject_input_data(instance_wrapper, &mut allocator, data)?;
 
-	let host_state = HostState::new(allocator);
+	let mut host_state = HostState::new(allocator);
+	if profiling::is_enabled() {
+		host_state.enable_profiling();
+	}
 
 	// Set the host state before calling into wasm.
 	instance_wrapper.store_mut().data_mut().host_state = Some(host_state);
@@ -785,10 +879,13 @@ fn perform_call(
 		.map(unpack_ptr_and_len);
 
 	// Reset the host state
-	let host_state = instance_wrapper.store_mut().data_mut().host_state.take().expect(
+	let mut host_state = instance_wrapper.store_mut().data_mut().host_state.take().expect(
 		"the host state is always set before calling into WASM so it can't be None here; qed",
 	);
 	*allocation_stats = Some(host_state.allocation_stats());
+	if let Some(profile) = host_state.take_profile() {
+		profiling::submit(method_name, profile);
+	}
 
 	let (output_ptr, output_len) = ret?;
 	let output = extract_output_data(instance_wrapper, output_ptr, output_len)?;
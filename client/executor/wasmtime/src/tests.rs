@@ -20,9 +20,10 @@ use codec::{Decode as _, Encode as _};
 use sc_executor_common::{
 	error::Error,
 	runtime_blob::RuntimeBlob,
-	wasm_runtime::{HeapAllocStrategy, WasmModule, DEFAULT_HEAP_ALLOC_STRATEGY},
+	wasm_runtime::{ExecutionLimits, HeapAllocStrategy, WasmModule, DEFAULT_HEAP_ALLOC_STRATEGY},
 };
 use sc_runtime_test::wasm_binary_unwrap;
+use std::time::Duration;
 
 use crate::InstantiationStrategy;
 
@@ -82,6 +83,7 @@ struct RuntimeBuilder {
 	canonicalize_nans: bool,
 	deterministic_stack: bool,
 	heap_pages: HeapAllocStrategy,
+	execution_limits: ExecutionLimits,
 	precompile_runtime: bool,
 	tmpdir: Option<tempfile::TempDir>,
 }
@@ -94,6 +96,7 @@ impl RuntimeBuilder {
 			canonicalize_nans: false,
 			deterministic_stack: false,
 			heap_pages: DEFAULT_HEAP_ALLOC_STRATEGY,
+			execution_limits: ExecutionLimits::default(),
 			precompile_runtime: false,
 			tmpdir: None,
 		}
@@ -124,6 +127,11 @@ impl RuntimeBuilder {
 		self
 	}
 
+	fn execution_limits(mut self, execution_limits: ExecutionLimits) -> Self {
+		self.execution_limits = execution_limits;
+		self
+	}
+
 	fn build(&mut self) -> impl WasmModule + '_ {
 		let blob = {
 			let wasm: Vec<u8>;
@@ -159,6 +167,7 @@ impl RuntimeBuilder {
 				wasm_bulk_memory: false,
 				wasm_reference_types: false,
 				wasm_simd: false,
+				execution_limits: self.execution_limits,
 			},
 		};
 
@@ -462,6 +471,77 @@ fn test_max_memory_pages(
 	assert_grow_fail(HeapAllocStrategy::Static { extra_pages: 10 }, 10, 10);
 }
 
+test_wasm_execution!(test_execution_time_limit);
+fn test_execution_time_limit(instantiation_strategy: InstantiationStrategy) {
+	let wat = r#"
+		(module
+			(memory $0 1)
+			(export "memory" (memory $0))
+			(global (export "__heap_base") i32 (i32.const 0))
+			(func (export "main")
+				(param i32 i32) (result i64)
+				(loop $spin (br $spin))
+				(i64.const 0)
+			)
+		)
+	"#;
+
+	let mut builder = RuntimeBuilder::new(instantiation_strategy)
+		.use_wat(wat.to_string())
+		.execution_limits(ExecutionLimits {
+			max_duration: Some(Duration::from_millis(50)),
+			max_memory: None,
+		});
+	let runtime = builder.build();
+	let mut instance = runtime.new_instance().expect("failed to instantiate a runtime");
+
+	match instance.call_export("main", &[]).unwrap_err() {
+		Error::ExecutionLimitExceeded(_) => {},
+		error => panic!("unexpected error: {:?}", error),
+	}
+}
+
+test_wasm_execution!(test_execution_memory_limit);
+fn test_execution_memory_limit(instantiation_strategy: InstantiationStrategy) {
+	let wat = r#"
+		(module
+			(memory $0 1)
+			(export "memory" (memory $0))
+			(global (export "__heap_base") i32 (i32.const 0))
+			(func (export "main")
+				(param i32 i32) (result i64)
+				(if
+					(i32.eq
+						(memory.grow (i32.const 2))
+						(i32.const -1)
+					)
+					(unreachable)
+				)
+				(i64.const 0)
+			)
+		)
+	"#;
+
+	let call = |max_pages: usize| {
+		let mut builder = RuntimeBuilder::new(instantiation_strategy)
+			.use_wat(wat.to_string())
+			.heap_alloc_strategy(HeapAllocStrategy::Dynamic { maximum_pages: None })
+			.execution_limits(ExecutionLimits {
+				max_duration: None,
+				max_memory: Some(max_pages * 65536),
+			});
+		let runtime = builder.build();
+		let mut instance = runtime.new_instance().expect("failed to instantiate a runtime");
+		instance.call_export("main", &[])
+	};
+
+	call(3).unwrap();
+	match call(2).unwrap_err() {
+		Error::ExecutionLimitExceeded(_) => {},
+		error => panic!("unexpected error: {:?}", error),
+	}
+}
+
 // This test takes quite a while to execute in a debug build (over 6 minutes on a TR 3970x)
 // so it's ignored by default unless it was compiled with `--release`.
 #[cfg_attr(build_type = "debug", ignore)]
@@ -482,6 +562,7 @@ fn test_instances_without_reuse_are_not_leaked() {
 				wasm_bulk_memory: false,
 				wasm_reference_types: false,
 				wasm_simd: false,
+				execution_limits: ExecutionLimits::default(),
 			},
 		},
 	)
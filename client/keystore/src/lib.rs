@@ -25,7 +25,10 @@ use std::io;
 
 /// Local keystore implementation
 mod local;
+/// Remote keystore implementation
+pub mod remote;
 pub use local::LocalKeystore;
+pub use remote::{RemoteKeystore, RemoteSigner};
 pub use sp_keystore::Keystore;
 
 /// Keystore error.
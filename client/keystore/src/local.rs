@@ -31,7 +31,7 @@ use sp_keystore::{Error as TraitError, Keystore, KeystorePtr};
 use std::{
 	collections::HashMap,
 	fs::{self, File},
-	io::Write,
+	io::{self, Write},
 	path::PathBuf,
 	sync::Arc,
 };
@@ -129,6 +129,40 @@ impl LocalKeystore {
 			.map(|pair| pair.vrf_output(input));
 		Ok(preout)
 	}
+
+	/// The VRF output of the sr25519 key `public` for an input hashed with
+	/// [`sr25519::Public::vrf_input_point`].
+	pub(crate) fn sr25519_vrf_output_from_point(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		point: &sr25519::vrf::VrfInputPoint,
+	) -> std::result::Result<Option<sr25519::vrf::VrfOutput>, TraitError> {
+		self.0
+			.read()
+			.key_pair_by_type::<sr25519::Pair>(public, key_type)?
+			.map(|pair| pair.vrf_output_from_point(point).ok_or_else(invalid_vrf_input_point))
+			.transpose()
+	}
+
+	/// Sign an input hashed with [`sr25519::Public::vrf_input_point`] with the sr25519 key
+	/// `public`.
+	pub(crate) fn sr25519_vrf_sign_point(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		point: &sr25519::vrf::VrfInputPoint,
+	) -> std::result::Result<Option<sr25519::vrf::VrfSignature>, TraitError> {
+		self.0
+			.read()
+			.key_pair_by_type::<sr25519::Pair>(public, key_type)?
+			.map(|pair| pair.vrf_sign_point(point).ok_or_else(invalid_vrf_input_point))
+			.transpose()
+	}
+}
+
+fn invalid_vrf_input_point() -> TraitError {
+	TraitError::ValidationError("Invalid VRF input point".into())
 }
 
 impl Keystore for LocalKeystore {
@@ -362,10 +396,18 @@ impl Keystore for LocalKeystore {
 		self.0.write().insert(key_type, suri, public).map_err(|_| ())
 	}
 
+	fn remove(&self, key_type: KeyTypeId, public: &[u8]) -> std::result::Result<bool, TraitError> {
+		self.0.write().remove(key_type, public).map_err(|e| e.into())
+	}
+
 	fn keys(&self, key_type: KeyTypeId) -> std::result::Result<Vec<Vec<u8>>, TraitError> {
 		self.0.read().raw_public_keys(key_type).map_err(|e| e.into())
 	}
 
+	fn all_keys(&self) -> std::result::Result<Vec<(KeyTypeId, Vec<u8>)>, TraitError> {
+		self.0.read().all_raw_public_keys().map_err(|e| e.into())
+	}
+
 	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
 		public_keys
 			.iter()
@@ -540,13 +582,19 @@ impl KeystoreInner {
 
 	/// Returns a list of raw public keys filtered by `KeyTypeId`
 	fn raw_public_keys(&self, key_type: KeyTypeId) -> Result<Vec<Vec<u8>>> {
-		let mut public_keys: Vec<Vec<u8>> = self
-			.additional
-			.keys()
+		let public_keys = self
+			.all_raw_public_keys()?
 			.into_iter()
-			.filter_map(|k| if k.0 == key_type { Some(k.1.clone()) } else { None })
+			.filter_map(|(t, public)| if t == key_type { Some(public) } else { None })
 			.collect();
 
+		Ok(public_keys)
+	}
+
+	/// Returns a list of all raw public keys together with their `KeyTypeId`
+	fn all_raw_public_keys(&self) -> Result<Vec<(KeyTypeId, Vec<u8>)>> {
+		let mut public_keys: Vec<(KeyTypeId, Vec<u8>)> = self.additional.keys().cloned().collect();
+
 		if let Some(path) = &self.path {
 			for entry in fs::read_dir(&path)? {
 				let entry = entry?;
@@ -556,11 +604,9 @@ impl KeystoreInner {
 				if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
 					match array_bytes::hex2bytes(name) {
 						Ok(ref hex) if hex.len() > 4 => {
-							if hex[0..4] != key_type.0 {
-								continue
-							}
+							let key_type = KeyTypeId([hex[0], hex[1], hex[2], hex[3]]);
 							let public = hex[4..].to_vec();
-							public_keys.push(public);
+							public_keys.push((key_type, public));
 						},
 						_ => continue,
 					}
@@ -571,6 +617,24 @@ impl KeystoreInner {
 		Ok(public_keys)
 	}
 
+	/// Remove the key with the given public key and key type.
+	///
+	/// Removes it from the memory cache and from the file system store, if a path is configured.
+	/// Returns `true` if the key was found in either of them.
+	fn remove(&mut self, key_type: KeyTypeId, public: &[u8]) -> Result<bool> {
+		let mut removed = self.additional.remove(&(key_type, public.to_vec())).is_some();
+
+		if let Some(path) = self.key_file_path(public, key_type) {
+			match fs::remove_file(path) {
+				Ok(()) => removed = true,
+				Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+				Err(e) => return Err(e.into()),
+			}
+		}
+
+		Ok(removed)
+	}
+
 	/// Get a key pair for the given public key.
 	///
 	/// Returns `Ok(None)` if the key doesn't exist, `Ok(Some(_))` if the key exists or `Err(_)`
@@ -779,6 +843,31 @@ mod tests {
 		assert_eq!(store.sr25519_public_keys(TEST_KEY_TYPE).len(), 2);
 	}
 
+	#[test]
+	fn remove_deletes_stored_and_ephemeral_keys() {
+		let temp_dir = TempDir::new().unwrap();
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+
+		let stored = store.sr25519_generate_new(TEST_KEY_TYPE, None).unwrap();
+		let ephemeral = store.ed25519_generate_new(SR25519, Some("//Alice")).unwrap();
+
+		let mut all_keys = store.all_keys().unwrap();
+		all_keys.sort();
+		let mut expected =
+			vec![(TEST_KEY_TYPE, stored.to_raw_vec()), (SR25519, ephemeral.to_raw_vec())];
+		expected.sort();
+		assert_eq!(all_keys, expected);
+
+		assert!(store.remove(TEST_KEY_TYPE, stored.as_ref()).unwrap());
+		assert!(store.remove(SR25519, ephemeral.as_ref()).unwrap());
+		assert!(!store.remove(TEST_KEY_TYPE, stored.as_ref()).unwrap());
+		assert!(store.all_keys().unwrap().is_empty());
+
+		drop(store);
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+		assert!(store.sr25519_public_keys(TEST_KEY_TYPE).is_empty());
+	}
+
 	#[test]
 	#[cfg(target_family = "unix")]
 	fn uses_correct_file_permissions_on_unix() {
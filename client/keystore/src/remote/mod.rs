@@ -0,0 +1,604 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Keystore forwarding all secret key operations to an external signer.
+//!
+//! The [`RemoteKeystore`] holds no secrets itself. Every call of the [`Keystore`] trait is sent
+//! to a [`RemoteSigner`] listening on a Unix socket or a TCP port, usually on a separate,
+//! hardened host. Both ends authenticate each other using a pre-shared secret.
+//!
+//! Sr25519 VRF transcripts can't be serialized, so they are hashed to the VRF input point of the
+//! key by the node and the signer evaluates the VRF on that point. Sr25519 VRF signatures with
+//! extra data and all bandersnatch VRF operations can't be forwarded for the same reason, and
+//! always fail with [`sp_keystore::Error::Unavailable`].
+
+use codec::Decode;
+use parking_lot::Mutex;
+#[cfg(feature = "bandersnatch-experimental")]
+use sp_core::bandersnatch;
+#[cfg(feature = "bls-experimental")]
+use sp_core::{bls377, bls381};
+use sp_core::{
+	crypto::{ByteArray, CryptoTypeId, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{Error as TraitError, Keystore, KeystorePtr};
+use std::{sync::Arc, time::Duration};
+
+use crate::Result;
+
+mod protocol;
+mod server;
+mod transport;
+
+use protocol::{Request, Response, Session};
+pub use server::{RemoteSigner, DEFAULT_MAX_CONNECTIONS};
+pub use transport::{Endpoint, Listener, Stream};
+
+const LOG_TARGET: &str = "remote-keystore";
+
+/// Default timeout applied to every read and write on the connection.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A keystore forwarding all operations to a [`RemoteSigner`].
+pub struct RemoteKeystore {
+	endpoint: Endpoint,
+	secret: Vec<u8>,
+	timeout: Duration,
+	session: Mutex<Option<Session<Stream>>>,
+}
+
+/// Hash the VRF `input` to the point the signer evaluates the VRF of `public` on.
+fn vrf_input_point(
+	public: &sr25519::Public,
+	input: &sr25519::vrf::VrfInput,
+) -> std::result::Result<sr25519::vrf::VrfInputPoint, TraitError> {
+	public
+		.vrf_input_point(input)
+		.ok_or_else(|| TraitError::ValidationError("Invalid public key format".into()))
+}
+
+/// Whether `error` means the peer closed the connection before answering.
+fn is_disconnect(error: &std::io::Error) -> bool {
+	use std::io::ErrorKind::*;
+	matches!(error.kind(), UnexpectedEof | ConnectionReset | ConnectionAborted | BrokenPipe)
+}
+
+impl RemoteKeystore {
+	/// Connect to the signer at `endpoint`, authenticating with the pre-shared `secret`.
+	///
+	/// Fails if the signer can't be reached or the handshake fails. The connection is
+	/// re-established on demand if it drops later on.
+	pub fn connect(endpoint: Endpoint, secret: Vec<u8>) -> Result<Self> {
+		let keystore =
+			Self { endpoint, secret, timeout: DEFAULT_TIMEOUT, session: Mutex::new(None) };
+		*keystore.session.lock() = Some(keystore.open_session()?);
+		Ok(keystore)
+	}
+
+	/// The endpoint of the signer.
+	pub fn endpoint(&self) -> &Endpoint {
+		&self.endpoint
+	}
+
+	fn open_session(&self) -> std::io::Result<Session<Stream>> {
+		Session::client(self.endpoint.connect(self.timeout)?, &self.secret)
+	}
+
+	/// Send a request and wait for the answer.
+	///
+	/// A request failing over an existing connection is retried once on a fresh one, as the
+	/// signer may have been restarted or closed the connection for being idle in the meantime.
+	/// Only failures to send, or a connection closed before any answer was read, are retried.
+	fn request(&self, request: &Request) -> std::result::Result<Response, TraitError> {
+		let mut session = self.session.lock();
+		let unavailable = |e: std::io::Error| {
+			log::warn!(target: LOG_TARGET, "Remote signer at {} unavailable: {}", self.endpoint, e);
+			TraitError::Unavailable
+		};
+
+		let mut fresh = session.is_none();
+		loop {
+			if session.is_none() {
+				*session = Some(self.open_session().map_err(unavailable)?);
+			}
+			let current = session.as_mut().expect("session was opened above; qed");
+
+			if let Err(e) = current.send(request) {
+				*session = None;
+				if fresh {
+					return Err(unavailable(e))
+				}
+				fresh = true;
+				continue
+			}
+
+			return match current.receive::<Response>() {
+				Ok(Response::Error(e)) => Err(e.into()),
+				Ok(response) => Ok(response),
+				Err(e) => {
+					*session = None;
+					if fresh || !is_disconnect(&e) {
+						return Err(unavailable(e))
+					}
+					fresh = true;
+					continue
+				},
+			}
+		}
+	}
+
+	fn public_keys<P: ByteArray>(&self, crypto_id: CryptoTypeId, key_type: KeyTypeId) -> Vec<P> {
+		match self.request(&Request::PublicKeys { crypto_id, key_type }) {
+			Ok(Response::PublicKeys(keys)) =>
+				keys.iter().filter_map(|k| P::from_slice(k).ok()).collect(),
+			Ok(_) => {
+				log::warn!(target: LOG_TARGET, "Unexpected response to public keys request");
+				Vec::new()
+			},
+			Err(_) => Vec::new(),
+		}
+	}
+
+	fn generate_new<P: ByteArray>(
+		&self,
+		crypto_id: CryptoTypeId,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<P, TraitError> {
+		let request = Request::GenerateNew { crypto_id, key_type, seed: seed.map(Into::into) };
+		match self.request(&request)? {
+			Response::Public(public) => P::from_slice(&public).map_err(|_| {
+				TraitError::ValidationError("Invalid public key returned by remote signer".into())
+			}),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	fn sign<S: Decode>(
+		&self,
+		crypto_id: CryptoTypeId,
+		key_type: KeyTypeId,
+		public: &[u8],
+		msg: &[u8],
+	) -> std::result::Result<Option<S>, TraitError> {
+		let request =
+			Request::Sign { crypto_id, key_type, public: public.to_vec(), msg: msg.to_vec() };
+		match self.request(&request)? {
+			Response::Signature(signature) => signature
+				.map(|s| {
+					S::decode(&mut &s[..]).map_err(|_| {
+						TraitError::Other("Invalid signature returned by remote signer".into())
+					})
+				})
+				.transpose(),
+			_ => Err(unexpected_response()),
+		}
+	}
+}
+
+fn unexpected_response() -> TraitError {
+	TraitError::Other("Unexpected response from remote signer".into())
+}
+
+impl Keystore for RemoteKeystore {
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys(sr25519::CRYPTO_ID, key_type)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		self.generate_new(sr25519::CRYPTO_ID, key_type, seed)
+	}
+
+	fn sr25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<sr25519::Signature>, TraitError> {
+		self.sign(sr25519::CRYPTO_ID, key_type, public.as_slice(), msg)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		data: &sr25519::vrf::VrfSignData,
+	) -> std::result::Result<Option<sr25519::vrf::VrfSignature>, TraitError> {
+		if data.has_extra() {
+			return Err(TraitError::Unavailable)
+		}
+		let input = vrf_input_point(public, data.as_ref())?;
+		let request = Request::Sr25519VrfSign { key_type, public: *public, input };
+		match self.request(&request)? {
+			Response::VrfSignature(signature) => Ok(signature),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	fn sr25519_vrf_output(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		input: &sr25519::vrf::VrfInput,
+	) -> std::result::Result<Option<sr25519::vrf::VrfOutput>, TraitError> {
+		let input = vrf_input_point(public, input)?;
+		let request = Request::Sr25519VrfOutput { key_type, public: *public, input };
+		match self.request(&request)? {
+			Response::VrfOutput(output) => Ok(output),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys(ed25519::CRYPTO_ID, key_type)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		self.generate_new(ed25519::CRYPTO_ID, key_type, seed)
+	}
+
+	fn ed25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ed25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ed25519::Signature>, TraitError> {
+		self.sign(ed25519::CRYPTO_ID, key_type, public.as_slice(), msg)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys(ecdsa::CRYPTO_ID, key_type)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		self.generate_new(ecdsa::CRYPTO_ID, key_type, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.sign(ecdsa::CRYPTO_ID, key_type, public.as_slice(), msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		let request =
+			Request::EcdsaSignPrehashed { key_type, public: public.to_raw_vec(), msg: *msg };
+		match self.request(&request)? {
+			Response::Signature(signature) => signature
+				.map(|s| {
+					ecdsa::Signature::decode(&mut &s[..]).map_err(|_| {
+						TraitError::Other("Invalid signature returned by remote signer".into())
+					})
+				})
+				.transpose(),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public> {
+		self.public_keys(bandersnatch::CRYPTO_ID, key_type)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bandersnatch::Public, TraitError> {
+		self.generate_new(bandersnatch::CRYPTO_ID, key_type, seed)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &bandersnatch::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<bandersnatch::Signature>, TraitError> {
+		self.sign(bandersnatch::CRYPTO_ID, key_type, public.as_slice(), msg)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &bandersnatch::Public,
+		_data: &bandersnatch::vrf::VrfSignData,
+	) -> std::result::Result<Option<bandersnatch::vrf::VrfSignature>, TraitError> {
+		Err(TraitError::Unavailable)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_vrf_output(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &bandersnatch::Public,
+		_input: &bandersnatch::vrf::VrfInput,
+	) -> std::result::Result<Option<bandersnatch::vrf::VrfOutput>, TraitError> {
+		Err(TraitError::Unavailable)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_ring_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &bandersnatch::Public,
+		_data: &bandersnatch::vrf::VrfSignData,
+		_prover: &bandersnatch::ring_vrf::RingProver,
+	) -> std::result::Result<Option<bandersnatch::ring_vrf::RingVrfSignature>, TraitError> {
+		Err(TraitError::Unavailable)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
+		self.public_keys(bls381::CRYPTO_ID, key_type)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls377_public_keys(&self, key_type: KeyTypeId) -> Vec<bls377::Public> {
+		self.public_keys(bls377::CRYPTO_ID, key_type)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bls381::Public, TraitError> {
+		self.generate_new(bls381::CRYPTO_ID, key_type, seed)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls377_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bls377::Public, TraitError> {
+		self.generate_new(bls377::CRYPTO_ID, key_type, seed)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &bls381::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<bls381::Signature>, TraitError> {
+		self.sign(bls381::CRYPTO_ID, key_type, public.as_slice(), msg)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls377_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &bls377::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<bls377::Signature>, TraitError> {
+		self.sign(bls377::CRYPTO_ID, key_type, public.as_slice(), msg)
+	}
+
+	fn insert(
+		&self,
+		key_type: KeyTypeId,
+		suri: &str,
+		public: &[u8],
+	) -> std::result::Result<(), ()> {
+		let request = Request::Insert { key_type, suri: suri.into(), public: public.to_vec() };
+		match self.request(&request) {
+			Ok(Response::Done) => Ok(()),
+			_ => Err(()),
+		}
+	}
+
+	fn remove(&self, key_type: KeyTypeId, public: &[u8]) -> std::result::Result<bool, TraitError> {
+		match self.request(&Request::Remove { key_type, public: public.to_vec() })? {
+			Response::Bool(removed) => Ok(removed),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	fn keys(&self, key_type: KeyTypeId) -> std::result::Result<Vec<Vec<u8>>, TraitError> {
+		match self.request(&Request::Keys { key_type })? {
+			Response::PublicKeys(keys) => Ok(keys),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	fn all_keys(&self) -> std::result::Result<Vec<(KeyTypeId, Vec<u8>)>, TraitError> {
+		match self.request(&Request::AllKeys)? {
+			Response::TypedKeys(keys) => Ok(keys),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let request = Request::HasKeys { public_keys: public_keys.to_vec() };
+		matches!(self.request(&request), Ok(Response::Bool(true)))
+	}
+}
+
+impl Into<KeystorePtr> for RemoteKeystore {
+	fn into(self) -> KeystorePtr {
+		Arc::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::LocalKeystore;
+	use sp_core::{
+		crypto::{Pair as _, VrfPublic},
+		testing::SR25519,
+	};
+	use tempfile::TempDir;
+
+	const SECRET: &[u8] = b"correct horse battery staple";
+
+	fn spawn_signer(dir: &TempDir) -> (Endpoint, Arc<LocalKeystore>) {
+		spawn_signer_with(dir, |signer| signer)
+	}
+
+	fn spawn_signer_with(
+		dir: &TempDir,
+		configure: impl FnOnce(RemoteSigner) -> RemoteSigner,
+	) -> (Endpoint, Arc<LocalKeystore>) {
+		let endpoint = Endpoint::Unix(dir.path().join("signer.sock"));
+		let keystore = Arc::new(LocalKeystore::in_memory());
+		let listener = endpoint.bind().unwrap();
+		let signer = configure(RemoteSigner::new(keystore.clone(), SECRET.to_vec()));
+		std::thread::spawn(move || signer.serve(listener));
+		(endpoint, keystore)
+	}
+
+	#[test]
+	fn forwards_operations_to_signer() {
+		let dir = TempDir::new().unwrap();
+		let (endpoint, local) = spawn_signer(&dir);
+		let remote = RemoteKeystore::connect(endpoint, SECRET.to_vec()).unwrap();
+
+		let public = remote.sr25519_generate_new(SR25519, None).unwrap();
+		assert_eq!(local.sr25519_public_keys(SR25519), vec![public]);
+		assert_eq!(remote.sr25519_public_keys(SR25519), vec![public]);
+		assert!(remote.has_keys(&[(public.to_raw_vec(), SR25519)]));
+
+		let msg = b"hello";
+		let signature = remote.sr25519_sign(SR25519, &public, msg).unwrap().unwrap();
+		assert!(sr25519::Pair::verify(&signature, msg, &public));
+
+		let input = sr25519::vrf::VrfTranscript::new(b"label", &[(b"domain", b"data")]);
+		let remote_output = remote.sr25519_vrf_output(SR25519, &public, &input).unwrap();
+		let local_output = local.sr25519_vrf_output(SR25519, &public, &input).unwrap();
+		assert!(remote_output.is_some());
+		assert_eq!(remote_output, local_output);
+
+		let data = input.into_sign_data();
+		let signature = remote.sr25519_vrf_sign(SR25519, &public, &data).unwrap().unwrap();
+		assert!(public.vrf_verify(&data, &signature));
+		assert_eq!(Some(signature.output), local_output);
+
+		// The extra data is a transcript as well, which can't be forwarded.
+		let extra = sr25519::vrf::VrfTranscript::new(b"extra", &[(b"domain", b"data")]);
+		assert!(matches!(
+			remote.sr25519_vrf_sign(SR25519, &public, &data.with_extra(extra)),
+			Err(TraitError::Unavailable)
+		));
+
+		let unknown = sr25519::Pair::generate().0.public();
+		assert!(remote.sr25519_sign(SR25519, &unknown, msg).unwrap().is_none());
+	}
+
+	#[test]
+	fn rejects_wrong_secret() {
+		let dir = TempDir::new().unwrap();
+		let (endpoint, _) = spawn_signer(&dir);
+
+		assert!(RemoteKeystore::connect(endpoint, b"wrong".to_vec()).is_err());
+	}
+
+	#[test]
+	fn limits_concurrent_connections() {
+		let dir = TempDir::new().unwrap();
+		let (endpoint, _) = spawn_signer_with(&dir, |signer| signer.with_max_connections(1));
+
+		let first = RemoteKeystore::connect(endpoint.clone(), SECRET.to_vec()).unwrap();
+		assert!(RemoteKeystore::connect(endpoint.clone(), SECRET.to_vec()).is_err());
+
+		drop(first);
+		let deadline = std::time::Instant::now() + Duration::from_secs(5);
+		while RemoteKeystore::connect(endpoint.clone(), SECRET.to_vec()).is_err() {
+			assert!(std::time::Instant::now() < deadline, "slot was never released");
+			std::thread::sleep(Duration::from_millis(10));
+		}
+	}
+
+	#[test]
+	fn closes_idle_connections() {
+		let dir = TempDir::new().unwrap();
+		let (endpoint, _) = spawn_signer_with(&dir, |signer| {
+			signer.with_max_connections(1).with_idle_timeout(Duration::from_millis(100))
+		});
+
+		let idle = RemoteKeystore::connect(endpoint.clone(), SECRET.to_vec()).unwrap();
+		std::thread::sleep(Duration::from_millis(500));
+
+		// The idle connection was dropped by the signer, freeing its only slot.
+		let active = RemoteKeystore::connect(endpoint, SECRET.to_vec()).unwrap();
+		assert!(active.sr25519_generate_new(SR25519, None).is_ok());
+		// The first client reconnects transparently once the slot is free again.
+		drop(active);
+		std::thread::sleep(Duration::from_millis(100));
+		assert!(idle.sr25519_generate_new(SR25519, None).is_ok());
+	}
+
+	#[test]
+	fn reconnects_over_tcp_after_idle_timeout() {
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let endpoint = Endpoint::Tcp(listener.local_addr().unwrap().to_string());
+		let signer = RemoteSigner::new(Arc::new(LocalKeystore::in_memory()), SECRET.to_vec())
+			.with_idle_timeout(Duration::from_millis(100));
+		std::thread::spawn(move || signer.serve(Listener::Tcp(listener)));
+
+		let remote = RemoteKeystore::connect(endpoint, SECRET.to_vec()).unwrap();
+		let public = remote.sr25519_generate_new(SR25519, None).unwrap();
+
+		// The signer closes the connection while idle, so the next request is first written to
+		// a dead socket and only fails once the answer is read.
+		std::thread::sleep(Duration::from_millis(500));
+		assert_eq!(remote.sr25519_public_keys(SR25519), vec![public]);
+		let signature = remote.sr25519_sign(SR25519, &public, b"hello").unwrap().unwrap();
+		assert!(sr25519::Pair::verify(&signature, b"hello", &public));
+	}
+
+	#[test]
+	fn bind_only_replaces_sockets() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("signer.sock");
+		let endpoint = Endpoint::Unix(path.clone());
+
+		drop(endpoint.bind().unwrap());
+		assert!(endpoint.bind().is_ok());
+
+		std::fs::remove_file(&path).unwrap();
+		std::fs::write(&path, b"precious").unwrap();
+		assert_eq!(endpoint.bind().err().unwrap().kind(), std::io::ErrorKind::AlreadyExists);
+		assert_eq!(std::fs::read(&path).unwrap(), b"precious");
+	}
+}
@@ -0,0 +1,296 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Wire protocol spoken between a [`RemoteKeystore`](super::RemoteKeystore) and a
+//! [`RemoteSigner`](super::RemoteSigner).
+//!
+//! A connection starts with a mutual challenge-response handshake proving that both ends know
+//! the same pre-shared secret. Afterwards every frame is a little endian `u32` length, the SCALE
+//! encoded payload and a 32 byte tag authenticating the payload and its position in the session.
+//! The transport is not encrypted: signing payloads are visible to anyone able to observe the
+//! connection, so TCP endpoints should only be exposed on a trusted network.
+
+use codec::{Decode, Encode};
+use rand::{rngs::OsRng, RngCore};
+use sp_core::{
+	crypto::{CryptoTypeId, KeyTypeId},
+	hashing::blake2_256,
+	sr25519,
+};
+use sp_keystore::Error as TraitError;
+use std::io::{self, Read, Write};
+
+/// Maximum size of a single frame payload.
+const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// Domain separator of the handshake proofs and session key.
+const PROTOCOL_ID: &[u8] = b"substrate-remote-signer/1";
+
+/// A request sent from the node to the signer.
+#[derive(Encode, Decode)]
+pub enum Request {
+	/// List the public keys of the given scheme and key type.
+	PublicKeys { crypto_id: CryptoTypeId, key_type: KeyTypeId },
+	/// Generate a new key pair of the given scheme, returning the public key.
+	GenerateNew { crypto_id: CryptoTypeId, key_type: KeyTypeId, seed: Option<String> },
+	/// Sign `msg` with the key matching `public`.
+	Sign { crypto_id: CryptoTypeId, key_type: KeyTypeId, public: Vec<u8>, msg: Vec<u8> },
+	/// Sign an already hashed message with the ecdsa key matching `public`.
+	EcdsaSignPrehashed { key_type: KeyTypeId, public: Vec<u8>, msg: [u8; 32] },
+	/// Produce a VRF signature without extra data over the hashed `input` with the sr25519 key
+	/// matching `public`.
+	Sr25519VrfSign {
+		key_type: KeyTypeId,
+		public: sr25519::Public,
+		input: sr25519::vrf::VrfInputPoint,
+	},
+	/// Produce a VRF output for the hashed `input` with the sr25519 key matching `public`.
+	Sr25519VrfOutput {
+		key_type: KeyTypeId,
+		public: sr25519::Public,
+		input: sr25519::vrf::VrfInputPoint,
+	},
+	/// Insert a new secret.
+	Insert { key_type: KeyTypeId, suri: String, public: Vec<u8> },
+	/// Remove the secret of the given key.
+	Remove { key_type: KeyTypeId, public: Vec<u8> },
+	/// List all raw public keys of the given key type.
+	Keys { key_type: KeyTypeId },
+	/// List all raw public keys together with their key type.
+	AllKeys,
+	/// Check whether all of the given keys are available.
+	HasKeys { public_keys: Vec<(Vec<u8>, KeyTypeId)> },
+}
+
+/// A response sent from the signer to the node.
+#[derive(Encode, Decode)]
+pub enum Response {
+	/// A list of raw public keys.
+	PublicKeys(Vec<Vec<u8>>),
+	/// A list of raw public keys together with their key type.
+	TypedKeys(Vec<(KeyTypeId, Vec<u8>)>),
+	/// A single raw public key.
+	Public(Vec<u8>),
+	/// A SCALE encoded signature, `None` if the key is not known.
+	Signature(Option<Vec<u8>>),
+	/// A VRF signature, `None` if the key is not known.
+	VrfSignature(Option<sr25519::vrf::VrfSignature>),
+	/// A VRF output, `None` if the key is not known.
+	VrfOutput(Option<sr25519::vrf::VrfOutput>),
+	/// A boolean answer.
+	Bool(bool),
+	/// The request was executed without returning any data.
+	Done,
+	/// The request failed.
+	Error(RemoteError),
+}
+
+/// Error reported by the signer, mirroring [`sp_keystore::Error`].
+#[derive(Debug, Encode, Decode)]
+pub enum RemoteError {
+	/// Key type is not supported.
+	KeyNotSupported(KeyTypeId),
+	/// Validation error.
+	Validation(String),
+	/// Keystore unavailable.
+	Unavailable,
+	/// Any other error.
+	Other(String),
+}
+
+impl From<TraitError> for RemoteError {
+	fn from(error: TraitError) -> Self {
+		match error {
+			TraitError::KeyNotSupported(id) => RemoteError::KeyNotSupported(id),
+			TraitError::ValidationError(e) => RemoteError::Validation(e),
+			TraitError::Unavailable => RemoteError::Unavailable,
+			TraitError::Other(e) => RemoteError::Other(e),
+		}
+	}
+}
+
+impl From<RemoteError> for TraitError {
+	fn from(error: RemoteError) -> Self {
+		match error {
+			RemoteError::KeyNotSupported(id) => TraitError::KeyNotSupported(id),
+			RemoteError::Validation(e) => TraitError::ValidationError(e),
+			RemoteError::Unavailable => TraitError::Unavailable,
+			RemoteError::Other(e) => TraitError::Other(e),
+		}
+	}
+}
+
+/// Which end of the connection we are.
+#[derive(Clone, Copy)]
+enum Role {
+	Client,
+	Server,
+}
+
+impl Role {
+	fn label(self) -> &'static [u8] {
+		match self {
+			Role::Client => b"client",
+			Role::Server => b"server",
+		}
+	}
+
+	fn peer(self) -> Self {
+		match self {
+			Role::Client => Role::Server,
+			Role::Server => Role::Client,
+		}
+	}
+}
+
+/// An authenticated connection to the other end.
+pub struct Session<S> {
+	stream: S,
+	role: Role,
+	key: [u8; 32],
+	sent: u64,
+	received: u64,
+}
+
+impl<S: Read + Write> Session<S> {
+	/// Run the handshake as the node, which connected to the signer.
+	pub fn client(mut stream: S, secret: &[u8]) -> io::Result<Self> {
+		let mut server_nonce = [0u8; 32];
+		stream.read_exact(&mut server_nonce)?;
+
+		let client_nonce = random_nonce();
+		let proof = handshake_proof(secret, Role::Client, &server_nonce, &client_nonce);
+		stream.write_all(&client_nonce)?;
+		stream.write_all(&proof)?;
+		stream.flush()?;
+
+		let mut server_proof = [0u8; 32];
+		stream.read_exact(&mut server_proof)?;
+		let expected = handshake_proof(secret, Role::Server, &server_nonce, &client_nonce);
+		if !constant_time_eq(&server_proof, &expected) {
+			return Err(auth_error("signer failed to prove knowledge of the shared secret"))
+		}
+
+		Ok(Self::new(stream, Role::Client, secret, &server_nonce, &client_nonce))
+	}
+
+	/// Run the handshake as the signer, which accepted the connection.
+	pub fn server(mut stream: S, secret: &[u8]) -> io::Result<Self> {
+		let server_nonce = random_nonce();
+		stream.write_all(&server_nonce)?;
+		stream.flush()?;
+
+		let mut client_nonce = [0u8; 32];
+		let mut client_proof = [0u8; 32];
+		stream.read_exact(&mut client_nonce)?;
+		stream.read_exact(&mut client_proof)?;
+		let expected = handshake_proof(secret, Role::Client, &server_nonce, &client_nonce);
+		if !constant_time_eq(&client_proof, &expected) {
+			return Err(auth_error("client failed to prove knowledge of the shared secret"))
+		}
+
+		let proof = handshake_proof(secret, Role::Server, &server_nonce, &client_nonce);
+		stream.write_all(&proof)?;
+		stream.flush()?;
+
+		Ok(Self::new(stream, Role::Server, secret, &server_nonce, &client_nonce))
+	}
+
+	fn new(
+		stream: S,
+		role: Role,
+		secret: &[u8],
+		server_nonce: &[u8; 32],
+		client_nonce: &[u8; 32],
+	) -> Self {
+		let key =
+			blake2_256(&[PROTOCOL_ID, b"session", secret, server_nonce, client_nonce].concat());
+		Self { stream, role, key, sent: 0, received: 0 }
+	}
+
+	/// The underlying stream.
+	pub fn stream(&self) -> &S {
+		&self.stream
+	}
+
+	/// Send a message to the other end.
+	pub fn send<T: Encode>(&mut self, message: &T) -> io::Result<()> {
+		let payload = message.encode();
+		if payload.len() > MAX_FRAME_SIZE {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, "Frame too large"))
+		}
+		let tag = self.tag(self.role, self.sent, &payload);
+		self.sent += 1;
+
+		self.stream.write_all(&(payload.len() as u32).to_le_bytes())?;
+		self.stream.write_all(&payload)?;
+		self.stream.write_all(&tag)?;
+		self.stream.flush()
+	}
+
+	/// Receive a message from the other end.
+	pub fn receive<T: Decode>(&mut self) -> io::Result<T> {
+		let mut len = [0u8; 4];
+		self.stream.read_exact(&mut len)?;
+		let len = u32::from_le_bytes(len) as usize;
+		if len > MAX_FRAME_SIZE {
+			return Err(io::Error::new(io::ErrorKind::InvalidData, "Frame too large"))
+		}
+
+		let mut payload = vec![0u8; len];
+		let mut tag = [0u8; 32];
+		self.stream.read_exact(&mut payload)?;
+		self.stream.read_exact(&mut tag)?;
+
+		let expected = self.tag(self.role.peer(), self.received, &payload);
+		if !constant_time_eq(&tag, &expected) {
+			return Err(auth_error("invalid frame authentication tag"))
+		}
+		self.received += 1;
+
+		T::decode(&mut &payload[..])
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+	}
+
+	fn tag(&self, sender: Role, counter: u64, payload: &[u8]) -> [u8; 32] {
+		blake2_256(&[&self.key[..], sender.label(), &counter.to_le_bytes(), payload].concat())
+	}
+}
+
+fn handshake_proof(
+	secret: &[u8],
+	role: Role,
+	server_nonce: &[u8; 32],
+	client_nonce: &[u8; 32],
+) -> [u8; 32] {
+	blake2_256(&[PROTOCOL_ID, role.label(), secret, server_nonce, client_nonce].concat())
+}
+
+fn random_nonce() -> [u8; 32] {
+	let mut nonce = [0u8; 32];
+	OsRng.fill_bytes(&mut nonce);
+	nonce
+}
+
+fn constant_time_eq(a: &[u8; 32], b: &[u8; 32]) -> bool {
+	a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn auth_error(msg: &'static str) -> io::Error {
+	io::Error::new(io::ErrorKind::PermissionDenied, msg)
+}
@@ -0,0 +1,232 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Signer side of the remote keystore protocol.
+
+use crate::LocalKeystore;
+use codec::Encode;
+#[cfg(feature = "bandersnatch-experimental")]
+use sp_core::bandersnatch;
+#[cfg(feature = "bls-experimental")]
+use sp_core::{bls377, bls381};
+use sp_core::{
+	crypto::{ByteArray, CryptoTypeId, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{Error as TraitError, Keystore};
+use std::{
+	io,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use super::{
+	protocol::{Request, Response, Session},
+	Listener, Stream, LOG_TARGET,
+};
+
+/// Default maximum number of connections served at the same time.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 16;
+
+/// Time a client has to complete the handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time after which a connection without any request is closed.
+///
+/// [`RemoteKeystore`](super::RemoteKeystore) clients transparently reconnect on their next
+/// request.
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Serves the keys of a [`LocalKeystore`] to [`RemoteKeystore`](super::RemoteKeystore) clients.
+#[derive(Clone)]
+pub struct RemoteSigner {
+	keystore: Arc<LocalKeystore>,
+	secret: Arc<Vec<u8>>,
+	max_connections: usize,
+	idle_timeout: Duration,
+	connections: Arc<AtomicUsize>,
+}
+
+/// Slot of a served connection, released on drop.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+	fn drop(&mut self) {
+		self.0.fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
+impl RemoteSigner {
+	/// Create a new signer serving `keystore` to clients knowing `secret`.
+	pub fn new(keystore: Arc<LocalKeystore>, secret: Vec<u8>) -> Self {
+		Self {
+			keystore,
+			secret: Arc::new(secret),
+			max_connections: DEFAULT_MAX_CONNECTIONS,
+			idle_timeout: DEFAULT_IDLE_TIMEOUT,
+			connections: Default::default(),
+		}
+	}
+
+	/// Serve at most `max_connections` connections at the same time.
+	///
+	/// Connections accepted above the limit are closed right away.
+	pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+		self.max_connections = max_connections;
+		self
+	}
+
+	/// Close connections not sending any request for `idle_timeout`.
+	pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+		self.idle_timeout = idle_timeout;
+		self
+	}
+
+	/// Accept connections on `listener` forever, serving each one on its own thread.
+	pub fn serve(&self, listener: Listener) -> io::Result<()> {
+		loop {
+			let stream = listener.accept()?;
+			let Some(slot) = self.acquire_slot() else {
+				log::warn!(
+					target: LOG_TARGET,
+					"Rejected client: {} connections already served",
+					self.max_connections,
+				);
+				continue
+			};
+			let signer = self.clone();
+			std::thread::spawn(move || {
+				let _slot = slot;
+				if let Err(e) = signer.serve_connection(stream) {
+					log::debug!(target: LOG_TARGET, "Connection closed: {}", e);
+				}
+			});
+		}
+	}
+
+	fn acquire_slot(&self) -> Option<ConnectionSlot> {
+		self.connections
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+				(n < self.max_connections).then_some(n + 1)
+			})
+			.ok()
+			.map(|_| ConnectionSlot(self.connections.clone()))
+	}
+
+	/// Authenticate the client on `stream` and answer its requests until it disconnects or
+	/// stays idle for too long.
+	pub fn serve_connection(&self, stream: Stream) -> io::Result<()> {
+		stream.set_timeout(Some(HANDSHAKE_TIMEOUT))?;
+		let mut session = Session::server(stream, &self.secret).map_err(|e| {
+			log::warn!(target: LOG_TARGET, "Rejected client: {}", e);
+			e
+		})?;
+		session.stream().set_timeout(Some(self.idle_timeout))?;
+
+		loop {
+			let request = session.receive::<Request>()?;
+			let response = self.handle(request).unwrap_or_else(|e| Response::Error(e.into()));
+			session.send(&response)?;
+		}
+	}
+
+	fn handle(&self, request: Request) -> Result<Response, TraitError> {
+		let keystore = &self.keystore;
+		let response = match request {
+			Request::PublicKeys { crypto_id, key_type } =>
+				Response::PublicKeys(self.public_keys(crypto_id, key_type)?),
+			Request::GenerateNew { crypto_id, key_type, seed } =>
+				Response::Public(self.generate_new(crypto_id, key_type, seed.as_deref())?),
+			Request::Sign { crypto_id, key_type, public, msg } =>
+				Response::Signature(keystore.sign_with(key_type, crypto_id, &public, &msg)?),
+			Request::EcdsaSignPrehashed { key_type, public, msg } => {
+				let public = ecdsa::Public::from_slice(&public)
+					.map_err(|_| TraitError::ValidationError("Invalid public key format".into()))?;
+				let signature = keystore.ecdsa_sign_prehashed(key_type, &public, &msg)?;
+				Response::Signature(signature.map(|s| s.encode()))
+			},
+			Request::Sr25519VrfSign { key_type, public, input } =>
+				Response::VrfSignature(keystore.sr25519_vrf_sign_point(key_type, &public, &input)?),
+			Request::Sr25519VrfOutput { key_type, public, input } => Response::VrfOutput(
+				keystore.sr25519_vrf_output_from_point(key_type, &public, &input)?,
+			),
+			Request::Insert { key_type, suri, public } => {
+				keystore.insert(key_type, &suri, &public).map_err(|_| {
+					TraitError::Other("Failed to insert key into the keystore".into())
+				})?;
+				Response::Done
+			},
+			Request::Remove { key_type, public } =>
+				Response::Bool(keystore.remove(key_type, &public)?),
+			Request::Keys { key_type } => Response::PublicKeys(keystore.keys(key_type)?),
+			Request::AllKeys => Response::TypedKeys(keystore.all_keys()?),
+			Request::HasKeys { public_keys } => Response::Bool(keystore.has_keys(&public_keys)),
+		};
+		Ok(response)
+	}
+
+	fn public_keys(
+		&self,
+		crypto_id: CryptoTypeId,
+		key_type: KeyTypeId,
+	) -> Result<Vec<Vec<u8>>, TraitError> {
+		fn raw<P: ByteArray>(keys: Vec<P>) -> Vec<Vec<u8>> {
+			keys.into_iter().map(|k| k.to_raw_vec()).collect()
+		}
+
+		let keystore = &self.keystore;
+		let keys = match crypto_id {
+			sr25519::CRYPTO_ID => raw(keystore.sr25519_public_keys(key_type)),
+			ed25519::CRYPTO_ID => raw(keystore.ed25519_public_keys(key_type)),
+			ecdsa::CRYPTO_ID => raw(keystore.ecdsa_public_keys(key_type)),
+			#[cfg(feature = "bandersnatch-experimental")]
+			bandersnatch::CRYPTO_ID => raw(keystore.bandersnatch_public_keys(key_type)),
+			#[cfg(feature = "bls-experimental")]
+			bls381::CRYPTO_ID => raw(keystore.bls381_public_keys(key_type)),
+			#[cfg(feature = "bls-experimental")]
+			bls377::CRYPTO_ID => raw(keystore.bls377_public_keys(key_type)),
+			_ => return Err(TraitError::KeyNotSupported(key_type)),
+		};
+		Ok(keys)
+	}
+
+	fn generate_new(
+		&self,
+		crypto_id: CryptoTypeId,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<Vec<u8>, TraitError> {
+		let keystore = &self.keystore;
+		let public = match crypto_id {
+			sr25519::CRYPTO_ID => keystore.sr25519_generate_new(key_type, seed)?.to_raw_vec(),
+			ed25519::CRYPTO_ID => keystore.ed25519_generate_new(key_type, seed)?.to_raw_vec(),
+			ecdsa::CRYPTO_ID => keystore.ecdsa_generate_new(key_type, seed)?.to_raw_vec(),
+			#[cfg(feature = "bandersnatch-experimental")]
+			bandersnatch::CRYPTO_ID => keystore.bandersnatch_generate_new(key_type, seed)?.to_raw_vec(),
+			#[cfg(feature = "bls-experimental")]
+			bls381::CRYPTO_ID => keystore.bls381_generate_new(key_type, seed)?.to_raw_vec(),
+			#[cfg(feature = "bls-experimental")]
+			bls377::CRYPTO_ID => keystore.bls377_generate_new(key_type, seed)?.to_raw_vec(),
+			_ => return Err(TraitError::KeyNotSupported(key_type)),
+		};
+		Ok(public)
+	}
+}
@@ -0,0 +1,204 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unix socket and TCP transports of the remote signer protocol.
+
+#[cfg(unix)]
+use std::os::unix::{
+	fs::FileTypeExt,
+	net::{UnixListener, UnixStream},
+};
+use std::{
+	fmt,
+	io::{self, Read, Write},
+	net::{TcpListener, TcpStream},
+	path::PathBuf,
+	str::FromStr,
+	time::Duration,
+};
+
+/// Address of a remote signer.
+///
+/// Parsed from `unix://<path>` or `tcp://<host>:<port>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+	/// A Unix domain socket at the given path.
+	Unix(PathBuf),
+	/// A TCP socket address.
+	Tcp(String),
+}
+
+impl FromStr for Endpoint {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some(path) = s.strip_prefix("unix://") {
+			if path.is_empty() {
+				return Err("Missing socket path in remote signer URI".into())
+			}
+			Ok(Endpoint::Unix(path.into()))
+		} else if let Some(addr) = s.strip_prefix("tcp://") {
+			if addr.is_empty() {
+				return Err("Missing address in remote signer URI".into())
+			}
+			Ok(Endpoint::Tcp(addr.into()))
+		} else {
+			Err(format!(
+				"Invalid remote signer URI `{}`, expected `unix://<path>` or `tcp://<host>:<port>`",
+				s
+			))
+		}
+	}
+}
+
+impl fmt::Display for Endpoint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Endpoint::Unix(path) => write!(f, "unix://{}", path.display()),
+			Endpoint::Tcp(addr) => write!(f, "tcp://{}", addr),
+		}
+	}
+}
+
+impl Endpoint {
+	/// Connect to the endpoint, applying `timeout` to every read and write.
+	pub fn connect(&self, timeout: Duration) -> io::Result<Stream> {
+		match self {
+			#[cfg(unix)]
+			Endpoint::Unix(path) => {
+				let stream = Stream::Unix(UnixStream::connect(path)?);
+				stream.set_timeout(Some(timeout))?;
+				Ok(stream)
+			},
+			#[cfg(not(unix))]
+			Endpoint::Unix(_) => Err(unix_unsupported()),
+			Endpoint::Tcp(addr) => {
+				let stream = TcpStream::connect(addr)?;
+				stream.set_nodelay(true)?;
+				let stream = Stream::Tcp(stream);
+				stream.set_timeout(Some(timeout))?;
+				Ok(stream)
+			},
+		}
+	}
+
+	/// Start listening on the endpoint.
+	///
+	/// A stale socket left at the path of a Unix endpoint is replaced, while any other kind of
+	/// file is left untouched and makes binding fail.
+	pub fn bind(&self) -> io::Result<Listener> {
+		match self {
+			#[cfg(unix)]
+			Endpoint::Unix(path) => {
+				match std::fs::symlink_metadata(path) {
+					Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path)?,
+					Ok(_) =>
+						return Err(io::Error::new(
+							io::ErrorKind::AlreadyExists,
+							format!("{} exists and is not a socket", path.display()),
+						)),
+					Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+					Err(e) => return Err(e),
+				}
+				Ok(Listener::Unix(UnixListener::bind(path)?))
+			},
+			#[cfg(not(unix))]
+			Endpoint::Unix(_) => Err(unix_unsupported()),
+			Endpoint::Tcp(addr) => Ok(Listener::Tcp(TcpListener::bind(addr)?)),
+		}
+	}
+}
+
+#[cfg(not(unix))]
+fn unix_unsupported() -> io::Error {
+	io::Error::new(io::ErrorKind::Unsupported, "Unix sockets are not supported on this platform")
+}
+
+/// A connected stream.
+pub enum Stream {
+	/// Unix domain socket.
+	#[cfg(unix)]
+	Unix(UnixStream),
+	/// TCP socket.
+	Tcp(TcpStream),
+}
+
+impl Stream {
+	/// Apply `timeout` to every read and write, `None` blocking forever.
+	pub fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+		match self {
+			#[cfg(unix)]
+			Stream::Unix(s) => {
+				s.set_read_timeout(timeout)?;
+				s.set_write_timeout(timeout)
+			},
+			Stream::Tcp(s) => {
+				s.set_read_timeout(timeout)?;
+				s.set_write_timeout(timeout)
+			},
+		}
+	}
+}
+
+impl Read for Stream {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		match self {
+			#[cfg(unix)]
+			Stream::Unix(s) => s.read(buf),
+			Stream::Tcp(s) => s.read(buf),
+		}
+	}
+}
+
+impl Write for Stream {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		match self {
+			#[cfg(unix)]
+			Stream::Unix(s) => s.write(buf),
+			Stream::Tcp(s) => s.write(buf),
+		}
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		match self {
+			#[cfg(unix)]
+			Stream::Unix(s) => s.flush(),
+			Stream::Tcp(s) => s.flush(),
+		}
+	}
+}
+
+/// A listening socket.
+pub enum Listener {
+	/// Unix domain socket.
+	#[cfg(unix)]
+	Unix(UnixListener),
+	/// TCP socket.
+	Tcp(TcpListener),
+}
+
+impl Listener {
+	/// Accept a new incoming connection.
+	pub fn accept(&self) -> io::Result<Stream> {
+		match self {
+			#[cfg(unix)]
+			Listener::Unix(l) => l.accept().map(|(s, _)| Stream::Unix(s)),
+			Listener::Tcp(l) => l.accept().map(|(s, _)| Stream::Tcp(s)),
+		}
+	}
+}
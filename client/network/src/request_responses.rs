@@ -264,8 +264,12 @@ pub struct RequestResponsesBehaviour {
 	>,
 
 	/// Pending requests, passed down to a request-response [`Behaviour`], awaiting a reply.
-	pending_requests:
-		HashMap<ProtocolRequestId, (Instant, oneshot::Sender<Result<Vec<u8>, RequestFailure>>)>,
+	///
+	/// The span of a request is closed once it is removed from here.
+	pending_requests: HashMap<
+		ProtocolRequestId,
+		(Instant, tracing::Span, oneshot::Sender<Result<Vec<u8>, RequestFailure>>),
+	>,
 
 	/// Whenever an incoming request arrives, a `Future` is added to this list and will yield the
 	/// start time and the response to send back to the remote.
@@ -357,10 +361,18 @@ impl RequestResponsesBehaviour {
 
 		if let Some((protocol, _)) = self.protocols.get_mut(protocol_name) {
 			if protocol.is_connected(target) || connect.should_connect() {
+				let span = tracing::debug_span!(
+					target: "sub-libp2p",
+					"request",
+					peer = %target,
+					protocol = protocol_name,
+					size = request.len(),
+					result = tracing::field::Empty,
+				);
 				let request_id = protocol.send_request(target, request);
 				let prev_req_id = self.pending_requests.insert(
 					(protocol_name.to_string().into(), request_id).into(),
-					(Instant::now(), pending_response),
+					(Instant::now(), span, pending_response),
 				);
 				debug_assert!(prev_req_id.is_none(), "Expect request id to be unique.");
 			} else if pending_response.send(Err(RequestFailure::NotConnected)).is_err() {
@@ -698,7 +710,8 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 								.pending_requests
 								.remove(&(protocol.clone(), request_id).into())
 							{
-								Some((started, pending_response)) => {
+								Some((started, span, pending_response)) => {
+									span.record("result", "response");
 									log::trace!(
 										target: "sub-libp2p",
 										"received response from {peer} ({protocol:?}), {} bytes",
@@ -742,7 +755,8 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 								.pending_requests
 								.remove(&(protocol.clone(), request_id).into())
 							{
-								Some((started, pending_response)) => {
+								Some((started, span, pending_response)) => {
+									span.record("result", tracing::field::display(&error));
 									if pending_response
 										.send(Err(RequestFailure::Network(error.clone())))
 										.is_err()
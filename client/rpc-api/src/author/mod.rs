@@ -20,11 +20,22 @@
 
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use sc_transaction_pool_api::TransactionStatus;
+use serde::{Deserialize, Serialize};
 use sp_core::Bytes;
 
 pub mod error;
 pub mod hash;
 
+/// A key held by the keystore of the node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreKey {
+	/// The key type, e.g. `"gran"` or `"babe"`.
+	pub key_type: String,
+	/// The raw public key.
+	pub public_key: Bytes,
+}
+
 /// Substrate authoring RPC API
 #[rpc(client, server)]
 pub trait AuthorApi<Hash, BlockHash> {
@@ -54,6 +65,18 @@ pub trait AuthorApi<Hash, BlockHash> {
 	#[method(name = "author_hasKey")]
 	fn has_key(&self, public_key: Bytes, key_type: String) -> RpcResult<bool>;
 
+	/// Lists the public keys held by the keystore.
+	///
+	/// Only returns keys of `key_type`, if given.
+	#[method(name = "author_listKeys")]
+	fn list_keys(&self, key_type: Option<String>) -> RpcResult<Vec<KeystoreKey>>;
+
+	/// Removes the private key for the given public key and key type from the keystore.
+	///
+	/// Returns `true` if a private key was found and removed.
+	#[method(name = "author_removeKey")]
+	fn remove_key(&self, public_key: Bytes, key_type: String) -> RpcResult<bool>;
+
 	/// Returns all pending extrinsics, potentially grouped by sender.
 	#[method(name = "author_pendingExtrinsics")]
 	fn pending_extrinsics(&self) -> RpcResult<Vec<Bytes>>;
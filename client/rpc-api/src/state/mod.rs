@@ -261,6 +261,14 @@ pub trait StateApi<Hash> {
 	/// If an empty string is specified no events will be filtered out. If anything other than
 	/// an empty string is specified, events will be filtered by method (so non-method events will
 	/// **not** show up).
+	/// - `format` (param index 4): Format of the response, `json` by default. `chromeTrace`
+	/// returns the spans in the Chrome trace-event format, which can be loaded into Perfetto.
+	/// `foldedStacks` returns the spans as folded stacks, which can be turned into a flamegraph
+	/// with `inferno-flamegraph`. Both are built from the span timings and parent ids.
+	/// - `profile_host_functions` (param index 5): Whether to profile the host functions called
+	/// while executing the block, `false` by default. The statistics are returned in the
+	/// `hostFunctions` field of the `json` format. Profiling slows down the execution, which also
+	/// shows in the span timings.
 	///
 	/// Additionally you would want to track the extrinsic index, which is under the
 	/// `:extrinsic_index` key. The key for this would be the aforementioned string as bytes
@@ -288,5 +296,7 @@ pub trait StateApi<Hash> {
 		targets: Option<String>,
 		storage_keys: Option<String>,
 		methods: Option<String>,
+		format: Option<sp_rpc::tracing::TraceBlockFormat>,
+		profile_host_functions: Option<bool>,
 	) -> RpcResult<sp_rpc::tracing::TraceBlockResponse>;
 }
@@ -0,0 +1,325 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Readiness and liveness endpoints, e.g. for Kubernetes probes or load balancers.
+//!
+//! `GET /health/readiness` and `GET /health/liveness` answer with `200 OK` if all checks pass and
+//! with `503 Service Unavailable` otherwise. The body always describes the health of the node and
+//! the checks that failed.
+
+use http::{header, Method, Request, Response, StatusCode};
+use hyper::Body;
+use std::{
+	error::Error as StdError,
+	fmt,
+	future::Future,
+	pin::Pin,
+	sync::Arc,
+	task::{Context, Poll},
+	time::Duration,
+};
+use tower::{Layer, Service};
+
+/// Path of the readiness endpoint.
+pub const READINESS_PATH: &str = "/health/readiness";
+
+/// Path of the liveness endpoint.
+pub const LIVENESS_PATH: &str = "/health/liveness";
+
+/// Thresholds deciding whether the node is ready and alive.
+///
+/// A node is alive as long as it keeps importing blocks. It is ready if it is alive and also
+/// connected, synced and finalizing.
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+	/// Minimum number of connected peers for the node to be ready.
+	///
+	/// `0` for nodes that aren't expected to have any peers, e.g. development nodes.
+	pub min_peers: usize,
+	/// Whether the node is ready while it is major syncing.
+	pub ready_while_syncing: bool,
+	/// Maximum time since the best block last changed for the node to be ready and alive.
+	pub max_best_block_age: Option<Duration>,
+	/// Maximum number of blocks between the best and the finalized block for the node to be
+	/// ready.
+	pub max_finality_lag: Option<u64>,
+}
+
+impl Default for HealthConfig {
+	fn default() -> Self {
+		Self {
+			min_peers: 1,
+			ready_while_syncing: false,
+			max_best_block_age: None,
+			max_finality_lag: None,
+		}
+	}
+}
+
+impl HealthConfig {
+	/// The liveness checks failed by `health`.
+	pub fn liveness_failures(&self, health: &NodeHealth) -> Vec<String> {
+		let mut failures = Vec::new();
+		if let Some(max_age) = self.max_best_block_age {
+			if health.best_block_age > max_age {
+				failures.push(format!(
+					"best block unchanged for {}s, more than {}s",
+					health.best_block_age.as_secs(),
+					max_age.as_secs(),
+				));
+			}
+		}
+		failures
+	}
+
+	/// The readiness checks failed by `health`, including the liveness checks.
+	pub fn readiness_failures(&self, health: &NodeHealth) -> Vec<String> {
+		let mut failures = self.liveness_failures(health);
+		if health.peers < self.min_peers {
+			failures
+				.push(format!("{} peers connected, less than {}", health.peers, self.min_peers));
+		}
+		if health.is_major_syncing && !self.ready_while_syncing {
+			failures.push("major syncing".into());
+		}
+		if let Some(max_lag) = self.max_finality_lag {
+			if health.finality_lag > max_lag {
+				failures.push(format!(
+					"finality lagging {} blocks behind, more than {}",
+					health.finality_lag, max_lag,
+				));
+			}
+		}
+		failures
+	}
+}
+
+/// The state of the node the health checks are based on.
+#[derive(Debug, Clone)]
+pub struct NodeHealth {
+	/// Number of connected peers.
+	pub peers: usize,
+	/// Whether the node is major syncing.
+	pub is_major_syncing: bool,
+	/// Time since the best block last changed.
+	pub best_block_age: Duration,
+	/// Number of blocks between the best and the finalized block.
+	pub finality_lag: u64,
+}
+
+/// Something that knows the [`NodeHealth`] of the node.
+pub trait NodeHealthProvider: Send + Sync {
+	/// The current health of the node.
+	///
+	/// Called for every request to the health endpoints, so it should be cheap.
+	fn node_health(&self) -> NodeHealth;
+}
+
+/// Layer answering requests to [`READINESS_PATH`] and [`LIVENESS_PATH`].
+#[derive(Clone)]
+pub struct NodeHealthLayer {
+	config: HealthConfig,
+	provider: Arc<dyn NodeHealthProvider>,
+}
+
+impl NodeHealthLayer {
+	/// Create a new [`NodeHealthLayer`].
+	pub fn new(config: HealthConfig, provider: Arc<dyn NodeHealthProvider>) -> Self {
+		Self { config, provider }
+	}
+}
+
+impl fmt::Debug for NodeHealthLayer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("NodeHealthLayer")
+			.field("config", &self.config)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<S> Layer<S> for NodeHealthLayer {
+	type Service = NodeHealthService<S>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		NodeHealthService { inner, config: self.config.clone(), provider: self.provider.clone() }
+	}
+}
+
+/// Service answering requests to [`READINESS_PATH`] and [`LIVENESS_PATH`], see
+/// [`NodeHealthLayer`].
+#[derive(Clone)]
+pub struct NodeHealthService<S> {
+	inner: S,
+	config: HealthConfig,
+	provider: Arc<dyn NodeHealthProvider>,
+}
+
+impl<S> Service<Request<Body>> for NodeHealthService<S>
+where
+	S: Service<Request<Body>, Response = Response<Body>>,
+	S::Error: Into<Box<dyn StdError + Send + Sync>> + 'static,
+	S::Future: Send + 'static,
+{
+	type Response = S::Response;
+	type Error = Box<dyn StdError + Send + Sync>;
+	type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.inner.poll_ready(cx).map_err(Into::into)
+	}
+
+	fn call(&mut self, req: Request<Body>) -> Self::Future {
+		let readiness = match (req.method(), req.uri().path()) {
+			(&Method::GET, READINESS_PATH) => true,
+			(&Method::GET, LIVENESS_PATH) => false,
+			_ => {
+				let fut = self.inner.call(req);
+				return Box::pin(async move { fut.await.map_err(Into::into) })
+			},
+		};
+
+		let health = self.provider.node_health();
+		let failures = if readiness {
+			self.config.readiness_failures(&health)
+		} else {
+			self.config.liveness_failures(&health)
+		};
+		let res = health_response(&health, failures);
+
+		Box::pin(async move { res })
+	}
+}
+
+fn health_response(
+	health: &NodeHealth,
+	failures: Vec<String>,
+) -> Result<Response<Body>, Box<dyn StdError + Send + Sync>> {
+	let status = if failures.is_empty() { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+	let body = serde_json::json!({
+		"peers": health.peers,
+		"isSyncing": health.is_major_syncing,
+		"bestBlockAgeSecs": health.best_block_age.as_secs(),
+		"finalityLag": health.finality_lag,
+		"failures": failures,
+	});
+
+	Response::builder()
+		.status(status)
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(body.to_string()))
+		.map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn health() -> NodeHealth {
+		NodeHealth {
+			peers: 3,
+			is_major_syncing: false,
+			best_block_age: Duration::from_secs(10),
+			finality_lag: 2,
+		}
+	}
+
+	#[test]
+	fn readiness_includes_liveness_checks() {
+		let config = HealthConfig {
+			min_peers: 5,
+			ready_while_syncing: false,
+			max_best_block_age: Some(Duration::from_secs(6)),
+			max_finality_lag: Some(2),
+		};
+
+		assert_eq!(config.liveness_failures(&health()).len(), 1);
+		assert_eq!(config.readiness_failures(&health()).len(), 2);
+		assert!(config
+			.readiness_failures(&NodeHealth { is_major_syncing: true, ..health() })
+			.contains(&"major syncing".to_string()));
+	}
+
+	#[test]
+	fn peers_are_ignored_if_not_expected() {
+		let config = HealthConfig::default();
+		assert!(!config.readiness_failures(&NodeHealth { peers: 0, ..health() }).is_empty());
+
+		let config = HealthConfig { min_peers: 0, ..Default::default() };
+		assert!(config.readiness_failures(&NodeHealth { peers: 0, ..health() }).is_empty());
+	}
+
+	struct FixedHealth(NodeHealth);
+
+	impl NodeHealthProvider for FixedHealth {
+		fn node_health(&self) -> NodeHealth {
+			self.0.clone()
+		}
+	}
+
+	/// Stands in for the RPC server, answering every request it gets with `418`.
+	#[derive(Clone)]
+	struct Inner;
+
+	impl Service<Request<Body>> for Inner {
+		type Response = Response<Body>;
+		type Error = http::Error;
+		type Future = std::future::Ready<Result<Response<Body>, http::Error>>;
+
+		fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+			Poll::Ready(Ok(()))
+		}
+
+		fn call(&mut self, _: Request<Body>) -> Self::Future {
+			std::future::ready(
+				Response::builder().status(StatusCode::IM_A_TEAPOT).body(Body::empty()),
+			)
+		}
+	}
+
+	async fn get(service: &mut NodeHealthService<Inner>, path: &str) -> (StatusCode, String) {
+		let req = Request::get(path).body(Body::empty()).unwrap();
+		let res = service.call(req).await.unwrap();
+		let status = res.status();
+		let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+		(status, String::from_utf8(body.to_vec()).unwrap())
+	}
+
+	#[tokio::test]
+	async fn service_answers_health_requests() {
+		let config = HealthConfig { max_finality_lag: Some(1), ..Default::default() };
+		let provider = Arc::new(FixedHealth(health()));
+		let mut service = NodeHealthLayer::new(config, provider).layer(Inner);
+
+		let (status, body) = get(&mut service, LIVENESS_PATH).await;
+		assert_eq!(status, StatusCode::OK);
+		let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+		assert_eq!(body["peers"], 3);
+		assert_eq!(body["failures"], serde_json::json!([]));
+
+		let (status, body) = get(&mut service, READINESS_PATH).await;
+		assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+		let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+		assert_eq!(body["finalityLag"], 2);
+		assert_eq!(body["failures"].as_array().unwrap().len(), 1);
+
+		// Everything else is left to the RPC server.
+		assert_eq!(get(&mut service, "/health").await.0, StatusCode::IM_A_TEAPOT);
+		let req = Request::post(READINESS_PATH).body(Body::empty()).unwrap();
+		assert_eq!(service.call(req).await.unwrap().status(), StatusCode::IM_A_TEAPOT);
+	}
+}
@@ -20,6 +20,7 @@
 
 #![warn(missing_docs)]
 
+pub mod health;
 pub mod middleware;
 
 use http::header::HeaderValue;
@@ -33,7 +34,10 @@ use jsonrpsee::{
 use std::{error::Error as StdError, net::SocketAddr};
 use tower_http::cors::{AllowOrigin, CorsLayer};
 
-pub use crate::middleware::RpcMetrics;
+pub use crate::{
+	health::{HealthConfig, NodeHealth, NodeHealthLayer, NodeHealthProvider},
+	middleware::RpcMetrics,
+};
 pub use jsonrpsee::core::{
 	id_providers::{RandomIntegerIdProvider, RandomStringIdProvider},
 	traits::IdProvider,
@@ -67,6 +71,8 @@ pub struct Config<'a, M: Send + Sync + 'static> {
 	pub id_provider: Option<Box<dyn IdProvider>>,
 	/// Tokio runtime handle.
 	pub tokio_handle: tokio::runtime::Handle,
+	/// Readiness and liveness endpoints.
+	pub health: NodeHealthLayer,
 }
 
 /// Start RPC server listening on given address.
@@ -84,6 +90,7 @@ pub async fn start_server<M: Send + Sync + 'static>(
 		id_provider,
 		tokio_handle,
 		rpc_api,
+		health,
 	} = config;
 
 	let host_filter = hosts_filtering(cors.is_some(), &addrs);
@@ -91,6 +98,8 @@ pub async fn start_server<M: Send + Sync + 'static>(
 	let middleware = tower::ServiceBuilder::new()
 		// Proxy `GET /health` requests to internal `system_health` method.
 		.layer(ProxyGetRequestLayer::new("/health", "system_health")?)
+		// Answer `GET /health/readiness` and `GET /health/liveness` with proper status codes.
+		.layer(health)
 		.layer(try_into_cors(cors)?);
 
 	let mut builder = ServerBuilder::new()
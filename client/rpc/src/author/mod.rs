@@ -39,7 +39,7 @@ use sc_transaction_pool_api::{
 };
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::Bytes;
+use sp_core::{crypto::KeyTypeId, Bytes};
 use sp_keystore::{KeystoreExt, KeystorePtr};
 use sp_runtime::{generic, traits::Block as BlockT};
 use sp_session::SessionKeys;
@@ -153,6 +153,39 @@ where
 		Ok(self.keystore.has_keys(&[(public_key.to_vec(), key_type)]))
 	}
 
+	fn list_keys(&self, key_type: Option<String>) -> RpcResult<Vec<KeystoreKey>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let keys = match key_type {
+			Some(key_type) => {
+				let key_type: KeyTypeId =
+					key_type.as_str().try_into().map_err(|_| Error::BadKeyType)?;
+				let keys = self.keystore.keys(key_type).map_err(|_| Error::KeystoreUnavailable)?;
+				keys.into_iter().map(|public| (key_type, public)).collect()
+			},
+			None => self.keystore.all_keys().map_err(|_| Error::KeystoreUnavailable)?,
+		};
+
+		Ok(keys
+			.into_iter()
+			.map(|(key_type, public)| KeystoreKey {
+				key_type: String::from_utf8_lossy(&key_type.0).into_owned(),
+				public_key: public.into(),
+			})
+			.collect())
+	}
+
+	fn remove_key(&self, public_key: Bytes, key_type: String) -> RpcResult<bool> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let key_type = key_type.as_str().try_into().map_err(|_| Error::BadKeyType)?;
+		let removed = self
+			.keystore
+			.remove(key_type, &public_key[..])
+			.map_err(|_| Error::KeystoreUnavailable)?;
+		Ok(removed)
+	}
+
 	fn pending_extrinsics(&self) -> RpcResult<Vec<Bytes>> {
 		Ok(self.pool.ready().map(|tx| tx.data().encode().into()).collect())
 	}
@@ -328,3 +328,40 @@ async fn author_has_key() {
 	};
 	assert!(!has_bob_ed);
 }
+
+#[tokio::test]
+async fn author_should_list_and_remove_keys() {
+	let setup = TestSetup::default();
+	let api = setup.author().into_rpc();
+	let alice = setup.keystore.ed25519_generate_new(ED25519, Some("//Alice")).unwrap();
+	let bob = setup.keystore.sr25519_generate_new(SR25519, Some("//Bob")).unwrap();
+	let ed25519_key_type = String::from_utf8(ED25519.0.to_vec()).unwrap();
+
+	let mut keys: Vec<KeystoreKey> = api.call("author_listKeys", [None::<String>]).await.unwrap();
+	keys.sort_by(|a, b| a.key_type.cmp(&b.key_type));
+	assert_eq!(
+		keys,
+		vec![
+			KeystoreKey {
+				key_type: ed25519_key_type.clone(),
+				public_key: alice.to_raw_vec().into()
+			},
+			KeystoreKey {
+				key_type: String::from_utf8(SR25519.0.to_vec()).unwrap(),
+				public_key: bob.to_raw_vec().into()
+			},
+		]
+	);
+
+	let keys: Vec<KeystoreKey> =
+		api.call("author_listKeys", [Some(ed25519_key_type.clone())]).await.unwrap();
+	assert_eq!(keys.len(), 1);
+
+	let params = (Bytes::from(alice.to_raw_vec()), ed25519_key_type.clone());
+	let removed: bool = api.call("author_removeKey", params.clone()).await.unwrap();
+	assert!(removed);
+	assert!(setup.keystore.keys(ED25519).unwrap().is_empty());
+
+	let removed: bool = api.call("author_removeKey", params).await.unwrap();
+	assert!(!removed);
+}
@@ -155,6 +155,8 @@ where
 		targets: Option<String>,
 		storage_keys: Option<String>,
 		methods: Option<String>,
+		format: Option<sp_rpc::tracing::TraceBlockFormat>,
+		profile_host_functions: Option<bool>,
 	) -> Result<sp_rpc::tracing::TraceBlockResponse, Error>;
 
 	/// New runtime version subscription
@@ -318,10 +320,12 @@ where
 		targets: Option<String>,
 		storage_keys: Option<String>,
 		methods: Option<String>,
+		format: Option<sp_rpc::tracing::TraceBlockFormat>,
+		profile_host_functions: Option<bool>,
 	) -> RpcResult<sp_rpc::tracing::TraceBlockResponse> {
 		self.deny_unsafe.check_if_safe()?;
 		self.backend
-			.trace_block(block, targets, storage_keys, methods)
+			.trace_block(block, targets, storage_keys, methods, format, profile_host_functions)
 			.map_err(Into::into)
 	}
 
@@ -198,7 +198,7 @@ where
 			.and_then(|block| {
 				self.client
 					.executor()
-					.call(block, &method, &call_data, CallContext::Offchain)
+					.call(block, &method, &call_data, CallContext::Rpc)
 					.map(Into::into)
 			})
 			.map_err(client_err)
@@ -463,6 +463,8 @@ where
 		targets: Option<String>,
 		storage_keys: Option<String>,
 		methods: Option<String>,
+		format: Option<sp_rpc::tracing::TraceBlockFormat>,
+		profile_host_functions: Option<bool>,
 	) -> std::result::Result<sp_rpc::tracing::TraceBlockResponse, Error> {
 		sc_tracing::block::BlockExecutor::new(
 			self.client.clone(),
@@ -471,6 +473,8 @@ where
 			storage_keys,
 			methods,
 		)
+		.with_format(format.unwrap_or_default())
+		.with_host_function_profiling(profile_host_functions.unwrap_or_default())
 		.trace_block()
 		.map_err(|e| invalid_block::<Block>(block, None, e.to_string()))
 	}
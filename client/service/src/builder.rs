@@ -21,6 +21,7 @@ use crate::{
 	client::{Client, ClientConfig},
 	config::{Configuration, KeystoreConfig, PrometheusConfig},
 	error::Error,
+	health::HealthProvider,
 	metrics::MetricsService,
 	start_rpc_servers, BuildGenesisBlock, GenesisBlockBuilder, RpcHandlers, SpawnTaskHandle,
 	TaskManager, TransactionPoolAdapter,
@@ -40,7 +41,7 @@ use sc_executor::{
 	sp_wasm_interface::HostFunctions, HeapAllocStrategy, NativeElseWasmExecutor,
 	NativeExecutionDispatch, RuntimeVersionOf, WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY,
 };
-use sc_keystore::LocalKeystore;
+use sc_keystore::{remote::Endpoint, LocalKeystore, RemoteKeystore};
 use sc_network::{
 	config::{FullNetworkConfiguration, SyncMode},
 	peer_store::PeerStore,
@@ -71,7 +72,10 @@ use sp_blockchain::{HeaderBackend, HeaderMetadata};
 use sp_consensus::block_validation::{
 	BlockAnnounceValidator, Chain, DefaultBlockAnnounceValidator,
 };
-use sp_core::traits::{CodeExecutor, SpawnNamed};
+use sp_core::{
+	crypto::ExposeSecret,
+	traits::{CodeExecutor, SpawnNamed},
+};
 use sp_keystore::KeystorePtr;
 use sp_runtime::traits::{Block as BlockT, BlockIdTo, NumberFor, Zero};
 use std::{str::FromStr, sync::Arc, time::SystemTime};
@@ -90,28 +94,45 @@ type TFullParts<TBl, TRtApi, TExec> =
 	(TFullClient<TBl, TRtApi, TExec>, Arc<TFullBackend<TBl>>, KeystoreContainer, TaskManager);
 
 /// Construct a local keystore shareable container
-pub struct KeystoreContainer(Arc<LocalKeystore>);
+pub struct KeystoreContainer {
+	local: Arc<LocalKeystore>,
+	remote: Option<Arc<RemoteKeystore>>,
+}
 
 impl KeystoreContainer {
 	/// Construct KeystoreContainer
 	pub fn new(config: &KeystoreConfig) -> Result<Self, Error> {
-		let keystore = Arc::new(match config {
+		let (local, remote) = match config {
 			KeystoreConfig::Path { path, password } =>
-				LocalKeystore::open(path.clone(), password.clone())?,
-			KeystoreConfig::InMemory => LocalKeystore::in_memory(),
-		});
+				(LocalKeystore::open(path.clone(), password.clone())?, None),
+			KeystoreConfig::InMemory => (LocalKeystore::in_memory(), None),
+			KeystoreConfig::Remote { uri, secret } => {
+				let endpoint = uri.parse::<Endpoint>()?;
+				let secret = secret.expose_secret().as_bytes().to_vec();
+				let remote = RemoteKeystore::connect(endpoint, secret)?;
+				info!("🔑 Using remote signer at {}", remote.endpoint());
+				(LocalKeystore::in_memory(), Some(Arc::new(remote)))
+			},
+		};
 
-		Ok(Self(keystore))
+		Ok(Self { local: Arc::new(local), remote })
 	}
 
 	/// Returns a shared reference to a dynamic `Keystore` trait implementation.
+	///
+	/// This is the remote keystore if one is configured, the local one otherwise.
 	pub fn keystore(&self) -> KeystorePtr {
-		self.0.clone()
+		match self.remote {
+			Some(ref remote) => remote.clone(),
+			None => self.local.clone(),
+		}
 	}
 
 	/// Returns a shared reference to the local keystore .
+	///
+	/// When a remote keystore is configured, this is an empty in-memory keystore.
 	pub fn local_keystore(&self) -> Arc<LocalKeystore> {
-		self.0.clone()
+		self.local.clone()
 	}
 }
 
@@ -251,6 +272,8 @@ pub fn new_wasm_executor<H: HostFunctions>(config: &Configuration) -> WasmExecut
 		.with_offchain_heap_alloc_strategy(strategy)
 		.with_max_runtime_instances(config.max_runtime_instances)
 		.with_runtime_cache_size(config.runtime_cache_size)
+		.with_rpc_execution_limits(config.rpc_execution_limits)
+		.with_precompiled_runtimes_path(config.precompiled_runtimes_path())
 		.build()
 }
 
@@ -487,7 +510,9 @@ where
 		)
 	};
 
-	let rpc = start_rpc_servers(&config, gen_rpc_module, rpc_id_provider)?;
+	let health_provider = Arc::new(HealthProvider::new(client.clone(), sync_service.clone()));
+	spawn_handle.spawn("rpc-health", None, health_provider.clone().track_best_block());
+	let rpc = start_rpc_servers(&config, gen_rpc_module, rpc_id_provider, health_provider)?;
 	let rpc_handlers = RpcHandlers(Arc::new(gen_rpc_module(sc_rpc::DenyUnsafe::No)?.into()));
 
 	// Spawn informant task
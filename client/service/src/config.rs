@@ -19,7 +19,7 @@
 //! Service configuration.
 
 pub use sc_client_db::{BlocksPruning, Database, DatabaseSource, PruningMode};
-pub use sc_executor::{WasmExecutionMethod, WasmtimeInstantiationStrategy};
+pub use sc_executor::{ExecutionLimits, WasmExecutionMethod, WasmtimeInstantiationStrategy};
 pub use sc_network::{
 	config::{
 		MultiaddrWithPeerId, NetworkConfiguration, NodeKeyConfig, NonDefaultSetConfig, ProtocolId,
@@ -33,6 +33,7 @@ pub use sc_network::{
 
 use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
+pub use sc_rpc_server::HealthConfig as RpcHealthConfig;
 pub use sc_telemetry::TelemetryEndpoints;
 pub use sc_transaction_pool::Options as TransactionPoolOptions;
 use sp_core::crypto::SecretString;
@@ -98,6 +99,10 @@ pub struct Configuration {
 	pub rpc_id_provider: Option<Box<dyn crate::RpcSubscriptionIdProvider>>,
 	/// Maximum allowed subscriptions per rpc connection
 	pub rpc_max_subs_per_conn: u32,
+	/// Limits on runtime calls issued through RPC, e.g. `state_call`.
+	pub rpc_execution_limits: ExecutionLimits,
+	/// Thresholds of the RPC readiness and liveness endpoints.
+	pub rpc_health: RpcHealthConfig,
 	/// JSON-RPC server default port.
 	pub rpc_port: u16,
 	/// Prometheus endpoint configuration. `None` if disabled.
@@ -160,6 +165,13 @@ pub enum KeystoreConfig {
 	},
 	/// In-memory keystore. Recommended for in-browser nodes.
 	InMemory,
+	/// Keystore forwarding all secret key operations to a remote signer.
+	Remote {
+		/// URI of the signer, either `unix://<path>` or `tcp://<host>:<port>`.
+		uri: String,
+		/// Secret shared with the signer, used by both ends to authenticate each other.
+		secret: SecretString,
+	},
 }
 
 impl KeystoreConfig {
@@ -167,7 +179,7 @@ impl KeystoreConfig {
 	pub fn path(&self) -> Option<&Path> {
 		match self {
 			Self::Path { path, .. } => Some(path),
-			Self::InMemory => None,
+			Self::InMemory | Self::Remote { .. } => None,
 		}
 	}
 }
@@ -207,6 +219,11 @@ impl Configuration {
 		self.role.to_string()
 	}
 
+	/// Returns the directory containing the ahead-of-time compiled runtimes of this chain.
+	pub fn precompiled_runtimes_path(&self) -> PathBuf {
+		self.data_path.join("precompiled-runtimes")
+	}
+
 	/// Returns the prometheus metrics registry, if available.
 	pub fn prometheus_registry(&self) -> Option<&Registry> {
 		self.prometheus_config.as_ref().map(|config| &config.registry)
@@ -0,0 +1,76 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The node health reported by the readiness and liveness endpoints of the RPC server.
+
+use futures::StreamExt;
+use parking_lot::Mutex;
+use sc_client_api::{BlockchainEvents, HeaderBackend};
+use sc_network_sync::SyncingService;
+use sc_rpc_server::{NodeHealth, NodeHealthProvider};
+use sp_consensus::SyncOracle;
+use sp_runtime::{
+	traits::{Block as BlockT, Saturating},
+	SaturatedConversion,
+};
+use std::{sync::Arc, time::Instant};
+
+/// Reports the [`NodeHealth`] based on the client info and the sync service.
+pub(crate) struct HealthProvider<Block: BlockT, Client> {
+	client: Arc<Client>,
+	sync_service: Arc<SyncingService<Block>>,
+	/// When the best block last changed, kept up to date by [`Self::track_best_block`].
+	best_block_changed: Mutex<Instant>,
+}
+
+impl<Block: BlockT, Client> HealthProvider<Block, Client> {
+	pub(crate) fn new(client: Arc<Client>, sync_service: Arc<SyncingService<Block>>) -> Self {
+		Self { client, sync_service, best_block_changed: Mutex::new(Instant::now()) }
+	}
+
+	/// Record every change of the best block, so that its age doesn't depend on how often the
+	/// health is probed.
+	pub(crate) async fn track_best_block(self: Arc<Self>)
+	where
+		Client: BlockchainEvents<Block>,
+	{
+		let mut notifications = self.client.import_notification_stream();
+		while let Some(notification) = notifications.next().await {
+			if notification.is_new_best {
+				*self.best_block_changed.lock() = Instant::now();
+			}
+		}
+	}
+}
+
+impl<Block, Client> NodeHealthProvider for HealthProvider<Block, Client>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block> + Send + Sync,
+{
+	fn node_health(&self) -> NodeHealth {
+		let info = self.client.info();
+
+		NodeHealth {
+			peers: self.sync_service.num_connected_peers(),
+			is_major_syncing: self.sync_service.is_major_syncing(),
+			best_block_age: self.best_block_changed.lock().elapsed(),
+			finality_lag: info.best_number.saturating_sub(info.finalized_number).saturated_into(),
+		}
+	}
+}
@@ -31,6 +31,7 @@ mod builder;
 pub mod client;
 #[cfg(not(feature = "test-helpers"))]
 mod client;
+mod health;
 mod metrics;
 mod task_manager;
 
@@ -368,6 +369,7 @@ fn start_rpc_servers<R>(
 	config: &Configuration,
 	gen_rpc_module: R,
 	rpc_id_provider: Option<Box<dyn RpcSubscriptionIdProvider>>,
+	health_provider: Arc<dyn sc_rpc_server::NodeHealthProvider>,
 ) -> Result<Box<dyn std::any::Any + Send + Sync>, error::Error>
 where
 	R: Fn(sc_rpc::DenyUnsafe) -> Result<RpcModule<()>, Error>,
@@ -401,6 +403,7 @@ where
 		id_provider: rpc_id_provider,
 		cors: config.rpc_cors.as_ref(),
 		tokio_handle: config.tokio_handle.clone(),
+		health: sc_rpc_server::NodeHealthLayer::new(config.rpc_health.clone(), health_provider),
 	};
 
 	// TODO: https://github.com/paritytech/substrate/issues/13773
@@ -253,6 +253,8 @@ fn node_config<
 		rpc_max_response_size: Default::default(),
 		rpc_id_provider: Default::default(),
 		rpc_max_subs_per_conn: Default::default(),
+		rpc_execution_limits: Default::default(),
+		rpc_health: Default::default(),
 		rpc_port: 9944,
 		prometheus_config: None,
 		telemetry_endpoints: None,
@@ -0,0 +1,169 @@
+// Copyright Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Conversion of block traces into formats understood by profiling tools.
+
+use std::collections::{BTreeMap, HashMap};
+
+use rustc_hash::FxHashMap;
+use sp_rpc::tracing::{BlockTrace, ChromeTrace, ChromeTraceEvent, Span};
+
+/// Convert the spans of `trace` into the Chrome trace-event format.
+///
+/// Every span becomes a complete event on the same thread, so nested spans are shown as a call
+/// stack.
+pub fn chrome_trace(trace: &BlockTrace) -> ChromeTrace {
+	let mut spans: Vec<_> = trace.spans.iter().collect();
+	// Parents are created before their children and thus have a lower id.
+	spans.sort_by_key(|span| (span.start_ns, span.id));
+
+	let trace_events = spans
+		.into_iter()
+		.map(|span| {
+			let mut args = FxHashMap::default();
+			args.insert("id".to_owned(), span.id.to_string());
+			if let Some(parent_id) = span.parent_id {
+				args.insert("parentId".to_owned(), parent_id.to_string());
+			}
+			if span.wasm {
+				args.insert("wasm".to_owned(), "true".to_owned());
+			}
+
+			ChromeTraceEvent {
+				name: span.name.clone(),
+				cat: span.target.clone(),
+				ph: "X".to_owned(),
+				ts: span.start_ns as f64 / 1_000.0,
+				dur: span.duration_ns as f64 / 1_000.0,
+				pid: 1,
+				tid: 1,
+				args,
+			}
+		})
+		.collect();
+
+	ChromeTrace { trace_events, display_time_unit: "ns".to_owned() }
+}
+
+/// Convert the spans of `trace` into folded stacks.
+///
+/// Every line is a `;` separated stack of `target::name` frames, followed by the time spent in the
+/// innermost span but not in any of its children, in nanoseconds. Stacks without self time are
+/// omitted. A span whose parent was not recorded starts a new stack.
+pub fn folded_stacks(trace: &BlockTrace) -> Vec<String> {
+	let spans: HashMap<u64, &Span> = trace.spans.iter().map(|span| (span.id, span)).collect();
+
+	let mut children_time = HashMap::<u64, u64>::new();
+	for span in &trace.spans {
+		if let Some(parent_id) = span.parent_id {
+			*children_time.entry(parent_id).or_default() += span.duration_ns;
+		}
+	}
+
+	let mut stacks = BTreeMap::<String, u64>::new();
+	for span in &trace.spans {
+		let mut frames = vec![frame(span)];
+		let mut parent_id = span.parent_id;
+		// Bounded by the number of spans, in case the parent ids form a cycle.
+		while let Some(parent) = parent_id.and_then(|id| spans.get(&id)) {
+			if frames.len() > spans.len() {
+				break
+			}
+			frames.push(frame(parent));
+			parent_id = parent.parent_id;
+		}
+		frames.reverse();
+
+		let self_time = span
+			.duration_ns
+			.saturating_sub(children_time.get(&span.id).copied().unwrap_or_default());
+		*stacks.entry(frames.join(";")).or_default() += self_time;
+	}
+
+	stacks
+		.into_iter()
+		.filter(|(_, time)| *time > 0)
+		.map(|(stack, time)| format!("{} {}", stack, time))
+		.collect()
+}
+
+fn frame(span: &Span) -> String {
+	format!("{}::{}", span.target, span.name).replace(';', ",")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn span(id: u64, parent_id: Option<u64>, name: &str, start_ns: u64, duration_ns: u64) -> Span {
+		Span {
+			id,
+			parent_id,
+			name: name.to_owned(),
+			target: "frame".to_owned(),
+			wasm: false,
+			start_ns,
+			duration_ns,
+		}
+	}
+
+	fn block_trace(spans: Vec<Span>) -> BlockTrace {
+		BlockTrace {
+			block_hash: Default::default(),
+			parent_hash: Default::default(),
+			tracing_targets: Default::default(),
+			storage_keys: Default::default(),
+			methods: Default::default(),
+			spans,
+			events: Vec::new(),
+			host_functions: Vec::new(),
+		}
+	}
+
+	#[test]
+	fn folded_stacks_contain_self_time() {
+		let trace = block_trace(vec![
+			span(3, Some(1), "on_finalize", 700, 200),
+			span(1, None, "execute_block", 0, 1_000),
+			span(2, Some(1), "apply_extrinsic", 100, 500),
+			span(4, Some(2), "transfer", 150, 500),
+		]);
+
+		assert_eq!(
+			folded_stacks(&trace),
+			vec![
+				"frame::execute_block 300",
+				"frame::execute_block;frame::apply_extrinsic;frame::transfer 500",
+				"frame::execute_block;frame::on_finalize 200",
+			]
+		);
+	}
+
+	#[test]
+	fn chrome_trace_is_ordered_by_start() {
+		let trace = block_trace(vec![
+			span(2, Some(1), "apply_extrinsic", 1_000, 500),
+			span(1, None, "execute_block", 1_000, 2_000),
+		]);
+
+		let events = chrome_trace(&trace).trace_events;
+		assert_eq!(
+			events.iter().map(|e| (e.name.as_str(), e.ts, e.dur)).collect::<Vec<_>>(),
+			vec![("execute_block", 1.0, 2.0), ("apply_extrinsic", 1.0, 0.5)],
+		);
+		assert_eq!(events[1].args.get("parentId").map(String::as_str), Some("1"));
+	}
+}
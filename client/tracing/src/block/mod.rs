@@ -16,13 +16,17 @@
 
 //! Utilities for tracing block execution
 
+mod format;
+
+pub use format::{chrome_trace, folded_stacks};
+
 use std::{
 	collections::HashMap,
 	sync::{
 		atomic::{AtomicU64, Ordering},
 		Arc,
 	},
-	time::Instant,
+	time::{Duration, Instant},
 };
 
 use parking_lot::Mutex;
@@ -34,10 +38,11 @@ use tracing::{
 
 use crate::{SpanDatum, TraceEvent, Values};
 use sc_client_api::BlockBackend;
+use sc_executor_common::profiling;
 use sp_api::{Core, Encode, Metadata, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::hexdisplay::HexDisplay;
-use sp_rpc::tracing::{BlockTrace, Span, TraceBlockResponse};
+use sp_rpc::tracing::{BlockTrace, HostFunctionStats, Span, TraceBlockFormat, TraceBlockResponse};
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, Header},
@@ -70,6 +75,8 @@ struct BlockSubscriber {
 	targets: Vec<(String, Level)>,
 	next_id: AtomicU64,
 	spans: Mutex<HashMap<Id, SpanDatum>>,
+	/// The spans currently entered, with the time they were entered.
+	entered: Mutex<HashMap<Id, Instant>>,
 	events: Mutex<Vec<TraceEvent>>,
 }
 
@@ -84,6 +91,7 @@ impl BlockSubscriber {
 			targets,
 			next_id,
 			spans: Mutex::new(HashMap::new()),
+			entered: Mutex::new(HashMap::new()),
 			events: Mutex::new(Vec::new()),
 		}
 	}
@@ -149,9 +157,17 @@ impl Subscriber for BlockSubscriber {
 		self.events.lock().push(trace_event);
 	}
 
-	fn enter(&self, _id: &Id) {}
+	fn enter(&self, id: &Id) {
+		self.entered.lock().insert(id.clone(), Instant::now());
+	}
 
-	fn exit(&self, _span: &Id) {}
+	fn exit(&self, span: &Id) {
+		if let Some(entered) = self.entered.lock().remove(span) {
+			if let Some(s) = self.spans.lock().get_mut(span) {
+				s.overall_time += entered.elapsed();
+			}
+		}
+	}
 }
 
 /// Holds a reference to the client in order to execute the given block.
@@ -165,6 +181,8 @@ pub struct BlockExecutor<Block: BlockT, Client> {
 	targets: Option<String>,
 	storage_keys: Option<String>,
 	methods: Option<String>,
+	format: TraceBlockFormat,
+	profile_host_functions: bool,
 }
 
 impl<Block, Client> BlockExecutor<Block, Client>
@@ -186,7 +204,29 @@ where
 		storage_keys: Option<String>,
 		methods: Option<String>,
 	) -> Self {
-		Self { client, block, targets, storage_keys, methods }
+		Self {
+			client,
+			block,
+			targets,
+			storage_keys,
+			methods,
+			format: Default::default(),
+			profile_host_functions: false,
+		}
+	}
+
+	/// Set the format of the response, [`TraceBlockFormat::Json`] by default.
+	pub fn with_format(mut self, format: TraceBlockFormat) -> Self {
+		self.format = format;
+		self
+	}
+
+	/// Profile the host functions called while executing the block, disabled by default.
+	///
+	/// Profiling slows down the execution, which also shows in the timings of the spans.
+	pub fn with_host_function_profiling(mut self, enabled: bool) -> Self {
+		self.profile_host_functions = enabled;
+		self
 	}
 
 	/// Execute block, record all spans and events belonging to `Self::targets`
@@ -215,25 +255,36 @@ where
 		let targets = if let Some(t) = &self.targets { t } else { DEFAULT_TARGETS };
 		let block_subscriber = BlockSubscriber::new(targets);
 		let dispatch = Dispatch::new(block_subscriber);
+		let trace_start = Instant::now();
 
-		{
+		let host_functions = {
 			let dispatcher_span = tracing::debug_span!(
 				target: "state_tracing",
 				"execute_block",
 				extrinsics_len = block.extrinsics().len(),
 			);
 			let _guard = dispatcher_span.enter();
-			if let Err(e) = dispatcher::with_default(&dispatch, || {
-				let span = tracing::info_span!(target: TRACE_TARGET, "trace_block");
-				let _enter = span.enter();
-				self.client.runtime_api().execute_block(parent_hash, block)
-			}) {
+			let execute_block = || {
+				dispatcher::with_default(&dispatch, || {
+					let span = tracing::info_span!(target: TRACE_TARGET, "trace_block");
+					let _enter = span.enter();
+					self.client.runtime_api().execute_block(parent_hash, block)
+				})
+			};
+			let (result, host_functions) = if self.profile_host_functions {
+				let (result, profile) = profiling::collect(execute_block);
+				(result, profile_to_stats(&profile))
+			} else {
+				(execute_block(), Vec::new())
+			};
+			if let Err(e) = result {
 				return Err(Error::Dispatch(format!(
 					"Failed to collect traces and execute block: {}",
 					e
 				)))
 			}
-		}
+			host_functions
+		};
 
 		let block_subscriber = dispatch.downcast_ref::<BlockSubscriber>().ok_or_else(|| {
 			Error::Dispatch(
@@ -245,7 +296,7 @@ where
 			.lock()
 			.drain()
 			// Patch wasm identifiers
-			.filter_map(|(_, s)| patch_and_filter(s, targets))
+			.filter_map(|(_, s)| patch_and_filter(s, targets, trace_start))
 			.collect();
 		let events: Vec<_> = block_subscriber
 			.events
@@ -267,7 +318,7 @@ where
 			.collect();
 		tracing::debug!(target: "state_tracing", "Captured {} spans and {} events", spans.len(), events.len());
 
-		Ok(TraceBlockResponse::BlockTrace(BlockTrace {
+		let trace = BlockTrace {
 			block_hash: block_id_as_string(BlockId::<Block>::Hash(self.block)),
 			parent_hash: block_id_as_string(BlockId::<Block>::Hash(parent_hash)),
 			tracing_targets: targets.to_string(),
@@ -275,10 +326,34 @@ where
 			methods: self.methods.clone().unwrap_or_default(),
 			spans,
 			events,
-		}))
+			host_functions,
+		};
+
+		Ok(match self.format {
+			TraceBlockFormat::Json => TraceBlockResponse::BlockTrace(trace),
+			TraceBlockFormat::ChromeTrace => TraceBlockResponse::ChromeTrace(chrome_trace(&trace)),
+			TraceBlockFormat::FoldedStacks =>
+				TraceBlockResponse::FoldedStacks(folded_stacks(&trace)),
+		})
 	}
 }
 
+/// Convert the host function profile into its RPC representation, most expensive function first.
+fn profile_to_stats(profile: &profiling::HostFunctionProfile) -> Vec<HostFunctionStats> {
+	let mut stats: Vec<_> = profile
+		.iter()
+		.map(|(name, stats)| HostFunctionStats {
+			name: name.to_string(),
+			calls: stats.calls,
+			time_ns: nanos(stats.time),
+			bytes_read: stats.bytes_read,
+			bytes_written: stats.bytes_written,
+		})
+		.collect();
+	stats.sort_by(|a, b| b.time_ns.cmp(&a.time_ns));
+	stats
+}
+
 fn event_values_filter(event: &TraceEvent, filter_kind: &str, values: &str) -> bool {
 	event
 		.values
@@ -296,7 +371,7 @@ fn event_values_filter(event: &TraceEvent, filter_kind: &str, values: &str) -> b
 // (normally they would be in the static metadata assembled at compile time). Here, if a special
 // WASM `name` or `target` key is found in the `values` we remove it and put the key value pair in
 // the span's metadata, making it consistent with spans that come from native code.
-fn patch_and_filter(mut span: SpanDatum, targets: &str) -> Option<Span> {
+fn patch_and_filter(mut span: SpanDatum, targets: &str, trace_start: Instant) -> Option<Span> {
 	if span.name == WASM_TRACE_IDENTIFIER {
 		span.values.bool_values.insert("wasm".to_owned(), true);
 		if let Some(n) = span.values.string_values.remove(WASM_NAME_KEY) {
@@ -309,7 +384,14 @@ fn patch_and_filter(mut span: SpanDatum, targets: &str) -> Option<Span> {
 			return None
 		}
 	}
-	Some(span.into())
+	let start = span.start_time.saturating_duration_since(trace_start);
+	let mut span: Span = span.into();
+	span.start_ns = nanos(start);
+	Some(span)
+}
+
+fn nanos(duration: Duration) -> u64 {
+	u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
 }
 
 /// Check if a `target` matches any `targets` by prefix
@@ -459,6 +459,8 @@ impl From<SpanDatum> for sp_rpc::tracing::Span {
 			name: span_datum.name,
 			target: span_datum.target,
 			wasm,
+			start_ns: 0,
+			duration_ns: u64::try_from(span_datum.overall_time.as_nanos()).unwrap_or(u64::MAX),
 		}
 	}
 }
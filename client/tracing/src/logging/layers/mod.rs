@@ -16,6 +16,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+mod otlp_layer;
 mod prefix_layer;
 
+pub use otlp_layer::*;
 pub use prefix_layer::*;
@@ -0,0 +1,99 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use opentelemetry::{
+	sdk::{
+		trace::{self, Sampler, Tracer},
+		Resource,
+	},
+	trace::TraceError,
+	KeyValue,
+};
+use parking_lot::Mutex;
+use tokio::runtime::Runtime;
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// The runtime driving the export of spans to the OTLP collector.
+///
+/// The exporter is set up while the logger is initialized, before the node has a runtime, and
+/// has to keep running until [`shutdown_otlp`] flushed the remaining spans.
+static OTLP_RUNTIME: Mutex<Option<Runtime>> = parking_lot::const_mutex(None);
+
+/// Configuration of the export of spans to an OpenTelemetry (OTLP) collector.
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+	/// The gRPC endpoint of the collector, e.g. `http://localhost:4317`.
+	pub endpoint: String,
+	/// The ratio of traces to export, between `0.0` and `1.0`.
+	///
+	/// The decision is taken for the root span of a trace and followed by all its children.
+	pub sampling_ratio: f64,
+	/// The `service.name` reported with every span, usually the name of the node.
+	pub service_name: String,
+}
+
+/// Create a `Layer` exporting all spans to the OTLP collector configured by `config`.
+///
+/// Spans emitted by the runtime are exported with the name `wasm_tracing`, their actual name and
+/// target are attached as the `name` and `target` attributes.
+pub fn otlp_layer<S>(config: &OtlpConfig) -> Result<OpenTelemetryLayer<S, Tracer>, TraceError>
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	let runtime = tokio::runtime::Builder::new_multi_thread()
+		.worker_threads(1)
+		.thread_name("otlp-exporter")
+		.enable_all()
+		.build()
+		.map_err(|e| TraceError::Other(Box::new(e)))?;
+
+	let trace_config = trace::config()
+		.with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+			config.sampling_ratio,
+		))))
+		.with_resource(Resource::new(vec![KeyValue::new(
+			"service.name",
+			config.service_name.clone(),
+		)]));
+
+	let tracer = {
+		// The exporter connects and spawns its background tasks on the current runtime.
+		let _guard = runtime.enter();
+		opentelemetry_otlp::new_pipeline()
+			.with_endpoint(config.endpoint.clone())
+			.with_trace_config(trace_config)
+			.with_tonic()
+			.install_batch(opentelemetry::runtime::Tokio)?
+	};
+
+	*OTLP_RUNTIME.lock() = Some(runtime);
+
+	Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Flush the spans not yet exported to the OTLP collector and stop the exporter.
+///
+/// Does nothing if no OTLP exporter was set up. Must not be called from within an async context.
+pub fn shutdown_otlp() {
+	if let Some(runtime) = OTLP_RUNTIME.lock().take() {
+		opentelemetry::global::shutdown_tracer_provider();
+		runtime.shutdown_background();
+	}
+}
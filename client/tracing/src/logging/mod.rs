@@ -65,6 +65,7 @@ pub enum Error {
 	SetGlobalDefaultError(#[from] tracing::subscriber::SetGlobalDefaultError),
 	DirectiveParseError(#[from] tracing_subscriber::filter::ParseError),
 	SetLoggerError(#[from] tracing_log::log_tracer::SetLoggerError),
+	OtlpError(#[from] opentelemetry::trace::TraceError),
 }
 
 macro_rules! enable_log_reloading {
@@ -204,6 +205,7 @@ pub struct LoggerBuilder {
 	log_reloading: bool,
 	force_colors: Option<bool>,
 	detailed_output: bool,
+	otlp: Option<OtlpConfig>,
 }
 
 impl LoggerBuilder {
@@ -216,6 +218,7 @@ impl LoggerBuilder {
 			log_reloading: false,
 			force_colors: None,
 			detailed_output: false,
+			otlp: None,
 		}
 	}
 
@@ -255,6 +258,16 @@ impl LoggerBuilder {
 		self
 	}
 
+	/// Export spans to the OpenTelemetry (OTLP) collector configured by `config`.
+	///
+	/// The spans are subject to the same filters as the logs, so runtime spans have to be enabled
+	/// with [`Self::with_profiling`]. Call [`shutdown_otlp`] before exiting to flush the remaining
+	/// spans.
+	pub fn with_otlp(&mut self, config: OtlpConfig) -> &mut Self {
+		self.otlp = Some(config);
+		self
+	}
+
 	/// Force enable/disable colors.
 	pub fn with_colors(&mut self, enable: bool) -> &mut Self {
 		self.force_colors = Some(enable);
@@ -265,6 +278,8 @@ impl LoggerBuilder {
 	///
 	/// This sets various global logging and tracing instances and thus may only be called once.
 	pub fn init(self) -> Result<()> {
+		let otlp = self.otlp.as_ref();
+
 		if let Some((tracing_receiver, profiling_targets)) = self.profiling {
 			if self.log_reloading {
 				let subscriber = prepare_subscriber(
@@ -281,7 +296,9 @@ impl LoggerBuilder {
 					.into_iter()
 					.for_each(|profiler| profiling.add_handler(profiler));
 
-				tracing::subscriber::set_global_default(subscriber.with(profiling))?;
+				let otlp = otlp.map(otlp_layer).transpose()?;
+
+				tracing::subscriber::set_global_default(subscriber.with(profiling).with(otlp))?;
 
 				Ok(())
 			} else {
@@ -299,7 +316,9 @@ impl LoggerBuilder {
 					.into_iter()
 					.for_each(|profiler| profiling.add_handler(profiler));
 
-				tracing::subscriber::set_global_default(subscriber.with(profiling))?;
+				let otlp = otlp.map(otlp_layer).transpose()?;
+
+				tracing::subscriber::set_global_default(subscriber.with(profiling).with(otlp))?;
 
 				Ok(())
 			}
@@ -312,7 +331,9 @@ impl LoggerBuilder {
 				|builder| enable_log_reloading!(builder),
 			)?;
 
-			tracing::subscriber::set_global_default(subscriber)?;
+			let otlp = otlp.map(otlp_layer).transpose()?;
+
+			tracing::subscriber::set_global_default(subscriber.with(otlp))?;
 
 			Ok(())
 		} else {
@@ -324,7 +345,9 @@ impl LoggerBuilder {
 				|builder| builder,
 			)?;
 
-			tracing::subscriber::set_global_default(subscriber)?;
+			let otlp = otlp.map(otlp_layer).transpose()?;
+
+			tracing::subscriber::set_global_default(subscriber.with(otlp))?;
 
 			Ok(())
 		}
@@ -548,6 +571,23 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn otlp_export_does_not_block_without_collector() {
+		run_test_in_another_process("otlp_export_does_not_block_without_collector", || {
+			let mut builder = LoggerBuilder::new("");
+			builder.with_otlp(OtlpConfig {
+				endpoint: "http://127.0.0.1:1".into(),
+				sampling_ratio: 1.0,
+				service_name: EXPECTED_NODE_NAME.into(),
+			});
+			builder.init().unwrap();
+
+			tracing::info_span!("import_block").in_scope(|| info!("{}", EXPECTED_LOG_MESSAGE));
+
+			shutdown_otlp();
+		});
+	}
+
 	// This creates a bunch of threads and makes sure they start executing
 	// a given callback almost exactly at the same time.
 	fn run_on_many_threads(thread_count: usize, callback: impl Fn(usize) + 'static + Send + Clone) {
@@ -118,11 +118,16 @@
 
 use codec::{Codec, Encode};
 use frame_support::{
-	dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, PostDispatchInfo},
+	dispatch::{
+		AuthorizedDispatch, DispatchClass, DispatchInfo, DispatchResultWithPostInfo, DryRunEffects,
+		GetDispatchInfo, PostDispatchInfo,
+	},
+	dispatch_context::run_in_context,
 	pallet_prelude::InvalidTransaction,
+	storage::{with_transaction_unchecked, TransactionOutcome},
 	traits::{
-		EnsureInherentsAreFirst, ExecuteBlock, OffchainWorker, OnFinalize, OnIdle, OnInitialize,
-		OnRuntimeUpgrade,
+		Authorize, Contains, EnsureInherentsAreFirst, ExecuteBlock, InspectMessageQueues, Nothing,
+		OffchainWorker, OnFinalize, OnIdle, OnInitialize, OnRuntimeUpgrade,
 	},
 	weights::Weight,
 };
@@ -133,8 +138,8 @@ use sp_runtime::{
 		self, Applyable, CheckEqual, Checkable, Dispatchable, Header, NumberFor, One,
 		ValidateUnsigned, Zero,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult,
+	transaction_validity::{TransactionSource, TransactionValidity, TransactionValidityError},
+	ApplyExtrinsicResult, ApplyExtrinsicResultWithInfo,
 };
 use sp_std::{marker::PhantomData, prelude::*};
 
@@ -146,21 +151,89 @@ use sp_runtime::TryRuntimeError;
 #[allow(dead_code)]
 const LOG_TARGET: &str = "runtime::executive";
 
+/// The maximum number of blocks `try_runtime_upgrade` steps the multi-block migrations for.
+#[cfg(feature = "try-runtime")]
+const TRY_RUNTIME_MAX_MIGRATION_BLOCKS: u32 = 100_000;
+
+/// An [`Applyable`] which gives access to the call it dispatches.
+///
+/// Used by the [`Executive`] to let the calls of its `MigrationCallFilter` through while
+/// multi-block migrations are ongoing.
+pub trait ApplyableCall: Applyable {
+	/// The call that is dispatched when this is applied.
+	fn call(&self) -> &Self::Call;
+}
+
+impl<AccountId, Call, Extra> ApplyableCall
+	for sp_runtime::generic::CheckedExtrinsic<AccountId, Call, Extra>
+where
+	Self: Applyable<Call = Call>,
+{
+	fn call(&self) -> &Call {
+		&self.function
+	}
+}
+
+#[cfg(feature = "std")]
+impl<Call, Extra> ApplyableCall for sp_runtime::testing::TestXt<Call, Extra>
+where
+	Self: Applyable<Call = Call>,
+{
+	fn call(&self) -> &Call {
+		&self.call
+	}
+}
+
 pub type CheckedOf<E, C> = <E as Checkable<C>>::Checked;
 pub type CallOf<E, C> = <CheckedOf<E, C> as Applyable>::Call;
 pub type OriginOf<E, C> = <CallOf<E, C> as Dispatchable>::RuntimeOrigin;
 
+/// Validates unsigned transactions with the `#[pallet::authorize]` of their call, and with the
+/// `UnsignedValidator` if the call has no authorization.
+///
+/// Authorized calls are granted the `Authorized` origin through [`AuthorizedDispatch`], so they
+/// must be applied within a [dispatch context](frame_support::dispatch_context).
+pub struct AuthorizeOrValidateUnsigned<UnsignedValidator>(PhantomData<UnsignedValidator>);
+
+impl<UnsignedValidator> ValidateUnsigned for AuthorizeOrValidateUnsigned<UnsignedValidator>
+where
+	UnsignedValidator: ValidateUnsigned,
+	UnsignedValidator::Call: Authorize,
+{
+	type Call = UnsignedValidator::Call;
+
+	fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+		match call.authorize(TransactionSource::InBlock) {
+			// The call is dispatched right after, in the dispatch context of the extrinsic.
+			Some(validity) => validity.map(|_| AuthorizedDispatch::grant()),
+			None => UnsignedValidator::pre_dispatch(call),
+		}
+	}
+
+	fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		call.authorize(source)
+			.unwrap_or_else(|| UnsignedValidator::validate_unsigned(source, call))
+	}
+}
+
 /// Main entry point for certain runtime actions as e.g. `execute_block`.
 ///
 /// Generic parameters:
 /// - `System`: Something that implements `frame_system::Config`
 /// - `Block`: The block type of the runtime
 /// - `Context`: The context that is used when checking an extrinsic.
-/// - `UnsignedValidator`: The unsigned transaction validator of the runtime.
+/// - `UnsignedValidator`: The unsigned transaction validator of the runtime. Calls with a
+///   `#[pallet::authorize]` are validated by their authorization instead, see
+///   [`AuthorizeOrValidateUnsigned`].
 /// - `AllPalletsWithSystem`: Tuple that contains all pallets including frame system pallet. Will be
 ///   used to call hooks e.g. `on_initialize`.
 /// - `OnRuntimeUpgrade`: Custom logic that should be called after a runtime upgrade. Modules are
 ///   already called by `AllPalletsWithSystem`. It will be called before all modules will be called.
+/// - `MultiStepMigrator`: Drives the multi-block migrations, e.g. `pallet-migrations`. It is
+///   stepped in every block after `on_initialize` while migrations are ongoing. Only inherents and
+///   the calls of `MigrationCallFilter` are applied in the meantime, and `on_idle` is skipped.
+/// - `MigrationCallFilter`: The calls which are still applied while multi-block migrations are
+///   ongoing, e.g. the governance calls needed to unstick a failed migration.
 pub struct Executive<
 	System,
 	Block,
@@ -168,6 +241,8 @@ pub struct Executive<
 	UnsignedValidator,
 	AllPalletsWithSystem,
 	OnRuntimeUpgrade = (),
+	MultiStepMigrator = (),
+	MigrationCallFilter = Nothing,
 >(
 	PhantomData<(
 		System,
@@ -176,6 +251,8 @@ pub struct Executive<
 		UnsignedValidator,
 		AllPalletsWithSystem,
 		OnRuntimeUpgrade,
+		MultiStepMigrator,
+		MigrationCallFilter,
 	)>,
 );
 
@@ -193,13 +270,24 @@ impl<
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
+		MultiStepMigrator: frame_support::migrations::MultiStepMigrator,
+		MigrationCallFilter: Contains<CallOf<Block::Extrinsic, Context>>,
 	> ExecuteBlock<Block>
-	for Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+	for Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigrator,
+		MigrationCallFilter,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
-	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
+	CheckedOf<Block::Extrinsic, Context>: ApplyableCall + GetDispatchInfo,
 	CallOf<Block::Extrinsic, Context>:
-		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + Authorize,
 	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
 	UnsignedValidator: ValidateUnsigned<Call = CallOf<Block::Extrinsic, Context>>,
 {
@@ -211,6 +299,8 @@ where
 			UnsignedValidator,
 			AllPalletsWithSystem,
 			COnRuntimeUpgrade,
+			MultiStepMigrator,
+			MigrationCallFilter,
 		>::execute_block(block);
 	}
 }
@@ -229,14 +319,27 @@ impl<
 			+ OnIdle<BlockNumberFor<System>>
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>
-			+ frame_support::traits::TryState<BlockNumberFor<System>>,
+			+ frame_support::traits::TryState<BlockNumberFor<System>>
+			+ frame_support::traits::TryDecodeEntireStorage,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		MultiStepMigrator: frame_support::migrations::MultiStepMigrator,
+		MigrationCallFilter: Contains<CallOf<Block::Extrinsic, Context>>,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigrator,
+		MigrationCallFilter,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
-	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
+	CheckedOf<Block::Extrinsic, Context>: ApplyableCall + GetDispatchInfo,
 	CallOf<Block::Extrinsic, Context>:
-		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + Authorize,
 	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
 	UnsignedValidator: ValidateUnsigned<Call = CallOf<Block::Extrinsic, Context>>,
 {
@@ -282,7 +385,13 @@ where
 			<frame_system::Pallet<System>>::note_extrinsic(encoded);
 
 			let dispatch_info = xt.get_dispatch_info();
-			let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
+			let r = run_in_context(|| {
+				Applyable::apply::<AuthorizeOrValidateUnsigned<UnsignedValidator>>(
+					xt,
+					&dispatch_info,
+					encoded_len,
+				)
+			})?;
 
 			<frame_system::Pallet<System>>::note_applied_extrinsic(&r, dispatch_info);
 
@@ -352,7 +461,9 @@ where
 	/// Execute all `OnRuntimeUpgrade` of this runtime, including the pre and post migration checks.
 	///
 	/// Runs the try-state code both before and after the migration function if `checks` is set to
-	/// `true`. Also, if set to `true`, it runs the `pre_upgrade` and `post_upgrade` hooks.
+	/// `true`. Also, if set to `true`, it runs the `pre_upgrade` and `post_upgrade` hooks. If
+	/// selected, every storage value is decoded into its declared type after the migration and the
+	/// keys of the values that fail to decode are logged.
 	pub fn try_runtime_upgrade(
 		checks: frame_try_runtime::UpgradeCheckSelect,
 	) -> Result<Weight, TryRuntimeError> {
@@ -366,11 +477,30 @@ where
 			)?;
 		}
 
-		let weight =
+		let mut weight =
 			<(COnRuntimeUpgrade, AllPalletsWithSystem) as OnRuntimeUpgrade>::try_on_runtime_upgrade(
 				checks.pre_and_post(),
 			)?;
 
+		// Run the multi-block migrations to completion, as if stepped in the following blocks.
+		let mut blocks = 0u32;
+		while MultiStepMigrator::ongoing() {
+			if blocks == TRY_RUNTIME_MAX_MIGRATION_BLOCKS {
+				return Err(
+					"Multi-block migrations did not complete, they are stuck or too slow".into()
+				)
+			}
+			weight = weight.saturating_add(MultiStepMigrator::step());
+			blocks += 1;
+		}
+		if blocks > 0 {
+			log::info!(
+				target: LOG_TARGET,
+				"try-runtime: multi-block migrations completed after {} blocks",
+				blocks,
+			);
+		}
+
 		if checks.try_state() {
 			let _guard = frame_support::StorageNoopGuard::default();
 			<AllPalletsWithSystem as frame_support::traits::TryState<
@@ -381,8 +511,93 @@ where
 			)?;
 		}
 
+		if checks.decode_entire_state() {
+			use frame_support::traits::TryDecodeEntireStorage;
+
+			let _guard = frame_support::StorageNoopGuard::default();
+			match AllPalletsWithSystem::try_decode_entire_state() {
+				Ok(bytes) => log::info!(
+					target: LOG_TARGET,
+					"try-runtime: decoded the entire state ({} bytes)",
+					bytes,
+				),
+				Err(errors) => {
+					for error in errors.iter() {
+						log::error!(target: LOG_TARGET, "try-runtime: {}", error);
+					}
+					return Err(
+						"Some storage values failed to decode into their declared type".into()
+					)
+				},
+			}
+		}
+
 		Ok(weight)
 	}
+
+	/// Dispatch `call` from `origin` on top of the current state, as if it was enacted, e.g. by
+	/// governance.
+	///
+	/// The events deposited so far are cleared first, such that `frame_system::Events` only holds
+	/// the events of the call afterwards.
+	pub fn try_execute_call(
+		call: CallOf<Block::Extrinsic, Context>,
+		origin: frame_try_runtime::CallOrigin,
+	) -> Result<(DispatchInfo, DispatchResultWithPostInfo), TryRuntimeError>
+	where
+		CallOf<Block::Extrinsic, Context>: GetDispatchInfo,
+		OriginOf<Block::Extrinsic, Context>: From<frame_system::RawOrigin<System::AccountId>>,
+	{
+		let origin = match origin {
+			frame_try_runtime::CallOrigin::Root => frame_system::RawOrigin::Root,
+			frame_try_runtime::CallOrigin::Signed(who) => frame_system::RawOrigin::Signed(
+				codec::DecodeAll::decode_all(&mut &who[..])
+					.map_err(|_| "Invalid account id of the signed origin")?,
+			),
+		};
+
+		<frame_system::Pallet<System>>::reset_events();
+		let info = call.get_dispatch_info();
+		let result = call.dispatch(origin.into());
+
+		log::info!(
+			target: LOG_TARGET,
+			"try-runtime: call dispatched with result {:?}",
+			result.map(|_| ()).map_err(|e| e.error),
+		);
+
+		Ok((info, result))
+	}
+
+	/// Decode `call` and dispatch it from `origin` with [`Self::try_execute_call`], as needed by
+	/// [`frame_try_runtime::TryRuntime::execute_call`].
+	///
+	/// `query_fee` returns the fee of the decoded call given the length of its encoding, e.g.
+	/// through `pallet_transaction_payment`. That length leaves out the signature and the signed
+	/// extensions of a transaction, so the length fee of the [`frame_try_runtime::CallOutcome`]
+	/// is lower than the one charged for submitting the call.
+	pub fn try_execute_encoded_call(
+		call: &[u8],
+		origin: frame_try_runtime::CallOrigin,
+		query_fee: impl FnOnce(&CallOf<Block::Extrinsic, Context>, u32) -> u128,
+	) -> Result<frame_try_runtime::CallOutcome, TryRuntimeError>
+	where
+		CallOf<Block::Extrinsic, Context>: GetDispatchInfo + codec::Decode,
+		OriginOf<Block::Extrinsic, Context>: From<frame_system::RawOrigin<System::AccountId>>,
+	{
+		use codec::DecodeLimit;
+
+		let len = call.len() as u32;
+		let call = <CallOf<Block::Extrinsic, Context>>::decode_all_with_depth_limit(
+			sp_api::MAX_EXTRINSIC_DEPTH,
+			&mut &call[..],
+		)
+		.map_err(|_| "Invalid SCALE encoding of the call")?;
+		let fee = query_fee(&call, len);
+		let (info, result) = Self::try_execute_call(call, origin)?;
+
+		Ok(frame_try_runtime::CallOutcome { result, info, fee })
+	}
 }
 
 impl<
@@ -399,12 +614,24 @@ impl<
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		MultiStepMigrator: frame_support::migrations::MultiStepMigrator,
+		MigrationCallFilter: Contains<CallOf<Block::Extrinsic, Context>>,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigrator,
+		MigrationCallFilter,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
-	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
+	CheckedOf<Block::Extrinsic, Context>: ApplyableCall + GetDispatchInfo,
 	CallOf<Block::Extrinsic, Context>:
-		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + Authorize,
 	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
 	UnsignedValidator: ValidateUnsigned<Call = CallOf<Block::Extrinsic, Context>>,
 {
@@ -449,6 +676,9 @@ where
 		weight = weight.saturating_add(<AllPalletsWithSystem as OnInitialize<
 			BlockNumberFor<System>,
 		>>::on_initialize(*block_number));
+		if MultiStepMigrator::ongoing() {
+			weight = weight.saturating_add(MultiStepMigrator::step());
+		}
 		weight = weight.saturating_add(
 			<System::BlockWeights as frame_support::traits::Get<_>>::get().base_block,
 		);
@@ -549,7 +779,9 @@ where
 		let max_weight = <System::BlockWeights as frame_support::traits::Get<_>>::get().max_block;
 		let remaining_weight = max_weight.saturating_sub(weight.total());
 
-		if remaining_weight.all_gt(Weight::zero()) {
+		// Multi-block migrations take priority over `on_idle`, so it is skipped until they are
+		// done.
+		if remaining_weight.all_gt(Weight::zero()) && !MultiStepMigrator::ongoing() {
 			let used_weight = <AllPalletsWithSystem as OnIdle<BlockNumberFor<System>>>::on_idle(
 				block_number,
 				remaining_weight,
@@ -568,6 +800,13 @@ where
 	/// This doesn't attempt to validate anything regarding the block, but it builds a list of uxt
 	/// hashes.
 	pub fn apply_extrinsic(uxt: Block::Extrinsic) -> ApplyExtrinsicResult {
+		Self::apply_extrinsic_with_info(uxt).map(|r| r.map(|_| ()).map_err(|e| e.error))
+	}
+
+	/// Same as [`Self::apply_extrinsic`], but returns the `PostDispatchInfo` of the extrinsic.
+	fn apply_extrinsic_with_info(
+		uxt: Block::Extrinsic,
+	) -> ApplyExtrinsicResultWithInfo<PostDispatchInfo> {
 		sp_io::init_tracing();
 		let encoded = uxt.encode();
 		let encoded_len = encoded.len();
@@ -575,6 +814,13 @@ where
 				ext=?sp_core::hexdisplay::HexDisplay::from(&encoded)));
 		// Verify that the signature is good.
 		let xt = uxt.check(&Default::default())?;
+		let dispatch_info = xt.get_dispatch_info();
+
+		// Transactions are suspended while multi-block migrations are ongoing. They are reported
+		// as exhausting the block, so that block authors keep them in the pool.
+		if Self::suspended(&xt, &dispatch_info) {
+			return Err(InvalidTransaction::ExhaustsResources.into())
+		}
 
 		// We don't need to make sure to `note_extrinsic` only after we know it's going to be
 		// executed to prevent it from leaking in storage since at this point, it will either
@@ -584,8 +830,13 @@ where
 		// AUDIT: Under no circumstances may this function panic from here onwards.
 
 		// Decode parameters and dispatch
-		let dispatch_info = xt.get_dispatch_info();
-		let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
+		let r = run_in_context(|| {
+			Applyable::apply::<AuthorizeOrValidateUnsigned<UnsignedValidator>>(
+				xt,
+				&dispatch_info,
+				encoded_len,
+			)
+		})?;
 
 		// Mandatory(inherents) are not allowed to fail.
 		//
@@ -597,7 +848,55 @@ where
 
 		<frame_system::Pallet<System>>::note_applied_extrinsic(&r, dispatch_info);
 
-		Ok(r.map(|_| ()).map_err(|e| e.error))
+		Ok(r)
+	}
+
+	/// Whether the transaction `xt` is suspended by ongoing multi-block migrations.
+	fn suspended(xt: &CheckedOf<Block::Extrinsic, Context>, dispatch_info: &DispatchInfo) -> bool {
+		dispatch_info.class != DispatchClass::Mandatory &&
+			MultiStepMigrator::ongoing() &&
+			!MigrationCallFilter::contains(xt.call())
+	}
+
+	/// Apply `uxt` on top of the current state and return its effects, without persisting any of
+	/// its changes.
+	///
+	/// The messages forwarded to other consensus systems are inspected through `Router`.
+	pub fn dry_run_extrinsic<Router: InspectMessageQueues>(
+		uxt: Block::Extrinsic,
+	) -> DryRunEffects<ApplyExtrinsicResultWithInfo<PostDispatchInfo>, System::RuntimeEvent> {
+		Self::dry_run::<Router, _>(|| Self::apply_extrinsic_with_info(uxt))
+	}
+
+	/// Dispatch `call` from `origin` on top of the current state and return its effects, without
+	/// persisting any of its changes.
+	///
+	/// Unlike [`Self::dry_run_extrinsic`], no transaction checks are done and no fees are charged.
+	/// The messages forwarded to other consensus systems are inspected through `Router`.
+	pub fn dry_run_call<Router: InspectMessageQueues>(
+		origin: OriginOf<Block::Extrinsic, Context>,
+		call: CallOf<Block::Extrinsic, Context>,
+	) -> DryRunEffects<DispatchResultWithPostInfo, System::RuntimeEvent> {
+		Self::dry_run::<Router, _>(|| call.dispatch(origin))
+	}
+
+	/// Run `f` in a storage transaction that is rolled back, collecting the events it deposited
+	/// and the messages it forwarded.
+	fn dry_run<Router: InspectMessageQueues, R>(
+		f: impl FnOnce() -> R,
+	) -> DryRunEffects<R, System::RuntimeEvent> {
+		with_transaction_unchecked(|| {
+			<frame_system::Pallet<System>>::reset_events();
+			Router::clear_messages();
+
+			let result = f();
+			let events = <frame_system::Pallet<System>>::read_events_no_consensus()
+				.map(|record| record.event)
+				.collect();
+			let forwarded_messages = Router::get_messages();
+
+			TransactionOutcome::Rollback(DryRunEffects { result, events, forwarded_messages })
+		})
 	}
 
 	fn final_checks(header: &frame_system::pallet_prelude::HeaderFor<System>) {
@@ -665,9 +964,15 @@ where
 			return Err(InvalidTransaction::MandatoryValidation.into())
 		}
 
+		// Don't fill the pool with transactions that can't be included until the multi-block
+		// migrations completed.
+		if Self::suspended(&xt, &dispatch_info) {
+			return Err(InvalidTransaction::ExhaustsResources.into())
+		}
+
 		within_span! {
 			sp_tracing::Level::TRACE, "validate";
-			xt.validate::<UnsignedValidator>(source, &dispatch_info, encoded_len)
+			xt.validate::<AuthorizeOrValidateUnsigned<UnsignedValidator>>(source, &dispatch_info, encoded_len)
 		}
 	}
 
@@ -696,6 +1001,7 @@ where
 mod tests {
 	use super::*;
 
+	use codec::Decode;
 	use sp_core::H256;
 	use sp_runtime::{
 		generic::{DigestItem, Era},
@@ -708,7 +1014,9 @@ mod tests {
 	};
 
 	use frame_support::{
-		assert_err, parameter_types,
+		assert_err,
+		migrations::MultiStepMigrator,
+		parameter_types,
 		traits::{fungible, ConstU32, ConstU64, ConstU8, Currency},
 		weights::{ConstantMultiplier, IdentityFee, RuntimeDbWeight, Weight, WeightToFee},
 	};
@@ -797,6 +1105,19 @@ mod tests {
 				sp_io::storage::set("storage_root".as_bytes(), &root);
 				Ok(())
 			}
+
+			#[pallet::authorize(|_source: TransactionSource, value: &u32| {
+				if value % 2 == 0 {
+					ValidTransaction::with_tag_prefix("Custom").and_provides(value).build()
+				} else {
+					InvalidTransaction::Call.into()
+				}
+			})]
+			#[pallet::weight_of_authorize(Weight::from_parts(10, 0))]
+			pub fn authorized_call(origin: OriginFor<T>, _value: u32) -> DispatchResult {
+				frame_system::ensure_authorized(origin)?;
+				Ok(())
+			}
 		}
 
 		#[pallet::inherent]
@@ -954,6 +1275,35 @@ mod tests {
 		}
 	}
 
+	// Will contain the number of remaining steps of the mocked multi-block migrations.
+	const MIGRATION_STEPS_KEY: &[u8] = b":migration_steps";
+
+	struct MockedMultiStepMigrator;
+	impl frame_support::migrations::MultiStepMigrator for MockedMultiStepMigrator {
+		fn ongoing() -> bool {
+			sp_io::storage::exists(MIGRATION_STEPS_KEY)
+		}
+
+		fn step() -> Weight {
+			let steps = sp_io::storage::get(MIGRATION_STEPS_KEY)
+				.map_or(0, |steps| u32::decode(&mut &steps[..]).unwrap());
+			if steps > 1 {
+				sp_io::storage::set(MIGRATION_STEPS_KEY, &(steps - 1).encode());
+			} else {
+				sp_io::storage::clear(MIGRATION_STEPS_KEY);
+			}
+			Weight::from_parts(10, 0)
+		}
+	}
+
+	/// Lets remarks through while multi-block migrations are ongoing.
+	struct MockedMigrationCallFilter;
+	impl Contains<RuntimeCall> for MockedMigrationCallFilter {
+		fn contains(call: &RuntimeCall) -> bool {
+			matches!(call, RuntimeCall::System(frame_system::Call::remark { .. }))
+		}
+	}
+
 	type Executive = super::Executive<
 		Runtime,
 		Block<TestXt>,
@@ -961,6 +1311,8 @@ mod tests {
 		Runtime,
 		AllPalletsWithSystem,
 		CustomOnRuntimeUpgrade,
+		MockedMultiStepMigrator,
+		MockedMigrationCallFilter,
 	>;
 
 	fn extra(nonce: u64, fee: Balance) -> SignedExtra {
@@ -1265,6 +1617,160 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn authorized_calls_are_validated_by_their_authorization() {
+		let valid =
+			TestXt::new(RuntimeCall::Custom(custom::Call::authorized_call { value: 2 }), None);
+		let invalid =
+			TestXt::new(RuntimeCall::Custom(custom::Call::authorized_call { value: 3 }), None);
+		let signed = TestXt::new(
+			RuntimeCall::Custom(custom::Call::authorized_call { value: 4 }),
+			sign_extra(1, 0, 0),
+		);
+		let mut t = new_test_ext(1);
+
+		t.execute_with(|| {
+			assert_eq!(
+				Executive::validate_transaction(
+					TransactionSource::External,
+					valid.clone(),
+					Default::default(),
+				),
+				ValidTransaction::with_tag_prefix("Custom").and_provides(2u32).build(),
+			);
+			assert_eq!(
+				Executive::validate_transaction(
+					TransactionSource::External,
+					invalid.clone(),
+					Default::default(),
+				),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call)),
+			);
+			// The weight of the call includes the weight of the authorization.
+			assert_eq!(valid.get_dispatch_info().weight, Weight::from_parts(10, 0));
+
+			Executive::initialize_block(&Header::new(
+				1,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			assert_eq!(Executive::apply_extrinsic(valid), Ok(Ok(())));
+			assert_eq!(
+				Executive::apply_extrinsic(invalid),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+			);
+			assert_eq!(Executive::apply_extrinsic(signed), Ok(Err(DispatchError::BadOrigin)));
+			// Only the executive grants the authorized origin.
+			assert_eq!(
+				RuntimeCall::Custom(custom::Call::authorized_call { value: 2 })
+					.dispatch(RuntimeOrigin::none())
+					.unwrap_err()
+					.error,
+				DispatchError::BadOrigin
+			);
+		});
+	}
+
+	#[test]
+	fn dry_run_reports_effects_without_persisting_them() {
+		let xt = TestXt::new(call_transfer(2, 69), sign_extra(1, 0, 0));
+		let transfer =
+			RuntimeEvent::Balances(pallet_balances::Event::Transfer { from: 1, to: 2, amount: 69 });
+		let mut t = new_test_ext(1);
+
+		t.execute_with(|| {
+			Executive::initialize_block(&Header::new(
+				1,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+
+			let effects =
+				Executive::dry_run_call::<()>(RuntimeOrigin::signed(1), call_transfer(2, 69));
+			assert!(effects.result.is_ok());
+			assert!(effects.events.contains(&transfer));
+			assert!(effects.forwarded_messages.is_empty());
+
+			let effects = Executive::dry_run_extrinsic::<()>(xt);
+			assert!(matches!(effects.result, Ok(Ok(_))));
+			assert!(effects.events.contains(&transfer));
+
+			// Nothing was persisted.
+			assert!(<frame_system::Pallet<Runtime>>::events().is_empty());
+			assert_eq!(<frame_system::Pallet<Runtime>>::account_nonce(1), 0);
+			assert_eq!(<pallet_balances::Pallet<Runtime>>::total_balance(&1), 111);
+			assert_eq!(<pallet_balances::Pallet<Runtime>>::total_balance(&2), 0);
+		});
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn try_execute_call_works() {
+		use frame_try_runtime::CallOrigin;
+
+		let transfer =
+			RuntimeEvent::Balances(pallet_balances::Event::Transfer { from: 1, to: 2, amount: 69 });
+		let events = || System::events().into_iter().map(|r| r.event).collect::<Vec<_>>();
+
+		new_test_ext(1).execute_with(|| {
+			Executive::initialize_block(&Header::new(
+				1,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			System::deposit_event(frame_system::Event::<Runtime>::CodeUpdated);
+
+			// signed origin, only the events of the call are left.
+			let (info, result) = Executive::try_execute_call(
+				call_transfer(2, 69),
+				CallOrigin::Signed(1u64.encode()),
+			)
+			.unwrap();
+			assert!(result.is_ok());
+			assert_eq!(info, call_transfer(2, 69).get_dispatch_info());
+			assert!(events().contains(&transfer));
+			assert!(!events().contains(&frame_system::Event::<Runtime>::CodeUpdated.into()));
+			assert_eq!(<pallet_balances::Pallet<Runtime>>::total_balance(&2), 69);
+
+			// root origin.
+			let call = RuntimeCall::Custom(custom::Call::some_root_operation {});
+			let (info, result) = Executive::try_execute_call(call, CallOrigin::Root).unwrap();
+			assert!(result.is_ok());
+			assert_eq!(info.class, DispatchClass::Operational);
+			assert!(events().is_empty());
+
+			// a failed dispatch is reported in the result.
+			let (_, result) =
+				Executive::try_execute_call(call_transfer(2, 69), CallOrigin::Root).unwrap();
+			assert_eq!(result.unwrap_err().error, DispatchError::BadOrigin);
+			assert!(events().is_empty());
+
+			// an undecodable account fails without dispatching.
+			assert!(Executive::try_execute_call(call_transfer(2, 1), CallOrigin::Signed(vec![1]))
+				.is_err());
+			assert_eq!(<pallet_balances::Pallet<Runtime>>::total_balance(&2), 69);
+
+			// the encoded call is decoded and its fee queried with the length of the encoding.
+			let call = call_transfer(2, 1).encode();
+			let outcome = Executive::try_execute_encoded_call(
+				&call,
+				CallOrigin::Signed(1u64.encode()),
+				|_, len| len.into(),
+			)
+			.unwrap();
+			assert!(outcome.result.is_ok());
+			assert_eq!(outcome.fee, call.len() as u128);
+			assert!(Executive::try_execute_encoded_call(&call[1..], CallOrigin::Root, |_, _| 0)
+				.is_err());
+		});
+	}
+
 	#[test]
 	fn can_not_pay_for_tx_fee_on_full_lock() {
 		let mut t = new_test_ext(1);
@@ -1505,7 +2011,8 @@ mod tests {
 				frame_system::Pallet::<Runtime>::block_weight().total(),
 				custom_runtime_upgrade_weight +
 					runtime_upgrade_weight +
-					on_initialize_weight + base_block_weight,
+					on_initialize_weight +
+					base_block_weight,
 			);
 		});
 	}
@@ -1629,6 +2136,124 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn transactions_are_suspended_during_multi_block_migrations() {
+		let xt1 = TestXt::new(RuntimeCall::Custom(custom::Call::inherent_call {}), None);
+		let xt2 = TestXt::new(call_transfer(33, 0), sign_extra(1, 0, 0));
+
+		new_test_ext(1).execute_with(|| {
+			sp_io::storage::set(MIGRATION_STEPS_KEY, &2u32.encode());
+
+			// The migrations are stepped once per block.
+			Executive::initialize_block(&Header::new(
+				1,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			assert!(MockedMultiStepMigrator::ongoing());
+			Executive::apply_extrinsic(xt1.clone()).unwrap().unwrap();
+			assert_eq!(
+				Executive::apply_extrinsic(xt2.clone()),
+				Err(InvalidTransaction::ExhaustsResources.into())
+			);
+			Executive::finalize_block();
+
+			Executive::initialize_block(&Header::new(
+				2,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			assert!(!MockedMultiStepMigrator::ongoing());
+			Executive::apply_extrinsic(xt1).unwrap().unwrap();
+			Executive::apply_extrinsic(xt2).unwrap().unwrap();
+		});
+	}
+
+	#[test]
+	fn filtered_calls_are_applied_during_multi_block_migrations() {
+		let remark = TestXt::new(
+			RuntimeCall::System(frame_system::Call::remark { remark: vec![1u8] }),
+			sign_extra(1, 0, 0),
+		);
+		let transfer = TestXt::new(call_transfer(33, 0), sign_extra(1, 0, 0));
+
+		new_test_ext(1).execute_with(|| {
+			sp_io::storage::set(MIGRATION_STEPS_KEY, &2u32.encode());
+
+			Executive::initialize_block(&Header::new_from_number(1));
+			assert!(MockedMultiStepMigrator::ongoing());
+			assert_eq!(
+				Executive::apply_extrinsic(transfer),
+				Err(InvalidTransaction::ExhaustsResources.into())
+			);
+			Executive::apply_extrinsic(remark).unwrap().unwrap();
+		});
+	}
+
+	#[test]
+	fn transactions_are_invalid_during_multi_block_migrations() {
+		let remark = TestXt::new(
+			RuntimeCall::System(frame_system::Call::remark { remark: vec![1u8] }),
+			sign_extra(1, 0, 0),
+		);
+		let transfer = TestXt::new(call_transfer(33, 0), sign_extra(1, 0, 0));
+
+		new_test_ext(1).execute_with(|| {
+			sp_io::storage::set(MIGRATION_STEPS_KEY, &1u32.encode());
+
+			assert_eq!(
+				Executive::validate_transaction(
+					TransactionSource::External,
+					transfer.clone(),
+					Default::default()
+				),
+				Err(InvalidTransaction::ExhaustsResources.into())
+			);
+			assert!(Executive::validate_transaction(
+				TransactionSource::External,
+				remark,
+				Default::default()
+			)
+			.is_ok());
+
+			sp_io::storage::clear(MIGRATION_STEPS_KEY);
+			assert!(Executive::validate_transaction(
+				TransactionSource::External,
+				transfer,
+				Default::default()
+			)
+			.is_ok());
+		});
+	}
+
+	#[test]
+	fn on_idle_is_skipped_during_multi_block_migrations() {
+		new_test_ext(1).execute_with(|| {
+			sp_io::storage::set(MIGRATION_STEPS_KEY, &2u32.encode());
+
+			// `on_initialize`, the migration step and the base block.
+			Executive::initialize_block(&Header::new_from_number(1));
+			Executive::finalize_block();
+			assert_eq!(
+				<frame_system::Pallet<Runtime>>::block_weight().total(),
+				Weight::from_parts(175 + 10 + 10, 0)
+			);
+
+			// The last step completes the migrations, so `on_idle` runs again.
+			Executive::initialize_block(&Header::new_from_number(2));
+			Executive::finalize_block();
+			assert!(!MockedMultiStepMigrator::ongoing());
+			assert_eq!(
+				<frame_system::Pallet<Runtime>>::block_weight().total(),
+				Weight::from_parts(175 + 10 + 10 + 175, 0)
+			);
+		});
+	}
+
 	// Inherents are created by the runtime and don't need to be validated.
 	#[test]
 	fn inherents_fail_validate_block() {
@@ -0,0 +1,399 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Migrations Pallet
+//!
+//! Executes multi-block migrations.
+//!
+//! ## Overview
+//!
+//! The [`Config::Migrations`] are started after a runtime upgrade and executed one after another,
+//! each in as many steps as needed. Every block spends at most [`Config::MaxServiceWeight`] on
+//! them. The pallet is meant to be the `MultiStepMigrator` of `frame-executive`, which suspends
+//! all transactions while migrations are [`MultiStepMigrator::ongoing`].
+//!
+//! Completed migrations are remembered in [`Historic`] by their identifier and skipped by later
+//! upgrades. When a migration fails, the [`Config::FailedMigrationHandler`] decides whether the
+//! chain stays stuck or resumes normal operation. A stuck chain restarts the migrations with the
+//! next runtime upgrade, or resumes normal operation after [`Pallet::force_set_cursor`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(rustdoc::broken_intra_doc_links)]
+
+mod mock;
+mod tests;
+
+use frame_support::{
+	defensive,
+	migrations::{
+		FailedMigrationHandler, FailedMigrationHandling, MigrationStatusHandler, MultiStepMigrator,
+		SteppedMigrationError, SteppedMigrations,
+	},
+	pallet_prelude::*,
+	weights::WeightMeter,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::Saturating;
+
+pub use pallet::*;
+
+const LOG_TARGET: &str = "runtime::migrations";
+
+/// An encoded [`SteppedMigration::Cursor`](frame_support::migrations::SteppedMigration::Cursor).
+pub type RawCursorOf<T> = BoundedVec<u8, <T as Config>::CursorMaxLen>;
+
+/// An encoded
+/// [`SteppedMigration::Identifier`](frame_support::migrations::SteppedMigration::Identifier).
+pub type IdentifierOf<T> = BoundedVec<u8, <T as Config>::IdentifierMaxLen>;
+
+/// The [`MigrationCursor`] of a runtime.
+pub type CursorOf<T> = MigrationCursor<RawCursorOf<T>, BlockNumberFor<T>>;
+
+/// The [`ActiveCursor`] of a runtime.
+pub type ActiveCursorOf<T> = ActiveCursor<RawCursorOf<T>, BlockNumberFor<T>>;
+
+/// The progress of the multi-block migrations.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub enum MigrationCursor<Cursor, BlockNumber> {
+	/// The migrations are ongoing.
+	Active(ActiveCursor<Cursor, BlockNumber>),
+	/// A migration failed and the chain is stuck, see [`FailedMigrationHandling::KeepStuck`].
+	Stuck,
+}
+
+/// The progress of the ongoing migrations.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct ActiveCursor<Cursor, BlockNumber> {
+	/// Index of the ongoing migration in [`Config::Migrations`].
+	pub index: u32,
+	/// The cursor of the ongoing migration, `None` before its first step.
+	pub inner_cursor: Option<Cursor>,
+	/// The number of steps the ongoing migration took so far.
+	pub steps: u32,
+	/// The block in which the migrations started.
+	pub started_at: BlockNumber,
+}
+
+impl<Cursor, BlockNumber> ActiveCursor<Cursor, BlockNumber> {
+	fn goto_next_migration(&mut self) {
+		self.index.saturating_inc();
+		self.inner_cursor = None;
+		self.steps = 0;
+	}
+}
+
+/// How to proceed after trying to execute a migration.
+enum StepOutcome {
+	/// Continue with the next step in the same block.
+	Continue,
+	/// Continue in the next block.
+	Yield,
+	/// All migrations completed.
+	Completed,
+	/// The migration failed.
+	Failed,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The migrations executed after a runtime upgrade, in order.
+		///
+		/// Migrations that completed before are skipped, so they don't need to be removed with the
+		/// next upgrade.
+		type Migrations: SteppedMigrations;
+
+		/// The maximum encoded length of the cursor of a migration.
+		#[pallet::constant]
+		type CursorMaxLen: Get<u32>;
+
+		/// The maximum encoded length of the identifier of a migration.
+		#[pallet::constant]
+		type IdentifierMaxLen: Get<u32>;
+
+		/// Notified when the migrations start and complete.
+		type MigrationStatusHandler: MigrationStatusHandler;
+
+		/// Decides how to proceed after a migration failed.
+		type FailedMigrationHandler: FailedMigrationHandler;
+
+		/// The maximum weight spent on the migrations in a block.
+		#[pallet::constant]
+		type MaxServiceWeight: Get<Weight>;
+	}
+
+	/// The progress of the migrations, `None` if there are no ongoing migrations.
+	#[pallet::storage]
+	pub type Cursor<T: Config> = StorageValue<_, CursorOf<T>, OptionQuery>;
+
+	/// The identifiers of the completed migrations.
+	#[pallet::storage]
+	pub type Historic<T: Config> = StorageMap<_, Twox64Concat, IdentifierOf<T>, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The migrations started after a runtime upgrade.
+		UpgradeStarted { migrations: u32 },
+		/// All migrations completed.
+		UpgradeCompleted,
+		/// A migration failed, see [`Config::FailedMigrationHandler`] for how the chain proceeds.
+		UpgradeFailed,
+		/// A migration was skipped since it completed before.
+		MigrationSkipped { index: u32 },
+		/// A migration made progress and `took` steps so far.
+		MigrationAdvanced { index: u32, took: u32 },
+		/// A migration completed after `took` steps.
+		MigrationCompleted { index: u32, took: u32 },
+		/// A migration failed after `took` steps.
+		MigrationFailed { index: u32, took: u32 },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			Self::start_migrations()
+		}
+
+		fn integrity_test() {
+			assert!(
+				T::Migrations::cursor_max_encoded_len() <= T::CursorMaxLen::get() as usize,
+				"`CursorMaxLen` is too small for the cursors of the migrations",
+			);
+			assert!(
+				T::Migrations::identifier_max_encoded_len() <= T::IdentifierMaxLen::get() as usize,
+				"`IdentifierMaxLen` is too small for the identifiers of the migrations",
+			);
+
+			let max_service_weight = T::MaxServiceWeight::get();
+			let max_block = <T as frame_system::Config>::BlockWeights::get().max_block;
+			assert!(
+				max_service_weight.all_lte(max_block),
+				"`MaxServiceWeight` must fit into a block",
+			);
+			let overhead = Self::service_overhead().saturating_add(Self::step_overhead());
+			assert!(
+				max_service_weight.all_gte(overhead),
+				"`MaxServiceWeight` is too small to execute any step",
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Overwrite the [`Cursor`] of the migrations, e.g. to unstick the chain after a migration
+		/// failed.
+		///
+		/// `None` stops the migrations and resumes normal operation of the chain. The executive
+		/// must let this call through while migrations are ongoing.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn force_set_cursor(
+			origin: OriginFor<T>,
+			cursor: Option<CursorOf<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Cursor::<T>::set(cursor);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Start the migrations after a runtime upgrade.
+	///
+	/// Migrations that are stuck are restarted, assuming that the upgrade fixes them.
+	fn start_migrations() -> Weight {
+		let weight = T::DbWeight::get().reads(1);
+		if let Some(MigrationCursor::Active(cursor)) = Cursor::<T>::get() {
+			log::error!(
+				target: LOG_TARGET,
+				"Runtime upgraded while migrations are ongoing, continuing them: {:?}",
+				cursor,
+			);
+			return weight
+		}
+
+		let migrations = T::Migrations::len();
+		if migrations == 0 {
+			Cursor::<T>::kill();
+			return weight.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		Cursor::<T>::put(MigrationCursor::Active(ActiveCursor {
+			index: 0,
+			inner_cursor: None,
+			steps: 0,
+			started_at: frame_system::Pallet::<T>::block_number(),
+		}));
+		Self::deposit_event(Event::UpgradeStarted { migrations });
+		T::MigrationStatusHandler::started();
+
+		weight.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	/// The weight of reading and writing the [`Cursor`] once per block.
+	fn service_overhead() -> Weight {
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	/// The weight of checking and updating the [`Historic`] for a step.
+	fn step_overhead() -> Weight {
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	/// Try to execute the next step of the migration at `cursor`.
+	fn exec_migration(
+		cursor: &mut ActiveCursorOf<T>,
+		max_step_weight: Weight,
+		meter: &mut WeightMeter,
+	) -> StepOutcome {
+		if meter.try_consume(Self::step_overhead()).is_err() {
+			return StepOutcome::Yield
+		}
+		let index = cursor.index;
+
+		let Some(id) = T::Migrations::nth_id(index) else { return StepOutcome::Completed };
+		let Ok(id) = IdentifierOf::<T>::try_from(id) else {
+			defensive!("Identifier of the migration is too long, see `integrity_test`");
+			Self::deposit_event(Event::MigrationFailed { index, took: cursor.steps });
+			return StepOutcome::Failed
+		};
+
+		if cursor.inner_cursor.is_none() && Historic::<T>::contains_key(&id) {
+			Self::deposit_event(Event::MigrationSkipped { index });
+			cursor.goto_next_migration();
+			return StepOutcome::Continue
+		}
+
+		if let Some(max_steps) = T::Migrations::nth_max_steps(index).flatten() {
+			if cursor.steps >= max_steps {
+				log::error!(target: LOG_TARGET, "Migration {} exceeded {} steps", index, max_steps);
+				Self::deposit_event(Event::MigrationFailed { index, took: cursor.steps });
+				return StepOutcome::Failed
+			}
+		}
+
+		let consumed = meter.consumed();
+		let inner_cursor = cursor.inner_cursor.clone().map(Into::into);
+		match T::Migrations::nth_transactional_step(index, inner_cursor, meter) {
+			Some(Ok(Some(next))) => {
+				cursor.steps.saturating_inc();
+				let Ok(next) = RawCursorOf::<T>::try_from(next) else {
+					defensive!("Cursor of the migration is too long, see `integrity_test`");
+					Self::deposit_event(Event::MigrationFailed { index, took: cursor.steps });
+					return StepOutcome::Failed
+				};
+				cursor.inner_cursor = Some(next);
+				Self::deposit_event(Event::MigrationAdvanced { index, took: cursor.steps });
+
+				// Steps that don't consume any weight could otherwise loop forever.
+				if meter.consumed() == consumed {
+					StepOutcome::Yield
+				} else {
+					StepOutcome::Continue
+				}
+			},
+			Some(Ok(None)) => {
+				cursor.steps.saturating_inc();
+				Historic::<T>::insert(&id, ());
+				Self::deposit_event(Event::MigrationCompleted { index, took: cursor.steps });
+				cursor.goto_next_migration();
+				StepOutcome::Continue
+			},
+			Some(Err(SteppedMigrationError::InsufficientWeight { required }))
+				if required.all_lte(max_step_weight) =>
+				StepOutcome::Yield,
+			Some(Err(err)) => {
+				log::error!(target: LOG_TARGET, "Migration {} failed: {:?}", index, err);
+				Self::deposit_event(Event::MigrationFailed { index, took: cursor.steps });
+				StepOutcome::Failed
+			},
+			None => {
+				defensive!("Migration disappeared while executing it");
+				Self::deposit_event(Event::MigrationFailed { index, took: cursor.steps });
+				StepOutcome::Failed
+			},
+		}
+	}
+
+	/// Handle a failed migration with the [`Config::FailedMigrationHandler`].
+	fn upgrade_failed(migration: u32) {
+		Self::deposit_event(Event::UpgradeFailed);
+
+		match T::FailedMigrationHandler::failed(Some(migration)) {
+			FailedMigrationHandling::KeepStuck => Cursor::<T>::put(MigrationCursor::Stuck),
+			FailedMigrationHandling::ForceUnstuck => {
+				Cursor::<T>::kill();
+				T::MigrationStatusHandler::completed();
+			},
+		}
+	}
+}
+
+impl<T: Config> MultiStepMigrator for Pallet<T> {
+	fn ongoing() -> bool {
+		Cursor::<T>::exists()
+	}
+
+	fn step() -> Weight {
+		let mut meter = WeightMeter::from_limit(T::MaxServiceWeight::get());
+		meter.consume(Self::service_overhead());
+
+		let mut cursor = match Cursor::<T>::get() {
+			Some(MigrationCursor::Active(cursor)) => cursor,
+			Some(MigrationCursor::Stuck) => {
+				log::error!(target: LOG_TARGET, "Migrations are stuck, see `UpgradeFailed`");
+				return meter.consumed()
+			},
+			None => return meter.consumed(),
+		};
+		// A step requiring more weight than this would never be executed.
+		let max_step_weight = meter.remaining().saturating_sub(Self::step_overhead());
+
+		loop {
+			match Self::exec_migration(&mut cursor, max_step_weight, &mut meter) {
+				StepOutcome::Continue => {},
+				StepOutcome::Yield => {
+					Cursor::<T>::put(MigrationCursor::Active(cursor));
+					break
+				},
+				StepOutcome::Completed => {
+					Cursor::<T>::kill();
+					Self::deposit_event(Event::UpgradeCompleted);
+					T::MigrationStatusHandler::completed();
+					break
+				},
+				StepOutcome::Failed => {
+					Self::upgrade_failed(cursor.index);
+					break
+				},
+			}
+		}
+
+		meter.consumed()
+	}
+}
@@ -0,0 +1,187 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for the migrations pallet.
+
+#![cfg(test)]
+
+use super::*;
+use crate as pallet_migrations;
+
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64, Everything, OnRuntimeUpgrade},
+};
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Migrations: pallet_migrations::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type BlockHashCount = ConstU64<250>;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type BaseCallFilter = Everything;
+	type PalletInfo = PalletInfo;
+	type OnSetCode = ();
+}
+
+/// How a [`MockedMigrations`] migration behaves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockedMigrationKind {
+	/// Completes after the given number of steps.
+	Succeed,
+	/// Fails in its first step.
+	Fail,
+	/// Requires more weight than any block has.
+	TooHeavy,
+	/// Never completes and fails after the given number of steps.
+	TimeOut,
+}
+use MockedMigrationKind::*;
+
+parameter_types! {
+	/// The weight of a step of a [`MockedMigrations`] migration.
+	pub const StepWeight: Weight = Weight::from_parts(100, 100);
+	/// Allows three steps per block.
+	pub const MaxServiceWeight: Weight = Weight::from_parts(350, 350);
+
+	/// The [`MockedMigrations`] with their number of steps.
+	pub static MockedMigrationSet: Vec<(MockedMigrationKind, u32)> = vec![];
+	pub static FailedHandling: FailedMigrationHandling = FailedMigrationHandling::KeepStuck;
+	pub static UpgradesStarted: u32 = 0;
+	pub static UpgradesCompleted: u32 = 0;
+}
+
+/// The migrations of the [`MockedMigrationSet`], identified by their index.
+pub struct MockedMigrations;
+impl SteppedMigrations for MockedMigrations {
+	fn len() -> u32 {
+		MockedMigrationSet::get().len() as u32
+	}
+
+	fn cursor_max_encoded_len() -> usize {
+		u32::max_encoded_len()
+	}
+
+	fn identifier_max_encoded_len() -> usize {
+		u32::max_encoded_len()
+	}
+
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		(n < Self::len()).then(|| n.encode())
+	}
+
+	fn nth_max_steps(n: u32) -> Option<Option<u32>> {
+		let (kind, steps) = *MockedMigrationSet::get().get(n as usize)?;
+		Some((kind == TimeOut).then_some(steps))
+	}
+
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		let (kind, steps) = *MockedMigrationSet::get().get(n as usize)?;
+		let insufficient_weight = |required| SteppedMigrationError::InsufficientWeight { required };
+
+		let result = match kind {
+			Succeed | TimeOut => meter
+				.try_consume(StepWeight::get())
+				.map_err(|()| insufficient_weight(StepWeight::get()))
+				.map(|()| {
+					let step = cursor.map_or(0, |c| u32::decode(&mut &c[..]).unwrap()) + 1;
+					(kind == TimeOut || step < steps).then(|| step.encode())
+				}),
+			Fail => Err(SteppedMigrationError::Failed),
+			TooHeavy => Err(insufficient_weight(Weight::MAX)),
+		};
+		Some(result)
+	}
+}
+
+pub struct MockedStatusHandler;
+impl MigrationStatusHandler for MockedStatusHandler {
+	fn started() {
+		UpgradesStarted::mutate(|n| *n += 1);
+	}
+
+	fn completed() {
+		UpgradesCompleted::mutate(|n| *n += 1);
+	}
+}
+
+pub struct MockedFailedHandler;
+impl FailedMigrationHandler for MockedFailedHandler {
+	fn failed(_migration: Option<u32>) -> FailedMigrationHandling {
+		FailedHandling::get()
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Migrations = MockedMigrations;
+	type CursorMaxLen = ConstU32<4>;
+	type IdentifierMaxLen = ConstU32<4>;
+	type MigrationStatusHandler = MockedStatusHandler;
+	type FailedMigrationHandler = MockedFailedHandler;
+	type MaxServiceWeight = MaxServiceWeight;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Upgrade the runtime to the given migrations.
+pub fn upgrade(migrations: Vec<(MockedMigrationKind, u32)>) {
+	MockedMigrationSet::set(migrations);
+	<Migrations as OnRuntimeUpgrade>::on_runtime_upgrade();
+}
+
+/// Step the migrations in the next block, as `frame-executive` does.
+pub fn next_block() {
+	System::set_block_number(System::block_number() + 1);
+	if Migrations::ongoing() {
+		Migrations::step();
+	}
+}
+
+/// Take the events of this pallet deposited so far.
+pub fn take_events() -> Vec<Event<Test>> {
+	let events = System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::Migrations(event) => Some(event),
+			_ => None,
+		})
+		.collect();
+	System::reset_events();
+	events
+}
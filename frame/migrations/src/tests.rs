@@ -0,0 +1,192 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(test)]
+
+use crate::{mock::*, Cursor, Event, Historic, IdentifierOf, MigrationCursor};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	migrations::{FailedMigrationHandling, MultiStepMigrator},
+};
+use sp_runtime::DispatchError;
+
+fn is_historic(index: u32) -> bool {
+	Historic::<Test>::contains_key(IdentifierOf::<Test>::try_from(index.encode()).unwrap())
+}
+
+#[test]
+fn migrations_are_stepped_across_blocks() {
+	new_test_ext().execute_with(|| {
+		upgrade(vec![(MockedMigrationKind::Succeed, 2), (MockedMigrationKind::Succeed, 3)]);
+		assert!(Migrations::ongoing());
+		assert_eq!(take_events(), vec![Event::UpgradeStarted { migrations: 2 }]);
+		assert_eq!(UpgradesStarted::get(), 1);
+
+		// Three steps fit into a block.
+		next_block();
+		assert!(Migrations::ongoing());
+		assert_eq!(
+			take_events(),
+			vec![
+				Event::MigrationAdvanced { index: 0, took: 1 },
+				Event::MigrationCompleted { index: 0, took: 2 },
+				Event::MigrationAdvanced { index: 1, took: 1 },
+			]
+		);
+
+		next_block();
+		assert!(!Migrations::ongoing());
+		assert_eq!(
+			take_events(),
+			vec![
+				Event::MigrationAdvanced { index: 1, took: 2 },
+				Event::MigrationCompleted { index: 1, took: 3 },
+				Event::UpgradeCompleted,
+			]
+		);
+		assert_eq!(UpgradesCompleted::get(), 1);
+		assert!(is_historic(0) && is_historic(1));
+	});
+}
+
+#[test]
+fn completed_migrations_are_skipped() {
+	new_test_ext().execute_with(|| {
+		upgrade(vec![(MockedMigrationKind::Succeed, 1)]);
+		next_block();
+		assert!(!Migrations::ongoing());
+		take_events();
+
+		upgrade(vec![(MockedMigrationKind::Succeed, 1), (MockedMigrationKind::Succeed, 1)]);
+		next_block();
+		assert!(!Migrations::ongoing());
+		assert_eq!(
+			take_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 2 },
+				Event::MigrationSkipped { index: 0 },
+				Event::MigrationCompleted { index: 1, took: 1 },
+				Event::UpgradeCompleted,
+			]
+		);
+	});
+}
+
+#[test]
+fn failed_migration_keeps_chain_stuck_until_next_upgrade() {
+	new_test_ext().execute_with(|| {
+		upgrade(vec![(MockedMigrationKind::Succeed, 1), (MockedMigrationKind::Fail, 1)]);
+		next_block();
+		assert_eq!(
+			take_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 2 },
+				Event::MigrationCompleted { index: 0, took: 1 },
+				Event::MigrationFailed { index: 1, took: 0 },
+				Event::UpgradeFailed,
+			]
+		);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+
+		next_block();
+		assert!(Migrations::ongoing());
+		assert!(take_events().is_empty());
+
+		// The fixed migration is executed with the next upgrade.
+		upgrade(vec![(MockedMigrationKind::Succeed, 1), (MockedMigrationKind::Succeed, 1)]);
+		next_block();
+		assert!(!Migrations::ongoing());
+		assert_eq!(
+			take_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 2 },
+				Event::MigrationSkipped { index: 0 },
+				Event::MigrationCompleted { index: 1, took: 1 },
+				Event::UpgradeCompleted,
+			]
+		);
+	});
+}
+
+#[test]
+fn failed_migration_can_force_unstuck() {
+	new_test_ext().execute_with(|| {
+		FailedHandling::set(FailedMigrationHandling::ForceUnstuck);
+		upgrade(vec![(MockedMigrationKind::Fail, 1), (MockedMigrationKind::Succeed, 1)]);
+		next_block();
+
+		assert!(!Migrations::ongoing());
+		assert_eq!(UpgradesCompleted::get(), 1);
+		assert!(!is_historic(0) && !is_historic(1));
+		assert_eq!(
+			take_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 2 },
+				Event::MigrationFailed { index: 0, took: 0 },
+				Event::UpgradeFailed,
+			]
+		);
+	});
+}
+
+#[test]
+fn stuck_chain_can_be_unstuck_by_root() {
+	new_test_ext().execute_with(|| {
+		upgrade(vec![(MockedMigrationKind::Fail, 1)]);
+		next_block();
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+
+		assert_noop!(
+			Migrations::force_set_cursor(RuntimeOrigin::signed(1), None),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Migrations::force_set_cursor(RuntimeOrigin::root(), None));
+		assert!(!Migrations::ongoing());
+	});
+}
+
+#[test]
+fn too_heavy_migration_fails() {
+	new_test_ext().execute_with(|| {
+		upgrade(vec![(MockedMigrationKind::TooHeavy, 1)]);
+		next_block();
+
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert!(take_events().contains(&Event::MigrationFailed { index: 0, took: 0 }));
+	});
+}
+
+#[test]
+fn migration_exceeding_max_steps_fails() {
+	new_test_ext().execute_with(|| {
+		upgrade(vec![(MockedMigrationKind::TimeOut, 4)]);
+		next_block();
+		assert!(!take_events().contains(&Event::UpgradeFailed));
+
+		next_block();
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert_eq!(
+			take_events(),
+			vec![
+				Event::MigrationAdvanced { index: 0, took: 4 },
+				Event::MigrationFailed { index: 0, took: 4 },
+				Event::UpgradeFailed,
+			]
+		);
+	});
+}
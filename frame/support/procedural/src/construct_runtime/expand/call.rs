@@ -124,6 +124,29 @@ pub fn expand_outer_dispatch(
 			}
 		}
 
+		impl #scrate::traits::Authorize for RuntimeCall {
+			fn authorize(
+				&self,
+				source: #scrate::pallet_prelude::TransactionSource,
+			) -> Option<#scrate::pallet_prelude::TransactionValidity> {
+				match self {
+					#(
+						#pallet_attrs
+						#variant_patterns => #scrate::traits::Authorize::authorize(call, source),
+					)*
+				}
+			}
+
+			fn weight_of_authorize(&self) -> #scrate::weights::Weight {
+				match self {
+					#(
+						#pallet_attrs
+						#variant_patterns => #scrate::traits::Authorize::weight_of_authorize(call),
+					)*
+				}
+			}
+		}
+
 		impl #scrate::dispatch::GetCallMetadata for RuntimeCall {
 			fn get_call_metadata(&self) -> #scrate::dispatch::CallMetadata {
 				use #scrate::dispatch::GetCallName;
@@ -88,6 +88,9 @@ pub fn expand_outer_origin(
 	let doc_string_signed_origin =
 		get_intra_doc_string("Create with system signed origin and", &system_path_name);
 
+	let doc_string_authorized_origin =
+		get_intra_doc_string("Create with system authorized origin and", &system_path_name);
+
 	let doc_string_runtime_origin =
 		get_intra_doc_string("Convert to runtime origin, using as filter:", &system_path_name);
 
@@ -194,6 +197,10 @@ pub fn expand_outer_origin(
 			fn signed(by: Self::AccountId) -> Self {
 				#system_path::RawOrigin::Signed(by).into()
 			}
+
+			fn authorized() -> Self {
+				#system_path::RawOrigin::Authorized.into()
+			}
 		}
 
 		#[derive(
@@ -226,6 +233,11 @@ pub fn expand_outer_origin(
 			pub fn signed(by: <#runtime as #system_path::Config>::AccountId) -> Self {
 				<RuntimeOrigin as #scrate::traits::OriginTrait>::signed(by)
 			}
+
+			#[doc = #doc_string_authorized_origin]
+			pub fn authorized() -> Self {
+				<RuntimeOrigin as #scrate::traits::OriginTrait>::authorized()
+			}
 		}
 
 		impl From<#system_path::Origin<#runtime>> for OriginCaller {
@@ -0,0 +1,78 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::construct_runtime::Pallet;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::str::FromStr;
+use syn::Ident;
+
+/// Expands implementation of runtime level `DispatchViewFunction`.
+pub fn expand_outer_view_function(
+	runtime: &Ident,
+	pallet_decls: &[Pallet],
+	scrate: &TokenStream,
+) -> TokenStream {
+	let mut pallet_names = Vec::new();
+	let mut pallet_attrs = Vec::new();
+
+	for pallet_decl in pallet_decls.iter().filter(|decl| decl.exists_part("Pallet")) {
+		let attr = pallet_decl.cfg_pattern.iter().fold(TokenStream::new(), |acc, pattern| {
+			let attr = TokenStream::from_str(&format!("#[cfg({})]", pattern.original()))
+				.expect("was successfully parsed before; qed");
+			quote! {
+				#acc
+				#attr
+			}
+		});
+
+		pallet_names.push(&pallet_decl.name);
+		pallet_attrs.push(attr);
+	}
+
+	quote! {
+		impl #scrate::view_functions::DispatchViewFunction for #runtime {
+			#[allow(unused_variables)]
+			fn dispatch_view_function<O: #scrate::__private::codec::Output>(
+				id: &#scrate::view_functions::ViewFunctionId,
+				input: &mut &[u8],
+				output: &mut O,
+			) -> Result<(), #scrate::view_functions::ViewFunctionDispatchError> {
+				#(
+					#pallet_attrs
+					if id.prefix == <#pallet_names as #scrate::view_functions::ViewFunctionIdPrefix>::prefix() {
+						return <#pallet_names as #scrate::view_functions::DispatchViewFunction>::dispatch_view_function(id, input, output)
+					}
+				)*
+				Err(#scrate::view_functions::ViewFunctionDispatchError::NotFound(*id))
+			}
+		}
+
+		impl #runtime {
+			/// Execute the view function `id` of a pallet of the runtime with the SCALE-encoded
+			/// arguments `input` and return its SCALE-encoded result.
+			///
+			/// Can be used to implement the `RuntimeViewFunction` runtime API.
+			pub fn execute_view_function(
+				id: #scrate::view_functions::ViewFunctionId,
+				input: #scrate::__private::sp_std::vec::Vec<u8>,
+			) -> Result<#scrate::__private::sp_std::vec::Vec<u8>, #scrate::view_functions::ViewFunctionDispatchError> {
+				#scrate::view_functions::execute_view_function::<#runtime>(id, input)
+			}
+		}
+	}
+}
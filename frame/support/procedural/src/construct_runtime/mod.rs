@@ -396,10 +396,12 @@ fn construct_runtime_final_expansion(
 	let inherent =
 		expand::expand_outer_inherent(&name, &block, &unchecked_extrinsic, &pallets, &scrate);
 	let validate_unsigned = expand::expand_outer_validate_unsigned(&name, &pallets, &scrate);
+	let view_function = expand::expand_outer_view_function(&name, &pallets, &scrate);
 	let freeze_reason = expand::expand_outer_freeze_reason(&pallets, &scrate);
 	let hold_reason = expand::expand_outer_hold_reason(&pallets, &scrate);
 	let lock_id = expand::expand_outer_lock_id(&pallets, &scrate);
 	let slash_reason = expand::expand_outer_slash_reason(&pallets, &scrate);
+	let task = expand::expand_outer_task(&name, &pallets, &scrate);
 	let integrity_test = decl_integrity_test(&scrate);
 	let static_assertions = decl_static_assertions(&name, &pallets, &scrate);
 
@@ -480,6 +482,8 @@ fn construct_runtime_final_expansion(
 
 		#validate_unsigned
 
+		#view_function
+
 		#freeze_reason
 
 		#hold_reason
@@ -488,6 +492,8 @@ fn construct_runtime_final_expansion(
 
 		#slash_reason
 
+		#task
+
 		#integrity_test
 
 		#static_assertions
@@ -44,6 +44,7 @@ mod keyword {
 	syn::custom_keyword!(HoldReason);
 	syn::custom_keyword!(LockId);
 	syn::custom_keyword!(SlashReason);
+	syn::custom_keyword!(Task);
 	syn::custom_keyword!(exclude_parts);
 	syn::custom_keyword!(use_parts);
 	syn::custom_keyword!(expanded);
@@ -406,6 +407,7 @@ pub enum PalletPartKeyword {
 	HoldReason(keyword::HoldReason),
 	LockId(keyword::LockId),
 	SlashReason(keyword::SlashReason),
+	Task(keyword::Task),
 }
 
 impl Parse for PalletPartKeyword {
@@ -438,6 +440,8 @@ impl Parse for PalletPartKeyword {
 			Ok(Self::LockId(input.parse()?))
 		} else if lookahead.peek(keyword::SlashReason) {
 			Ok(Self::SlashReason(input.parse()?))
+		} else if lookahead.peek(keyword::Task) {
+			Ok(Self::Task(input.parse()?))
 		} else {
 			Err(lookahead.error())
 		}
@@ -461,6 +465,7 @@ impl PalletPartKeyword {
 			Self::HoldReason(_) => "HoldReason",
 			Self::LockId(_) => "LockId",
 			Self::SlashReason(_) => "SlashReason",
+			Self::Task(_) => "Task",
 		}
 	}
 
@@ -491,6 +496,7 @@ impl ToTokens for PalletPartKeyword {
 			Self::HoldReason(inner) => inner.to_tokens(tokens),
 			Self::LockId(inner) => inner.to_tokens(tokens),
 			Self::SlashReason(inner) => inner.to_tokens(tokens),
+			Self::Task(inner) => inner.to_tokens(tokens),
 		}
 	}
 }
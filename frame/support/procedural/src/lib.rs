@@ -1125,7 +1125,7 @@ pub fn compact(_: TokenStream, _: TokenStream) -> TokenStream {
 ///
 /// The macro creates an enum `Call` with one variant per dispatchable. This enum implements:
 /// [`Clone`], [`Eq`], [`PartialEq`], [`Debug`] (with stripped implementation in `not("std")`),
-/// `Encode`, `Decode`, `GetDispatchInfo`, `GetCallName`, `GetCallIndex` and
+/// `Encode`, `Decode`, `GetDispatchInfo`, `GetCallName`, `GetCallIndex`, `Authorize` and
 /// `UnfilteredDispatchable`.
 ///
 /// The macro implements the `Callable` trait on `Pallet` and a function `call_functions`
@@ -1135,6 +1135,26 @@ pub fn call_index(_: TokenStream, _: TokenStream) -> TokenStream {
 	pallet_macro_stub()
 }
 
+/// Each dispatchable may be annotated with `#[pallet::authorize($expr)]` to be dispatchable
+/// as an unsigned transaction.
+///
+/// The expression is a function taking the `TransactionSource` and references to the arguments
+/// of the dispatchable, and returning a `TransactionValidity`. Authorized dispatchables are
+/// dispatched with the `Authorized` origin of `frame_system` instead of the `None` origin.
+#[proc_macro_attribute]
+pub fn authorize(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Each dispatchable with `#[pallet::authorize($expr)]` must define the weight of its
+/// authorization with `#[pallet::weight_of_authorize($expr)]`, unless the pallet is in dev mode.
+///
+/// The arguments of the dispatchable are in scope of the expression as references.
+#[proc_macro_attribute]
+pub fn weight_of_authorize(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
 /// Allows you to define some extra constants to be added into constant metadata.
 ///
 /// Item must be defined as:
@@ -1579,6 +1599,104 @@ pub fn composite_enum(_: TokenStream, _: TokenStream) -> TokenStream {
 	pallet_macro_stub()
 }
 
+/// Allows you to define read-only queries of the pallet state, the view functions.
+///
+/// Item must be defined as:
+///
+/// ```ignore
+/// #[pallet::view_functions]
+/// impl<T: Config> Pallet<T> where $optional_where_clause {
+/// 	/// $some_doc
+/// 	$vis fn $fn_name($arg: $arg_type, ...) -> $some_return_type {
+/// 		...
+/// 	}
+/// 	...
+/// }
+/// ```
+/// I.e. a regular rust `impl` block with some optional where clause and functions with 0
+/// generics, no receiver and some return type. The arguments and the return type must implement
+/// `Decode`, `Encode` and `TypeInfo`.
+///
+/// ## Macro expansion
+///
+/// The macro implements `ViewFunctionIdPrefix` and `DispatchViewFunction` for the pallet and adds
+/// the view functions to the pallet metadata. A view function is identified by the `twox_128`
+/// hash of the pallet name and the `twox_128` hash of its signature, e.g.
+/// `get_value(u32) -> Option<u32>`. `construct_runtime!` aggregates the view functions of all
+/// pallets into `Runtime::execute_view_function`.
+#[proc_macro_attribute]
+pub fn view_functions(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Allows you to define background work of the pallet which anybody can execute, the tasks.
+///
+/// Item must be defined as:
+///
+/// ```ignore
+/// #[pallet::tasks]
+/// impl<T: Config> Pallet<T> where $optional_where_clause {
+/// 	/// $some_doc
+/// 	#[pallet::task_index($index)]
+/// 	#[pallet::task_list($iterator_over_args)]
+/// 	#[pallet::task_condition($closure_checking_args)]
+/// 	#[pallet::task_weight($weight)]
+/// 	$vis fn $fn_name($arg: $arg_type, ...) -> DispatchResult {
+/// 		...
+/// 	}
+/// 	...
+/// }
+/// ```
+/// I.e. a regular rust `impl` block with some optional where clause and functions with 0
+/// generics, no receiver and the return type `DispatchResult`. Each function must be annotated
+/// with all of [`macro@task_index`], [`macro@task_list`], [`macro@task_condition`] and
+/// [`macro@task_weight`].
+///
+/// ## Macro expansion
+///
+/// The macro generates the enum `Task` with a variant for each function, named after the
+/// function and with a field for each argument, and implements the trait `Task` on it.
+/// `construct_runtime!` aggregates the `Task` enums of all pallets into `RuntimeTask`.
+#[proc_macro_attribute]
+pub fn tasks(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Each task in `#[pallet::tasks]` must define its index with `#[pallet::task_index($index)]`.
+///
+/// The index is used to encode the task, so it must not be changed once the task is used.
+#[proc_macro_attribute]
+pub fn task_index(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Each task in `#[pallet::tasks]` must define the pending tasks with
+/// `#[pallet::task_list($iterator)]`.
+///
+/// The iterator yields the argument of each pending task, or a tuple of its arguments if the
+/// task has many arguments.
+#[proc_macro_attribute]
+pub fn task_list(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Each task in `#[pallet::tasks]` must define when it can run with
+/// `#[pallet::task_condition($closure)]`.
+///
+/// The closure is called with references to the arguments of the task and returns a `bool`.
+#[proc_macro_attribute]
+pub fn task_condition(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Each task in `#[pallet::tasks]` must define its weight with `#[pallet::task_weight($expr)]`.
+///
+/// The arguments of the task are in scope of the expression as references.
+#[proc_macro_attribute]
+pub fn task_weight(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
 /// Can be attached to a module. Doing so will declare that module as importable into a pallet
 /// via [`#[import_section]`](`macro@import_section`).
 ///
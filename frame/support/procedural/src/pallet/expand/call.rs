@@ -179,6 +179,74 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 		.map(|method| method.args.iter().map(|(_, _, type_)| type_.clone()).collect::<Vec<_>>())
 		.collect::<Vec<_>>();
 
+	// The calls with `#[pallet::authorize]` are dispatched with the authorized origin instead of
+	// the none origin once the executive granted it, and their weight includes the weight of the
+	// authorization.
+	let mut authorize_arms = Vec::new();
+	let mut weight_of_authorize_arms = Vec::new();
+	let mut maybe_add_weight_of_authorize = Vec::new();
+	let mut maybe_authorized_origin = Vec::new();
+	for (method, args_name_pattern_ref) in methods.iter().zip(args_name_pattern_ref.iter()) {
+		let authorize = match &method.authorize {
+			Some(authorize) => authorize,
+			None => {
+				maybe_add_weight_of_authorize.push(quote!());
+				maybe_authorized_origin.push(quote!());
+				continue
+			},
+		};
+
+		let fn_name = &method.name;
+		let args_name = method.args.iter().map(|(_, name, _)| name).collect::<Vec<_>>();
+		let authorize_expr = &authorize.expr;
+		let weight = match &authorize.weight {
+			Some(weight) => weight.to_token_stream(),
+			None => quote!(#frame_support::weights::Weight::zero()),
+		};
+
+		authorize_arms.push(quote!(
+			Self::#fn_name { #( #args_name_pattern_ref, )* } => {
+				let __pallet_authorize = #authorize_expr;
+				Some(__pallet_authorize(source, #( #args_name, )*))
+			},
+		));
+		weight_of_authorize_arms.push(quote!(
+			Self::#fn_name { #( #args_name_pattern_ref, )* } => #weight,
+		));
+		maybe_add_weight_of_authorize.push(quote!(
+			let __pallet_weight = __pallet_weight.saturating_add(
+				<Self as #frame_support::traits::Authorize>::weight_of_authorize(self)
+			);
+		));
+		maybe_authorized_origin.push(quote!(
+			let mut origin = origin;
+			if #frame_support::traits::OriginTrait::as_system_ref(&origin)
+				.map_or(false, #frame_support::dispatch::RawOrigin::is_none) &&
+				#frame_support::dispatch::AuthorizedDispatch::take()
+			{
+				#frame_support::traits::OriginTrait::set_caller_from(
+					&mut origin,
+					<Self::RuntimeOrigin as #frame_support::traits::OriginTrait>::authorized(),
+				);
+			}
+		));
+	}
+
+	let (authorize_body, weight_of_authorize_body) = if authorize_arms.is_empty() {
+		(quote!(None), quote!(#frame_support::weights::Weight::zero()))
+	} else {
+		(
+			quote!(match *self {
+				#( #authorize_arms )*
+				_ => None,
+			}),
+			quote!(match *self {
+				#( #weight_of_authorize_arms )*
+				_ => #frame_support::weights::Weight::zero(),
+			}),
+		)
+	};
+
 	let args_compact_attr = methods.iter().map(|method| {
 		method
 			.args
@@ -339,6 +407,8 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 								dyn #frame_support::dispatch::PaysFee<( #( & #args_type, )* )>
 							>::pays_fee(&__pallet_base_weight, ( #( #args_name, )* ));
 
+							#maybe_add_weight_of_authorize
+
 							#frame_support::dispatch::DispatchInfo {
 								weight: __pallet_weight,
 								class: __pallet_class,
@@ -397,6 +467,7 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 								#frame_support::__private::sp_tracing::enter_span!(
 									#frame_support::__private::sp_tracing::trace_span!(stringify!(#fn_name))
 								);
+								#maybe_authorized_origin
 								#maybe_allow_attrs
 								<#pallet_ident<#type_use_gen>>::#fn_name(origin, #( #args_name, )* )
 									.map(Into::into).map_err(Into::into)
@@ -411,6 +482,24 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 			}
 		}
 
+		impl<#type_impl_gen> #frame_support::traits::Authorize for #call_ident<#type_use_gen>
+			#where_clause
+		{
+			#[allow(unreachable_patterns)]
+			fn authorize(
+				&self,
+				source: #frame_support::pallet_prelude::TransactionSource,
+			) -> Option<#frame_support::pallet_prelude::TransactionValidity> {
+				let _ = source;
+				#authorize_body
+			}
+
+			#[allow(unreachable_patterns, unused_variables)]
+			fn weight_of_authorize(&self) -> #frame_support::weights::Weight {
+				#weight_of_authorize_body
+			}
+		}
+
 		impl<#type_impl_gen> #frame_support::dispatch::Callable<T> for #pallet_ident<#type_use_gen>
 			#where_clause
 		{
@@ -31,9 +31,11 @@ mod origin;
 mod pallet_struct;
 mod storage;
 mod store_trait;
+mod tasks;
 mod tt_default_parts;
 mod type_value;
 mod validate_unsigned;
+mod view_functions;
 
 use crate::pallet::Def;
 use quote::ToTokens;
@@ -71,6 +73,8 @@ pub fn expand(mut def: Def) -> proc_macro2::TokenStream {
 	let type_values = type_value::expand_type_values(&mut def);
 	let origins = origin::expand_origins(&mut def);
 	let validate_unsigned = validate_unsigned::expand_validate_unsigned(&mut def);
+	let view_functions = view_functions::expand_view_functions(&mut def);
+	let tasks = tasks::expand_tasks(&mut def);
 	let tt_default_parts = tt_default_parts::expand_tt_default_parts(&mut def);
 	let doc_only = doc_only::expand_doc_only(&mut def);
 
@@ -111,6 +115,8 @@ storage item. Otherwise, all storage items are listed among [*Type Definitions*]
 		#type_values
 		#origins
 		#validate_unsigned
+		#view_functions
+		#tasks
 		#tt_default_parts
 		#doc_only
 	);
@@ -430,6 +430,24 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 		)
 	});
 
+	let try_decode_entire_state = def.storages.iter().map(|storage| {
+		let ident = &storage.ident;
+		let gen = &def.type_use_generics(storage.attr_span);
+		let full_ident = quote::quote_spanned!(storage.attr_span => #ident<#gen> );
+
+		let cfg_attrs = &storage.cfg_attrs;
+
+		quote::quote_spanned!(storage.attr_span =>
+			#(#cfg_attrs)*
+			{
+				match <#full_ident as #frame_support::traits::TryDecodeEntireStorage>::try_decode_entire_state() {
+					Ok(bytes) => decoded = decoded.saturating_add(bytes),
+					Err(errs) => errors.extend(errs),
+				}
+			}
+		)
+	});
+
 	let getters = def.storages.iter().map(|storage| {
 		if let Some(getter) = &storage.getter {
 			let completed_where_clause =
@@ -808,6 +826,31 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 			}
 		}
 
+		#[cfg(feature = "try-runtime")]
+		impl<#type_impl_gen> #frame_support::traits::TryDecodeEntireStorage
+			for #pallet_ident<#type_use_gen>
+			#completed_where_clause
+		{
+			fn try_decode_entire_state() -> Result<
+				usize,
+				#frame_support::__private::sp_std::vec::Vec<
+					#frame_support::traits::TryDecodeEntireStorageError
+				>,
+			> {
+				#[allow(unused_mut)]
+				let mut decoded = 0usize;
+				#[allow(unused_mut)]
+				let mut errors = #frame_support::__private::sp_std::vec::Vec::new();
+				#( #try_decode_entire_state )*
+
+				if errors.is_empty() {
+					Ok(decoded)
+				} else {
+					Err(errors)
+				}
+			}
+		}
+
 		#( #getters )*
 		#( #prefix_structs )*
 		#( #on_empty_structs )*
@@ -0,0 +1,155 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::pallet::Def;
+
+/// * Generate enum `Task` with a variant for each task.
+/// * Implement trait `Task` on it.
+pub fn expand_tasks(def: &mut Def) -> proc_macro2::TokenStream {
+	let tasks = match def.tasks.as_ref() {
+		Some(tasks) => tasks,
+		None => return Default::default(),
+	};
+
+	let frame_support = &def.frame_support;
+	let span = tasks.attr_span;
+	let type_impl_gen = &def.type_impl_generics(span);
+	let type_decl_bounded_gen = &def.type_decl_bounded_generics(span);
+	let type_use_gen = &def.type_use_generics(span);
+	let pallet_ident = &def.pallet_struct.pallet;
+	let task_ident = syn::Ident::new("Task", span);
+	let capture_docs = if cfg!(feature = "no-metadata-docs") { "never" } else { "always" };
+
+	let where_clause =
+		super::merge_where_clauses(&[&def.config.where_clause, &tasks.where_clause]);
+
+	let task_name = tasks.tasks.iter().map(|task| &task.name).collect::<Vec<_>>();
+	let task_index = tasks.tasks.iter().map(|task| task.task_index).collect::<Vec<_>>();
+	let task_index_u32 = task_index.iter().map(|index| *index as u32);
+	let task_list = tasks.tasks.iter().map(|task| &task.list);
+	let task_condition = tasks.tasks.iter().map(|task| &task.condition);
+	let task_weight = tasks.tasks.iter().map(|task| &task.weight);
+	let task_doc = tasks.tasks.iter().map(|task| &task.docs).collect::<Vec<_>>();
+	let args_name = tasks
+		.tasks
+		.iter()
+		.map(|task| task.args.iter().map(|(name, _)| name).collect::<Vec<_>>())
+		.collect::<Vec<_>>();
+	let args_type = tasks
+		.tasks
+		.iter()
+		.map(|task| task.args.iter().map(|(_, ty)| ty).collect::<Vec<_>>());
+
+	// The items of `task_list` are the argument, or the tuple of the arguments if there are many.
+	let list_item_pattern = args_name.iter().map(|args_name| match &args_name[..] {
+		[arg_name] => quote::quote!(#arg_name),
+		args_name => quote::quote!(( #( #args_name ),* )),
+	});
+
+	quote::quote_spanned!(span =>
+		/// The background work of this pallet, declared in `#[pallet::tasks]`.
+		#[derive(
+			#frame_support::RuntimeDebugNoBound,
+			#frame_support::CloneNoBound,
+			#frame_support::EqNoBound,
+			#frame_support::PartialEqNoBound,
+			#frame_support::__private::codec::Encode,
+			#frame_support::__private::codec::Decode,
+			#frame_support::__private::scale_info::TypeInfo,
+		)]
+		#[codec(encode_bound())]
+		#[codec(decode_bound())]
+		#[scale_info(skip_type_params(#type_use_gen), capture_docs = #capture_docs)]
+		#[allow(non_camel_case_types)]
+		pub enum #task_ident<#type_decl_bounded_gen> #where_clause {
+			#[doc(hidden)]
+			#[codec(skip)]
+			__Ignore(
+				#frame_support::__private::sp_std::marker::PhantomData<(#type_use_gen,)>,
+				#frame_support::Never,
+			),
+			#(
+				#( #[doc = #task_doc] )*
+				#[codec(index = #task_index)]
+				#task_name {
+					#(
+						#[allow(missing_docs)]
+						#args_name: #args_type
+					),*
+				},
+			)*
+		}
+
+		impl<#type_impl_gen> #frame_support::traits::Task for #task_ident<#type_use_gen>
+			#where_clause
+		{
+			type Enumeration = #frame_support::__private::sp_std::boxed::Box<
+				dyn #frame_support::__private::sp_std::iter::Iterator<Item = Self>
+			>;
+
+			fn iter() -> Self::Enumeration {
+				// Chained lazily, so only the inspected tasks are read from the storage.
+				let tasks = #frame_support::__private::sp_std::iter::empty::<Self>();
+				#(
+					let tasks = tasks.chain(
+						(#task_list).map(|#list_item_pattern| Self::#task_name { #( #args_name ),* })
+					);
+				)*
+				#frame_support::__private::sp_std::boxed::Box::new(tasks)
+			}
+
+			fn is_valid(&self) -> bool {
+				match self {
+					#(
+						Self::#task_name { #( #args_name ),* } =>
+							(#task_condition)(#( #args_name ),*),
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			fn run(&self) -> #frame_support::dispatch::DispatchResult {
+				match self {
+					#(
+						Self::#task_name { #( #args_name ),* } =>
+							<#pallet_ident<#type_use_gen>>::#task_name(#( #args_name.clone() ),*),
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			#[allow(unused_variables)]
+			fn weight(&self) -> #frame_support::weights::Weight {
+				match self {
+					#(
+						Self::#task_name { #( #args_name ),* } => #task_weight,
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			fn task_index(&self) -> u32 {
+				match self {
+					#(
+						Self::#task_name { .. } => #task_index_u32,
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+		}
+	)
+}
@@ -79,6 +79,8 @@ pub fn expand_tt_default_parts(def: &mut Def) -> proc_macro2::TokenStream {
 		.any(|c| matches!(c.composite_keyword, CompositeKeyword::SlashReason(_)))
 		.then_some(quote::quote!(SlashReason,));
 
+	let task_part = def.tasks.as_ref().map(|_| quote::quote!(Task,));
+
 	quote::quote!(
 		// This macro follows the conventions as laid out by the `tt-call` crate. It does not
 		// accept any arguments and simply returns the pallet parts, separated by commas, then
@@ -102,7 +104,7 @@ pub fn expand_tt_default_parts(def: &mut Def) -> proc_macro2::TokenStream {
 						expanded::{
 							Pallet, #call_part #storage_part #event_part #error_part #origin_part #config_part
 							#inherent_part #validate_unsigned_part #freeze_reason_part
-							#hold_reason_part #lock_id_part #slash_reason_part
+							#hold_reason_part #lock_id_part #slash_reason_part #task_part
 						}
 					}]
 				}
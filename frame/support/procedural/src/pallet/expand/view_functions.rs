@@ -0,0 +1,122 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::pallet::Def;
+
+/// * Impl `ViewFunctionIdPrefix` and `DispatchViewFunction` for pallet.
+/// * Impl fn `pallet_view_functions_metadata` for pallet.
+pub fn expand_view_functions(def: &mut Def) -> proc_macro2::TokenStream {
+	let frame_support = &def.frame_support;
+	let span = def
+		.view_functions
+		.as_ref()
+		.map_or(def.pallet_struct.attr_span, |view_functions| view_functions.attr_span);
+	let type_impl_gen = &def.type_impl_generics(span);
+	let type_use_gen = &def.type_use_generics(span);
+	let pallet_ident = &def.pallet_struct.pallet;
+
+	let mut where_clauses = vec![&def.config.where_clause];
+	where_clauses.extend(def.view_functions.iter().map(|d| &d.where_clause));
+	let completed_where_clause = super::merge_where_clauses(&where_clauses);
+
+	let view_functions = def.view_functions.iter().flat_map(|d| &d.view_functions);
+
+	let dispatch_branches = view_functions.clone().map(|view_fn| {
+		let name = &view_fn.name;
+		let signature = syn::LitByteStr::new(view_fn.signature().as_bytes(), span);
+		let arg_names = view_fn.args.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
+		let arg_types = view_fn.args.iter().map(|(_, ty)| ty);
+
+		quote::quote_spanned!(span =>
+			if id.suffix == #frame_support::__private::sp_core_hashing_proc_macro::twox_128!(#signature) {
+				let (#( #arg_names, )*) = <(#( #arg_types, )*) as
+					#frame_support::__private::codec::DecodeAll>::decode_all(input)?;
+				let result = Self::#name(#( #arg_names, )*);
+				#frame_support::__private::codec::Encode::encode_to(&result, output);
+				return Ok(())
+			}
+		)
+	});
+
+	let view_functions_metadata = view_functions.map(|view_fn| {
+		let name_str = view_fn.name.to_string();
+		let signature = syn::LitByteStr::new(view_fn.signature().as_bytes(), span);
+		let return_type = &view_fn.return_type;
+		let inputs = view_fn.args.iter().map(|(ident, ty)| {
+			let ident_str = ident.to_string();
+			quote::quote!(
+				#frame_support::__private::metadata_ir::PalletViewFunctionParamMetadataIR {
+					name: #ident_str,
+					ty: #frame_support::__private::scale_info::meta_type::<#ty>(),
+				}
+			)
+		});
+
+		let no_docs = vec![];
+		let docs = if cfg!(feature = "no-metadata-docs") { &no_docs } else { &view_fn.docs };
+
+		quote::quote!({
+			let id = #frame_support::view_functions::ViewFunctionId {
+				prefix,
+				suffix: #frame_support::__private::sp_core_hashing_proc_macro::twox_128!(#signature),
+			};
+			#frame_support::__private::metadata_ir::PalletViewFunctionMetadataIR {
+				name: #name_str,
+				id: id.into(),
+				inputs: #frame_support::__private::sp_std::vec![ #( #inputs ),* ],
+				output: #frame_support::__private::scale_info::meta_type::<#return_type>(),
+				docs: #frame_support::__private::sp_std::vec![ #( #docs ),* ],
+			}
+		})
+	});
+
+	quote::quote_spanned!(span =>
+		impl<#type_impl_gen> #frame_support::view_functions::ViewFunctionIdPrefix
+			for #pallet_ident<#type_use_gen> #completed_where_clause
+		{
+			fn prefix() -> [u8; 16] {
+				let name = <Self as #frame_support::traits::PalletInfoAccess>::name();
+				#frame_support::__private::sp_io::hashing::twox_128(name.as_bytes())
+			}
+		}
+
+		impl<#type_impl_gen> #frame_support::view_functions::DispatchViewFunction
+			for #pallet_ident<#type_use_gen> #completed_where_clause
+		{
+			#[allow(unused_variables)]
+			fn dispatch_view_function<O: #frame_support::__private::codec::Output>(
+				id: &#frame_support::view_functions::ViewFunctionId,
+				input: &mut &[u8],
+				output: &mut O,
+			) -> Result<(), #frame_support::view_functions::ViewFunctionDispatchError> {
+				#( #dispatch_branches )*
+				Err(#frame_support::view_functions::ViewFunctionDispatchError::NotFound(*id))
+			}
+		}
+
+		impl<#type_impl_gen> #pallet_ident<#type_use_gen> #completed_where_clause {
+			#[doc(hidden)]
+			#[allow(unused_variables)]
+			pub fn pallet_view_functions_metadata()
+				-> #frame_support::__private::sp_std::vec::Vec<#frame_support::__private::metadata_ir::PalletViewFunctionMetadataIR>
+			{
+				let prefix = <Self as #frame_support::view_functions::ViewFunctionIdPrefix>::prefix();
+				#frame_support::__private::sp_std::vec![ #( #view_functions_metadata ),* ]
+			}
+		}
+	)
+}
@@ -27,6 +27,8 @@ mod keyword {
 	syn::custom_keyword!(OriginFor);
 	syn::custom_keyword!(weight);
 	syn::custom_keyword!(call_index);
+	syn::custom_keyword!(authorize);
+	syn::custom_keyword!(weight_of_authorize);
 	syn::custom_keyword!(compact);
 	syn::custom_keyword!(T);
 	syn::custom_keyword!(pallet);
@@ -82,13 +84,28 @@ pub struct CallVariantDef {
 	pub docs: Vec<syn::Expr>,
 	/// Attributes annotated at the top of the dispatchable function.
 	pub attrs: Vec<syn::Attribute>,
+	/// The authorization of the dispatchable, given with `#[pallet::authorize(..)]`.
+	pub authorize: Option<AuthorizeDef>,
+}
+
+/// Definition of the authorization of a dispatchable.
+#[derive(Clone)]
+pub struct AuthorizeDef {
+	/// Expression of the function validating the call.
+	pub expr: syn::Expr,
+	/// The weight of the validation, given with `#[pallet::weight_of_authorize(..)]`. `None` for
+	/// the default weight of dev-mode pallets.
+	pub weight: Option<syn::Expr>,
 }
 
 /// Attributes for functions in call impl block.
-/// Parse for `#[pallet::weight(expr)]` or `#[pallet::call_index(expr)]
+/// Parse for `#[pallet::weight(expr)]`, `#[pallet::call_index(expr)]`,
+/// `#[pallet::authorize(expr)]` or `#[pallet::weight_of_authorize(expr)]`
 pub enum FunctionAttr {
 	CallIndex(u8),
 	Weight(syn::Expr),
+	Authorize(syn::Expr),
+	WeightOfAuthorize(syn::Expr),
 }
 
 impl syn::parse::Parse for FunctionAttr {
@@ -105,6 +122,16 @@ impl syn::parse::Parse for FunctionAttr {
 			let weight_content;
 			syn::parenthesized!(weight_content in content);
 			Ok(FunctionAttr::Weight(weight_content.parse::<syn::Expr>()?))
+		} else if lookahead.peek(keyword::weight_of_authorize) {
+			content.parse::<keyword::weight_of_authorize>()?;
+			let weight_content;
+			syn::parenthesized!(weight_content in content);
+			Ok(FunctionAttr::WeightOfAuthorize(weight_content.parse::<syn::Expr>()?))
+		} else if lookahead.peek(keyword::authorize) {
+			content.parse::<keyword::authorize>()?;
+			let authorize_content;
+			syn::parenthesized!(authorize_content in content);
+			Ok(FunctionAttr::Authorize(authorize_content.parse::<syn::Expr>()?))
 		} else if lookahead.peek(keyword::call_index) {
 			content.parse::<keyword::call_index>()?;
 			let call_index_content;
@@ -227,16 +254,18 @@ impl CallDef {
 					return Err(syn::Error::new(method.sig.span(), msg))
 				}
 
-				let (mut weight_attrs, mut call_idx_attrs): (Vec<FunctionAttr>, Vec<FunctionAttr>) =
-					helper::take_item_pallet_attrs(&mut method.attrs)?.into_iter().partition(
-						|attr| {
-							if let FunctionAttr::Weight(_) = attr {
-								true
-							} else {
-								false
-							}
-						},
-					);
+				let mut weight_attrs = vec![];
+				let mut call_idx_attrs = vec![];
+				let mut authorize_attrs = vec![];
+				let mut authorize_weight_attrs = vec![];
+				for attr in helper::take_item_pallet_attrs(&mut method.attrs)? {
+					match attr {
+						FunctionAttr::Weight(_) => weight_attrs.push(attr),
+						FunctionAttr::CallIndex(_) => call_idx_attrs.push(attr),
+						FunctionAttr::Authorize(expr) => authorize_attrs.push(expr),
+						FunctionAttr::WeightOfAuthorize(expr) => authorize_weight_attrs.push(expr),
+					}
+				}
 
 				if weight_attrs.is_empty() && dev_mode {
 					// inject a default O(1) weight when dev mode is enabled and no weight has
@@ -275,6 +304,30 @@ impl CallDef {
 				});
 				let explicit_call_index = call_index.is_some();
 
+				if authorize_attrs.len() > 1 {
+					let msg = "Invalid pallet::call, too many authorize attributes given";
+					return Err(syn::Error::new(method.sig.span(), msg))
+				}
+				if authorize_weight_attrs.len() > 1 {
+					let msg = "Invalid pallet::call, too many weight_of_authorize attributes given";
+					return Err(syn::Error::new(method.sig.span(), msg))
+				}
+				let authorize = match (authorize_attrs.pop(), authorize_weight_attrs.pop()) {
+					(Some(expr), weight @ Some(_)) => Some(AuthorizeDef { expr, weight }),
+					(Some(expr), None) if dev_mode => Some(AuthorizeDef { expr, weight: None }),
+					(Some(_), None) => {
+						let msg = "Invalid pallet::call, a call with `#[pallet::authorize(..)]` \
+							requires a `#[pallet::weight_of_authorize($expr)]` attribute";
+						return Err(syn::Error::new(method.sig.span(), msg))
+					},
+					(None, Some(weight)) => {
+						let msg = "Invalid pallet::call, `#[pallet::weight_of_authorize(..)]` \
+							requires a `#[pallet::authorize(..)]` attribute";
+						return Err(syn::Error::new(weight.span(), msg))
+					},
+					(None, None) => None,
+				};
+
 				let final_index = match call_index {
 					Some(i) => i,
 					None =>
@@ -331,6 +384,7 @@ impl CallDef {
 					args,
 					docs,
 					attrs: method.attrs.clone(),
+					authorize,
 				});
 			} else {
 				let msg = "Invalid pallet::call, only method accepted";
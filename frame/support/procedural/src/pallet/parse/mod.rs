@@ -33,8 +33,10 @@ pub mod inherent;
 pub mod origin;
 pub mod pallet_struct;
 pub mod storage;
+pub mod tasks;
 pub mod type_value;
 pub mod validate_unsigned;
+pub mod view_functions;
 
 use composite::{keyword::CompositeKeyword, CompositeDef};
 use frame_support_procedural_tools::generate_crate_access_2018;
@@ -58,6 +60,8 @@ pub struct Def {
 	pub genesis_build: Option<genesis_build::GenesisBuildDef>,
 	pub validate_unsigned: Option<validate_unsigned::ValidateUnsignedDef>,
 	pub extra_constants: Option<extra_constants::ExtraConstantsDef>,
+	pub view_functions: Option<view_functions::ViewFunctionsImplDef>,
+	pub tasks: Option<tasks::TasksDef>,
 	pub composites: Vec<composite::CompositeDef>,
 	pub type_values: Vec<type_value::TypeValueDef>,
 	pub frame_system: syn::Ident,
@@ -92,6 +96,8 @@ impl Def {
 		let mut genesis_build = None;
 		let mut validate_unsigned = None;
 		let mut extra_constants = None;
+		let mut view_functions = None;
+		let mut tasks = None;
 		let mut storages = vec![];
 		let mut type_values = vec![];
 		let mut composites: Vec<CompositeDef> = vec![];
@@ -145,6 +151,12 @@ impl Def {
 				Some(PalletAttr::ExtraConstants(_)) =>
 					extra_constants =
 						Some(extra_constants::ExtraConstantsDef::try_from(index, item)?),
+				Some(PalletAttr::ViewFunctions(span)) if view_functions.is_none() => {
+					let v = view_functions::ViewFunctionsImplDef::try_from(span, index, item)?;
+					view_functions = Some(v);
+				},
+				Some(PalletAttr::Tasks(span)) if tasks.is_none() =>
+					tasks = Some(tasks::TasksDef::try_from(span, index, item)?),
 				Some(PalletAttr::Composite(span)) => {
 					let composite =
 						composite::CompositeDef::try_from(span, index, &frame_support, item)?;
@@ -199,6 +211,8 @@ impl Def {
 			hooks,
 			call,
 			extra_constants,
+			view_functions,
+			tasks,
 			genesis_config,
 			genesis_build,
 			validate_unsigned,
@@ -273,6 +287,12 @@ impl Def {
 		if let Some(extra_constants) = &self.extra_constants {
 			instances.extend_from_slice(&extra_constants.instances[..]);
 		}
+		if let Some(view_functions) = &self.view_functions {
+			instances.extend_from_slice(&view_functions.instances[..]);
+		}
+		if let Some(tasks) = &self.tasks {
+			instances.extend_from_slice(&tasks.instances[..]);
+		}
 
 		let mut errors = instances.into_iter().filter_map(|instances| {
 			if instances.has_instance == self.config.has_instance {
@@ -425,6 +445,8 @@ mod keyword {
 	syn::custom_keyword!(Store);
 	syn::custom_keyword!(extra_constants);
 	syn::custom_keyword!(composite_enum);
+	syn::custom_keyword!(view_functions);
+	syn::custom_keyword!(tasks);
 }
 
 /// Parse attributes for item in pallet module
@@ -482,6 +504,8 @@ enum PalletAttr {
 	TypeValue(proc_macro2::Span),
 	ExtraConstants(proc_macro2::Span),
 	Composite(proc_macro2::Span),
+	ViewFunctions(proc_macro2::Span),
+	Tasks(proc_macro2::Span),
 }
 
 impl PalletAttr {
@@ -502,6 +526,8 @@ impl PalletAttr {
 			Self::TypeValue(span) => *span,
 			Self::ExtraConstants(span) => *span,
 			Self::Composite(span) => *span,
+			Self::ViewFunctions(span) => *span,
+			Self::Tasks(span) => *span,
 		}
 	}
 }
@@ -557,6 +583,10 @@ impl syn::parse::Parse for PalletAttr {
 			Ok(PalletAttr::ExtraConstants(content.parse::<keyword::extra_constants>()?.span()))
 		} else if lookahead.peek(keyword::composite_enum) {
 			Ok(PalletAttr::Composite(content.parse::<keyword::composite_enum>()?.span()))
+		} else if lookahead.peek(keyword::view_functions) {
+			Ok(PalletAttr::ViewFunctions(content.parse::<keyword::view_functions>()?.span()))
+		} else if lookahead.peek(keyword::tasks) {
+			Ok(PalletAttr::Tasks(content.parse::<keyword::tasks>()?.span()))
 		} else {
 			Err(lookahead.error())
 		}
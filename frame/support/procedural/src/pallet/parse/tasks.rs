@@ -0,0 +1,247 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helper;
+use frame_support_procedural_tools::get_doc_literals;
+use std::collections::HashMap;
+use syn::spanned::Spanned;
+
+/// List of additional token to be used for parsing.
+mod keyword {
+	syn::custom_keyword!(pallet);
+	syn::custom_keyword!(task_index);
+	syn::custom_keyword!(task_list);
+	syn::custom_keyword!(task_condition);
+	syn::custom_keyword!(task_weight);
+}
+
+/// Definition of tasks typically `impl<T: Config> Pallet<T> { ... }`
+pub struct TasksDef {
+	/// The where_clause used.
+	pub where_clause: Option<syn::WhereClause>,
+	/// A set of usage of instance, must be check for consistency with trait.
+	pub instances: Vec<helper::InstanceUsage>,
+	/// The index of tasks item in pallet module.
+	pub index: usize,
+	/// The span of the pallet::tasks attribute.
+	pub attr_span: proc_macro2::Span,
+	/// The tasks defined.
+	pub tasks: Vec<TaskDef>,
+}
+
+/// Definition of a task typically:
+/// `#[pallet::task_index(0)] #[pallet::task_list(..)] ... fn foo(param1: ...) -> DispatchResult`
+pub struct TaskDef {
+	/// Function name.
+	pub name: syn::Ident,
+	/// Information on args: `(name, type)`
+	pub args: Vec<(syn::Ident, Box<syn::Type>)>,
+	/// The index of the task, used for the encoding of the task.
+	pub task_index: u8,
+	/// Expression of the iterator over the arguments of the pending tasks.
+	pub list: syn::Expr,
+	/// Expression of the closure checking the arguments of a task are valid.
+	pub condition: syn::Expr,
+	/// Expression of the weight of the task.
+	pub weight: syn::Expr,
+	/// Docs, used for metadata.
+	pub docs: Vec<syn::Expr>,
+}
+
+/// Attributes for functions in tasks impl block.
+pub enum TaskAttr {
+	/// Parse for `#[pallet::task_index(expr)]`
+	Index(u8),
+	/// Parse for `#[pallet::task_list(expr)]`
+	List(syn::Expr),
+	/// Parse for `#[pallet::task_condition(expr)]`
+	Condition(syn::Expr),
+	/// Parse for `#[pallet::task_weight(expr)]`
+	Weight(syn::Expr),
+}
+
+impl syn::parse::Parse for TaskAttr {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		input.parse::<syn::Token![#]>()?;
+		let content;
+		syn::bracketed!(content in input);
+		content.parse::<keyword::pallet>()?;
+		content.parse::<syn::Token![::]>()?;
+
+		let lookahead = content.lookahead1();
+		if lookahead.peek(keyword::task_index) {
+			content.parse::<keyword::task_index>()?;
+			let index_content;
+			syn::parenthesized!(index_content in content);
+			let index = index_content.parse::<syn::LitInt>()?;
+			if !index.suffix().is_empty() {
+				let msg = "Number literal must not have a suffix";
+				return Err(syn::Error::new(index.span(), msg))
+			}
+			Ok(TaskAttr::Index(index.base10_parse()?))
+		} else if lookahead.peek(keyword::task_list) {
+			content.parse::<keyword::task_list>()?;
+			let list_content;
+			syn::parenthesized!(list_content in content);
+			Ok(TaskAttr::List(list_content.parse::<syn::Expr>()?))
+		} else if lookahead.peek(keyword::task_condition) {
+			content.parse::<keyword::task_condition>()?;
+			let condition_content;
+			syn::parenthesized!(condition_content in content);
+			Ok(TaskAttr::Condition(condition_content.parse::<syn::Expr>()?))
+		} else if lookahead.peek(keyword::task_weight) {
+			content.parse::<keyword::task_weight>()?;
+			let weight_content;
+			syn::parenthesized!(weight_content in content);
+			Ok(TaskAttr::Weight(weight_content.parse::<syn::Expr>()?))
+		} else {
+			Err(lookahead.error())
+		}
+	}
+}
+
+impl TasksDef {
+	pub fn try_from(
+		attr_span: proc_macro2::Span,
+		index: usize,
+		item: &mut syn::Item,
+	) -> syn::Result<Self> {
+		let item = if let syn::Item::Impl(item) = item {
+			item
+		} else {
+			return Err(syn::Error::new(item.span(), "Invalid pallet::tasks, expected item impl"))
+		};
+
+		let instances = vec![
+			helper::check_impl_gen(&item.generics, item.impl_token.span())?,
+			helper::check_pallet_struct_usage(&item.self_ty)?,
+		];
+
+		if let Some((_, _, for_)) = item.trait_ {
+			let msg = "Invalid pallet::tasks, expected no trait ident as in \
+				`impl<..> Pallet<..> { .. }`";
+			return Err(syn::Error::new(for_.span(), msg))
+		}
+
+		let mut tasks = vec![];
+		let mut indices = HashMap::new();
+		for impl_item in &mut item.items {
+			let method = if let syn::ImplItem::Fn(method) = impl_item {
+				method
+			} else {
+				let msg = "Invalid pallet::tasks, only method accepted";
+				return Err(syn::Error::new(impl_item.span(), msg))
+			};
+
+			if !method.sig.generics.params.is_empty() {
+				let msg = "Invalid pallet::tasks, method must have 0 generics";
+				return Err(syn::Error::new(method.sig.generics.params[0].span(), msg))
+			}
+
+			if let syn::ReturnType::Default = method.sig.output {
+				let msg = "Invalid pallet::tasks, require return type DispatchResult";
+				return Err(syn::Error::new(method.sig.span(), msg))
+			}
+
+			let mut args = vec![];
+			for input in &method.sig.inputs {
+				let arg = match input {
+					syn::FnArg::Typed(arg) => arg,
+					syn::FnArg::Receiver(_) => {
+						let msg = "Invalid pallet::tasks, method must not have a receiver";
+						return Err(syn::Error::new(input.span(), msg))
+					},
+				};
+
+				let ident = if let syn::Pat::Ident(pat) = &*arg.pat {
+					pat.ident.clone()
+				} else {
+					let msg = "Invalid pallet::tasks, argument must be ident";
+					return Err(syn::Error::new(arg.pat.span(), msg))
+				};
+
+				args.push((ident, arg.ty.clone()));
+			}
+
+			let mut task_index = None;
+			let mut list = None;
+			let mut condition = None;
+			let mut weight = None;
+			for attr in helper::take_item_pallet_attrs(&mut method.attrs)? {
+				let (slot, attr_name) = match attr {
+					TaskAttr::Index(idx) => (task_index.replace(idx).map(|_| ()), "task_index"),
+					TaskAttr::List(expr) => (list.replace(expr).map(|_| ()), "task_list"),
+					TaskAttr::Condition(expr) =>
+						(condition.replace(expr).map(|_| ()), "task_condition"),
+					TaskAttr::Weight(expr) => (weight.replace(expr).map(|_| ()), "task_weight"),
+				};
+				if slot.is_some() {
+					let msg = format!(
+						"Invalid pallet::tasks, too many `#[pallet::{}]` attributes given",
+						attr_name,
+					);
+					return Err(syn::Error::new(method.sig.span(), msg))
+				}
+			}
+
+			let missing = |attr_name: &str| {
+				let msg = format!(
+					"Invalid pallet::tasks, requires `#[pallet::{}(..)]` attribute",
+					attr_name,
+				);
+				syn::Error::new(method.sig.span(), msg)
+			};
+			let task_index = task_index.ok_or_else(|| missing("task_index"))?;
+			let list = list.ok_or_else(|| missing("task_list"))?;
+			let condition = condition.ok_or_else(|| missing("task_condition"))?;
+			let weight = weight.ok_or_else(|| missing("task_weight"))?;
+
+			if let Some(used_fn) = indices.insert(task_index, method.sig.ident.clone()) {
+				let msg = format!(
+					"Task indices are conflicting: Both functions {} and {} are at index {}",
+					used_fn, method.sig.ident, task_index,
+				);
+				let mut err = syn::Error::new(used_fn.span(), &msg);
+				err.combine(syn::Error::new(method.sig.ident.span(), msg));
+				return Err(err)
+			}
+
+			if let Some(cfg_attr) = helper::get_item_cfg_attrs(&method.attrs).first() {
+				let msg = "Invalid pallet::tasks, `#[cfg]` attributes are not supported on tasks";
+				return Err(syn::Error::new(cfg_attr.span(), msg))
+			}
+
+			tasks.push(TaskDef {
+				name: method.sig.ident.clone(),
+				args,
+				task_index,
+				list,
+				condition,
+				weight,
+				docs: get_doc_literals(&method.attrs),
+			});
+		}
+
+		Ok(Self {
+			where_clause: item.generics.where_clause.clone(),
+			instances,
+			index,
+			attr_span,
+			tasks,
+		})
+	}
+}
@@ -0,0 +1,150 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helper;
+use frame_support_procedural_tools::get_doc_literals;
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+/// Definition of view functions typically `impl<T: Config> Pallet<T> { ... }`
+pub struct ViewFunctionsImplDef {
+	/// The where_clause used.
+	pub where_clause: Option<syn::WhereClause>,
+	/// A set of usage of instance, must be check for consistency with trait.
+	pub instances: Vec<helper::InstanceUsage>,
+	/// The index of view functions item in pallet module.
+	pub index: usize,
+	/// The span of the pallet::view_functions attribute.
+	pub attr_span: proc_macro2::Span,
+	/// The view functions defined.
+	pub view_functions: Vec<ViewFunctionDef>,
+}
+
+/// Definition of a view function.
+pub struct ViewFunctionDef {
+	/// Name of the function.
+	pub name: syn::Ident,
+	/// The doc associated.
+	pub docs: Vec<syn::Expr>,
+	/// The arguments of the function with their name and type.
+	pub args: Vec<(syn::Ident, Box<syn::Type>)>,
+	/// The type returned by the function.
+	pub return_type: syn::Type,
+}
+
+impl ViewFunctionDef {
+	/// The signature of the view function, e.g. `get_value(u32) -> Option<u32>`.
+	///
+	/// Its hash identifies the view function within the pallet.
+	pub fn signature(&self) -> String {
+		let arg_types = self
+			.args
+			.iter()
+			.map(|(_, ty)| ty.to_token_stream().to_string().replace(' ', ""))
+			.collect::<Vec<_>>()
+			.join(",");
+		let return_type = self.return_type.to_token_stream().to_string().replace(' ', "");
+		format!("{}({}) -> {}", self.name, arg_types, return_type)
+	}
+}
+
+impl ViewFunctionsImplDef {
+	pub fn try_from(
+		attr_span: proc_macro2::Span,
+		index: usize,
+		item: &mut syn::Item,
+	) -> syn::Result<Self> {
+		let item = if let syn::Item::Impl(item) = item {
+			item
+		} else {
+			return Err(syn::Error::new(
+				item.span(),
+				"Invalid pallet::view_functions, expected item impl",
+			))
+		};
+
+		let instances = vec![
+			helper::check_impl_gen(&item.generics, item.impl_token.span())?,
+			helper::check_pallet_struct_usage(&item.self_ty)?,
+		];
+
+		if let Some((_, _, for_)) = item.trait_ {
+			let msg = "Invalid pallet::view_functions, expected no trait ident as in \
+				`impl<..> Pallet<..> { .. }`";
+			return Err(syn::Error::new(for_.span(), msg))
+		}
+
+		let mut view_functions = vec![];
+		for impl_item in &item.items {
+			let method = if let syn::ImplItem::Fn(method) = impl_item {
+				method
+			} else {
+				let msg = "Invalid pallet::view_functions, only method accepted";
+				return Err(syn::Error::new(impl_item.span(), msg))
+			};
+
+			if !method.sig.generics.params.is_empty() {
+				let msg = "Invalid pallet::view_functions, method must have 0 generics";
+				return Err(syn::Error::new(method.sig.generics.params[0].span(), msg))
+			}
+
+			let mut args = vec![];
+			for input in &method.sig.inputs {
+				let arg = match input {
+					syn::FnArg::Typed(arg) => arg,
+					syn::FnArg::Receiver(_) => {
+						let msg = "Invalid pallet::view_functions, method must not have a \
+							receiver as view functions are called without an instance";
+						return Err(syn::Error::new(input.span(), msg))
+					},
+				};
+
+				let ident = if let syn::Pat::Ident(pat) = &*arg.pat {
+					pat.ident.clone()
+				} else {
+					let msg = "Invalid pallet::view_functions, argument must be ident";
+					return Err(syn::Error::new(arg.pat.span(), msg))
+				};
+
+				args.push((ident, arg.ty.clone()));
+			}
+
+			let return_type = match &method.sig.output {
+				syn::ReturnType::Default => {
+					let msg = "Invalid pallet::view_functions, method must have a return type";
+					return Err(syn::Error::new(method.sig.span(), msg))
+				},
+				syn::ReturnType::Type(_, type_) => *type_.clone(),
+			};
+
+			view_functions.push(ViewFunctionDef {
+				name: method.sig.ident.clone(),
+				docs: get_doc_literals(&method.attrs),
+				args,
+				return_type,
+			});
+		}
+
+		Ok(Self {
+			where_clause: item.generics.where_clause.clone(),
+			instances,
+			index,
+			attr_span,
+			view_functions,
+		})
+	}
+}
@@ -57,6 +57,20 @@ pub type DispatchResult = Result<(), sp_runtime::DispatchError>;
 /// The error type contained in a `DispatchResultWithPostInfo`.
 pub type DispatchErrorWithPostInfo = sp_runtime::DispatchErrorWithPostInfo<PostDispatchInfo>;
 
+/// The effects of dry-running an extrinsic or a call on top of the current state.
+///
+/// `R` is the result of applying the extrinsic, or of dispatching the call.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct DryRunEffects<R, Event> {
+	/// The result of the dry-run.
+	pub result: R,
+	/// The events deposited during the dry-run.
+	pub events: Vec<Event>,
+	/// The messages forwarded to other consensus systems during the dry-run, as the encoded
+	/// destination and the encoded messages sent to it.
+	pub forwarded_messages: Vec<(Vec<u8>, Vec<Vec<u8>>)>,
+}
+
 /// Serializable version of pallet dispatchable.
 pub trait Callable<T> {
 	type RuntimeCall: UnfilteredDispatchable + Codec + Clone + PartialEq + Eq;
@@ -77,6 +91,9 @@ pub enum RawOrigin<AccountId> {
 	/// * included and agreed upon by the validators anyway,
 	/// * or unsigned transaction validated by a pallet.
 	None,
+	/// It is signed by nobody, but authorized by the `#[pallet::authorize]` of the dispatched
+	/// call.
+	Authorized,
 }
 
 impl<AccountId> From<Option<AccountId>> for RawOrigin<AccountId> {
@@ -106,6 +123,35 @@ impl<AccountId> RawOrigin<AccountId> {
 	pub fn is_none(&self) -> bool {
 		matches!(&self, Self::None)
 	}
+
+	/// Returns `true` if `self` is `Authorized`, `false` otherwise.
+	pub fn is_authorized(&self) -> bool {
+		matches!(&self, Self::Authorized)
+	}
+}
+
+/// Grants the [`RawOrigin::Authorized`] origin to the next authorized call dispatched with the
+/// `None` origin in the current [dispatch context](crate::dispatch_context).
+///
+/// `frame-executive` grants it once the [`Authorize::authorize`](crate::traits::Authorize) of an
+/// unsigned transaction succeeded. Calls dispatched with the `None` origin anywhere else, or
+/// outside of a dispatch context, are not authorized.
+#[derive(Default)]
+pub struct AuthorizedDispatch(bool);
+
+impl AuthorizedDispatch {
+	/// Grant the authorized origin in the current dispatch context.
+	pub fn grant() {
+		crate::dispatch_context::with_context::<Self, _>(|v| v.or_default().0 = true);
+	}
+
+	/// Take the grant of the current dispatch context, returns whether it was granted.
+	pub fn take() -> bool {
+		crate::dispatch_context::with_context::<Self, _>(|v| {
+			sp_std::mem::take(&mut v.or_default().0)
+		})
+		.unwrap_or(false)
+	}
 }
 
 /// A type that can be used as a parameter in a dispatchable function.
@@ -69,6 +69,7 @@ pub mod storage;
 #[cfg(test)]
 mod tests;
 pub mod traits;
+pub mod view_functions;
 pub mod weights;
 #[doc(hidden)]
 pub mod unsigned {
@@ -920,6 +921,8 @@ pub mod pallet_prelude {
 /// * [`pallet::validate_unsigned`](#validate-unsigned-palletvalidate_unsigned-optional)
 /// * [`pallet::origin`](#origin-palletorigin-optional)
 /// * [`pallet::composite_enum`](#composite-enum-palletcomposite_enum-optional)
+/// * [`pallet::view_functions`](#view-functions-palletview_functions-optional)
+/// * [`pallet::tasks`](#tasks-pallettasks-optional)
 ///
 /// Note that at compile-time, the `#[pallet]` macro will analyze and expand all of these
 /// attributes, ultimately removing their AST nodes before they can be parsed as real
@@ -1208,6 +1211,38 @@ pub mod pallet_prelude {
 ///
 /// Also see [`pallet::call_index`](`frame_support::pallet_macros::call_index`)
 ///
+/// ## `#[pallet::authorize($expr)]`
+///
+/// A dispatchable annotated with `#[pallet::authorize($expr)]` can be submitted as an unsigned
+/// transaction without a `#[pallet::validate_unsigned]`. The expression is a function taking the
+/// [`TransactionSource`](`pallet_prelude::TransactionSource`) and references to the arguments of
+/// the dispatchable, which returns a
+/// [`TransactionValidity`](`pallet_prelude::TransactionValidity`). The weight of the function is
+/// given with `#[pallet::weight_of_authorize($expr)]` and added to the weight of the
+/// dispatchable:
+///
+/// ```ignore
+/// #[pallet::weight(T::WeightInfo::claim())]
+/// #[pallet::authorize(|_source: TransactionSource, who: &T::AccountId| {
+/// 	ensure!(Claims::<T>::contains_key(who), InvalidTransaction::Call);
+/// 	ValidTransaction::with_tag_prefix("Claim").and_provides(who).build()
+/// })]
+/// #[pallet::weight_of_authorize(T::WeightInfo::authorize_claim())]
+/// pub fn claim(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+/// 	ensure_authorized(origin)?;
+/// 	...
+/// }
+/// ```
+///
+/// `frame-executive` validates unsigned transactions with the authorization of their call before
+/// falling back to the `ValidateUnsigned` of the runtime. Once the authorization succeeded, the
+/// executive grants the `Authorized` origin through
+/// [`AuthorizedDispatch`](`dispatch::AuthorizedDispatch`) and the dispatchable is dispatched with
+/// it instead of the `None` origin, checked with `frame_system::ensure_authorized`. Dispatching
+/// the call with the `None` origin anywhere else doesn't authorize it.
+///
+/// Also see [`pallet::authorize`](`frame_support::pallet_macros::authorize`)
+///
 /// # Extra constants: `#[pallet::extra_constants]` (optional)
 ///
 /// Allows you to define some extra constants to be added into constant metadata.
@@ -1486,6 +1521,10 @@ pub mod pallet_prelude {
 /// * for a storage double map, the type of the values, and the types of `key1` and `key2` are
 ///   copied into the metadata.
 ///
+/// With the `try-runtime` feature, the macro also implements `TryDecodeEntireStorage` on the
+/// `Pallet`, decoding all the values of all storage items into their declared type and reporting
+/// the keys of the values that fail to decode.
+///
 /// # Type value: `#[pallet::type_value]` (optional)
 ///
 /// The `#[pallet::type_value]` attribute lets you define a struct implementing the
@@ -1655,6 +1694,73 @@ pub mod pallet_prelude {
 /// The inverse is also true: if there are any #[derive] attributes present for the enum, then
 /// the attribute will not automatically derive any of the traits described above.
 ///
+/// # View functions: `#[pallet::view_functions]` (optional)
+///
+/// Allows you to define read-only queries of the pallet state which can be executed through the
+/// generic `RuntimeViewFunction` runtime API, without a dedicated runtime API per query.
+///
+/// Item must be defined as:
+///
+/// ```ignore
+/// #[pallet::view_functions]
+/// impl<T: Config> Pallet<T> where $optional_where_clause {
+/// 	/// $some_doc
+/// 	$vis fn $fn_name($arg: $arg_type, ...) -> $some_return_type {
+/// 		...
+/// 	}
+/// 	...
+/// }
+/// ```
+/// I.e. a regular rust `impl` block with some optional where clause and functions with 0
+/// generics, no receiver and some return type.
+///
+/// ## Macro expansion
+///
+/// The macro adds the view functions to the pallet metadata and implements
+/// [`DispatchViewFunction`](view_functions::DispatchViewFunction) for the pallet. A view
+/// function is executed by its [`ViewFunctionId`](view_functions::ViewFunctionId) and the
+/// SCALE-encoded tuple of its arguments. `construct_runtime!` dispatches the view functions of
+/// all pallets, see [`view_functions`].
+///
+/// Also see [`pallet::view_functions`](`frame_support::pallet_macros::view_functions`)
+///
+/// # Tasks: `#[pallet::tasks]` (optional)
+///
+/// Allows you to declare background work of the pallet, e.g. cleaning up expired entries, which
+/// anybody can execute once it is valid, instead of doing it in hooks or in dedicated calls.
+///
+/// Item must be defined as:
+///
+/// ```ignore
+/// #[pallet::tasks]
+/// impl<T: Config> Pallet<T> where $optional_where_clause {
+/// 	/// $some_doc
+/// 	#[pallet::task_index($index)]
+/// 	#[pallet::task_list($iterator_over_args)]
+/// 	#[pallet::task_condition($closure_checking_args)]
+/// 	#[pallet::task_weight($weight)]
+/// 	$vis fn $fn_name($arg: $arg_type, ...) -> DispatchResult {
+/// 		...
+/// 	}
+/// 	...
+/// }
+/// ```
+///
+/// `task_list` is an iterator over the arguments of the pending tasks, which must be `'static`
+/// and is consumed lazily, e.g. `StorageMap::iter_keys()`. `task_condition` is a closure checking
+/// whether a task can be run, called with references to its arguments, and `task_weight` the
+/// weight of running the task.
+///
+/// ## Macro expansion
+///
+/// The macro generates the enum `Task` with a variant for each function and implements
+/// [`traits::Task`] on it. `construct_runtime!` aggregates the `Task` enums of all pallets into
+/// the `RuntimeTask` enum, which is also part of the metadata. The tasks are run by
+/// `pallet-tasks`, either in `on_idle`, from unsigned transactions submitted by its offchain
+/// worker or by anyone through its `do_task` call.
+///
+/// Also see [`pallet::tasks`](`frame_support::pallet_macros::tasks`)
+///
 /// # General notes on instantiable pallets
 ///
 /// An instantiable pallet is one where Config is generic, i.e. `Config<I>`. This allows
@@ -2188,11 +2294,13 @@ pub use frame_support_procedural::pallet;
 /// Contains macro stubs for all of the pallet:: macros
 pub mod pallet_macros {
 	pub use frame_support_procedural::{
-		call_index, compact, composite_enum, config, constant,
+		authorize, call_index, compact, composite_enum, config, constant,
 		disable_frame_system_supertrait_check, error, event, extra_constants, generate_deposit,
 		generate_store, genesis_build, genesis_config, getter, hooks, import_section, inherent,
 		no_default, no_default_bounds, origin, pallet_section, storage, storage_prefix,
-		storage_version, type_value, unbounded, validate_unsigned, weight, whitelist_storage,
+		storage_version, task_condition, task_index, task_list, task_weight, tasks, type_value,
+		unbounded, validate_unsigned, view_functions, weight, weight_of_authorize,
+		whitelist_storage,
 	};
 }
 
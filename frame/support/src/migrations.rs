@@ -16,13 +16,17 @@
 // limitations under the License.
 
 use crate::{
+	storage::transactional::with_transaction,
 	traits::{GetStorageVersion, NoStorageVersionSet, PalletInfoAccess, StorageVersion},
-	weights::{RuntimeDbWeight, Weight},
+	weights::{RuntimeDbWeight, Weight, WeightMeter},
 };
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use impl_trait_for_tuples::impl_for_tuples;
+use scale_info::TypeInfo;
 use sp_core::Get;
 use sp_io::{hashing::twox_128, storage::clear_prefix, KillStorageResult};
-use sp_std::marker::PhantomData;
+use sp_runtime::{DispatchError, TransactionOutcome};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 /// EXPERIMENTAL: The API of this feature may change.
 ///
@@ -105,7 +109,6 @@ impl<
 	/// migration ran or not.
 	#[cfg(feature = "try-runtime")]
 	fn pre_upgrade() -> Result<sp_std::vec::Vec<u8>, sp_runtime::TryRuntimeError> {
-		use codec::Encode;
 		let on_chain_version = Pallet::on_chain_storage_version();
 		if on_chain_version == FROM {
 			Ok(VersionedPostUpgradeData::MigrationExecuted(Inner::pre_upgrade()?).encode())
@@ -349,3 +352,236 @@ impl<P: Get<&'static str>, DbWeight: Get<RuntimeDbWeight>> frame_support::traits
 		Ok(())
 	}
 }
+
+/// Error of a [`SteppedMigration`] step.
+#[derive(Debug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
+pub enum SteppedMigrationError {
+	/// The remaining weight is not enough to do anything.
+	///
+	/// The step is retried in the next block, unless `required` can never fit into a block. No
+	/// storage changes are allowed.
+	InsufficientWeight {
+		/// The weight needed to make progress.
+		required: Weight,
+	},
+	/// The cursor passed to the step is invalid.
+	InvalidCursor,
+	/// The migration failed and can't be continued.
+	Failed,
+}
+
+impl From<DispatchError> for SteppedMigrationError {
+	fn from(_: DispatchError) -> Self {
+		Self::Failed
+	}
+}
+
+/// EXPERIMENTAL: The API of this feature may change.
+///
+/// A migration that is executed in steps over as many blocks as needed.
+///
+/// Every step is given a `cursor` and a [`WeightMeter`]. It should make as much progress as the
+/// meter allows and return the cursor of the next step, or `None` once the migration is
+/// complete. The first step of a migration is called with a `None` cursor.
+///
+/// Migrations are driven by a [`MultiStepMigrator`], e.g. from the `frame-executive`.
+pub trait SteppedMigration {
+	/// The cursor remembering the progress of the migration between steps.
+	type Cursor: FullCodec + MaxEncodedLen;
+
+	/// The unique identifier of the migration.
+	///
+	/// A migration with the same identifier is only executed once.
+	type Identifier: FullCodec + MaxEncodedLen;
+
+	/// The identifier of this migration.
+	fn id() -> Self::Identifier;
+
+	/// The maximum number of steps the migration is allowed to take.
+	///
+	/// The migration is considered failed if it takes more steps. `None` for no limit.
+	fn max_steps() -> Option<u32> {
+		None
+	}
+
+	/// Do the next step of the migration.
+	///
+	/// Must not consume more weight than available in `meter`. The storage changes of a failed
+	/// step may be persisted, use [`Self::transactional_step`] to discard them.
+	fn step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError>;
+
+	/// Same as [`Self::step`], but discards the storage changes of a failed step.
+	fn transactional_step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		with_transaction(|| match Self::step(cursor, meter) {
+			Ok(cursor) => TransactionOutcome::Commit(Ok(cursor)),
+			Err(err) => TransactionOutcome::Rollback(Err(err)),
+		})
+	}
+}
+
+/// An ordered list of [`SteppedMigration`]s with their cursors and identifiers in encoded form.
+///
+/// Implemented for tuples of [`SteppedMigration`]s.
+pub trait SteppedMigrations {
+	/// The number of migrations.
+	fn len() -> u32;
+
+	/// The maximum [`MaxEncodedLen`] of the [`SteppedMigration::Cursor`]s.
+	fn cursor_max_encoded_len() -> usize;
+
+	/// The maximum [`MaxEncodedLen`] of the [`SteppedMigration::Identifier`]s.
+	fn identifier_max_encoded_len() -> usize;
+
+	/// The encoded [`SteppedMigration::id`] of the `n`th migration.
+	///
+	/// `None` if there is no `n`th migration, as for all the other functions.
+	fn nth_id(n: u32) -> Option<Vec<u8>>;
+
+	/// The [`SteppedMigration::max_steps`] of the `n`th migration.
+	fn nth_max_steps(n: u32) -> Option<Option<u32>>;
+
+	/// Do a [`SteppedMigration::transactional_step`] of the `n`th migration with an encoded
+	/// cursor.
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>>;
+}
+
+type EncodedStep =
+	fn(Option<Vec<u8>>, &mut WeightMeter) -> Result<Option<Vec<u8>>, SteppedMigrationError>;
+
+fn encoded_id<M: SteppedMigration>() -> Vec<u8> {
+	M::id().encode()
+}
+
+fn encoded_transactional_step<M: SteppedMigration>(
+	cursor: Option<Vec<u8>>,
+	meter: &mut WeightMeter,
+) -> Result<Option<Vec<u8>>, SteppedMigrationError> {
+	let cursor = cursor
+		.map(|cursor| M::Cursor::decode(&mut &cursor[..]))
+		.transpose()
+		.map_err(|_| SteppedMigrationError::InvalidCursor)?;
+
+	M::transactional_step(cursor, meter).map(|cursor| cursor.map(|cursor| cursor.encode()))
+}
+
+#[impl_for_tuples(30)]
+#[tuple_types_custom_trait_bound(SteppedMigration)]
+impl SteppedMigrations for Tuple {
+	fn len() -> u32 {
+		let ids: &[fn() -> Vec<u8>] = &[for_tuples!( #( encoded_id::<Tuple> ),* )];
+		ids.len() as u32
+	}
+
+	fn cursor_max_encoded_len() -> usize {
+		let lens: &[usize] =
+			&[for_tuples!( #( <Tuple::Cursor as MaxEncodedLen>::max_encoded_len() ),* )];
+		lens.iter().copied().max().unwrap_or_default()
+	}
+
+	fn identifier_max_encoded_len() -> usize {
+		let lens: &[usize] =
+			&[for_tuples!( #( <Tuple::Identifier as MaxEncodedLen>::max_encoded_len() ),* )];
+		lens.iter().copied().max().unwrap_or_default()
+	}
+
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		let ids: &[fn() -> Vec<u8>] = &[for_tuples!( #( encoded_id::<Tuple> ),* )];
+		ids.get(n as usize).map(|id| id())
+	}
+
+	fn nth_max_steps(n: u32) -> Option<Option<u32>> {
+		let max_steps: &[fn() -> Option<u32>] = &[for_tuples!( #( Tuple::max_steps ),* )];
+		max_steps.get(n as usize).map(|max_steps| max_steps())
+	}
+
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		let steps: &[EncodedStep] = &[for_tuples!( #( encoded_transactional_step::<Tuple> ),* )];
+		steps.get(n as usize).map(|step| step(cursor, meter))
+	}
+}
+
+/// Drives [`SteppedMigrations`] over multiple blocks.
+///
+/// `frame-executive` calls [`Self::step`] in every block while [`Self::ongoing`] and only applies
+/// inherents in the meantime.
+pub trait MultiStepMigrator {
+	/// Whether migrations are ongoing.
+	fn ongoing() -> bool;
+
+	/// Do the next steps of the ongoing migrations and return the consumed weight.
+	fn step() -> Weight;
+}
+
+impl MultiStepMigrator for () {
+	fn ongoing() -> bool {
+		false
+	}
+
+	fn step() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Notified about the status of multi-block migrations.
+pub trait MigrationStatusHandler {
+	/// Called once the migrations started, before the first step.
+	fn started() {}
+
+	/// Called once all migrations completed.
+	fn completed() {}
+}
+
+#[impl_for_tuples(8)]
+impl MigrationStatusHandler for Tuple {
+	fn started() {
+		for_tuples!( #( Tuple::started(); )* );
+	}
+
+	fn completed() {
+		for_tuples!( #( Tuple::completed(); )* );
+	}
+}
+
+/// How to proceed after a multi-block migration failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailedMigrationHandling {
+	/// Keep [`MultiStepMigrator::ongoing`], which suspends all transactions until a governance
+	/// intervention.
+	///
+	/// The runtime must let the governance calls through while migrations are ongoing, e.g. with
+	/// the `MigrationCallFilter` of `frame-executive`, or the chain can't be unstuck.
+	KeepStuck,
+	/// Resume normal operation, as if the migrations had completed.
+	///
+	/// Only safe if the remaining migrations can be skipped or are harmless when half done.
+	ForceUnstuck,
+}
+
+/// Decides how to proceed after a multi-block migration failed.
+pub trait FailedMigrationHandler {
+	/// Called when the `migration` at the given index failed.
+	fn failed(migration: Option<u32>) -> FailedMigrationHandling;
+}
+
+/// Keeps the chain stuck after a failed migration, see [`FailedMigrationHandling::KeepStuck`].
+pub struct FreezeChainOnFailedMigration;
+
+impl FailedMigrationHandler for FreezeChainOnFailedMigration {
+	fn failed(_migration: Option<u32>) -> FailedMigrationHandling {
+		FailedMigrationHandling::KeepStuck
+	}
+}
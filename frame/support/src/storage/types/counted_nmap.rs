@@ -631,6 +631,23 @@ where
 	}
 }
 
+#[cfg(feature = "try-runtime")]
+impl<Prefix, Key, Value, QueryKind, OnEmpty, MaxValues> crate::traits::TryDecodeEntireStorage
+	for CountedStorageNMap<Prefix, Key, Value, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: CountedStorageNMapInstance,
+	Key: super::key::KeyGenerator,
+	Value: FullCodec,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn try_decode_entire_state() -> Result<usize, Vec<crate::traits::TryDecodeEntireStorageError>> {
+		use crate::traits::TryDecodeEntireStorage;
+		<(<Self as MapWrapper>::Map, CounterFor<Prefix>)>::try_decode_entire_state()
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
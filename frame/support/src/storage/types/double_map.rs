@@ -731,6 +731,30 @@ where
 	}
 }
 
+#[cfg(feature = "try-runtime")]
+impl<Prefix, Hasher1, Hasher2, Key1, Key2, Value, QueryKind, OnEmpty, MaxValues>
+	crate::traits::TryDecodeEntireStorage
+	for StorageDoubleMap<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: StorageInstance,
+	Hasher1: crate::hash::StorageHasher,
+	Hasher2: crate::hash::StorageHasher,
+	Key1: FullCodec,
+	Key2: FullCodec,
+	Value: FullCodec,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn try_decode_entire_state() -> Result<usize, Vec<crate::traits::TryDecodeEntireStorageError>> {
+		crate::traits::decode_storage_prefix::<Value>(
+			Prefix::pallet_prefix(),
+			Prefix::STORAGE_PREFIX,
+			&Self::final_prefix(),
+		)
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -506,6 +506,28 @@ where
 	}
 }
 
+#[cfg(feature = "try-runtime")]
+impl<Prefix, Hasher, Key, Value, QueryKind, OnEmpty, MaxValues>
+	crate::traits::TryDecodeEntireStorage
+	for StorageMap<Prefix, Hasher, Key, Value, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: StorageInstance,
+	Hasher: crate::hash::StorageHasher,
+	Key: FullCodec,
+	Value: FullCodec,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn try_decode_entire_state() -> Result<usize, Vec<crate::traits::TryDecodeEntireStorageError>> {
+		crate::traits::decode_storage_prefix::<Value>(
+			Prefix::pallet_prefix(),
+			Prefix::STORAGE_PREFIX,
+			&Self::final_prefix(),
+		)
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
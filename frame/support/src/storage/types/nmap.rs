@@ -615,6 +615,26 @@ where
 		}]
 	}
 }
+
+#[cfg(feature = "try-runtime")]
+impl<Prefix, Key, Value, QueryKind, OnEmpty, MaxValues> crate::traits::TryDecodeEntireStorage
+	for StorageNMap<Prefix, Key, Value, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: StorageInstance,
+	Key: super::key::KeyGenerator,
+	Value: FullCodec,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn try_decode_entire_state() -> Result<usize, Vec<crate::traits::TryDecodeEntireStorageError>> {
+		crate::traits::decode_storage_prefix::<Value>(
+			Prefix::pallet_prefix(),
+			Prefix::STORAGE_PREFIX,
+			&Self::final_prefix(),
+		)
+	}
+}
 #[cfg(test)]
 mod test {
 	use super::*;
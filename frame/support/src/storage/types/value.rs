@@ -280,6 +280,24 @@ where
 	}
 }
 
+#[cfg(feature = "try-runtime")]
+impl<Prefix, Value, QueryKind, OnEmpty> crate::traits::TryDecodeEntireStorage
+	for StorageValue<Prefix, Value, QueryKind, OnEmpty>
+where
+	Prefix: StorageInstance,
+	Value: FullCodec,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: crate::traits::Get<QueryKind::Query> + 'static,
+{
+	fn try_decode_entire_state() -> Result<usize, Vec<crate::traits::TryDecodeEntireStorageError>> {
+		crate::traits::decode_storage_key::<Value>(
+			Prefix::pallet_prefix(),
+			Prefix::STORAGE_PREFIX,
+			&Self::hashed_key(),
+		)
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
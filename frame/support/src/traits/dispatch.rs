@@ -21,9 +21,11 @@ use crate::dispatch::{DispatchResultWithPostInfo, Parameter, RawOrigin};
 use codec::MaxEncodedLen;
 use sp_runtime::{
 	traits::{BadOrigin, Get, Member, Morph, TryMorph},
+	transaction_validity::{TransactionSource, TransactionValidity},
 	Either,
 };
 use sp_std::{cmp::Ordering, marker::PhantomData};
+use sp_weights::Weight;
 
 use super::misc;
 
@@ -449,6 +451,20 @@ pub trait UnfilteredDispatchable {
 	fn dispatch_bypass_filter(self, origin: Self::RuntimeOrigin) -> DispatchResultWithPostInfo;
 }
 
+/// Type that can be authorized to be dispatched without a signature.
+///
+/// Implemented for pallet dispatchable type by `#[pallet::call]`, using the
+/// `#[pallet::authorize]` of the calls, and for runtime dispatchable by `construct_runtime`.
+pub trait Authorize {
+	/// Validate the call as an unsigned transaction from `source`.
+	///
+	/// Returns `None` if the call has no authorization, `Some` with its validity otherwise.
+	fn authorize(&self, source: TransactionSource) -> Option<TransactionValidity>;
+
+	/// The weight of [`Self::authorize`].
+	fn weight_of_authorize(&self) -> Weight;
+}
+
 /// The trait implemented by the overarching enumeration of the different pallets' origins.
 /// Unlike `OriginTrait` impls, this does not include any kind of dispatch/call filter. Also, this
 /// trait is more flexible in terms of how it can be used: it is a `Parameter` and `Member`, so it
@@ -523,6 +539,9 @@ pub trait OriginTrait: Sized {
 	/// Create with system signed origin and `frame_system::Config::BaseCallFilter`.
 	fn signed(by: Self::AccountId) -> Self;
 
+	/// Create with system authorized origin and `frame_system::Config::BaseCallFilter`.
+	fn authorized() -> Self;
+
 	/// Extract the signer from the message if it is a `Signed` origin.
 	#[deprecated = "Use `into_signer` instead"]
 	fn as_signed(self) -> Option<Self::AccountId> {
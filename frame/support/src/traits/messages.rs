@@ -231,6 +231,31 @@ where
 	}
 }
 
+/// Inspects the messages sent to other consensus systems, e.g. over XCM.
+///
+/// Used to report the messages that a dry-run would have forwarded.
+pub trait InspectMessageQueues {
+	/// Clear the messages sent so far.
+	fn clear_messages();
+
+	/// The messages sent since the last [`Self::clear_messages`], as the encoded destination and
+	/// the encoded messages sent to it.
+	fn get_messages() -> Vec<(Vec<u8>, Vec<Vec<u8>>)>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl InspectMessageQueues for Tuple {
+	fn clear_messages() {
+		for_tuples!( #( Tuple::clear_messages(); )* );
+	}
+
+	fn get_messages() -> Vec<(Vec<u8>, Vec<Vec<u8>>)> {
+		let mut messages = Vec::new();
+		for_tuples!( #( messages.extend(Tuple::get_messages()); )* );
+		messages
+	}
+}
+
 /// Provides information on paused queues.
 pub trait QueuePausedQuery<Origin> {
 	/// Whether this queue is paused.
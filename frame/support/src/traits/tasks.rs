@@ -0,0 +1,76 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Types for background work declared with `#[pallet::tasks]`.
+
+use codec::FullCodec;
+use scale_info::TypeInfo;
+use sp_runtime::DispatchResult;
+use sp_std::{fmt::Debug, iter};
+use sp_weights::Weight;
+
+/// A unit of background work which anybody can execute once it is valid.
+///
+/// Implemented by the `Task` enum generated by `#[pallet::tasks]` and by the `RuntimeTask` enum
+/// generated by `construct_runtime!`, which aggregates the tasks of all pallets.
+pub trait Task: Sized + FullCodec + TypeInfo + Clone + Debug + PartialEq + Eq {
+	/// An iterator over the pending tasks.
+	type Enumeration: Iterator<Item = Self>;
+
+	/// The pending tasks.
+	///
+	/// A pending task may still be invalid, check [`Self::is_valid`] before running it.
+	fn iter() -> Self::Enumeration;
+
+	/// Whether the task can be run in the current state.
+	fn is_valid(&self) -> bool;
+
+	/// Run the task.
+	///
+	/// Must only be called if [`Self::is_valid`] returns `true`.
+	fn run(&self) -> DispatchResult;
+
+	/// The weight of running the task.
+	fn weight(&self) -> Weight;
+
+	/// The index of the task within its pallet.
+	fn task_index(&self) -> u32;
+}
+
+impl Task for () {
+	type Enumeration = iter::Empty<()>;
+
+	fn iter() -> Self::Enumeration {
+		iter::empty()
+	}
+
+	fn is_valid(&self) -> bool {
+		false
+	}
+
+	fn run(&self) -> DispatchResult {
+		Err("`()` has no tasks".into())
+	}
+
+	fn weight(&self) -> Weight {
+		Weight::zero()
+	}
+
+	fn task_index(&self) -> u32 {
+		0
+	}
+}
@@ -17,9 +17,10 @@
 
 //! Try-runtime specific traits and types.
 
+use codec::{Decode, DecodeAll};
 use impl_trait_for_tuples::impl_for_tuples;
 use sp_arithmetic::traits::AtLeast32BitUnsigned;
-use sp_runtime::TryRuntimeError;
+use sp_runtime::{RuntimeDebug, TryRuntimeError};
 use sp_std::prelude::*;
 
 /// Which state tests to execute.
@@ -87,12 +88,14 @@ impl sp_std::str::FromStr for Select {
 pub enum UpgradeCheckSelect {
 	/// Run no checks.
 	None,
-	/// Run the `try_state`, `pre_upgrade` and `post_upgrade` checks.
+	/// Run the `try_state`, `pre_upgrade` and `post_upgrade` checks, and decode the entire state.
 	All,
 	/// Run the `pre_upgrade` and `post_upgrade` checks.
 	PreAndPost,
 	/// Run the `try_state` checks.
 	TryState,
+	/// Decode every storage item against its declared type after the upgrade.
+	DecodeEntireState,
 }
 
 impl UpgradeCheckSelect {
@@ -105,6 +108,11 @@ impl UpgradeCheckSelect {
 	pub fn try_state(&self) -> bool {
 		matches!(self, Self::All | Self::TryState)
 	}
+
+	/// Whether the entire state should be decoded after the upgrade.
+	pub fn decode_entire_state(&self) -> bool {
+		matches!(self, Self::All | Self::DecodeEntireState)
+	}
 }
 
 #[cfg(feature = "std")]
@@ -117,11 +125,124 @@ impl core::str::FromStr for UpgradeCheckSelect {
 			"all" => Ok(Self::All),
 			"pre-and-post" => Ok(Self::PreAndPost),
 			"try-state" => Ok(Self::TryState),
+			"decode-entire-state" => Ok(Self::DecodeEntireState),
 			_ => Err("Invalid CheckSelector"),
 		}
 	}
 }
 
+/// A value of a storage item that failed to decode into the item's declared type.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TryDecodeEntireStorageError {
+	/// The name of the pallet the storage item belongs to.
+	pub pallet_name: &'static str,
+	/// The name of the storage item.
+	pub storage_name: &'static str,
+	/// The full key the value is stored under.
+	pub key: Vec<u8>,
+	/// The raw value that failed to decode.
+	pub raw: Vec<u8>,
+}
+
+impl sp_std::fmt::Display for TryDecodeEntireStorageError {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter<'_>) -> sp_std::fmt::Result {
+		write!(
+			f,
+			"`{}::{}` failed to decode the value of {} bytes at key 0x{}",
+			self.pallet_name,
+			self.storage_name,
+			self.raw.len(),
+			sp_core::hexdisplay::HexDisplay::from(&self.key),
+		)
+	}
+}
+
+/// Decode all the values stored under a storage item.
+///
+/// Implemented by the storage types, by every pallet over all of its storage items and by tuples of
+/// pallets. Useful to catch values whose type changed without a migration.
+pub trait TryDecodeEntireStorage {
+	/// Decode all the values, returning the number of bytes decoded on success or an error for each
+	/// value that failed to decode.
+	fn try_decode_entire_state() -> Result<usize, Vec<TryDecodeEntireStorageError>>;
+}
+
+#[cfg_attr(all(not(feature = "tuples-96"), not(feature = "tuples-128")), impl_for_tuples(64))]
+#[cfg_attr(all(feature = "tuples-96", not(feature = "tuples-128")), impl_for_tuples(96))]
+#[cfg_attr(all(feature = "tuples-128"), impl_for_tuples(128))]
+impl TryDecodeEntireStorage for Tuple {
+	fn try_decode_entire_state() -> Result<usize, Vec<TryDecodeEntireStorageError>> {
+		let mut decoded = 0usize;
+		let mut errors = Vec::new();
+		for_tuples!( #(
+			match Tuple::try_decode_entire_state() {
+				Ok(bytes) => decoded = decoded.saturating_add(bytes),
+				Err(errs) => errors.extend(errs),
+			}
+		)* );
+
+		if errors.is_empty() {
+			Ok(decoded)
+		} else {
+			Err(errors)
+		}
+	}
+}
+
+/// Decode the value stored at `key` as `V`, if any.
+pub(crate) fn decode_storage_key<V: Decode>(
+	pallet_name: &'static str,
+	storage_name: &'static str,
+	key: &[u8],
+) -> Result<usize, Vec<TryDecodeEntireStorageError>> {
+	match crate::storage::unhashed::get_raw(key) {
+		Some(raw) => decode_raw_value::<V>(pallet_name, storage_name, key.to_vec(), raw)
+			.map_err(|error| vec![error]),
+		None => Ok(0),
+	}
+}
+
+/// Decode all the values stored under keys starting with `prefix` as `V`.
+pub(crate) fn decode_storage_prefix<V: Decode>(
+	pallet_name: &'static str,
+	storage_name: &'static str,
+	prefix: &[u8],
+) -> Result<usize, Vec<TryDecodeEntireStorageError>> {
+	let mut decoded = 0usize;
+	let mut errors = Vec::new();
+	let mut previous_key = prefix.to_vec();
+	while let Some(key) = sp_io::storage::next_key(&previous_key) {
+		if !key.starts_with(prefix) {
+			break
+		}
+		if let Some(raw) = crate::storage::unhashed::get_raw(&key) {
+			match decode_raw_value::<V>(pallet_name, storage_name, key.clone(), raw) {
+				Ok(bytes) => decoded = decoded.saturating_add(bytes),
+				Err(error) => errors.push(error),
+			}
+		}
+		previous_key = key;
+	}
+
+	if errors.is_empty() {
+		Ok(decoded)
+	} else {
+		Err(errors)
+	}
+}
+
+fn decode_raw_value<V: Decode>(
+	pallet_name: &'static str,
+	storage_name: &'static str,
+	key: Vec<u8>,
+	raw: Vec<u8>,
+) -> Result<usize, TryDecodeEntireStorageError> {
+	match V::decode_all(&mut &raw[..]) {
+		Ok(_) => Ok(raw.len()),
+		Err(_) => Err(TryDecodeEntireStorageError { pallet_name, storage_name, key, raw }),
+	}
+}
+
 /// Execute some checks to ensure the internal state of a pallet is consistent.
 ///
 /// Usually, these checks should check all of the invariants that are expected to be held on all of
@@ -0,0 +1,136 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Types and traits to execute pallet view functions.
+//!
+//! View functions are read-only queries declared in the `#[pallet::view_functions]` section of a
+//! pallet. `construct_runtime!` dispatches them by their [`ViewFunctionId`], so that a runtime
+//! only needs to implement the generic [`runtime_api::RuntimeViewFunction`] to expose the view
+//! functions of all its pallets.
+
+use codec::{Decode, Encode, Output};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// The identifier of a view function.
+///
+/// The `prefix` is the `twox_128` hash of the name of the pallet in the runtime, the `suffix` the
+/// `twox_128` hash of the signature of the view function, e.g. `get_value(u32) -> Option<u32>`.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct ViewFunctionId {
+	/// Identifies the pallet of the view function.
+	pub prefix: [u8; 16],
+	/// Identifies the view function within its pallet.
+	pub suffix: [u8; 16],
+}
+
+impl From<ViewFunctionId> for [u8; 32] {
+	fn from(id: ViewFunctionId) -> Self {
+		let mut output = [0u8; 32];
+		output[..16].copy_from_slice(&id.prefix);
+		output[16..].copy_from_slice(&id.suffix);
+		output
+	}
+}
+
+impl From<[u8; 32]> for ViewFunctionId {
+	fn from(id: [u8; 32]) -> Self {
+		let mut prefix = [0u8; 16];
+		let mut suffix = [0u8; 16];
+		prefix.copy_from_slice(&id[..16]);
+		suffix.copy_from_slice(&id[16..]);
+		Self { prefix, suffix }
+	}
+}
+
+/// Error of executing a view function.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub enum ViewFunctionDispatchError {
+	/// The runtime doesn't support view functions.
+	NotImplemented,
+	/// There is no view function with the given identifier.
+	NotFound(ViewFunctionId),
+	/// The arguments could not be decoded.
+	Codec,
+}
+
+impl From<codec::Error> for ViewFunctionDispatchError {
+	fn from(_: codec::Error) -> Self {
+		Self::Codec
+	}
+}
+
+/// The [`ViewFunctionId::prefix`] of the view functions of a pallet.
+///
+/// Implemented by the pallet macro.
+pub trait ViewFunctionIdPrefix {
+	/// The prefix of the view functions of this pallet.
+	fn prefix() -> [u8; 16];
+}
+
+/// Execute view functions by their [`ViewFunctionId`].
+///
+/// Implemented by the pallet macro for every pallet and by `construct_runtime!` for the runtime.
+pub trait DispatchViewFunction {
+	/// Execute the view function `id` with the SCALE-encoded arguments `input` and write its
+	/// SCALE-encoded result to `output`.
+	fn dispatch_view_function<O: Output>(
+		id: &ViewFunctionId,
+		input: &mut &[u8],
+		output: &mut O,
+	) -> Result<(), ViewFunctionDispatchError>;
+}
+
+impl DispatchViewFunction for () {
+	fn dispatch_view_function<O: Output>(
+		_id: &ViewFunctionId,
+		_input: &mut &[u8],
+		_output: &mut O,
+	) -> Result<(), ViewFunctionDispatchError> {
+		Err(ViewFunctionDispatchError::NotImplemented)
+	}
+}
+
+/// Execute the view function `id` of `D` and return its SCALE-encoded result.
+pub fn execute_view_function<D: DispatchViewFunction>(
+	id: ViewFunctionId,
+	input: Vec<u8>,
+) -> Result<Vec<u8>, ViewFunctionDispatchError> {
+	let mut output = Vec::new();
+	D::dispatch_view_function(&id, &mut &input[..], &mut output)?;
+	Ok(output)
+}
+
+/// The runtime API to execute view functions.
+pub mod runtime_api {
+	use super::*;
+
+	sp_api::decl_runtime_apis! {
+		/// Execute the view functions of the pallets of the runtime.
+		///
+		/// Implemented by calling [`execute_view_function`] for the runtime.
+		pub trait RuntimeViewFunction {
+			/// Execute the view function `id` with the SCALE-encoded arguments `input` and return
+			/// its SCALE-encoded result.
+			fn execute_view_function(
+				id: ViewFunctionId,
+				input: Vec<u8>,
+			) -> Result<Vec<u8>, ViewFunctionDispatchError>;
+		}
+	}
+}
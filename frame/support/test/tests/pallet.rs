@@ -173,6 +173,22 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T>
+	where
+		T::AccountId: From<SomeType1> + SomeAssociation1,
+	{
+		/// Query value no args.
+		pub fn get_value() -> Option<u32> {
+			Value::<T>::get()
+		}
+
+		/// Query value with args.
+		pub fn get_value_with_arg(key: u16) -> Option<u32> {
+			Map2::<T>::get(key)
+		}
+	}
+
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
@@ -258,6 +274,17 @@ pub mod pallet {
 		pub fn check_for_dispatch_context(_origin: OriginFor<T>) -> DispatchResult {
 			with_context::<(), _>(|_| ()).ok_or_else(|| DispatchError::Unavailable)
 		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight({1})]
+		#[pallet::authorize(|_source: TransactionSource, value: &u32| {
+			ValidTransaction::with_tag_prefix("Example").and_provides(value).build()
+		})]
+		#[pallet::weight_of_authorize(Weight::from_parts(2, 0))]
+		pub fn authorized_call(origin: OriginFor<T>, _value: u32) -> DispatchResult {
+			frame_system::ensure_authorized(origin)?;
+			Ok(())
+		}
 	}
 
 	#[pallet::error]
@@ -803,12 +830,13 @@ fn call_expand() {
 			"foo_storage_layer",
 			"foo_index_out_of_order",
 			"foo_no_post_info",
-			"check_for_dispatch_context"
+			"check_for_dispatch_context",
+			"authorized_call"
 		],
 	);
 
 	assert_eq!(call_foo.get_call_index(), 0u8);
-	assert_eq!(pallet::Call::<Runtime>::get_call_indices(), &[0u8, 1u8, 4u8, 2u8, 3u8])
+	assert_eq!(pallet::Call::<Runtime>::get_call_indices(), &[0u8, 1u8, 4u8, 2u8, 3u8, 5u8])
 }
 
 #[test]
@@ -816,7 +844,7 @@ fn call_expand_index() {
 	let call_foo = pallet::Call::<Runtime>::foo_index_out_of_order {};
 
 	assert_eq!(call_foo.get_call_index(), 4u8);
-	assert_eq!(pallet::Call::<Runtime>::get_call_indices(), &[0u8, 1u8, 4u8, 2u8, 3u8])
+	assert_eq!(pallet::Call::<Runtime>::get_call_indices(), &[0u8, 1u8, 4u8, 2u8, 3u8, 5u8])
 }
 
 #[test]
@@ -1035,6 +1063,51 @@ fn validate_unsigned_expand() {
 	assert_eq!(validity, ValidTransaction::default());
 }
 
+#[test]
+fn authorize_expand() {
+	use frame_support::{
+		pallet_prelude::{TransactionSource, ValidTransaction},
+		traits::Authorize,
+	};
+	let call = pallet::Call::<Runtime>::authorized_call { value: 7 };
+	let validity = ValidTransaction::with_tag_prefix("Example").and_provides(7u32).build();
+
+	assert_eq!(call.authorize(TransactionSource::External), Some(validity.clone()));
+	assert_eq!(
+		RuntimeCall::from(call.clone()).authorize(TransactionSource::External),
+		Some(validity)
+	);
+	assert_eq!(call.weight_of_authorize(), Weight::from_parts(2, 0));
+	// The weight of the call includes the weight of the authorization.
+	assert_eq!(call.get_dispatch_info().weight, Weight::from_parts(3, 0));
+
+	let call_without_authorize = pallet::Call::<Runtime>::foo_no_post_info {};
+	assert_eq!(call_without_authorize.authorize(TransactionSource::External), None);
+	assert_eq!(call_without_authorize.weight_of_authorize(), Weight::zero());
+
+	TestExternalities::default().execute_with(|| {
+		// Unsigned calls are only dispatched with the authorized origin once it was granted.
+		assert_eq!(
+			call.clone().dispatch_bypass_filter(RuntimeOrigin::none()).unwrap_err().error,
+			DispatchError::BadOrigin,
+		);
+		frame_support::dispatch_context::run_in_context(|| {
+			frame_support::dispatch::AuthorizedDispatch::grant();
+			assert_ok!(call.clone().dispatch_bypass_filter(RuntimeOrigin::none()));
+			// The grant is used up by the first dispatch.
+			assert_eq!(
+				call.clone().dispatch_bypass_filter(RuntimeOrigin::none()).unwrap_err().error,
+				DispatchError::BadOrigin,
+			);
+		});
+		assert_ok!(call.clone().dispatch_bypass_filter(RuntimeOrigin::authorized()));
+		assert_eq!(
+			call.dispatch_bypass_filter(RuntimeOrigin::root()).unwrap_err().error,
+			DispatchError::BadOrigin,
+		);
+	});
+}
+
 #[test]
 fn composite_expand() {
 	use codec::Encode;
@@ -2425,6 +2498,57 @@ fn post_runtime_upgrade_detects_storage_version_issues() {
 	});
 }
 
+#[cfg(feature = "try-runtime")]
+#[test]
+fn try_decode_entire_state_reports_undecodable_values() {
+	use frame_support::traits::{
+		TryDecodeEntireStorage, TryDecodeEntireStorageError, UpgradeCheckSelect,
+	};
+
+	type Executive = frame_executive::Executive<
+		Runtime,
+		Block,
+		frame_system::ChainContext<Runtime>,
+		Runtime,
+		AllPalletsWithSystem,
+	>;
+
+	TestExternalities::default().execute_with(|| {
+		pallet::Value::<Runtime>::put(1);
+		pallet::Map2::<Runtime>::insert(1, 2);
+		assert_eq!(Example::try_decode_entire_state(), Ok(8));
+		Executive::try_runtime_upgrade(UpgradeCheckSelect::DecodeEntireState).unwrap();
+
+		// A trailing byte and a truncated value don't decode into a `u32`.
+		let value_key = pallet::Value::<Runtime>::hashed_key().to_vec();
+		unhashed::put_raw(&value_key, &[1, 0, 0, 0, 0]);
+		let map_key = pallet::Map2::<Runtime>::hashed_key_for(2);
+		unhashed::put_raw(&map_key, &[1]);
+
+		assert_eq!(
+			Example::try_decode_entire_state(),
+			Err(vec![
+				TryDecodeEntireStorageError {
+					pallet_name: "Example",
+					storage_name: "Value",
+					key: value_key,
+					raw: vec![1, 0, 0, 0, 0],
+				},
+				TryDecodeEntireStorageError {
+					pallet_name: "Example",
+					storage_name: "Map2",
+					key: map_key,
+					raw: vec![1],
+				},
+			])
+		);
+		assert_eq!(AllPalletsWithSystem::try_decode_entire_state().unwrap_err().len(), 2);
+		assert!(Executive::try_runtime_upgrade(UpgradeCheckSelect::DecodeEntireState).is_err());
+		// The decode checks aren't selected.
+		Executive::try_runtime_upgrade(UpgradeCheckSelect::None).unwrap();
+	});
+}
+
 #[test]
 fn test_dispatch_context() {
 	TestExternalities::default().execute_with(|| {
@@ -2442,3 +2566,103 @@ fn test_dispatch_context() {
 			.dispatch(RuntimeOrigin::root()));
 	});
 }
+
+#[test]
+fn view_functions_are_dispatched() {
+	use codec::Encode;
+	use frame_support::view_functions::{
+		ViewFunctionDispatchError, ViewFunctionId, ViewFunctionIdPrefix,
+	};
+
+	let metadata = Example::pallet_view_functions_metadata();
+	let id = |name| {
+		let view_function = metadata.iter().find(|view_function| view_function.name == name);
+		ViewFunctionId::from(view_function.expect("view function is in the metadata").id)
+	};
+	assert_eq!(id("get_value").prefix, twox_128(b"Example"));
+	assert_eq!(<Example as ViewFunctionIdPrefix>::prefix(), twox_128(b"Example"));
+
+	TestExternalities::default().execute_with(|| {
+		pallet::Value::<Runtime>::put(42);
+		pallet::Map2::<Runtime>::insert(1, 43);
+
+		assert_eq!(
+			Runtime::execute_view_function(id("get_value"), vec![]),
+			Ok(Some(42u32).encode())
+		);
+		assert_eq!(
+			Runtime::execute_view_function(id("get_value_with_arg"), 1u16.encode()),
+			Ok(Some(43u32).encode())
+		);
+		assert_eq!(
+			Runtime::execute_view_function(id("get_value_with_arg"), 2u16.encode()),
+			Ok(None::<u32>.encode())
+		);
+
+		// The arguments must be decoded entirely.
+		assert_eq!(
+			Runtime::execute_view_function(id("get_value_with_arg"), vec![]),
+			Err(ViewFunctionDispatchError::Codec)
+		);
+		assert_eq!(
+			Runtime::execute_view_function(id("get_value"), 1u16.encode()),
+			Err(ViewFunctionDispatchError::Codec)
+		);
+
+		let unknown = ViewFunctionId { prefix: twox_128(b"Example"), suffix: [0; 16] };
+		assert_eq!(
+			Runtime::execute_view_function(unknown, vec![]),
+			Err(ViewFunctionDispatchError::NotFound(unknown))
+		);
+		let unknown = ViewFunctionId { prefix: [0; 16], suffix: id("get_value").suffix };
+		assert_eq!(
+			Runtime::execute_view_function(unknown, vec![]),
+			Err(ViewFunctionDispatchError::NotFound(unknown))
+		);
+	});
+}
+
+#[test]
+fn view_functions_are_in_the_metadata() {
+	use frame_metadata::*;
+	use scale_info::{TypeDef, TypeDefPrimitive};
+	use sp_core::Decode;
+	use sp_metadata_ir::{PalletViewFunctionsMetadataV15, VIEW_FUNCTIONS_CUSTOM_METADATA_KEY};
+
+	let bytes = &Runtime::metadata_at_version(LATEST_METADATA_VERSION)
+		.expect("Metadata must be present; qed");
+	let metadata = match RuntimeMetadataPrefixed::decode(&mut &bytes[..]).unwrap().1 {
+		RuntimeMetadata::V15(metadata) => metadata,
+		_ => panic!("metadata has been bumped, test needs to be updated"),
+	};
+
+	let custom = &metadata.custom.map[VIEW_FUNCTIONS_CUSTOM_METADATA_KEY];
+	let pallets: Vec<PalletViewFunctionsMetadataV15> =
+		Decode::decode(&mut &custom.value[..]).unwrap();
+	assert!(matches!(
+		metadata.types.resolve(custom.ty.id).map(|ty| &ty.type_def),
+		Some(TypeDef::Sequence(_))
+	));
+
+	assert_eq!(pallets.len(), 1);
+	assert_eq!(pallets[0].pallet, "Example");
+	let expected = Example::pallet_view_functions_metadata();
+	assert_eq!(pallets[0].view_functions.len(), expected.len());
+	for (view_function, expected) in pallets[0].view_functions.iter().zip(&expected) {
+		assert_eq!(view_function.name, expected.name);
+		assert_eq!(view_function.id, expected.id);
+		assert_eq!(view_function.docs, expected.docs);
+	}
+
+	let get_value_with_arg = &pallets[0].view_functions[1];
+	assert_eq!(get_value_with_arg.inputs.len(), 1);
+	assert_eq!(get_value_with_arg.inputs[0].name, "key");
+	assert_eq!(
+		metadata.types.resolve(get_value_with_arg.inputs[0].ty).unwrap().type_def,
+		TypeDef::Primitive(TypeDefPrimitive::U16)
+	);
+	assert_eq!(
+		metadata.types.resolve(get_value_with_arg.output).unwrap().path.segments,
+		vec!["Option"]
+	);
+}
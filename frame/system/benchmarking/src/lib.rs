@@ -25,10 +25,14 @@ use frame_benchmarking::{
 	v1::{benchmarks, whitelisted_caller},
 	BenchmarkError,
 };
-use frame_support::{dispatch::DispatchClass, storage, traits::Get};
+use frame_support::{
+	dispatch::DispatchClass,
+	storage,
+	traits::{Authorize, Get},
+};
 use frame_system::{Call, Pallet as System, RawOrigin};
 use sp_core::storage::well_known_keys;
-use sp_runtime::traits::Hash;
+use sp_runtime::{traits::Hash, transaction_validity::TransactionSource};
 use sp_std::{prelude::*, vec};
 
 mod mock;
@@ -165,5 +169,36 @@ benchmarks! {
 		}
 	}
 
+	authorize_upgrade {
+		let runtime_blob = T::prepare_set_code_data();
+		let hash = T::Hashing::hash(&runtime_blob);
+	}: _(RawOrigin::Root, hash)
+	verify {
+		assert!(System::<T>::authorized_upgrade().is_some());
+	}
+
+	apply_authorized_upgrade {
+		let runtime_blob = T::prepare_set_code_data();
+		T::setup_set_code_requirements(&runtime_blob)?;
+		// The upgrade is heavier when its code is checked, i.e. not authorized without checks.
+		System::<T>::do_authorize_upgrade(T::Hashing::hash(&runtime_blob), true);
+		let caller = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), runtime_blob)
+	verify {
+		assert!(System::<T>::authorized_upgrade().is_none());
+		T::verify_set_code()
+	}
+
+	authorize_apply_authorized_upgrade {
+		let runtime_blob = T::prepare_set_code_data();
+		T::setup_set_code_requirements(&runtime_blob)?;
+		System::<T>::do_authorize_upgrade(T::Hashing::hash(&runtime_blob), true);
+		let call = Call::<T>::apply_authorized_upgrade { code: runtime_blob };
+	}: {
+		call.authorize(TransactionSource::External)
+			.ok_or("The call is not authorizable")?
+			.map_err(|_| "The upgrade is not authorized")?;
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
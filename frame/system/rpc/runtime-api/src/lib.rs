@@ -23,6 +23,11 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use frame_support::dispatch::{DispatchResultWithPostInfo, PostDispatchInfo};
+use sp_runtime::{traits::Block as BlockT, ApplyExtrinsicResultWithInfo};
+
+pub use frame_support::dispatch::DryRunEffects;
+
 sp_api::decl_runtime_apis! {
 	/// The API to query account nonce.
 	pub trait AccountNonceApi<AccountId, Nonce> where
@@ -32,4 +37,24 @@ sp_api::decl_runtime_apis! {
 		/// Get current account nonce of given `AccountId`.
 		fn account_nonce(account: AccountId) -> Nonce;
 	}
+
+	/// The API to dry-run extrinsics and calls on top of the state of a block.
+	///
+	/// Nothing is persisted: the returned effects are all that is left of the dry-run.
+	pub trait DryRunApi<Call, Event, OriginCaller> where
+		Call: codec::Codec,
+		Event: codec::Codec,
+		OriginCaller: codec::Codec,
+	{
+		/// Apply the given extrinsic, including all of its transaction checks.
+		fn dry_run_extrinsic(
+			extrinsic: <Block as BlockT>::Extrinsic,
+		) -> DryRunEffects<ApplyExtrinsicResultWithInfo<PostDispatchInfo>, Event>;
+
+		/// Dispatch the given call from the given origin, without any transaction checks.
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: Call,
+		) -> DryRunEffects<DispatchResultWithPostInfo, Event>;
+	}
 }
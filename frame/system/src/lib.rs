@@ -75,7 +75,7 @@ use sp_runtime::{
 	traits::{
 		self, AtLeast32Bit, BadOrigin, BlockNumberProvider, Bounded, CheckEqual, Dispatchable,
 		Hash, Header, Lookup, LookupError, MaybeDisplay, MaybeSerializeDeserialize, Member, One,
-		Saturating, SimpleBitOps, StaticLookup, Zero,
+		SaturatedConversion, Saturating, SimpleBitOps, StaticLookup, Zero,
 	},
 	DispatchError, RuntimeDebug,
 };
@@ -90,7 +90,7 @@ use frame_support::traits::BuildGenesisConfig;
 use frame_support::{
 	dispatch::{
 		extract_actual_pays_fee, extract_actual_weight, DispatchClass, DispatchInfo,
-		DispatchResult, DispatchResultWithPostInfo, PerDispatchClass,
+		DispatchResult, DispatchResultWithPostInfo, PerDispatchClass, PostDispatchInfo,
 	},
 	impl_ensure_origin_with_arg_ignoring_arg,
 	storage::{self, StorageStreamIter},
@@ -98,7 +98,7 @@ use frame_support::{
 		ConstU32, Contains, EnsureOrigin, EnsureOriginWithArg, Get, HandleLifetime,
 		OnKilledAccount, OnNewAccount, OriginTrait, PalletInfo, SortedMembers, StoredMap, TypedGet,
 	},
-	Parameter,
+	CloneNoBound, EqNoBound, Parameter, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use scale_info::TypeInfo;
 use sp_core::storage::well_known_keys;
@@ -520,6 +520,81 @@ pub mod pallet {
 			Self::deposit_event(Event::Remarked { sender: who, hash });
 			Ok(().into())
 		}
+
+		/// Authorize an upgrade to a given `code_hash` for the runtime. The runtime code can be
+		/// supplied later by anyone with [`Call::apply_authorized_upgrade`].
+		///
+		/// The code is checked with [`Pallet::check_upgrade`] before being enacted, and the
+		/// compatibility issues found are emitted as [`Event::UpgradeWarning`].
+		///
+		/// This call requires Root origin.
+		#[pallet::call_index(8)]
+		#[pallet::weight((T::SystemWeightInfo::authorize_upgrade(), DispatchClass::Operational))]
+		pub fn authorize_upgrade(
+			origin: OriginFor<T>,
+			code_hash: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			Self::do_authorize_upgrade(code_hash, true);
+			Ok(().into())
+		}
+
+		/// Authorize an upgrade to a given `code_hash` for the runtime, without any checks of the
+		/// code before it is enacted. The runtime code can be supplied later by anyone with
+		/// [`Call::apply_authorized_upgrade`].
+		///
+		/// WARNING: The code is enacted without checking that it is a runtime with the same spec
+		/// name and a higher spec version. Use [`Call::authorize_upgrade`] instead, unless these
+		/// checks can't pass.
+		///
+		/// This call requires Root origin.
+		#[pallet::call_index(9)]
+		#[pallet::weight((T::SystemWeightInfo::authorize_upgrade(), DispatchClass::Operational))]
+		pub fn authorize_upgrade_without_checks(
+			origin: OriginFor<T>,
+			code_hash: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			Self::do_authorize_upgrade(code_hash, false);
+			Ok(().into())
+		}
+
+		/// Provide the runtime code of the upgrade authorized with [`Call::authorize_upgrade`] or
+		/// [`Call::authorize_upgrade_without_checks`], and enact it.
+		///
+		/// Can be dispatched by any origin, or submitted as an unsigned transaction. No fee is paid
+		/// when the code is enacted.
+		#[pallet::call_index(10)]
+		#[pallet::weight((
+			T::SystemWeightInfo::apply_authorized_upgrade(),
+			DispatchClass::Operational,
+		))]
+		#[pallet::authorize(|_source: TransactionSource, code: &Vec<u8>| -> TransactionValidity {
+			let (code_hash, _) = Pallet::<T>::validate_authorized_upgrade(&code[..])
+				.map_err(|_| InvalidTransaction::Call)?;
+			ValidTransaction::with_tag_prefix("SystemApplyAuthorizedUpgrade")
+				.and_provides(code_hash)
+				.priority(TransactionPriority::max_value())
+				.build()
+		})]
+		#[pallet::weight_of_authorize(T::SystemWeightInfo::authorize_apply_authorized_upgrade())]
+		pub fn apply_authorized_upgrade(
+			_origin: OriginFor<T>,
+			code: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let (_, warnings) = Self::validate_authorized_upgrade(&code[..])?;
+			for warning in warnings {
+				Self::deposit_event(Event::UpgradeWarning { warning });
+			}
+			T::OnSetCode::set_code(code)?;
+			AuthorizedUpgrade::<T>::kill();
+
+			// consume the rest of the block to prevent further transactions
+			Ok(PostDispatchInfo {
+				actual_weight: Some(T::BlockWeights::get().max_block),
+				pays_fee: Pays::No,
+			})
+		}
 	}
 
 	/// Event for the System pallet.
@@ -537,6 +612,10 @@ pub mod pallet {
 		KilledAccount { account: T::AccountId },
 		/// On on-chain remark happened.
 		Remarked { sender: T::AccountId, hash: T::Hash },
+		/// An upgrade was authorized.
+		UpgradeAuthorized { code_hash: T::Hash, check_version: bool },
+		/// The checks of an authorized upgrade found a compatibility issue.
+		UpgradeWarning { warning: UpgradeWarning },
 	}
 
 	/// Error for the System pallet
@@ -558,6 +637,10 @@ pub mod pallet {
 		NonZeroRefCount,
 		/// The origin filter prevent the call to be dispatched.
 		CallFiltered,
+		/// No upgrade authorized.
+		NothingAuthorized,
+		/// The submitted code is not authorized.
+		Unauthorized,
 	}
 
 	/// Exposed trait-generic origin type.
@@ -673,6 +756,12 @@ pub mod pallet {
 	#[pallet::whitelist_storage]
 	pub(super) type ExecutionPhase<T: Config> = StorageValue<_, Phase>;
 
+	/// `Some` if a code upgrade has been authorized.
+	#[pallet::storage]
+	#[pallet::getter(fn authorized_upgrade)]
+	pub(super) type AuthorizedUpgrade<T: Config> =
+		StorageValue<_, CodeUpgradeAuthorization<T>, OptionQuery>;
+
 	#[derive(frame_support::DefaultNoBound)]
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -790,6 +879,50 @@ impl From<sp_version::RuntimeVersion> for LastRuntimeUpgradeInfo {
 	}
 }
 
+/// A runtime upgrade authorized with [`Call::authorize_upgrade`] or
+/// [`Call::authorize_upgrade_without_checks`], waiting for its code to be provided.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct CodeUpgradeAuthorization<T: Config> {
+	/// The hash of the authorized runtime code.
+	pub code_hash: T::Hash,
+	/// Whether the code is checked with [`Pallet::check_upgrade`] before being enacted.
+	pub check_version: bool,
+}
+
+/// A compatibility issue of a runtime upgrade, found by [`Pallet::check_upgrade`].
+///
+/// Unlike the errors of [`Pallet::check_upgrade`], these don't prevent the upgrade from being
+/// enacted. They are emitted as [`Event::UpgradeWarning`] when enacting an authorized upgrade.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub enum UpgradeWarning {
+	/// The transaction version changes, so transactions signed for the current runtime are not
+	/// valid with the new one.
+	TransactionVersionChanged { from: u32, to: u32 },
+	/// The state version changes, so the state is migrated to the new trie layout.
+	StateVersionChanged { from: u8, to: u8 },
+	/// A runtime API of the current runtime is not implemented by the new one.
+	RuntimeApiRemoved { api: sp_version::ApiId },
+	/// A runtime API is implemented at a lower version by the new runtime.
+	RuntimeApiDowngraded { api: sp_version::ApiId, from: u32, to: u32 },
+	/// The code is larger than the heap configured with `:heappages`, so the no-std runtime can't
+	/// load it, e.g. when an `OnSetCode` implementation defers the upgrade and reads it back.
+	///
+	/// This only compares the size of the code with the size of the heap. How much heap the new
+	/// runtime needs is only known once it runs, so an upgrade without this warning can still
+	/// run out of heap.
+	CodeExceedsHeap { code_size: u32, heap_size: u64 },
+}
+
 /// Ensure the origin is Root.
 pub struct EnsureRoot<AccountId>(sp_std::marker::PhantomData<AccountId>);
 impl<O: Into<Result<RawOrigin<AccountId>, O>> + From<RawOrigin<AccountId>>, AccountId>
@@ -1023,6 +1156,18 @@ where
 	}
 }
 
+/// Ensure that the origin `o` represents an unsigned extrinsic authorized by the
+/// `#[pallet::authorize]` of its call. Returns `Ok` or an `Err` otherwise.
+pub fn ensure_authorized<OuterOrigin, AccountId>(o: OuterOrigin) -> Result<(), BadOrigin>
+where
+	OuterOrigin: Into<Result<RawOrigin<AccountId>, OuterOrigin>>,
+{
+	match o.into() {
+		Ok(RawOrigin::Authorized) => Ok(()),
+		_ => Err(BadOrigin),
+	}
+}
+
 /// Reference status; can be either referenced or unreferenced.
 #[derive(RuntimeDebug)]
 pub enum RefStatus {
@@ -1666,16 +1811,25 @@ impl<T: Config> Pallet<T> {
 	/// it and extracting the runtime version of it. It checks that the runtime version
 	/// of the old and new runtime has the same spec name and that the spec version is increasing.
 	pub fn can_set_code(code: &[u8]) -> Result<(), sp_runtime::DispatchError> {
+		Self::check_upgrade(code).map(drop)
+	}
+
+	/// Check the given code before it is enacted as the new runtime.
+	///
+	/// Fails like [`Self::can_set_code`]. Otherwise returns the compatibility issues of the
+	/// upgrade, which don't prevent it from being enacted.
+	pub fn check_upgrade(code: &[u8]) -> Result<Vec<UpgradeWarning>, sp_runtime::DispatchError> {
 		let current_version = T::Version::get();
 		let new_version = sp_io::misc::runtime_version(code)
 			.and_then(|v| RuntimeVersion::decode(&mut &v[..]).ok())
 			.ok_or(Error::<T>::FailedToExtractRuntimeVersion)?;
+		let warnings = Self::upgrade_warnings(&current_version, &new_version, code);
 
 		cfg_if::cfg_if! {
 			if #[cfg(all(feature = "runtime-benchmarks", not(test)))] {
 					// Let's ensure the compiler doesn't optimize our fetching of the runtime version away.
 					core::hint::black_box((new_version, current_version));
-					Ok(())
+					Ok(warnings)
 			} else {
 				if new_version.spec_name != current_version.spec_name {
 					return Err(Error::<T>::InvalidSpecName.into())
@@ -1685,9 +1839,81 @@ impl<T: Config> Pallet<T> {
 					return Err(Error::<T>::SpecVersionNeedsToIncrease.into())
 				}
 
-				Ok(())
+				Ok(warnings)
+			}
+		}
+	}
+
+	/// The compatibility issues of upgrading from `current` to the runtime `code` of version `new`.
+	fn upgrade_warnings(
+		current: &RuntimeVersion,
+		new: &RuntimeVersion,
+		code: &[u8],
+	) -> Vec<UpgradeWarning> {
+		/// The size of a wasm page, the unit of `:heappages`.
+		const WASM_PAGE_SIZE: u64 = 64 * 1024;
+
+		let mut warnings = Vec::new();
+		if new.transaction_version != current.transaction_version {
+			warnings.push(UpgradeWarning::TransactionVersionChanged {
+				from: current.transaction_version,
+				to: new.transaction_version,
+			});
+		}
+		if new.state_version != current.state_version {
+			warnings.push(UpgradeWarning::StateVersionChanged {
+				from: current.state_version,
+				to: new.state_version,
+			});
+		}
+		for (api, version) in current.apis.iter() {
+			match new.apis.iter().find(|(new_api, _)| new_api == api) {
+				None => warnings.push(UpgradeWarning::RuntimeApiRemoved { api: *api }),
+				Some((_, new_version)) if new_version < version =>
+					warnings.push(UpgradeWarning::RuntimeApiDowngraded {
+						api: *api,
+						from: *version,
+						to: *new_version,
+					}),
+				Some(_) => {},
+			}
+		}
+		if let Some(heap_pages) = storage::unhashed::get::<u64>(well_known_keys::HEAP_PAGES) {
+			let heap_size = heap_pages.saturating_mul(WASM_PAGE_SIZE);
+			if code.len() as u64 > heap_size {
+				warnings.push(UpgradeWarning::CodeExceedsHeap {
+					code_size: code.len().saturated_into(),
+					heap_size,
+				});
 			}
 		}
+		warnings
+	}
+
+	/// Authorize the runtime code with the given `code_hash` to be enacted by anyone with
+	/// [`Call::apply_authorized_upgrade`], checked with [`Self::check_upgrade`] if
+	/// `check_version` is set.
+	pub fn do_authorize_upgrade(code_hash: T::Hash, check_version: bool) {
+		AuthorizedUpgrade::<T>::put(CodeUpgradeAuthorization { code_hash, check_version });
+		Self::deposit_event(Event::UpgradeAuthorized { code_hash, check_version });
+	}
+
+	/// Check that the given code is the one of the authorized upgrade, and run the checks of the
+	/// upgrade if it was authorized with them.
+	///
+	/// Returns the hash of the code and the compatibility issues found by the checks.
+	pub fn validate_authorized_upgrade(
+		code: &[u8],
+	) -> Result<(T::Hash, Vec<UpgradeWarning>), sp_runtime::DispatchError> {
+		let authorization = AuthorizedUpgrade::<T>::get().ok_or(Error::<T>::NothingAuthorized)?;
+		let code_hash = T::Hashing::hash(code);
+		if code_hash != authorization.code_hash {
+			return Err(Error::<T>::Unauthorized.into())
+		}
+
+		let warnings =
+			if authorization.check_version { Self::check_upgrade(code)? } else { Vec::new() };
+		Ok((code_hash, warnings))
 	}
 }
 
@@ -1805,7 +2031,9 @@ impl<T: Config> Lookup for ChainContext<T> {
 
 /// Prelude to be used alongside pallet macro, for ease of use.
 pub mod pallet_prelude {
-	pub use crate::{ensure_none, ensure_root, ensure_signed, ensure_signed_or_root};
+	pub use crate::{
+		ensure_authorized, ensure_none, ensure_root, ensure_signed, ensure_signed_or_root,
+	};
 
 	/// Type alias for the `Origin` associated type of system config.
 	pub type OriginFor<T> = <T as crate::Config>::RuntimeOrigin;
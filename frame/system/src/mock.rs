@@ -45,7 +45,7 @@ parameter_types! {
 		authoring_version: 1,
 		spec_version: 1,
 		impl_version: 1,
-		apis: sp_version::create_apis_vec!([]),
+		apis: sp_version::create_apis_vec!([([1; 8], 1), ([2; 8], 2)]),
 		transaction_version: 1,
 		state_version: 1,
 	};
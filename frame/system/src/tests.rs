@@ -19,7 +19,7 @@ use crate::*;
 use frame_support::{
 	assert_noop, assert_ok,
 	dispatch::{Pays, PostDispatchInfo, WithPostDispatchInfo},
-	traits::WhitelistedStorageKeys,
+	traits::{Authorize, WhitelistedStorageKeys},
 };
 use std::collections::BTreeSet;
 
@@ -27,6 +27,7 @@ use mock::{RuntimeOrigin, *};
 use sp_core::{hexdisplay::HexDisplay, H256};
 use sp_runtime::{
 	traits::{BlakeTwo256, Header},
+	transaction_validity::{InvalidTransaction, TransactionSource},
 	DispatchError, DispatchErrorWithPostInfo,
 };
 
@@ -651,6 +652,28 @@ fn assert_runtime_updated_digest(num: usize) {
 	);
 }
 
+/// Reads any code as a runtime of the wrapped encoded version.
+struct FixedRuntimeVersion(Vec<u8>);
+
+impl sp_core::traits::ReadRuntimeVersion for FixedRuntimeVersion {
+	fn read_runtime_version(
+		&self,
+		_wasm_code: &[u8],
+		_ext: &mut dyn sp_externalities::Externalities,
+	) -> Result<Vec<u8>, String> {
+		Ok(self.0.clone())
+	}
+}
+
+/// Test externalities in which any code is a runtime of the given `version`.
+fn new_test_ext_with_runtime_version(version: RuntimeVersion) -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(FixedRuntimeVersion(
+		version.encode(),
+	)));
+	ext
+}
+
 #[test]
 fn set_code_with_real_wasm_blob() {
 	let executor = substrate_test_runtime_client::new_native_or_wasm_executor();
@@ -675,6 +698,182 @@ fn set_code_with_real_wasm_blob() {
 	});
 }
 
+#[test]
+fn authorized_upgrade_can_be_applied_by_anyone() {
+	let version = RuntimeVersion {
+		spec_name: "test".into(),
+		spec_version: 2,
+		transaction_version: 2,
+		state_version: 1,
+		..Default::default()
+	};
+	let code = vec![1, 2, 3, 4];
+
+	new_test_ext_with_runtime_version(version).execute_with(|| {
+		System::set_block_number(1);
+		let code_hash = <Test as Config>::Hashing::hash(&code);
+
+		assert_noop!(
+			System::authorize_upgrade(RuntimeOrigin::signed(1), code_hash),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			System::apply_authorized_upgrade(RuntimeOrigin::signed(1), code.clone()),
+			Error::<Test>::NothingAuthorized,
+		);
+
+		assert_ok!(System::authorize_upgrade(RawOrigin::Root.into(), code_hash));
+		System::assert_has_event(
+			SysEvent::UpgradeAuthorized { code_hash, check_version: true }.into(),
+		);
+		assert_noop!(
+			System::apply_authorized_upgrade(RuntimeOrigin::signed(1), vec![4, 3, 2, 1]),
+			Error::<Test>::Unauthorized,
+		);
+
+		let post_info =
+			System::apply_authorized_upgrade(RuntimeOrigin::signed(1), code.clone()).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		System::assert_has_event(
+			SysEvent::UpgradeWarning {
+				warning: UpgradeWarning::TransactionVersionChanged { from: 1, to: 2 },
+			}
+			.into(),
+		);
+		System::assert_last_event(SysEvent::CodeUpdated.into());
+		assert_eq!(storage::unhashed::get_raw(well_known_keys::CODE), Some(code.clone()));
+
+		// The authorization is used up.
+		assert!(System::authorized_upgrade().is_none());
+		assert_noop!(
+			System::apply_authorized_upgrade(RuntimeOrigin::signed(1), code),
+			Error::<Test>::NothingAuthorized,
+		);
+	});
+}
+
+#[test]
+fn authorized_upgrade_checks_are_optional() {
+	// The spec version doesn't increase.
+	let version =
+		RuntimeVersion { spec_name: "test".into(), spec_version: 1, ..Default::default() };
+	let code = vec![1, 2, 3, 4];
+
+	new_test_ext_with_runtime_version(version).execute_with(|| {
+		System::set_block_number(1);
+		let code_hash = <Test as Config>::Hashing::hash(&code);
+
+		assert_ok!(System::authorize_upgrade(RawOrigin::Root.into(), code_hash));
+		assert_noop!(
+			System::apply_authorized_upgrade(RuntimeOrigin::signed(1), code.clone()),
+			Error::<Test>::SpecVersionNeedsToIncrease,
+		);
+
+		assert_ok!(System::authorize_upgrade_without_checks(RawOrigin::Root.into(), code_hash));
+		assert_ok!(System::apply_authorized_upgrade(RuntimeOrigin::signed(1), code));
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::System(SysEvent::UpgradeWarning { .. })
+		)));
+		System::assert_last_event(SysEvent::CodeUpdated.into());
+	});
+}
+
+#[test]
+fn apply_authorized_upgrade_is_authorized_for_the_authorized_code() {
+	let version =
+		RuntimeVersion { spec_name: "test".into(), spec_version: 2, ..Default::default() };
+	let code = vec![1, 2, 3, 4];
+	let call = Call::<Test>::apply_authorized_upgrade { code: code.clone() };
+
+	new_test_ext_with_runtime_version(version).execute_with(|| {
+		assert_eq!(
+			call.authorize(TransactionSource::External),
+			Some(Err(InvalidTransaction::Call.into())),
+		);
+
+		System::do_authorize_upgrade(<Test as Config>::Hashing::hash(&code), true);
+		assert!(matches!(call.authorize(TransactionSource::External), Some(Ok(_))));
+
+		System::do_authorize_upgrade(<Test as Config>::Hashing::hash(&[4, 3, 2, 1]), true);
+		assert_eq!(
+			call.authorize(TransactionSource::External),
+			Some(Err(InvalidTransaction::Call.into())),
+		);
+	});
+}
+
+#[test]
+fn upgrade_checks_report_compatibility_issues() {
+	let version = RuntimeVersion {
+		spec_name: "test".into(),
+		spec_version: 2,
+		transaction_version: 2,
+		state_version: 0,
+		apis: <Test as Config>::Version::get().apis,
+		..Default::default()
+	};
+
+	new_test_ext_with_runtime_version(version).execute_with(|| {
+		assert_eq!(
+			System::check_upgrade(&[1, 2, 3, 4]),
+			Ok(vec![
+				UpgradeWarning::TransactionVersionChanged { from: 1, to: 2 },
+				UpgradeWarning::StateVersionChanged { from: 1, to: 0 },
+			])
+		);
+
+		assert_ok!(System::set_heap_pages(RawOrigin::Root.into(), 0));
+		assert_eq!(
+			System::check_upgrade(&[1, 2, 3, 4]),
+			Ok(vec![
+				UpgradeWarning::TransactionVersionChanged { from: 1, to: 2 },
+				UpgradeWarning::StateVersionChanged { from: 1, to: 0 },
+				UpgradeWarning::CodeExceedsHeap { code_size: 4, heap_size: 0 },
+			]),
+		);
+	});
+}
+
+#[test]
+fn upgrade_checks_report_removed_runtime_apis() {
+	let version = RuntimeVersion {
+		spec_name: "test".into(),
+		spec_version: 2,
+		transaction_version: 1,
+		state_version: 1,
+		apis: sp_version::create_apis_vec!([([1; 8], 1)]),
+		..Default::default()
+	};
+
+	new_test_ext_with_runtime_version(version).execute_with(|| {
+		assert_eq!(
+			System::check_upgrade(&[1, 2, 3, 4]),
+			Ok(vec![UpgradeWarning::RuntimeApiRemoved { api: [2; 8] }]),
+		);
+	});
+}
+
+#[test]
+fn upgrade_checks_report_downgraded_runtime_apis() {
+	// Upgraded and added runtime APIs are compatible.
+	let version = RuntimeVersion {
+		spec_name: "test".into(),
+		spec_version: 2,
+		transaction_version: 1,
+		state_version: 1,
+		apis: sp_version::create_apis_vec!([([1; 8], 2), ([2; 8], 1), ([3; 8], 1)]),
+		..Default::default()
+	};
+
+	new_test_ext_with_runtime_version(version).execute_with(|| {
+		assert_eq!(
+			System::check_upgrade(&[1, 2, 3, 4]),
+			Ok(vec![UpgradeWarning::RuntimeApiDowngraded { api: [2; 8], from: 2, to: 1 }]),
+		);
+	});
+}
+
 #[test]
 fn runtime_upgraded_with_set_storage() {
 	let executor = substrate_test_runtime_client::new_native_or_wasm_executor();
@@ -57,6 +57,9 @@ pub trait WeightInfo {
 	fn set_storage(i: u32, ) -> Weight;
 	fn kill_storage(i: u32, ) -> Weight;
 	fn kill_prefix(p: u32, ) -> Weight;
+	fn authorize_upgrade() -> Weight;
+	fn apply_authorized_upgrade() -> Weight;
+	fn authorize_apply_authorized_upgrade() -> Weight;
 }
 
 /// Weights for frame_system using the Substrate node and recommended hardware.
@@ -149,6 +152,41 @@ impl<T: crate::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
 			.saturating_add(Weight::from_parts(0, 70).saturating_mul(p.into()))
 	}
+	/// Storage: System AuthorizedUpgrade (r:0 w:1)
+	/// Proof: System AuthorizedUpgrade (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	fn authorize_upgrade() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 5_921_000 picoseconds.
+		Weight::from_parts(6_186_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: System AuthorizedUpgrade (r:1 w:1)
+	/// Proof: System AuthorizedUpgrade (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	/// Storage: System Digest (r:1 w:1)
+	/// Proof Skipped: System Digest (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: unknown `0x3a636f6465` (r:0 w:1)
+	/// Proof Skipped: unknown `0x3a636f6465` (r:0 w:1)
+	fn apply_authorized_upgrade() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `22`
+		//  Estimated: `1518`
+		// Minimum execution time: 86_724_119_000 picoseconds.
+		Weight::from_parts(89_531_307_000, 1518)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: System AuthorizedUpgrade (r:1 w:0)
+	/// Proof: System AuthorizedUpgrade (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	fn authorize_apply_authorized_upgrade() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `22`
+		//  Estimated: `1518`
+		// Minimum execution time: 85_893_802_000 picoseconds.
+		Weight::from_parts(88_627_114_000, 1518)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -240,4 +278,39 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
 			.saturating_add(Weight::from_parts(0, 70).saturating_mul(p.into()))
 	}
+	/// Storage: System AuthorizedUpgrade (r:0 w:1)
+	/// Proof: System AuthorizedUpgrade (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	fn authorize_upgrade() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 5_921_000 picoseconds.
+		Weight::from_parts(6_186_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: System AuthorizedUpgrade (r:1 w:1)
+	/// Proof: System AuthorizedUpgrade (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	/// Storage: System Digest (r:1 w:1)
+	/// Proof Skipped: System Digest (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: unknown `0x3a636f6465` (r:0 w:1)
+	/// Proof Skipped: unknown `0x3a636f6465` (r:0 w:1)
+	fn apply_authorized_upgrade() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `22`
+		//  Estimated: `1518`
+		// Minimum execution time: 86_724_119_000 picoseconds.
+		Weight::from_parts(89_531_307_000, 1518)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: System AuthorizedUpgrade (r:1 w:0)
+	/// Proof: System AuthorizedUpgrade (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	fn authorize_apply_authorized_upgrade() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `22`
+		//  Estimated: `1518`
+		// Minimum execution time: 85_893_802_000 picoseconds.
+		Weight::from_parts(88_627_114_000, 1518)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
 }
@@ -0,0 +1,99 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the tasks pallet.
+
+use super::*;
+use crate::Pallet as Tasks;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+/// Provides the tasks executed by the benchmarks of the tasks pallet.
+pub trait BenchmarkHelper<Task> {
+	/// Set up the state such that the returned task is valid and succeeds when it runs.
+	///
+	/// The benchmarks are skipped if there is no such task, e.g. because no pallet of the runtime
+	/// declares tasks.
+	fn valid_task() -> Option<Task>;
+
+	/// A task which is not valid.
+	fn invalid_task() -> Option<Task>;
+}
+
+impl<Task> BenchmarkHelper<Task> for () {
+	fn valid_task() -> Option<Task> {
+		None
+	}
+
+	fn invalid_task() -> Option<Task> {
+		None
+	}
+}
+
+fn valid_task<T: Config>() -> Result<T::RuntimeTask, BenchmarkError> {
+	T::BenchmarkHelper::valid_task().ok_or(BenchmarkError::Weightless)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	// A task which failed before is executed with a signed origin, so the failure is removed.
+	#[benchmark]
+	fn do_task() -> Result<(), BenchmarkError> {
+		let task = valid_task::<T>()?;
+		FailedTasks::<T>::insert(&task, frame_system::Pallet::<T>::block_number());
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), task.clone());
+
+		assert!(!FailedTasks::<T>::contains_key(&task));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn scan_task() -> Result<(), BenchmarkError> {
+		let task = valid_task::<T>()?;
+		let pending;
+
+		#[block]
+		{
+			pending = Tasks::<T>::is_pending(&task);
+		}
+
+		assert!(pending);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn prune_failed_task() -> Result<(), BenchmarkError> {
+		let task = T::BenchmarkHelper::invalid_task().ok_or(BenchmarkError::Weightless)?;
+		FailedTasks::<T>::insert(&task, frame_system::Pallet::<T>::block_number());
+
+		#[block]
+		{
+			Tasks::<T>::prune_failed_tasks(&mut WeightMeter::max_limit());
+		}
+
+		assert!(!FailedTasks::<T>::contains_key(&task));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Tasks, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,243 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Tasks Pallet
+//!
+//! Executes the tasks of other pallets.
+//!
+//! ## Overview
+//!
+//! Pallets declare background work in a `#[pallet::tasks]` section, which `construct_runtime!`
+//! aggregates into the `RuntimeTask` enum of the runtime. This pallet executes the valid tasks of
+//! the [`Config::RuntimeTask`]:
+//!
+//! - in [`Hooks::on_idle`], with the weight that remains in a block,
+//! - by the offchain worker, which submits them as unsigned [`Call::do_task`] transactions,
+//! - by anyone through [`Call::do_task`].
+//!
+//! Tasks are executed in a storage layer, so a failing task doesn't change any storage. A failed
+//! task is recorded in [`FailedTasks`] and only executed again through a signed
+//! [`Call::do_task`], since it would most likely fail again. At most [`Config::MaxTaskScan`]
+//! pending tasks are inspected per block by `on_idle` and the offchain worker.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(rustdoc::broken_intra_doc_links)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::BenchmarkHelper;
+
+use codec::Encode;
+use frame_support::{
+	pallet_prelude::*, storage::with_storage_layer, traits::Task, weights::WeightMeter,
+};
+use frame_system::{
+	offchain::{SendTransactionTypes, SubmitTransaction},
+	pallet_prelude::*,
+	RawOrigin,
+};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+const LOG_TARGET: &str = "runtime::tasks";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The tasks of all pallets, usually the `RuntimeTask` of `construct_runtime!`.
+		type RuntimeTask: Task;
+
+		/// The maximum number of pending tasks inspected per block by `on_idle` and the offchain
+		/// worker.
+		#[pallet::constant]
+		type MaxTaskScan: Get<u32>;
+
+		/// Weight information for the extrinsics and hooks of this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Provides the tasks executed by the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::RuntimeTask>;
+	}
+
+	/// The tasks whose last execution failed, with the block number of the failure.
+	///
+	/// They are skipped by `on_idle` and the offchain worker and rejected as unsigned transactions,
+	/// but can still be executed through a signed [`Call::do_task`]. Entries are removed once the
+	/// task succeeds or isn't valid anymore.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type FailedTasks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::RuntimeTask, BlockNumberFor<T>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A task was executed successfully.
+		TaskCompleted { task: T::RuntimeTask },
+		/// A task failed, its changes were reverted.
+		TaskFailed { task: T::RuntimeTask, err: DispatchError },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The task is not valid, e.g. because it was executed already.
+		InvalidTask,
+		/// The task failed before, it can only be executed again with a signed origin.
+		FailedTask,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let mut meter = WeightMeter::from_limit(remaining_weight);
+
+			for task in Self::pending_tasks() {
+				if meter.try_consume(T::WeightInfo::scan_task()).is_err() {
+					break
+				}
+				if !Self::is_pending(&task) {
+					continue
+				}
+				// Smaller tasks further down the list may still fit.
+				if meter.try_consume(Self::run_weight(&task)).is_err() {
+					continue
+				}
+				Self::run_task(task);
+			}
+
+			Self::prune_failed_tasks(&mut meter);
+			meter.consumed()
+		}
+
+		fn offchain_worker(_n: BlockNumberFor<T>) {
+			for task in Self::pending_tasks().filter(Self::is_pending) {
+				let call = Call::<T>::do_task { task };
+				if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+					.is_err()
+				{
+					log::error!(target: LOG_TARGET, "Unable to submit unsigned task transaction");
+				}
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Execute a valid task.
+		///
+		/// The origin must be signed, or none for the transactions of the offchain worker. Tasks
+		/// which failed before can only be executed with a signed origin. The call succeeds even if
+		/// the task fails, see [`Event::TaskFailed`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(Pallet::<T>::run_weight(task))]
+		pub fn do_task(origin: OriginFor<T>, task: T::RuntimeTask) -> DispatchResult {
+			let origin: Result<RawOrigin<T::AccountId>, _> = origin.into();
+			let signed = match origin {
+				Ok(RawOrigin::Signed(_)) => true,
+				Ok(RawOrigin::None) => false,
+				_ => return Err(DispatchError::BadOrigin),
+			};
+			ensure!(task.is_valid(), Error::<T>::InvalidTask);
+			ensure!(signed || !FailedTasks::<T>::contains_key(&task), Error::<T>::FailedTask);
+
+			Self::run_task(task);
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::do_task { task } = call else { return InvalidTransaction::Call.into() };
+			if !task.is_valid() {
+				return InvalidTransaction::Stale.into()
+			}
+			if FailedTasks::<T>::contains_key(task) {
+				return InvalidTransaction::Call.into()
+			}
+
+			ValidTransaction::with_tag_prefix("Tasks")
+				.and_provides(task.encode())
+				.longevity(5)
+				.propagate(true)
+				.build()
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The pending tasks inspected in a block.
+	fn pending_tasks() -> impl Iterator<Item = T::RuntimeTask> {
+		T::RuntimeTask::iter().take(T::MaxTaskScan::get() as usize)
+	}
+
+	/// Whether `task` is valid and didn't fail before, i.e. can be executed by `on_idle` and the
+	/// offchain worker.
+	fn is_pending(task: &T::RuntimeTask) -> bool {
+		task.is_valid() && !FailedTasks::<T>::contains_key(task)
+	}
+
+	/// The weight of [`Call::do_task`], including running the task and recording its outcome.
+	fn run_weight(task: &T::RuntimeTask) -> Weight {
+		T::WeightInfo::do_task().saturating_add(task.weight())
+	}
+
+	/// Execute a task in a storage layer and deposit its outcome.
+	fn run_task(task: T::RuntimeTask) {
+		match with_storage_layer(|| task.run()) {
+			Ok(()) => {
+				FailedTasks::<T>::remove(&task);
+				Self::deposit_event(Event::TaskCompleted { task });
+			},
+			Err(err) => {
+				log::debug!(target: LOG_TARGET, "Task {:?} failed: {:?}", task, err);
+				FailedTasks::<T>::insert(&task, frame_system::Pallet::<T>::block_number());
+				Self::deposit_event(Event::TaskFailed { task, err });
+			},
+		}
+	}
+
+	/// Remove the failed tasks which aren't valid anymore, with at most [`Config::MaxTaskScan`]
+	/// of them inspected.
+	fn prune_failed_tasks(meter: &mut WeightMeter) {
+		for task in FailedTasks::<T>::iter_keys().take(T::MaxTaskScan::get() as usize) {
+			if meter.try_consume(T::WeightInfo::prune_failed_task()).is_err() {
+				break
+			}
+			if !task.is_valid() {
+				FailedTasks::<T>::remove(&task);
+			}
+		}
+	}
+}
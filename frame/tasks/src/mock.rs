@@ -0,0 +1,159 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for the tasks pallet.
+
+#![cfg(test)]
+
+use super::*;
+use crate as pallet_tasks;
+
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU64, Everything},
+};
+use sp_runtime::{testing::TestXt, BuildStorage};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+/// A pallet declaring a task.
+#[frame_support::pallet]
+pub mod pallet_example {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	/// The numbers waiting to be added into the [`Total`].
+	#[pallet::storage]
+	pub type Numbers<T: Config> = StorageMap<_, Twox64Concat, u32, u32, OptionQuery>;
+
+	/// The sum of the added numbers.
+	#[pallet::storage]
+	pub type Total<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The total overflowed.
+		Overflow,
+	}
+
+	#[pallet::tasks]
+	impl<T: Config> Pallet<T> {
+		/// Add the number at `i` into the total.
+		#[pallet::task_index(0)]
+		#[pallet::task_list(Numbers::<T>::iter_keys())]
+		#[pallet::task_condition(|i: &u32| Numbers::<T>::contains_key(i))]
+		#[pallet::task_weight(Weight::from_parts(100, 100))]
+		pub fn add_number_into_total(i: u32) -> DispatchResult {
+			let number = Numbers::<T>::take(i).ok_or(DispatchError::CannotLookup)?;
+			Total::<T>::try_mutate(|total| {
+				*total = total.checked_add(number).ok_or(Error::<T>::Overflow)?;
+				Ok(())
+			})
+		}
+	}
+}
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Tasks: pallet_tasks::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
+		Example: pallet_example::{Pallet, Storage, Error<T>, Task},
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type BlockHashCount = ConstU64<250>;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type BaseCallFilter = Everything;
+	type PalletInfo = PalletInfo;
+	type OnSetCode = ();
+}
+
+impl<LocalCall> SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl pallet_example::Config for Test {}
+
+parameter_types! {
+	pub static MaxTaskScan: u32 = 10;
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeTask = RuntimeTask;
+	type MaxTaskScan = MaxTaskScan;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ExampleTasks;
+}
+
+/// Provides the task of [`pallet_example`] to the benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ExampleTasks;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<RuntimeTask> for ExampleTasks {
+	fn valid_task() -> Option<RuntimeTask> {
+		pallet_example::Numbers::<Test>::insert(0, 1);
+		Some(add_number_task(0))
+	}
+
+	fn invalid_task() -> Option<RuntimeTask> {
+		Some(add_number_task(1))
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// The task adding the number at `i` into the total.
+pub fn add_number_task(i: u32) -> RuntimeTask {
+	pallet_example::Task::add_number_into_total { i }.into()
+}
+
+/// Take the events of this pallet deposited so far.
+pub fn take_events() -> Vec<Event<Test>> {
+	let events = System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::Tasks(event) => Some(event),
+			_ => None,
+		})
+		.collect();
+	System::reset_events();
+	events
+}
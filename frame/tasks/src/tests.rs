@@ -0,0 +1,225 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(test)]
+
+use crate::{
+	mock::{
+		pallet_example::{Numbers, Total},
+		*,
+	},
+	Error, Event, FailedTasks, WeightInfo,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::*,
+	traits::{Hooks, Task, UnfilteredDispatchable},
+};
+use sp_core::offchain::{testing::TestTransactionPoolExt, TransactionPoolExt};
+
+#[test]
+fn runtime_task_aggregates_the_tasks_of_pallets() {
+	new_test_ext().execute_with(|| {
+		Numbers::<Test>::insert(1, 10);
+		Numbers::<Test>::insert(2, 20);
+
+		let tasks = RuntimeTask::iter().collect::<Vec<_>>();
+		assert_eq!(tasks.len(), 2);
+		assert!(tasks.contains(&add_number_task(1)) && tasks.contains(&add_number_task(2)));
+
+		let task = add_number_task(1);
+		assert!(task.is_valid());
+		assert!(!add_number_task(3).is_valid());
+		assert_eq!(task.weight(), Weight::from_parts(100, 100));
+		assert_eq!(task.task_index(), 0);
+		// The index of the pallet, the index of the task and its arguments.
+		assert_eq!(task.encode(), (2u8, 0u8, 1u32).encode());
+		assert_eq!(RuntimeTask::decode(&mut &task.encode()[..]), Ok(task));
+	});
+}
+
+#[test]
+fn do_task_executes_valid_tasks() {
+	new_test_ext().execute_with(|| {
+		Numbers::<Test>::insert(1, 10);
+
+		assert_noop!(
+			Tasks::do_task(RuntimeOrigin::root(), add_number_task(1)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Tasks::do_task(RuntimeOrigin::signed(1), add_number_task(1)));
+		assert_eq!(Total::<Test>::get(), 10);
+		assert!(!Numbers::<Test>::contains_key(1));
+		assert_eq!(take_events(), vec![Event::TaskCompleted { task: add_number_task(1) }]);
+
+		assert_noop!(
+			Tasks::do_task(RuntimeOrigin::none(), add_number_task(1)),
+			Error::<Test>::InvalidTask
+		);
+	});
+}
+
+#[test]
+fn failed_tasks_are_reverted() {
+	new_test_ext().execute_with(|| {
+		Numbers::<Test>::insert(1, 10);
+		Total::<Test>::put(u32::MAX);
+
+		assert_ok!(Tasks::do_task(RuntimeOrigin::none(), add_number_task(1)));
+		assert_eq!(Total::<Test>::get(), u32::MAX);
+		assert!(Numbers::<Test>::contains_key(1));
+		assert_eq!(
+			take_events(),
+			vec![Event::TaskFailed {
+				task: add_number_task(1),
+				err: pallet_example::Error::<Test>::Overflow.into(),
+			}]
+		);
+	});
+}
+
+#[test]
+fn failed_tasks_are_not_offered_again() {
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		Numbers::<Test>::insert(1, 10);
+		Total::<Test>::put(u32::MAX);
+
+		Tasks::on_idle(1, Weight::MAX);
+		assert_eq!(take_events().len(), 1);
+		assert_eq!(FailedTasks::<Test>::get(add_number_task(1)), Some(1));
+
+		// Neither retried in `on_idle` nor by the offchain worker.
+		Tasks::on_idle(2, Weight::MAX);
+		assert!(take_events().is_empty());
+		Tasks::offchain_worker(2);
+		assert!(pool_state.read().transactions.is_empty());
+
+		// Nor accepted as an unsigned transaction.
+		let call = crate::Call::<Test>::do_task { task: add_number_task(1) };
+		assert_eq!(
+			Tasks::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Call.into()
+		);
+		assert_noop!(
+			Tasks::do_task(RuntimeOrigin::none(), add_number_task(1)),
+			Error::<Test>::FailedTask
+		);
+
+		// A signed origin can execute it again once it succeeds.
+		Total::<Test>::put(0);
+		assert_ok!(Tasks::do_task(RuntimeOrigin::signed(1), add_number_task(1)));
+		assert_eq!(Total::<Test>::get(), 10);
+		assert_eq!(FailedTasks::<Test>::get(add_number_task(1)), None);
+	});
+}
+
+#[test]
+fn failed_tasks_are_pruned_once_invalid() {
+	new_test_ext().execute_with(|| {
+		Numbers::<Test>::insert(1, 10);
+		Total::<Test>::put(u32::MAX);
+
+		Tasks::on_idle(1, Weight::MAX);
+		assert!(FailedTasks::<Test>::contains_key(add_number_task(1)));
+
+		Numbers::<Test>::remove(1);
+		Tasks::on_idle(2, Weight::MAX);
+		assert!(!FailedTasks::<Test>::contains_key(add_number_task(1)));
+	});
+}
+
+#[test]
+fn on_idle_executes_tasks_that_fit() {
+	new_test_ext().execute_with(|| {
+		for i in 1..=3 {
+			Numbers::<Test>::insert(i, i);
+		}
+
+		// Two tasks fit into the remaining weight, the third one is only inspected.
+		let scan = <() as WeightInfo>::scan_task();
+		let run = <() as WeightInfo>::do_task().saturating_add(Weight::from_parts(100, 100));
+		let consumed = scan.saturating_mul(3).saturating_add(run.saturating_mul(2));
+		let weight = Tasks::on_idle(1, consumed.saturating_add(Weight::from_parts(50, 50)));
+		assert_eq!(weight, consumed);
+		assert_eq!(take_events().len(), 2);
+		assert_eq!(Numbers::<Test>::iter().count(), 1);
+
+		Tasks::on_idle(2, Weight::from_parts(50, 50));
+		assert!(take_events().is_empty());
+
+		Tasks::on_idle(3, Weight::MAX);
+		assert_eq!(take_events().len(), 1);
+		assert_eq!(Total::<Test>::get(), 6);
+	});
+}
+
+#[test]
+fn on_idle_inspects_at_most_max_task_scan_tasks() {
+	new_test_ext().execute_with(|| {
+		MaxTaskScan::set(1);
+		for i in 1..=3 {
+			Numbers::<Test>::insert(i, i);
+		}
+
+		Tasks::on_idle(1, Weight::MAX);
+		assert_eq!(take_events().len(), 1);
+		assert_eq!(Numbers::<Test>::iter().count(), 2);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_valid_tasks() {
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		Numbers::<Test>::insert(1, 10);
+		Numbers::<Test>::insert(2, 20);
+
+		Tasks::offchain_worker(1);
+		let calls = pool_state
+			.read()
+			.transactions
+			.iter()
+			.map(|tx| Extrinsic::decode(&mut &tx[..]).unwrap())
+			.map(|tx| {
+				assert!(tx.signature.is_none());
+				match tx.call {
+					RuntimeCall::Tasks(call) => call,
+					call => panic!("Unexpected call: {:?}", call),
+				}
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(calls.len(), 2);
+
+		for call in calls {
+			assert_ok!(Tasks::validate_unsigned(TransactionSource::External, &call));
+			assert_ok!(call.clone().dispatch_bypass_filter(RuntimeOrigin::none()));
+			assert_eq!(
+				Tasks::validate_unsigned(TransactionSource::External, &call),
+				InvalidTransaction::Stale.into()
+			);
+		}
+		assert_eq!(Total::<Test>::get(), 30);
+	});
+}
@@ -0,0 +1,131 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Autogenerated weights for pallet_tasks
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2023-07-11, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `runner-e8ezs4ez-project-145-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// ./target/production/substrate
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_tasks
+// --no-storage-info
+// --no-median-slopes
+// --no-min-squares
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./frame/tasks/src/weights.rs
+// --header=./HEADER-APACHE2
+// --template=./.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_tasks.
+pub trait WeightInfo {
+	fn do_task() -> Weight;
+	fn scan_task() -> Weight;
+	fn prune_failed_task() -> Weight;
+}
+
+/// Weights for pallet_tasks using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Tasks FailedTasks (r:1 w:1)
+	/// Proof Skipped: Tasks FailedTasks (max_values: None, max_size: None, mode: Measured)
+	fn do_task() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `139`
+		//  Estimated: `3604`
+		// Minimum execution time: 14_812_000 picoseconds.
+		Weight::from_parts(15_403_000, 3604)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tasks FailedTasks (r:1 w:0)
+	/// Proof Skipped: Tasks FailedTasks (max_values: None, max_size: None, mode: Measured)
+	fn scan_task() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3541`
+		// Minimum execution time: 4_207_000 picoseconds.
+		Weight::from_parts(4_458_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Storage: Tasks FailedTasks (r:1 w:1)
+	/// Proof Skipped: Tasks FailedTasks (max_values: None, max_size: None, mode: Measured)
+	fn prune_failed_task() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `139`
+		//  Estimated: `3604`
+		// Minimum execution time: 9_386_000 picoseconds.
+		Weight::from_parts(9_795_000, 3604)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Tasks FailedTasks (r:1 w:1)
+	/// Proof Skipped: Tasks FailedTasks (max_values: None, max_size: None, mode: Measured)
+	fn do_task() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `139`
+		//  Estimated: `3604`
+		// Minimum execution time: 14_812_000 picoseconds.
+		Weight::from_parts(15_403_000, 3604)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tasks FailedTasks (r:1 w:0)
+	/// Proof Skipped: Tasks FailedTasks (max_values: None, max_size: None, mode: Measured)
+	fn scan_task() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3541`
+		// Minimum execution time: 4_207_000 picoseconds.
+		Weight::from_parts(4_458_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Storage: Tasks FailedTasks (r:1 w:1)
+	/// Proof Skipped: Tasks FailedTasks (max_values: None, max_size: None, mode: Measured)
+	fn prune_failed_task() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `139`
+		//  Estimated: `3604`
+		// Minimum execution time: 9_386_000 picoseconds.
+		Weight::from_parts(9_795_000, 3604)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -20,8 +20,37 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg(feature = "try-runtime")]
 
+use codec::{Decode, Encode};
 pub use frame_support::traits::{TryStateSelect, UpgradeCheckSelect};
-use frame_support::weights::Weight;
+use frame_support::{
+	dispatch::{DispatchInfo, DispatchResultWithPostInfo},
+	weights::Weight,
+};
+use sp_runtime::{RuntimeDebug, TryRuntimeError};
+use sp_std::prelude::*;
+
+/// The origin to dispatch a call from in [`TryRuntime::execute_call`].
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+pub enum CallOrigin {
+	/// The root origin.
+	Root,
+	/// Signed by the account with the given SCALE encoded account id.
+	Signed(Vec<u8>),
+}
+
+/// The outcome of dispatching a call in [`TryRuntime::execute_call`].
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+pub struct CallOutcome {
+	/// The result of the dispatch, including the actual weight of the call.
+	pub result: DispatchResultWithPostInfo,
+	/// The dispatch info of the call, as declared before the dispatch.
+	pub info: DispatchInfo,
+	/// The fee the call would be charged if it was submitted in a transaction.
+	///
+	/// The length fee only covers the encoded call, not the signature and the signed extensions
+	/// of the transaction.
+	pub fee: u128,
+}
 
 sp_api::decl_runtime_apis! {
 	/// Runtime api for testing the execution of a runtime upgrade.
@@ -49,5 +78,14 @@ sp_api::decl_runtime_apis! {
 			signature_check: bool,
 			try_state: TryStateSelect,
 		) -> Weight;
+
+		/// Dispatch the SCALE encoded `call` from `origin` on top of the current state.
+		///
+		/// Only the events deposited by the call are left in `frame_system::Events` afterwards.
+		///
+		/// Fails without dispatching if the call or the account of the origin doesn't decode. A
+		/// failed dispatch is reported in [`CallOutcome::result`] instead.
+		#[api_version(2)]
+		fn execute_call(call: Vec<u8>, origin: CallOrigin) -> Result<CallOutcome, TryRuntimeError>;
 	}
 }
@@ -623,6 +623,58 @@ pub mod vrf {
 			self.extra = Some(extra);
 			self
 		}
+
+		/// Returns `true` if extra data is signed, see [`Self::with_extra`].
+		pub fn has_extra(&self) -> bool {
+			self.extra.is_some()
+		}
+	}
+
+	/// VRF input hashed to a curve point for the public key it is evaluated with.
+	///
+	/// Unlike the transcript it is derived from, the point can be encoded. This allows the VRF to
+	/// be evaluated with a secret key held elsewhere, e.g. by a remote signer.
+	#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct VrfInputPoint(pub [u8; 32]);
+
+	impl Public {
+		/// Hash `input` to the point the VRF of this key is evaluated on.
+		///
+		/// Returns `None` if `self` is not a valid public key.
+		pub fn vrf_input_point(&self, input: &VrfInput) -> Option<VrfInputPoint> {
+			let public = schnorrkel::PublicKey::from_bytes(self).ok()?;
+			Some(VrfInputPoint(public.vrf_hash(input.0.clone()).as_compressed().to_bytes()))
+		}
+	}
+
+	#[cfg(feature = "full_crypto")]
+	impl Pair {
+		/// The VRF output for an input already hashed with [`Public::vrf_input_point`].
+		///
+		/// Returns `None` if `point` is not a valid curve point.
+		pub fn vrf_output_from_point(&self, point: &VrfInputPoint) -> Option<VrfOutput> {
+			Some(VrfOutput(self.vrf_create_from_point(point)?.to_output()))
+		}
+
+		/// Sign an input already hashed with [`Public::vrf_input_point`], without extra data.
+		///
+		/// The signature is the same as the one of [`VrfSecret::vrf_sign`] for the sign data the
+		/// point was derived from. Returns `None` if `point` is not a valid curve point.
+		pub fn vrf_sign_point(&self, point: &VrfInputPoint) -> Option<VrfSignature> {
+			let inout = self.vrf_create_from_point(point)?;
+			let extra = merlin::Transcript::new(DEFAULT_EXTRA_DATA_LABEL);
+			let proof = self.0.dleq_proove(extra, &inout, true).0;
+
+			Some(VrfSignature { output: VrfOutput(inout.to_output()), proof: VrfProof(proof) })
+		}
+
+		fn vrf_create_from_point(
+			&self,
+			point: &VrfInputPoint,
+		) -> Option<schnorrkel::vrf::VRFInOut> {
+			let point = schnorrkel::vrf::VRFOutput::from_bytes(&point.0).ok()?;
+			self.0.secret.vrf_create_from_compressed_point(&point).ok()
+		}
 	}
 
 	/// VRF signature data
@@ -1089,6 +1141,21 @@ mod tests {
 		assert!(public.vrf_verify(&data, &signature));
 	}
 
+	#[test]
+	fn vrf_sign_input_point() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let public = pair.public();
+
+		let input = VrfTranscript::new(b"label", &[(b"domain1", b"data1")]);
+		let point = public.vrf_input_point(&input).unwrap();
+		let data = input.clone().into_sign_data();
+
+		let signature = pair.vrf_sign_point(&point).unwrap();
+		assert!(public.vrf_verify(&data, &signature));
+		assert_eq!(signature.output, pair.vrf_sign(&data).output);
+		assert_eq!(pair.vrf_output_from_point(&point), Some(pair.vrf_output(&input)));
+	}
+
 	#[test]
 	fn vrf_make_bytes_matches() {
 		let pair = Pair::from_seed(b"12345678901234567890123456789012");
@@ -34,6 +34,11 @@ pub enum CallContext {
 	Offchain,
 	/// The call is happening in some on-chain context like building or importing a block.
 	Onchain,
+	/// The call was issued by a JSON-RPC client, e.g. through `state_call`.
+	///
+	/// Behaves like [`Self::Offchain`], but the executor may additionally bound the resources
+	/// the call is allowed to consume.
+	Rpc,
 }
 
 /// Code execution engine.
@@ -326,11 +326,21 @@ pub trait Keystore: Send + Sync {
 	/// Insert a new secret key.
 	fn insert(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()>;
 
+	/// Remove the secret key of the given type matching the raw public key.
+	///
+	/// Returns `true` if a key was found and removed.
+	fn remove(&self, key_type: KeyTypeId, public: &[u8]) -> Result<bool, Error>;
+
 	/// List all supported keys of a given type.
 	///
 	/// Returns a set of public keys the signer supports in raw format.
 	fn keys(&self, key_type: KeyTypeId) -> Result<Vec<Vec<u8>>, Error>;
 
+	/// List all keys, regardless of their type.
+	///
+	/// Returns the key type and raw public key of every key the signer supports.
+	fn all_keys(&self) -> Result<Vec<(KeyTypeId, Vec<u8>)>, Error>;
+
 	/// Checks if the private keys for the given public key and key type combinations exist.
 	///
 	/// Returns `true` iff all private keys could be found.
@@ -601,10 +611,18 @@ impl<T: Keystore + ?Sized> Keystore for Arc<T> {
 		(**self).insert(key_type, suri, public)
 	}
 
+	fn remove(&self, key_type: KeyTypeId, public: &[u8]) -> Result<bool, Error> {
+		(**self).remove(key_type, public)
+	}
+
 	fn keys(&self, key_type: KeyTypeId) -> Result<Vec<Vec<u8>>, Error> {
 		(**self).keys(key_type)
 	}
 
+	fn all_keys(&self) -> Result<Vec<(KeyTypeId, Vec<u8>)>, Error> {
+		(**self).all_keys()
+	}
+
 	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
 		(**self).has_keys(public_keys)
 	}
@@ -331,6 +331,15 @@ impl Keystore for MemoryKeystore {
 		Ok(())
 	}
 
+	fn remove(&self, key_type: KeyTypeId, public: &[u8]) -> Result<bool, Error> {
+		let removed = self
+			.keys
+			.write()
+			.get_mut(&key_type)
+			.map_or(false, |keys| keys.remove(public).is_some());
+		Ok(removed)
+	}
+
 	fn keys(&self, key_type: KeyTypeId) -> Result<Vec<Vec<u8>>, Error> {
 		let keys = self
 			.keys
@@ -341,6 +350,16 @@ impl Keystore for MemoryKeystore {
 		Ok(keys)
 	}
 
+	fn all_keys(&self) -> Result<Vec<(KeyTypeId, Vec<u8>)>, Error> {
+		let keys = self
+			.keys
+			.read()
+			.iter()
+			.flat_map(|(key_type, keys)| keys.keys().map(|public| (*key_type, public.clone())))
+			.collect();
+		Ok(keys)
+	}
+
 	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
 		public_keys
 			.iter()
@@ -30,6 +30,10 @@ pub use types::*;
 
 mod v14;
 mod v15;
+pub use v15::{
+	PalletViewFunctionsMetadataV15, ViewFunctionMetadataV15, ViewFunctionParamMetadataV15,
+	TASK_ENUM_CUSTOM_METADATA_KEY, VIEW_FUNCTIONS_CUSTOM_METADATA_KEY,
+};
 
 /// Metadata V14.
 const V14: u32 = 14;
@@ -92,6 +96,7 @@ mod test {
 				call_enum_ty: meta_type::<()>(),
 				event_enum_ty: meta_type::<()>(),
 				error_enum_ty: meta_type::<()>(),
+				task_enum_ty: meta_type::<()>(),
 			},
 		}
 	}
@@ -115,4 +120,29 @@ mod test {
 
 		assert!(matches!(metadata.1, RuntimeMetadata::V15(_)));
 	}
+
+	#[test]
+	fn task_enum_is_in_the_custom_metadata_of_v15() {
+		use codec::{Compact, Decode};
+
+		#[derive(scale_info::TypeInfo)]
+		#[allow(dead_code)]
+		enum RuntimeTask {
+			Task,
+		}
+
+		let mut ir = ir_metadata();
+		ir.outer_enums.task_enum_ty = meta_type::<RuntimeTask>();
+		let metadata = match into_version(ir, V15).unwrap().1 {
+			RuntimeMetadata::V15(metadata) => metadata,
+			_ => unreachable!(),
+		};
+
+		let custom = &metadata.custom.map[TASK_ENUM_CUSTOM_METADATA_KEY];
+		let Compact(task_enum_ty) = Compact::<u32>::decode(&mut &custom.value[..]).unwrap();
+		assert_eq!(
+			metadata.types.resolve(task_enum_ty).unwrap().path.ident(),
+			Some("RuntimeTask".into())
+		);
+	}
 }
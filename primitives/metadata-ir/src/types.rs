@@ -127,6 +127,8 @@ pub struct PalletMetadataIR<T: Form = MetaForm> {
 	pub constants: Vec<PalletConstantMetadataIR<T>>,
 	/// Pallet error metadata.
 	pub error: Option<PalletErrorMetadataIR<T>>,
+	/// Pallet view functions metadata.
+	pub view_functions: Vec<PalletViewFunctionMetadataIR<T>>,
 	/// Define the index of the pallet, this index will be used for the encoding of pallet event,
 	/// call and origin variants.
 	pub index: u8,
@@ -145,6 +147,7 @@ impl IntoPortable for PalletMetadataIR {
 			event: self.event.map(|event| event.into_portable(registry)),
 			constants: registry.map_into_portable(self.constants),
 			error: self.error.map(|error| error.into_portable(registry)),
+			view_functions: registry.map_into_portable(self.view_functions),
 			index: self.index,
 			docs: registry.map_into_portable(self.docs),
 		}
@@ -394,6 +397,55 @@ impl IntoPortable for PalletConstantMetadataIR {
 	}
 }
 
+/// Metadata about a pallet view function.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub struct PalletViewFunctionMetadataIR<T: Form = MetaForm> {
+	/// Name of the view function.
+	pub name: T::String,
+	/// The identifier of the view function, used to execute it.
+	pub id: [u8; 32],
+	/// Parameters of the view function.
+	pub inputs: Vec<PalletViewFunctionParamMetadataIR<T>>,
+	/// Output of the view function.
+	pub output: T::Type,
+	/// Documentation of the view function.
+	pub docs: Vec<T::String>,
+}
+
+impl IntoPortable for PalletViewFunctionMetadataIR {
+	type Output = PalletViewFunctionMetadataIR<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		PalletViewFunctionMetadataIR {
+			name: self.name.into_portable(registry),
+			id: self.id,
+			inputs: registry.map_into_portable(self.inputs),
+			output: registry.register_type(&self.output),
+			docs: registry.map_into_portable(self.docs),
+		}
+	}
+}
+
+/// Metadata of a pallet view function parameter.
+#[derive(Clone, PartialEq, Eq, Encode, Debug)]
+pub struct PalletViewFunctionParamMetadataIR<T: Form = MetaForm> {
+	/// Parameter name.
+	pub name: T::String,
+	/// Parameter type.
+	pub ty: T::Type,
+}
+
+impl IntoPortable for PalletViewFunctionParamMetadataIR {
+	type Output = PalletViewFunctionParamMetadataIR<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		PalletViewFunctionParamMetadataIR {
+			name: self.name.into_portable(registry),
+			ty: registry.register_type(&self.ty),
+		}
+	}
+}
+
 /// Metadata about a pallet error.
 #[derive(Clone, PartialEq, Eq, Encode, Debug)]
 pub struct PalletErrorMetadataIR<T: Form = MetaForm> {
@@ -438,6 +490,8 @@ pub struct OuterEnumsIR<T: Form = MetaForm> {
 	/// - Decoding the 5 error bytes into this type will not always lead to all of the bytes being
 	///   consumed; many error types do not require all of the bytes to represent them fully.
 	pub error_enum_ty: T::Type,
+	/// The type of the outer `RuntimeTask` enum.
+	pub task_enum_ty: T::Type,
 }
 
 impl IntoPortable for OuterEnumsIR {
@@ -448,6 +502,7 @@ impl IntoPortable for OuterEnumsIR {
 			call_enum_ty: registry.register_type(&self.call_enum_ty),
 			event_enum_ty: registry.register_type(&self.event_enum_ty),
 			error_enum_ty: registry.register_type(&self.error_enum_ty),
+			task_enum_ty: registry.register_type(&self.task_enum_ty),
 		}
 	}
 }
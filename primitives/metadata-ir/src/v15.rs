@@ -20,28 +20,144 @@
 use crate::OuterEnumsIR;
 
 use super::types::{
-	ExtrinsicMetadataIR, MetadataIR, PalletMetadataIR, RuntimeApiMetadataIR,
-	RuntimeApiMethodMetadataIR, RuntimeApiMethodParamMetadataIR, SignedExtensionMetadataIR,
+	ExtrinsicMetadataIR, MetadataIR, PalletMetadataIR, PalletViewFunctionMetadataIR,
+	RuntimeApiMetadataIR, RuntimeApiMethodMetadataIR, RuntimeApiMethodParamMetadataIR,
+	SignedExtensionMetadataIR,
 };
 
+use codec::{Decode, Encode};
 use frame_metadata::v15::{
-	CustomMetadata, ExtrinsicMetadata, OuterEnums, PalletMetadata, RuntimeApiMetadata,
-	RuntimeApiMethodMetadata, RuntimeApiMethodParamMetadata, RuntimeMetadataV15,
-	SignedExtensionMetadata,
+	CustomMetadata, CustomValueMetadata, ExtrinsicMetadata, OuterEnums, PalletMetadata,
+	RuntimeApiMetadata, RuntimeApiMethodMetadata, RuntimeApiMethodParamMetadata,
+	RuntimeMetadataV15, SignedExtensionMetadata,
 };
+use scale_info::{
+	form::PortableForm,
+	meta_type,
+	prelude::{collections::BTreeMap, string::String, vec::Vec},
+	IntoPortable, Registry, TypeInfo,
+};
+
+/// The key of the custom metadata entry that lists the view functions of the pallets.
+///
+/// V15 has no place for view functions in the pallet metadata, so they are stored as a custom
+/// value of type `Vec<PalletViewFunctionsMetadataV15>`. The entry is only present if at least one
+/// pallet declares view functions.
+pub const VIEW_FUNCTIONS_CUSTOM_METADATA_KEY: &str = "view_functions";
+
+/// The key of the custom metadata entry that holds the type of the outer `RuntimeTask` enum.
+///
+/// The outer enums of V15 have no field for the task enum, so the id of its type in the type
+/// registry of the metadata is stored as a custom value of type `Compact<u32>`.
+pub const TASK_ENUM_CUSTOM_METADATA_KEY: &str = "task_enum_ty";
+
+/// The view functions of a pallet, as listed in the custom metadata of V15.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+pub struct PalletViewFunctionsMetadataV15 {
+	/// Pallet name.
+	pub pallet: String,
+	/// The view functions of the pallet.
+	pub view_functions: Vec<ViewFunctionMetadataV15>,
+}
+
+/// A view function, as listed in the custom metadata of V15.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+pub struct ViewFunctionMetadataV15 {
+	/// Name of the view function.
+	pub name: String,
+	/// The identifier of the view function, used to execute it.
+	pub id: [u8; 32],
+	/// Parameters of the view function.
+	pub inputs: Vec<ViewFunctionParamMetadataV15>,
+	/// The id of the output type in the type registry of the metadata.
+	#[codec(compact)]
+	pub output: u32,
+	/// Documentation of the view function.
+	pub docs: Vec<String>,
+}
+
+/// A view function parameter, as listed in the custom metadata of V15.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+pub struct ViewFunctionParamMetadataV15 {
+	/// Parameter name.
+	pub name: String,
+	/// The id of the parameter type in the type registry of the metadata.
+	#[codec(compact)]
+	pub ty: u32,
+}
+
+impl From<PalletViewFunctionMetadataIR<PortableForm>> for ViewFunctionMetadataV15 {
+	fn from(ir: PalletViewFunctionMetadataIR<PortableForm>) -> Self {
+		ViewFunctionMetadataV15 {
+			name: ir.name,
+			id: ir.id,
+			inputs: ir
+				.inputs
+				.into_iter()
+				.map(|input| ViewFunctionParamMetadataV15 { name: input.name, ty: input.ty.id })
+				.collect(),
+			output: ir.output.id,
+			docs: ir.docs,
+		}
+	}
+}
 
 impl From<MetadataIR> for RuntimeMetadataV15 {
 	fn from(ir: MetadataIR) -> Self {
-		RuntimeMetadataV15::new(
-			ir.pallets.into_iter().map(Into::into).collect(),
-			ir.extrinsic.into(),
-			ir.ty,
-			ir.apis.into_iter().map(Into::into).collect(),
-			ir.outer_enums.into(),
-			// Substrate does not collect yet the custom metadata fields.
-			// This allows us to extend the V15 easily.
-			CustomMetadata { map: Default::default() },
-		)
+		let view_functions = ir
+			.pallets
+			.iter()
+			.filter(|pallet| !pallet.view_functions.is_empty())
+			.map(|pallet| (pallet.name, pallet.view_functions.clone()))
+			.collect::<Vec<_>>();
+
+		// Same as `RuntimeMetadataV15::new`, but the view functions and the task enum are
+		// registered in the same type registry.
+		let mut registry = Registry::new();
+		let pallets = registry.map_into_portable(ir.pallets.into_iter().map(PalletMetadata::from));
+		let extrinsic = ExtrinsicMetadata::from(ir.extrinsic).into_portable(&mut registry);
+		let ty = registry.register_type(&ir.ty);
+		let apis = registry.map_into_portable(ir.apis.into_iter().map(RuntimeApiMetadata::from));
+		let task_enum_ty = registry.register_type(&ir.outer_enums.task_enum_ty);
+		let outer_enums = OuterEnums::from(ir.outer_enums).into_portable(&mut registry);
+
+		let mut custom = CustomMetadata { map: BTreeMap::new() };
+		custom.map.insert(
+			TASK_ENUM_CUSTOM_METADATA_KEY.into(),
+			CustomValueMetadata {
+				ty: registry.register_type(&meta_type::<codec::Compact<u32>>()),
+				value: codec::Compact(task_enum_ty.id).encode(),
+			},
+		);
+		if !view_functions.is_empty() {
+			let value = view_functions
+				.into_iter()
+				.map(|(pallet, view_functions)| PalletViewFunctionsMetadataV15 {
+					pallet: pallet.into(),
+					view_functions: view_functions
+						.into_iter()
+						.map(|view_function| view_function.into_portable(&mut registry).into())
+						.collect(),
+				})
+				.collect::<Vec<_>>();
+			custom.map.insert(
+				VIEW_FUNCTIONS_CUSTOM_METADATA_KEY.into(),
+				CustomValueMetadata {
+					ty: registry.register_type(&meta_type::<Vec<PalletViewFunctionsMetadataV15>>()),
+					value: value.encode(),
+				},
+			);
+		}
+
+		RuntimeMetadataV15 {
+			types: registry.into(),
+			pallets,
+			extrinsic,
+			ty,
+			apis,
+			outer_enums,
+			custom,
+		}
 	}
 }
 
@@ -83,6 +199,7 @@ impl From<PalletMetadataIR> for PalletMetadata {
 			error: ir.error.map(Into::into),
 			index: ir.index,
 			docs: ir.docs,
+			// Note: ir.view_functions are listed in the custom metadata.
 		}
 	}
 }
@@ -116,6 +233,7 @@ impl From<OuterEnumsIR> for OuterEnums {
 			call_enum_ty: ir.call_enum_ty,
 			event_enum_ty: ir.event_enum_ty,
 			error_enum_ty: ir.error_enum_ty,
+			// Note: ir.task_enum_ty is stored in the custom metadata.
 		}
 	}
 }
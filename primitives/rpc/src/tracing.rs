@@ -42,6 +42,26 @@ pub struct BlockTrace {
 	pub spans: Vec<Span>,
 	/// Vec of tracing events
 	pub events: Vec<Event>,
+	/// Statistics of the host functions called while executing the block, empty unless host
+	/// function profiling was requested.
+	#[serde(default)]
+	pub host_functions: Vec<HostFunctionStats>,
+}
+
+/// Aggregated statistics of a single host function called by the runtime.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HostFunctionStats {
+	/// Name of the host function
+	pub name: String,
+	/// Number of times the host function was called
+	pub calls: u64,
+	/// Total time spent inside of the host function, in nanoseconds
+	pub time_ns: u64,
+	/// Number of bytes read from the runtime memory by the host function
+	pub bytes_read: u64,
+	/// Number of bytes written into the runtime memory by the host function
+	pub bytes_written: u64,
 }
 
 /// Represents a tracing event, complete with recorded data.
@@ -72,6 +92,12 @@ pub struct Span {
 	pub target: String,
 	/// Indicates if the span is from wasm
 	pub wasm: bool,
+	/// Time between the start of the trace and the creation of the span, in nanoseconds
+	#[serde(default)]
+	pub start_ns: u64,
+	/// Total time spent inside of the span, in nanoseconds
+	#[serde(default)]
+	pub duration_ns: u64,
 }
 
 /// Holds associated values for a tracing span.
@@ -90,6 +116,53 @@ pub struct TraceError {
 	pub error: String,
 }
 
+/// Spans of a traced block in the Chrome trace-event format.
+///
+/// Can be loaded into `chrome://tracing` or Perfetto.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChromeTrace {
+	/// One complete event per span
+	pub trace_events: Vec<ChromeTraceEvent>,
+	/// Unit used to display the timestamps
+	pub display_time_unit: String,
+}
+
+/// A complete event (`"ph": "X"`) of the Chrome trace-event format.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChromeTraceEvent {
+	/// Name of the span
+	pub name: String,
+	/// Category, the target of the span
+	pub cat: String,
+	/// Phase, always `X` for complete events
+	pub ph: String,
+	/// Start of the span, in microseconds
+	pub ts: f64,
+	/// Duration of the span, in microseconds
+	pub dur: f64,
+	/// Process id
+	pub pid: u64,
+	/// Thread id
+	pub tid: u64,
+	/// Additional data of the span
+	pub args: FxHashMap<String, String>,
+}
+
+/// Output format of the `state_traceBlock` RPC.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceBlockFormat {
+	/// All recorded spans and events, see [`BlockTrace`]
+	#[default]
+	Json,
+	/// The spans in the Chrome trace-event format, see [`ChromeTrace`]
+	ChromeTrace,
+	/// The spans as folded stacks with their self time in nanoseconds, one stack per line, as
+	/// consumed by flamegraph tools like `inferno-flamegraph`
+	FoldedStacks,
+}
+
 /// Response for the `state_traceBlock` RPC.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -98,4 +171,8 @@ pub enum TraceBlockResponse {
 	TraceError(TraceError),
 	/// Successful block tracing response
 	BlockTrace(BlockTrace),
+	/// Successful block tracing response in the Chrome trace-event format
+	ChromeTrace(ChromeTrace),
+	/// Successful block tracing response as folded stacks
+	FoldedStacks(Vec<String>),
 }
@@ -30,7 +30,7 @@ use codec::{Decode, Encode};
 use frame_support::genesis_builder_helper::{build_config, create_default_config};
 use frame_support::{
 	construct_runtime,
-	dispatch::DispatchClass,
+	dispatch::{DispatchClass, DispatchResultWithPostInfo, PostDispatchInfo},
 	parameter_types,
 	traits::{ConstU32, ConstU64},
 	weights::{
@@ -62,7 +62,7 @@ use sp_runtime::{
 	create_runtime_str, impl_opaque_keys,
 	traits::{BlakeTwo256, Block as BlockT, DispatchInfoOf, NumberFor, Verify},
 	transaction_validity::{TransactionSource, TransactionValidity, TransactionValidityError},
-	ApplyExtrinsicResult, Perbill,
+	ApplyExtrinsicResult, ApplyExtrinsicResultWithInfo, Perbill,
 };
 #[cfg(any(feature = "std", test))]
 use sp_version::NativeVersion;
@@ -522,8 +522,8 @@ impl_runtime_apis! {
 			Executive::finalize_block()
 		}
 
-		fn inherent_extrinsics(_data: InherentData) -> Vec<<Block as BlockT>::Extrinsic> {
-			vec![]
+		fn inherent_extrinsics(data: InherentData) -> Vec<<Block as BlockT>::Extrinsic> {
+			data.create_extrinsics()
 		}
 
 		fn check_inherents(_block: Block, _data: InherentData) -> CheckInherentsResult {
@@ -537,6 +537,35 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl frame_system_rpc_runtime_api::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller>
+		for Runtime
+	{
+		fn dry_run_extrinsic(
+			extrinsic: <Block as BlockT>::Extrinsic,
+		) -> frame_system_rpc_runtime_api::DryRunEffects<
+			ApplyExtrinsicResultWithInfo<PostDispatchInfo>,
+			RuntimeEvent,
+		> {
+			Executive::dry_run_extrinsic::<()>(extrinsic)
+		}
+
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: RuntimeCall,
+		) -> frame_system_rpc_runtime_api::DryRunEffects<DispatchResultWithPostInfo, RuntimeEvent> {
+			Executive::dry_run_call::<()>(origin.into(), call)
+		}
+	}
+
+	impl frame_support::view_functions::runtime_api::RuntimeViewFunction<Block> for Runtime {
+		fn execute_view_function(
+			id: frame_support::view_functions::ViewFunctionId,
+			input: Vec<u8>,
+		) -> Result<Vec<u8>, frame_support::view_functions::ViewFunctionDispatchError> {
+			Runtime::execute_view_function(id, input)
+		}
+	}
+
 	impl self::TestAPI<Block> for Runtime {
 		fn balance_of(id: AccountId) -> u64 {
 			Balances::free_balance(id)
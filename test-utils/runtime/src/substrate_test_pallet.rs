@@ -21,10 +21,11 @@
 //! functioning runtime. Some calls are allowed to be submitted as unsigned extrinsics, however most
 //! of them requires signing. Refer to `pallet::Call` for further details.
 
+use codec::DecodeLimit;
 use frame_support::{pallet_prelude::*, storage};
 use sp_core::sr25519::Public;
 use sp_runtime::{
-	traits::Hash,
+	traits::{Dispatchable, Hash},
 	transaction_validity::{
 		InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction,
 	},
@@ -35,6 +36,12 @@ pub use self::pallet::*;
 
 const LOG_TARGET: &str = "substrate_test_pallet";
 
+/// The inherent identifier of the calls impersonated through the manual-seal
+/// `engine_impersonate` RPC.
+///
+/// The data is a list of SCALE encoded `(origin, call)` pairs.
+pub const IMPERSONATED_CALLS_INHERENT_IDENTIFIER: InherentIdentifier = *b"impcalls";
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	use super::*;
@@ -69,6 +76,14 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T> {
+		/// The authorities set at genesis.
+		pub fn get_authorities() -> Vec<Public> {
+			Authorities::<T>::get()
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Legacy call used in transaction pool benchmarks.
@@ -189,6 +204,24 @@ pub mod pallet {
 		pub fn read_and_panic(_origin: OriginFor<T>, count: u32) -> DispatchResult {
 			Self::execute_read(count, true)
 		}
+
+		/// Dispatch every call on behalf of its account, without a signature.
+		///
+		/// Only created as an inherent from [`IMPERSONATED_CALLS_INHERENT_IDENTIFIER`].
+		#[pallet::call_index(12)]
+		#[pallet::weight((Weight::zero(), DispatchClass::Mandatory))]
+		pub fn impersonate(
+			origin: OriginFor<T>,
+			calls: Vec<(T::AccountId, <T as frame_system::Config>::RuntimeCall)>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			for (who, call) in calls {
+				if let Err(e) = call.dispatch(frame_system::RawOrigin::Signed(who).into()) {
+					log::debug!(target: LOG_TARGET, "impersonated call failed: {:?}", e.error);
+				}
+			}
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -217,6 +250,36 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::inherent]
+	impl<T: Config> ProvideInherent for Pallet<T> {
+		type Call = Call<T>;
+		type Error = MakeFatalError<()>;
+		const INHERENT_IDENTIFIER: InherentIdentifier = IMPERSONATED_CALLS_INHERENT_IDENTIFIER;
+
+		fn create_inherent(data: &InherentData) -> Option<Self::Call> {
+			let calls: Vec<(Vec<u8>, Vec<u8>)> =
+				data.get_data(&Self::INHERENT_IDENTIFIER).ok().flatten()?;
+			let calls = calls
+				.into_iter()
+				.filter_map(|(origin, call)| {
+					Some((
+						T::AccountId::decode(&mut &origin[..]).ok()?,
+						<T as frame_system::Config>::RuntimeCall::decode_with_depth_limit(
+							sp_api::MAX_EXTRINSIC_DEPTH,
+							&mut &call[..],
+						)
+						.ok()?,
+					))
+				})
+				.collect();
+			Some(Call::impersonate { calls })
+		}
+
+		fn is_inherent(call: &Self::Call) -> bool {
+			matches!(call, Call::impersonate { .. })
+		}
+	}
+
 	#[pallet::validate_unsigned]
 	impl<T: Config> ValidateUnsigned for Pallet<T> {
 		type Call = Call<T>;
@@ -237,6 +300,14 @@ pub mod pallet {
 				_ => Err(TransactionValidityError::Invalid(InvalidTransaction::Call)),
 			}
 		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			// Inherents are never validated through the transaction pool.
+			if Self::is_inherent(call) {
+				return Ok(())
+			}
+			Self::validate_unsigned(TransactionSource::InBlock, call).map(drop)
+		}
 	}
 }
 
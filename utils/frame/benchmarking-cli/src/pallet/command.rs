@@ -25,7 +25,7 @@ use frame_support::traits::StorageInfo;
 use linked_hash_map::LinkedHashMap;
 use sc_cli::{execution_method_from_cli, CliConfiguration, Result, SharedParams};
 use sc_client_db::BenchmarkingState;
-use sc_executor::WasmExecutor;
+use sc_executor::{profiling::HostFunctionProfile, WasmExecutor};
 use sc_service::Configuration;
 use serde::Serialize;
 use sp_core::{
@@ -323,6 +323,7 @@ impl PalletCmd {
 				String::from_utf8(pallet.clone()).expect("Encoded from String; qed"),
 				String::from_utf8(extrinsic.clone()).expect("Encoded from String; qed"),
 			);
+			let mut host_function_profile = HostFunctionProfile::default();
 			let all_components = if components.is_empty() {
 				vec![Default::default()]
 			} else {
@@ -445,25 +446,37 @@ impl PalletCmd {
 				// Finally run a bunch of loops to get extrinsic timing information.
 				for r in 0..self.external_repeat {
 					let state = &state_without_tracking;
-					let result = StateMachine::new(
-						state, // todo remove tracking
-						&mut changes,
-						&executor,
-						"Benchmark_dispatch_benchmark",
-						&(
-							&pallet.clone(),
-							&extrinsic.clone(),
-							&selected_components.clone(),
-							false, // dont run verification code for final values
-							self.repeat,
-						)
-							.encode(),
-						&mut extensions(),
-						&sp_state_machine::backend::BackendRuntimeCode::new(state)
-							.runtime_code()?,
-						CallContext::Offchain,
+					let backend_runtime_code =
+						sp_state_machine::backend::BackendRuntimeCode::new(state);
+					let runtime_code = backend_runtime_code.runtime_code()?;
+					let input = (
+						&pallet.clone(),
+						&extrinsic.clone(),
+						&selected_components.clone(),
+						false, // dont run verification code for final values
+						self.repeat,
 					)
-					.execute()
+						.encode();
+					let mut execute = || {
+						StateMachine::new(
+							state, // todo remove tracking
+							&mut changes,
+							&executor,
+							"Benchmark_dispatch_benchmark",
+							&input,
+							&mut extensions(),
+							&runtime_code,
+							CallContext::Offchain,
+						)
+						.execute()
+					};
+					let result = if self.profile_host_functions {
+						let (result, profile) = sc_executor::profiling::collect(execute);
+						host_function_profile.merge(&profile);
+						result
+					} else {
+						execute()
+					}
 					.map_err(|e| format!("Error executing runtime benchmark: {}", e))?;
 
 					let batch =
@@ -496,6 +509,16 @@ impl PalletCmd {
 					}
 				}
 			}
+
+			if self.profile_host_functions {
+				println!(
+					"Host functions called by {}::{} ({} µs in total):\n{}",
+					String::from_utf8_lossy(&pallet),
+					String::from_utf8_lossy(&extrinsic),
+					host_function_profile.total_time().as_micros(),
+					host_function_profile,
+				);
+			}
 		}
 
 		// Combine all of the benchmark results, so that benchmarks of the same pallet/function
@@ -125,6 +125,14 @@ pub struct PalletCmd {
 	#[arg(long)]
 	pub extra: bool,
 
+	/// Profile the host functions called by the timed runs of every benchmark and print a
+	/// per-benchmark summary of their call counts, time and bytes moved.
+	///
+	/// Profiling adds overhead to every host function call and therefore skews the measured
+	/// extrinsic times. Do not use the resulting weights.
+	#[arg(long)]
+	pub profile_host_functions: bool,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: sc_cli::SharedParams,
@@ -0,0 +1,117 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API computing the runtime statistics of a block.
+//!
+//! The statistics are read from the state of `frame_system` after the block and from the events
+//! it deposited. They are computed by the runtime itself, so that clients don't depend on the
+//! types of the runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::traits::{ExtrinsicCall, GetCallMetadata};
+use frame_system::{ConsumedWeight, EventRecord, Phase};
+use sp_runtime::{
+	traits::{Block as BlockT, Extrinsic},
+	RuntimeDebug, SaturatedConversion,
+};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// The pallet reported for failed dispatches that don't belong to an extrinsic.
+pub const UNKNOWN_PALLET: &[u8] = b"unknown";
+
+/// Runtime statistics of a block.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BlockStats {
+	/// Weight consumed per dispatch class, read from `System::BlockWeight`.
+	pub weight: ConsumedWeight,
+	/// Length of the block in bytes.
+	pub length: u64,
+	/// Number of extrinsics per pallet name.
+	pub extrinsics: BTreeMap<Vec<u8>, u64>,
+	/// Number of failed dispatches per pallet name, from the `System::ExtrinsicFailed` events.
+	pub failed_dispatches: BTreeMap<Vec<u8>, u64>,
+	/// Fees paid for the extrinsics including the tips, from the
+	/// `TransactionPayment::TransactionFeePaid` events.
+	pub fees: u128,
+	/// Tips paid for the extrinsics.
+	pub tips: u128,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to compute the runtime statistics of a block.
+	pub trait BlockStatsApi {
+		/// The [`BlockStats`] of `block`, which must be the block the state is at.
+		fn block_stats(block: Block) -> BlockStats;
+	}
+}
+
+/// Compute the [`BlockStats`] of `block` from the current state, for the runtime `R`.
+///
+/// Meant to implement [`BlockStatsApi`], the state must be the one right after `block` was
+/// executed.
+pub fn block_stats<R, Block>(block: &Block) -> BlockStats
+where
+	R: frame_system::Config + pallet_transaction_payment::Config,
+	<R as frame_system::Config>::RuntimeEvent:
+		TryInto<frame_system::Event<R>> + TryInto<pallet_transaction_payment::Event<R>>,
+	Block: BlockT,
+	Block::Extrinsic: ExtrinsicCall,
+	<Block::Extrinsic as Extrinsic>::Call: GetCallMetadata,
+{
+	let pallets = block
+		.extrinsics()
+		.iter()
+		.map(|xt| xt.call().get_call_metadata().pallet_name.as_bytes())
+		.collect::<Vec<_>>();
+
+	let mut stats = BlockStats {
+		weight: frame_system::Pallet::<R>::block_weight(),
+		length: block.encoded_size() as u64,
+		..Default::default()
+	};
+	for pallet in &pallets {
+		*stats.extrinsics.entry(pallet.to_vec()).or_default() += 1;
+	}
+
+	for record in frame_system::Pallet::<R>::read_events_no_consensus() {
+		let EventRecord { phase, event, .. } = *record;
+		let system_event = TryInto::<frame_system::Event<R>>::try_into(event.clone());
+		if let Ok(frame_system::Event::ExtrinsicFailed { .. }) = system_event {
+			let pallet = match phase {
+				Phase::ApplyExtrinsic(index) => pallets.get(index as usize).copied(),
+				_ => None,
+			};
+			*stats
+				.failed_dispatches
+				.entry(pallet.unwrap_or(UNKNOWN_PALLET).to_vec())
+				.or_default() += 1;
+		}
+
+		let payment_event = TryInto::<pallet_transaction_payment::Event<R>>::try_into(event);
+		if let Ok(pallet_transaction_payment::Event::TransactionFeePaid {
+			actual_fee, tip, ..
+		}) = payment_event
+		{
+			stats.fees = stats.fees.saturating_add(actual_fee.saturated_into());
+			stats.tips = stats.tips.saturating_add(tip.saturated_into());
+		}
+	}
+
+	stats
+}
@@ -0,0 +1,306 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus metrics with runtime statistics of every finalized block.
+//!
+//! The statistics are computed by the runtime through the [`BlockStatsApi`], from the state of
+//! `frame_system` after the block and from the events it deposited. Unlike the
+//! `dev_getBlockStats` RPC, the block is not re-executed. Only finalized blocks are reported, so
+//! that blocks retracted by a reorg are never counted.
+
+use frame_support::dispatch::DispatchClass;
+use futures::StreamExt;
+use prometheus_endpoint::{
+	register, CounterVec, Gauge, GaugeVec, Opts, PrometheusError, Registry, F64, U64,
+};
+use sc_client_api::{BlockBackend, BlockchainEvents, FinalityNotification};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_core::traits::SpawnNamed;
+use sp_runtime::traits::Block as BlockT;
+use std::{iter, sync::Arc};
+
+pub use frame_block_stats_runtime_api::{BlockStats, BlockStatsApi};
+
+const LOG_TARGET: &str = "block-stats";
+
+/// Parameters of the block statistics metrics.
+#[derive(Default, Debug, Clone, clap::Args)]
+pub struct BlockStatsParams {
+	/// Export runtime statistics of every finalized block as Prometheus metrics.
+	///
+	/// These are the weight consumed per dispatch class, the block length, the extrinsics and
+	/// failed dispatches per pallet and the fees paid. Requires the Prometheus endpoint and a
+	/// runtime implementing the `BlockStatsApi`.
+	#[arg(long)]
+	pub block_stats_metrics: bool,
+}
+
+/// Read the [`BlockStats`] of the block `hash`.
+///
+/// Returns `None` if the block isn't available or its runtime doesn't implement the
+/// [`BlockStatsApi`].
+pub fn block_stats<Block, Client>(
+	client: &Client,
+	hash: Block::Hash,
+) -> sp_blockchain::Result<Option<BlockStats>>
+where
+	Block: BlockT,
+	Client: BlockBackend<Block> + ProvideRuntimeApi<Block>,
+	Client::Api: BlockStatsApi<Block>,
+{
+	let Some(block) = client.block(hash)?.map(|signed| signed.block) else { return Ok(None) };
+
+	let api = client.runtime_api();
+	if !api.has_api::<dyn BlockStatsApi<Block>>(hash)? {
+		return Ok(None)
+	}
+
+	Ok(Some(api.block_stats(hash, block)?))
+}
+
+/// The blocks finalized by `notification`, from the oldest to the newest.
+///
+/// Every block is finalized by exactly one notification, blocks of retracted forks by none.
+fn finalized_blocks<Block: BlockT>(
+	notification: &FinalityNotification<Block>,
+) -> impl Iterator<Item = Block::Hash> + '_ {
+	notification.tree_route.iter().copied().chain(iter::once(notification.hash))
+}
+
+/// Prometheus metrics of the [`BlockStats`] of the finalized blocks.
+pub struct Metrics {
+	weight_ref_time: GaugeVec<U64>,
+	weight_proof_size: GaugeVec<U64>,
+	length: Gauge<U64>,
+	extrinsics: CounterVec<U64>,
+	failed_dispatches: CounterVec<U64>,
+	fees: Gauge<F64>,
+	tips: Gauge<F64>,
+	/// The exact total of the fees reported so far, only converted to `f64` when exported.
+	fees_total: u128,
+	/// The exact total of the tips reported so far, only converted to `f64` when exported.
+	tips_total: u128,
+}
+
+impl Metrics {
+	/// Register the metrics in `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			weight_ref_time: register(
+				GaugeVec::new(
+					Opts::new(
+						"substrate_block_stats_weight_ref_time",
+						"Reference time consumed by the last finalized block per dispatch class",
+					),
+					&["class"],
+				)?,
+				registry,
+			)?,
+			weight_proof_size: register(
+				GaugeVec::new(
+					Opts::new(
+						"substrate_block_stats_weight_proof_size",
+						"Proof size consumed by the last finalized block per dispatch class",
+					),
+					&["class"],
+				)?,
+				registry,
+			)?,
+			length: register(
+				Gauge::new(
+					"substrate_block_stats_length_bytes",
+					"Length of the last finalized block",
+				)?,
+				registry,
+			)?,
+			extrinsics: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_block_stats_extrinsics_total",
+						"Number of extrinsics in finalized blocks per pallet",
+					),
+					&["pallet"],
+				)?,
+				registry,
+			)?,
+			failed_dispatches: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_block_stats_failed_dispatches_total",
+						"Number of failed dispatches in finalized blocks per pallet",
+					),
+					&["pallet"],
+				)?,
+				registry,
+			)?,
+			fees: register(
+				Gauge::new(
+					"substrate_block_stats_fees",
+					"Fees paid in the blocks finalized since the start, including the tips",
+				)?,
+				registry,
+			)?,
+			tips: register(
+				Gauge::new(
+					"substrate_block_stats_tips",
+					"Tips paid in the blocks finalized since the start",
+				)?,
+				registry,
+			)?,
+			fees_total: 0,
+			tips_total: 0,
+		})
+	}
+
+	/// Report the `stats` of a finalized block.
+	pub fn report(&mut self, stats: &BlockStats) {
+		for class in DispatchClass::all() {
+			let label = match class {
+				DispatchClass::Normal => "normal",
+				DispatchClass::Operational => "operational",
+				DispatchClass::Mandatory => "mandatory",
+			};
+			let weight = stats.weight.get(*class);
+			self.weight_ref_time.with_label_values(&[label]).set(weight.ref_time());
+			self.weight_proof_size.with_label_values(&[label]).set(weight.proof_size());
+		}
+		self.length.set(stats.length);
+		for (pallet, count) in &stats.extrinsics {
+			let pallet = String::from_utf8_lossy(pallet);
+			self.extrinsics.with_label_values(&[&pallet]).inc_by(*count);
+		}
+		for (pallet, count) in &stats.failed_dispatches {
+			let pallet = String::from_utf8_lossy(pallet);
+			self.failed_dispatches.with_label_values(&[&pallet]).inc_by(*count);
+		}
+		self.fees_total = self.fees_total.saturating_add(stats.fees);
+		self.tips_total = self.tips_total.saturating_add(stats.tips);
+		self.fees.set(self.fees_total as f64);
+		self.tips.set(self.tips_total as f64);
+	}
+}
+
+/// Spawn the task reporting the [`BlockStats`] of every finalized block, if enabled by `params`.
+pub fn spawn<Block, Client>(
+	params: BlockStatsParams,
+	client: Arc<Client>,
+	registry: Option<&Registry>,
+	spawner: &impl SpawnNamed,
+) -> Result<(), PrometheusError>
+where
+	Block: BlockT,
+	Client: BlockBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ BlockchainEvents<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: BlockStatsApi<Block>,
+{
+	if !params.block_stats_metrics {
+		return Ok(())
+	}
+	let Some(registry) = registry else {
+		log::warn!(
+			target: LOG_TARGET,
+			"Block statistics are only exported with the Prometheus endpoint enabled",
+		);
+		return Ok(())
+	};
+
+	let mut metrics = Metrics::register(registry)?;
+	let mut notifications = client.finality_notification_stream();
+	let task = async move {
+		while let Some(notification) = notifications.next().await {
+			for hash in finalized_blocks(&notification) {
+				match block_stats(&*client, hash) {
+					Ok(Some(stats)) => metrics.report(&stats),
+					Ok(None) => {},
+					Err(e) => log::debug!(
+						target: LOG_TARGET,
+						"Failed to read the statistics of block {}: {}",
+						hash,
+						e,
+					),
+				}
+			}
+		}
+	};
+	spawner.spawn("block-stats", None, Box::pin(task));
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+	use sc_block_builder::BlockBuilderProvider;
+	use substrate_test_runtime_client::{prelude::*, runtime::Hash, sp_consensus::BlockOrigin};
+
+	fn import(client: &mut Arc<TestClient>, parent: Hash, change: u8) -> Hash {
+		let mut builder = client.new_block_at(parent, Default::default(), false).unwrap();
+		builder.push_storage_change(vec![change], Some(vec![change])).unwrap();
+		let block = builder.build().unwrap().block;
+		let hash = block.hash();
+		block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		hash
+	}
+
+	#[test]
+	fn finalized_blocks_are_reported_once() {
+		let mut client = Arc::new(substrate_test_runtime_client::new());
+		let mut finality = client.finality_notification_stream();
+		let genesis = client.genesis_hash();
+
+		let a1 = import(&mut client, genesis, 1);
+		let _b1 = import(&mut client, genesis, 2);
+		let a2 = import(&mut client, a1, 3);
+		let a3 = import(&mut client, a2, 4);
+
+		client.finalize_block(a2, None).unwrap();
+		let notification = block_on(finality.next()).unwrap();
+		assert_eq!(finalized_blocks(&notification).collect::<Vec<_>>(), vec![a1, a2]);
+
+		client.finalize_block(a3, None).unwrap();
+		let notification = block_on(finality.next()).unwrap();
+		assert_eq!(finalized_blocks(&notification).collect::<Vec<_>>(), vec![a3]);
+	}
+
+	#[test]
+	fn fees_are_totalled_exactly() {
+		let registry = Registry::new();
+		let mut metrics = Metrics::register(&registry).unwrap();
+		let stats = |fees: u128| BlockStats {
+			length: 100,
+			extrinsics: [(b"Balances".to_vec(), 2)].into(),
+			fees,
+			tips: fees / 2,
+			..Default::default()
+		};
+
+		metrics.report(&stats(1 << 53));
+		// Adding these to the exported `f64` directly would round them away.
+		metrics.report(&stats(1));
+		metrics.report(&stats(1));
+
+		assert_eq!(metrics.fees.get(), ((1u64 << 53) + 2) as f64);
+		assert_eq!(metrics.tips.get(), (1u64 << 52) as f64);
+		assert_eq!(metrics.extrinsics.with_label_values(&["Balances"]).get(), 6);
+		assert_eq!(metrics.length.get(), 100);
+	}
+}
@@ -18,7 +18,7 @@
 //! # Remote Externalities
 //!
 //! An equivalent of `sp_io::TestExternalities` that can load its state from a remote substrate
-//! based chain, or a local state snapshot file.
+//! based chain, a local state snapshot file, or the database of a local node.
 
 use async_recursion::async_recursion;
 use codec::{Compact, Decode, Encode};
@@ -28,17 +28,19 @@ use jsonrpsee::{
 	http_client::{HttpClient, HttpClientBuilder},
 };
 use log::*;
+use sc_client_db::{BlocksPruning, DatabaseSettings, DatabaseSource};
+use sc_executor_common::runtime_blob::RuntimeBlob;
 use serde::de::DeserializeOwned;
 use sp_core::{
 	hashing::twox_128,
 	hexdisplay::HexDisplay,
 	storage::{
-		well_known_keys::{is_default_child_storage_key, DEFAULT_CHILD_STORAGE_KEY_PREFIX},
+		well_known_keys::{self, is_default_child_storage_key, DEFAULT_CHILD_STORAGE_KEY_PREFIX},
 		ChildInfo, ChildType, PrefixedStorageKey, StorageData, StorageKey,
 	},
 };
 use sp_runtime::{
-	traits::{Block as BlockT, HashingFor},
+	traits::{Block as BlockT, HashingFor, One},
 	StateVersion,
 };
 use sp_state_machine::TestExternalities;
@@ -61,6 +63,8 @@ type SnapshotVersion = Compact<u16>;
 const LOG_TARGET: &str = "remote-ext";
 const DEFAULT_HTTP_ENDPOINT: &str = "https://rpc.polkadot.io:443";
 const SNAPSHOT_VERSION: SnapshotVersion = Compact(3);
+/// The cache size in MiB of a local RocksDb database.
+const LOCAL_DB_CACHE_SIZE: usize = 1024;
 
 /// The snapshot that we store on disk.
 #[derive(Decode, Encode)]
@@ -135,6 +139,8 @@ pub enum Mode<B: BlockT> {
 	Offline(OfflineConfig),
 	/// Prefer using a snapshot file if it exists, else use a remote server.
 	OfflineOrElseOnline(OfflineConfig, OnlineConfig<B>),
+	/// Local. Reads the database of a node, which may be running.
+	Local(LocalConfig<B>),
 }
 
 impl<B: BlockT> Default for Mode<B> {
@@ -152,6 +158,82 @@ pub struct OfflineConfig {
 	pub state_snapshot: SnapshotConfig,
 }
 
+/// Configuration of the local execution.
+///
+/// The database of a node is read at a given block. It is opened read-only, as a secondary
+/// instance for RocksDb, so the node may be running.
+#[derive(Clone)]
+pub struct LocalConfig<B: BlockT> {
+	/// The path to the database of the node, e.g. `<base-path>/chains/<chain>/db/full` for RocksDb
+	/// or `<base-path>/chains/<chain>/paritydb/full` for ParityDb.
+	pub path: PathBuf,
+	/// The block hash at which to read the state. Will be the latest finalized block of the
+	/// database if not provided.
+	pub at: Option<B::Hash>,
+}
+
+impl<B: BlockT> LocalConfig<B> {
+	pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+		Self { path: path.into(), at: None }
+	}
+}
+
+/// Open the database of a local node read-only, see [`LocalConfig`].
+fn open_local_backend<B: BlockT>(path: &Path) -> Result<sc_client_db::Backend<B>, &'static str> {
+	info!(target: LOG_TARGET, "Opening local database at {:?}", path);
+	sc_client_db::Backend::<B>::open_existing(
+		DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: None,
+			source: DatabaseSource::Auto {
+				paritydb_path: path.to_path_buf(),
+				rocksdb_path: path.to_path_buf(),
+				cache_size: LOCAL_DB_CACHE_SIZE,
+			},
+			blocks_pruning: BlocksPruning::KeepAll,
+		},
+		0,
+	)
+	.map_err(|e| {
+		error!(target: LOG_TARGET, "Error = {:?}", e);
+		"failed to open the local database"
+	})
+}
+
+/// Read the block following the block `hash` from the database of a local node at `path`.
+///
+/// Like [`Mode::Local`], the database is opened read-only.
+pub fn next_local_block<B: BlockT>(path: &Path, hash: B::Hash) -> Result<B, &'static str> {
+	use sc_client_api::Backend as _;
+	use sp_blockchain::{Backend as _, HeaderBackend as _};
+
+	let backend = open_local_backend::<B>(path)?;
+	let blockchain = backend.blockchain();
+	let read_err = |e| {
+		error!(target: LOG_TARGET, "Error = {:?}", e);
+		"failed to read the local database"
+	};
+
+	let number = blockchain
+		.number(hash)
+		.map_err(read_err)?
+		.ok_or("the block is not in the local database")?;
+	let next_hash = blockchain
+		.hash(number + One::one())
+		.map_err(read_err)?
+		.ok_or("the next block is not in the local database")?;
+	let header = blockchain
+		.header(next_hash)
+		.map_err(read_err)?
+		.ok_or("the local database has no header for the next block")?;
+	let extrinsics = blockchain
+		.body(next_hash)
+		.map_err(read_err)?
+		.ok_or("the local database has no body for the next block")?;
+
+	Ok(B::new(header, extrinsics))
+}
+
 /// Description of the transport protocol (for online execution).
 #[derive(Debug, Clone)]
 pub enum Transport {
@@ -998,9 +1080,104 @@ where
 		Ok(RemoteExternalities { inner_ext, block_hash })
 	}
 
+	/// Read the entire state at `config.at` from the database of a local node, including the child
+	/// tries.
+	fn do_load_local(
+		&mut self,
+		config: LocalConfig<B>,
+	) -> Result<RemoteExternalities<B>, &'static str> {
+		use sc_client_api::Backend as _;
+		use sp_blockchain::HeaderBackend as _;
+		use sp_state_machine::{Backend as _, IterArgs};
+
+		let mut sp = Spinner::with_timer(Spinners::Dots, "Reading local database...".into());
+		let start = Instant::now();
+		let backend = open_local_backend::<B>(&config.path)?;
+
+		let block_hash = config.at.unwrap_or_else(|| backend.blockchain().info().finalized_hash);
+		info!(target: LOG_TARGET, "reading the local state at block {:?}", block_hash);
+		let state = backend.state_at(block_hash).map_err(|e| {
+			error!(target: LOG_TARGET, "Error = {:?}", e);
+			"the local database has no state at the given block"
+		})?;
+
+		let state_version = match self.overwrite_state_version {
+			Some(state_version) => state_version,
+			None => {
+				let code = state
+					.storage(well_known_keys::CODE)
+					.map_err(|_| "failed to read the code from the local database")?
+					.ok_or("the local database has no code at the given block")?;
+				Self::state_version_of_code(&code)?
+			},
+		};
+
+		let read_err = |e| {
+			error!(target: LOG_TARGET, "Error = {:?}", e);
+			"failed to read the local database"
+		};
+		let mut inner_ext = TestExternalities::new_with_code_and_state(
+			Default::default(),
+			Default::default(),
+			state_version,
+		);
+		let mut top_kv = Vec::new();
+		let mut child_roots = Vec::new();
+		for pair in state.pairs(Default::default()).map_err(read_err)? {
+			let (key, value) = pair.map_err(read_err)?;
+			// The child roots are inserted along with the data of their child trie.
+			if is_default_child_storage_key(&key) {
+				child_roots.push(key);
+			} else {
+				top_kv.push((key, value));
+			}
+		}
+		let top_keys = top_kv.len();
+		inner_ext.batch_insert(top_kv);
+
+		for prefixed_top_key in child_roots {
+			let un_prefixed =
+				match ChildType::from_prefixed_key(&PrefixedStorageKey::new_ref(&prefixed_top_key))
+				{
+					Some((ChildType::ParentKeyId, storage_key)) => storage_key.to_vec(),
+					None => {
+						log::error!(target: LOG_TARGET, "invalid key: {:?}", prefixed_top_key);
+						return Err("Invalid child key")
+					},
+				};
+			let info = ChildInfo::new_default(&un_prefixed);
+			let mut args = IterArgs::default();
+			args.child_info = Some(info.clone());
+			for pair in state.pairs(args).map_err(read_err)? {
+				let (key, value) = pair.map_err(read_err)?;
+				inner_ext.insert_child(info.clone(), key, value);
+			}
+		}
+
+		sp.stop_with_message(format!(
+			"✅ Read {} top keys from the local database ({:.2}s)",
+			top_keys,
+			start.elapsed().as_secs_f32()
+		));
+
+		Ok(RemoteExternalities { inner_ext, block_hash })
+	}
+
+	/// Read the state version of the runtime `code` from its embedded version.
+	fn state_version_of_code(code: &[u8]) -> Result<StateVersion, &'static str> {
+		let blob = RuntimeBlob::uncompress_if_needed(code)
+			.map_err(|_| "failed to decompress the runtime code")?;
+		let version = sc_executor::read_embedded_version(&blob)
+			.map_err(|_| "failed to read the version of the runtime code")?
+			.ok_or("the runtime code has no embedded version")?;
+
+		Ok(version.state_version())
+	}
+
 	pub(crate) async fn pre_build(mut self) -> Result<RemoteExternalities<B>, &'static str> {
 		let mut ext = match self.mode.clone() {
 			Mode::Offline(config) => self.do_load_offline(config)?,
+			Mode::Local(config) => self.do_load_local(config)?,
 			Mode::Online(_) => self.do_load_remote().await?,
 			Mode::OfflineOrElseOnline(offline_config, _) => {
 				match self.do_load_offline(offline_config) {
@@ -1146,6 +1323,68 @@ mod tests {
 			.expect("Can't read state snapshot file")
 			.execute_with(|| assert!(sp_io::storage::get(&some_key).is_none()));
 	}
+
+	#[tokio::test]
+	async fn can_load_local_database() {
+		use sc_client_api::{backend::NewBlockState, Backend as _, BlockImportOperation as _};
+		use sp_core::storage::Storage;
+		use sp_runtime::traits::Header as _;
+
+		init_logger();
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("paritydb");
+
+		// write a finalized genesis block with some state, then close the database.
+		let block_hash = {
+			let backend = sc_client_db::Backend::<Block>::new(
+				DatabaseSettings {
+					trie_cache_maximum_size: None,
+					state_pruning: None,
+					source: DatabaseSource::ParityDb { path: path.clone() },
+					blocks_pruning: BlocksPruning::KeepAll,
+				},
+				0,
+			)
+			.unwrap();
+			let mut op = backend.begin_operation().unwrap();
+			let storage = Storage {
+				top: vec![(b"key".to_vec(), b"value".to_vec())].into_iter().collect(),
+				children_default: Default::default(),
+			};
+			let state_root = op.reset_storage(storage, StateVersion::V1).unwrap();
+			let header = <Block as BlockT>::Header::new(
+				0,
+				Default::default(),
+				state_root,
+				Default::default(),
+				Default::default(),
+			);
+			let block_hash = header.hash();
+			op.set_block_data(header, Some(vec![]), None, None, NewBlockState::Final)
+				.unwrap();
+			backend.commit_operation(op).unwrap();
+			block_hash
+		};
+
+		let mut ext = Builder::<Block>::new()
+			.mode(Mode::Local(LocalConfig::new(&path)))
+			.overwrite_state_version(StateVersion::V1)
+			.build()
+			.await
+			.expect("Can't read the local database");
+		assert_eq!(ext.block_hash, block_hash);
+		ext.execute_with(|| {
+			assert_eq!(sp_io::storage::get(b"key").map(|v| v.to_vec()), Some(b"value".to_vec()))
+		});
+
+		// reading at a block that is not in the database fails.
+		assert!(Builder::<Block>::new()
+			.mode(Mode::Local(LocalConfig { path, at: Some(Hash::repeat_byte(1)) }))
+			.overwrite_state_version(StateVersion::V1)
+			.build()
+			.await
+			.is_err());
+	}
 }
 
 #[cfg(all(test, feature = "remote-test"))]
@@ -156,6 +156,21 @@ where
 		}
 	}
 
+	/// Same as [`Self::new`], but the first returned header is the one finalized right after
+	/// `last_returned`, even if it was finalized before the subscription started.
+	pub fn new_after(
+		header_provider: &'a HP,
+		subscription: HS,
+		last_returned: <Block::Header as HeaderT>::Hash,
+	) -> Self {
+		Self {
+			header_provider,
+			subscription,
+			fetched_headers: VecDeque::new(),
+			last_returned: Some(last_returned),
+		}
+	}
+
 	/// Reads next finalized header from the subscription. If some headers (without justification)
 	/// have been skipped, fetches them as well. Returns number of headers that have been fetched.
 	///
@@ -266,4 +281,20 @@ mod tests {
 		}
 		assert_eq!(None, headers.next().await);
 	}
+
+	#[tokio::test]
+	async fn finalized_headers_start_after_the_given_header() {
+		let heights_in_subscription = vec![6, 7];
+		// Consecutive headers will be requested in the reversed order.
+		let heights_not_in_subscription = vec![5, 4, 3];
+
+		let provider = MockHeaderProvider(Arc::new(Mutex::new(heights_not_in_subscription.into())));
+		let subscription = MockHeaderSubscription(heights_in_subscription.into());
+		let mut headers = FinalizedHeaders::new_after(&provider, subscription, headers()[2].hash());
+
+		for h in 3..8 {
+			assert_eq!(h, headers.next().await.unwrap().number);
+		}
+		assert_eq!(None, headers.next().await);
+	}
 }
@@ -26,15 +26,16 @@ use jsonrpsee::{
 	types::error::{CallError, ErrorObject},
 };
 
+use sc_client_api::{CallExecutor, ExecutorProvider};
 use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
 use sp_api::ApiExt;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::HeaderBackend;
-use sp_core::{hexdisplay::HexDisplay, Bytes};
+use sp_core::{hexdisplay::HexDisplay, traits::CallContext, Bytes};
 use sp_runtime::{legacy, traits};
 
-pub use frame_system_rpc_runtime_api::AccountNonceApi;
+pub use frame_system_rpc_runtime_api::{AccountNonceApi, DryRunApi};
 
 /// System RPC methods.
 #[rpc(client, server)]
@@ -52,6 +53,31 @@ pub trait SystemApi<BlockHash, AccountId, Nonce> {
 	async fn dry_run(&self, extrinsic: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
 }
 
+/// RPC methods to dry-run extrinsics and calls, reporting all of their effects.
+#[rpc(client, server)]
+pub trait SystemDryRunApi<BlockHash> {
+	/// Dry run an extrinsic at a given block, or at the best block if none is given.
+	///
+	/// Return the SCALE encoded `DryRunEffects` of applying it: its `ApplyExtrinsicResultWithInfo`
+	/// along with the events it emitted and the messages it sent.
+	#[method(name = "system_dryRunExtrinsic")]
+	async fn dry_run_extrinsic(&self, extrinsic: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+	/// Dry run the SCALE encoded call from the SCALE encoded origin at a given block, or at the
+	/// best block if none is given. Both are decoded by the runtime, as the types of its
+	/// `DryRunApi`.
+	///
+	/// Return the SCALE encoded `DryRunEffects` of dispatching it: its `DispatchResultWithPostInfo`
+	/// along with the events it emitted and the messages it sent.
+	#[method(name = "system_dryRunCall")]
+	async fn dry_run_call(
+		&self,
+		origin: Bytes,
+		call: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Bytes>;
+}
+
 /// Error type of this RPC api.
 pub enum Error {
 	/// The transaction was not decodable.
@@ -118,7 +144,9 @@ where
 		at: Option<<Block as traits::Block>::Hash>,
 	) -> RpcResult<Bytes> {
 		self.deny_unsafe.check_if_safe()?;
-		let api = self.client.runtime_api();
+		let mut api = self.client.runtime_api();
+		// Run under the execution limits of runtime calls issued through RPC.
+		api.set_call_context(CallContext::Rpc);
 		let best_hash = at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash);
@@ -174,6 +202,106 @@ where
 	}
 }
 
+/// An implementation of the dry-run RPC methods on full client.
+pub struct DryRun<C, B> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> DryRun<C, B> {
+	/// Create new `DryRun` given client.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> SystemDryRunApiServer<<Block as traits::Block>::Hash> for DryRun<C, Block>
+where
+	C: ExecutorProvider<Block>,
+	C: HeaderBackend<Block>,
+	C: Send + Sync + 'static,
+	Block: traits::Block,
+{
+	async fn dry_run_extrinsic(
+		&self,
+		extrinsic: Bytes,
+		at: Option<<Block as traits::Block>::Hash>,
+	) -> RpcResult<Bytes> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let uxt: <Block as traits::Block>::Extrinsic =
+			Decode::decode(&mut &*extrinsic).map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::DecodeError.into(),
+					"Unable to dry run extrinsic.",
+					Some(e.to_string()),
+				))
+			})?;
+
+		let effects = self.call(at, "DryRunApi_dry_run_extrinsic", &uxt.encode()).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to dry run extrinsic.",
+				Some(e.to_string()),
+			))
+		})?;
+
+		Ok(effects)
+	}
+
+	async fn dry_run_call(
+		&self,
+		origin: Bytes,
+		call: Bytes,
+		at: Option<<Block as traits::Block>::Hash>,
+	) -> RpcResult<Bytes> {
+		self.deny_unsafe.check_if_safe()?;
+
+		// The arguments of a runtime API are SCALE encoded as a tuple, which is the concatenation
+		// of the encoded origin and call.
+		let args = [&origin[..], &call[..]].concat();
+		let effects = self.call(at, "DryRunApi_dry_run_call", &args).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to dry run call.",
+				Some(e.to_string()),
+			))
+		})?;
+
+		Ok(effects)
+	}
+}
+
+impl<C, Block> DryRun<C, Block>
+where
+	C: ExecutorProvider<Block> + HeaderBackend<Block>,
+	Block: traits::Block,
+{
+	/// Call the [`DryRunApi`] `method` with the SCALE encoded `args`, at the given block or at the
+	/// best block if none is given.
+	///
+	/// The arguments and the result stay SCALE encoded, so that the runtime types the API is
+	/// generic over don't need to be known by the node.
+	fn call(
+		&self,
+		at: Option<<Block as traits::Block>::Hash>,
+		method: &str,
+		args: &[u8],
+	) -> sp_blockchain::Result<Bytes> {
+		let best_hash = at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash);
+
+		// Run under the execution limits of runtime calls issued through RPC.
+		self.client
+			.executor()
+			.call(best_hash, method, args, CallContext::Rpc)
+			.map(Into::into)
+	}
+}
+
 /// Adjust account nonce from state, so that tx with the nonce will be
 /// placed after all ready txpool transactions.
 fn adjust_nonce<P, AccountId, Nonce>(pool: &P, account: AccountId, nonce: Nonce) -> Nonce
@@ -215,15 +343,20 @@ mod tests {
 	use super::*;
 
 	use assert_matches::assert_matches;
+	use frame_support::dispatch::{DispatchResultWithPostInfo, PostDispatchInfo, RawOrigin};
+	use frame_system_rpc_runtime_api::DryRunEffects;
 	use futures::executor::block_on;
 	use jsonrpsee::{core::Error as JsonRpseeError, types::error::CallError};
 	use sc_transaction_pool::BasicPool;
 	use sp_runtime::{
 		generic::BlockId,
 		transaction_validity::{InvalidTransaction, TransactionValidityError},
-		ApplyExtrinsicResult,
+		ApplyExtrinsicResult, ApplyExtrinsicResultWithInfo,
+	};
+	use substrate_test_runtime_client::{
+		runtime::{BalancesCall, OriginCaller, RuntimeCall, RuntimeEvent, Transfer},
+		AccountKeyring,
 	};
-	use substrate_test_runtime_client::{runtime::Transfer, AccountKeyring};
 
 	#[tokio::test]
 	async fn should_return_next_nonce_for_some_account() {
@@ -334,4 +467,83 @@ mod tests {
 		let apply_res: ApplyExtrinsicResult = Decode::decode(&mut bytes.as_ref()).unwrap();
 		assert_eq!(apply_res, Err(TransactionValidityError::Invalid(InvalidTransaction::Future)));
 	}
+
+	#[tokio::test]
+	async fn dry_run_extrinsic_should_return_effects() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let dry_run = DryRun::new(client, DenyUnsafe::No);
+
+		let tx = Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Bob.into(),
+			amount: 5,
+			nonce: 0,
+		}
+		.into_unchecked_extrinsic();
+
+		// when
+		let bytes = dry_run
+			.dry_run_extrinsic(tx.encode().into(), None)
+			.await
+			.expect("Call is successful");
+
+		// then
+		let effects: DryRunEffects<ApplyExtrinsicResultWithInfo<PostDispatchInfo>, RuntimeEvent> =
+			Decode::decode(&mut bytes.as_ref()).unwrap();
+		assert_matches!(effects.result, Ok(Ok(_)));
+		assert!(!effects.events.is_empty());
+		assert!(effects.forwarded_messages.is_empty());
+	}
+
+	#[tokio::test]
+	async fn dry_run_call_should_return_effects() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let dry_run = DryRun::new(client, DenyUnsafe::No);
+
+		let origin = OriginCaller::system(RawOrigin::Signed(AccountKeyring::Alice.into()));
+		let call = RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+			dest: AccountKeyring::Bob.into(),
+			value: 5,
+		});
+
+		// when
+		let bytes = dry_run
+			.dry_run_call(origin.encode().into(), call.encode().into(), None)
+			.await
+			.expect("Call is successful");
+
+		// then
+		let effects: DryRunEffects<DispatchResultWithPostInfo, RuntimeEvent> =
+			Decode::decode(&mut bytes.as_ref()).unwrap();
+		assert_matches!(effects.result, Ok(_));
+		assert!(!effects.events.is_empty());
+
+		// the origin is decoded by the runtime
+		let res = dry_run.dry_run_call(vec![0xff].into(), call.encode().into(), None).await;
+		assert_matches!(res, Err(JsonRpseeError::Call(CallError::Custom(e))) => {
+			assert_eq!(e.code(), i32::from(Error::RuntimeError));
+			assert!(e.data().unwrap().get().contains("Bad input data provided to dry_run_call"));
+		});
+	}
+
+	#[tokio::test]
+	async fn dry_run_call_should_deny_unsafe() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let dry_run = DryRun::new(client, DenyUnsafe::Yes);
+
+		// when
+		let res = dry_run.dry_run_call(vec![].into(), vec![].into(), None).await;
+		assert_matches!(res, Err(JsonRpseeError::Call(CallError::Custom(e))) => {
+			assert!(e.message().contains("RPC call is unsafe to be called externally"));
+		});
+	}
 }
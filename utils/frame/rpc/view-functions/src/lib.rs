@@ -0,0 +1,206 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC to execute the view functions of FRAME pallets.
+//!
+//! View functions are declared with `#[pallet::view_functions]` and listed with their identifier
+//! and types in the `view_functions` custom entry of the V15 metadata of the runtime, so a single
+//! generic RPC method is enough to execute all of them.
+
+use std::sync::Arc;
+
+use codec::DecodeAll;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{traits::CallContext, Bytes};
+use sp_runtime::traits::Block as BlockT;
+
+pub use frame_support::view_functions::{
+	runtime_api::RuntimeViewFunction, ViewFunctionDispatchError, ViewFunctionId,
+};
+
+/// View functions RPC methods.
+#[rpc(client, server)]
+pub trait ViewFunctionsApi<BlockHash> {
+	/// Execute a view function at the given block, or the best block if `None`.
+	///
+	/// `id` is the 32 bytes identifier of the view function from the metadata and `input` the
+	/// SCALE-encoded tuple of its arguments. Returns the SCALE-encoded result.
+	#[method(name = "viewFunctions_execute")]
+	fn execute(&self, id: Bytes, input: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The identifier of the view function was not decodable.
+	DecodeError,
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The view function could not be executed.
+	DispatchError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+			Error::DispatchError => 3,
+		}
+	}
+}
+
+/// An implementation of the view functions RPC methods on full client.
+pub struct ViewFunctions<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> ViewFunctions<C, B> {
+	/// Create new `ViewFunctions` given client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> ViewFunctionsApiServer<<Block as BlockT>::Hash> for ViewFunctions<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: RuntimeViewFunction<Block>,
+{
+	fn execute(&self, id: Bytes, input: Bytes, at: Option<Block::Hash>) -> RpcResult<Bytes> {
+		let mut api = self.client.runtime_api();
+		// Run under the execution limits of runtime calls issued through RPC.
+		api.set_call_context(CallContext::Rpc);
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let id = ViewFunctionId::decode_all(&mut &*id).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode the view function identifier.",
+				Some(format!("{:?}", e)),
+			))
+		})?;
+
+		let result = api.execute_view_function(at_hash, id, input.to_vec()).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to execute the view function.",
+				Some(e.to_string()),
+			))
+		})?;
+
+		result.map(Into::into).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::DispatchError.into(),
+				"Unable to execute the view function.",
+				Some(format!("{:?}", e)),
+			))
+			.into()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use assert_matches::assert_matches;
+	use codec::{Decode, Encode};
+	use jsonrpsee::{core::Error as JsonRpseeError, types::error::CallError};
+	use sp_core::sr25519;
+	use substrate_test_runtime_client::{
+		runtime::{substrate_test_pallet, Runtime},
+		Sr25519Keyring,
+	};
+
+	fn view_function_id(name: &str) -> ViewFunctionId {
+		substrate_test_pallet::Pallet::<Runtime>::pallet_view_functions_metadata()
+			.into_iter()
+			.find(|view_function| view_function.name == name)
+			.expect("view function is in the metadata")
+			.id
+			.into()
+	}
+
+	#[test]
+	fn execute_should_return_the_result_of_the_view_function() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let view_functions = ViewFunctions::new(client);
+		let id: [u8; 32] = view_function_id("get_authorities").into();
+
+		// when
+		let result = view_functions.execute(id.to_vec().into(), Bytes(vec![]), None).unwrap();
+
+		// then
+		let authorities = Vec::<sr25519::Public>::decode(&mut &result[..]).unwrap();
+		assert_eq!(
+			authorities,
+			vec![
+				Sr25519Keyring::Alice.public(),
+				Sr25519Keyring::Bob.public(),
+				Sr25519Keyring::Charlie.public(),
+			]
+		);
+	}
+
+	#[test]
+	fn execute_should_fail_to_decode_the_id() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let view_functions = ViewFunctions::new(client);
+
+		// when
+		let res = view_functions.execute(Bytes(vec![1; 31]), Bytes(vec![]), None);
+
+		// then
+		assert_matches!(res, Err(JsonRpseeError::Call(CallError::Custom(e))) => {
+			assert_eq!(e.code(), i32::from(Error::DecodeError));
+			assert_eq!(e.message(), "Unable to decode the view function identifier.");
+		});
+	}
+
+	#[test]
+	fn execute_should_report_an_unknown_view_function() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let view_functions = ViewFunctions::new(client);
+		let id = ViewFunctionId { prefix: [0; 16], suffix: [0; 16] };
+
+		// when
+		let res = view_functions.execute(id.encode().into(), Bytes(vec![]), None);
+
+		// then
+		assert_matches!(res, Err(JsonRpseeError::Call(CallError::Custom(e))) => {
+			assert_eq!(e.code(), i32::from(Error::DispatchError));
+			assert!(e.data().unwrap().get().contains("NotFound"));
+		});
+	}
+}
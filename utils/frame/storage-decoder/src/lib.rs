@@ -0,0 +1,408 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Storage Decoder
+//!
+//! Decodes the top-level storage keys and values of a FRAME runtime to JSON, using the metadata
+//! of the runtime, and lists the differences between decoded values.
+
+use codec::{Compact, Decode};
+use frame_metadata::{
+	v14::{StorageEntryType, StorageHasher},
+	RuntimeMetadata, RuntimeMetadataPrefixed,
+};
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::Value;
+use sp_core::{
+	bytes::to_hex,
+	crypto::{AccountId32, Ss58Codec},
+	hashing::twox_128,
+};
+use std::collections::BTreeMap;
+
+/// A storage item declared in the runtime metadata.
+pub struct StorageItem {
+	/// The prefix of the pallet that declares the item.
+	pub pallet: String,
+	/// The name of the item.
+	pub name: String,
+	ty: StorageEntryType<PortableForm>,
+}
+
+/// A top-level storage key, decoded through the metadata.
+pub struct DecodedKey<'a> {
+	/// The storage item of the key.
+	pub item: &'a StorageItem,
+	/// The keys of a map entry, empty for a plain storage value.
+	pub keys: Vec<Value>,
+	value_ty: u32,
+}
+
+impl DecodedKey<'_> {
+	/// The name of the key, e.g.
+	/// `System.Account[5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY]`.
+	pub fn name(&self) -> String {
+		entry_name(&self.item.pallet, &self.item.name, &self.keys)
+	}
+}
+
+/// The name of the entry with `keys` of the storage item `item` of `pallet`.
+pub fn entry_name(pallet: &str, item: &str, keys: &[Value]) -> String {
+	let mut name = format!("{}.{}", pallet, item);
+	for key in keys {
+		match key {
+			Value::String(key) => name.push_str(&format!("[{}]", key)),
+			key => name.push_str(&format!("[{}]", key)),
+		}
+	}
+	name
+}
+
+/// Decodes storage keys and values using the metadata of a runtime.
+pub struct StorageDecoder {
+	types: PortableRegistry,
+	/// The storage items, keyed by the hashed pallet prefix and item name.
+	items: BTreeMap<[u8; 32], StorageItem>,
+}
+
+impl StorageDecoder {
+	/// Create a new instance with the output of the `Metadata_metadata` runtime API.
+	pub fn new(encoded: &[u8]) -> Result<Self, String> {
+		let metadata = Vec::<u8>::decode(&mut &encoded[..])
+			.and_then(|metadata| RuntimeMetadataPrefixed::decode(&mut &metadata[..]))
+			.map_err(|e| format!("Invalid runtime metadata: {}", e))?;
+
+		let (types, storages) = match metadata.1 {
+			RuntimeMetadata::V14(metadata) => (
+				metadata.types,
+				metadata.pallets.into_iter().filter_map(|p| p.storage).collect::<Vec<_>>(),
+			),
+			RuntimeMetadata::V15(metadata) => (
+				metadata.types,
+				metadata.pallets.into_iter().filter_map(|p| p.storage).collect::<Vec<_>>(),
+			),
+			_ => return Err("Unsupported runtime metadata, V14 or V15 is required".into()),
+		};
+
+		let mut items = BTreeMap::new();
+		for storage in storages {
+			for entry in storage.entries {
+				let mut key = [0; 32];
+				key[..16].copy_from_slice(&twox_128(storage.prefix.as_bytes()));
+				key[16..].copy_from_slice(&twox_128(entry.name.as_bytes()));
+				items.insert(
+					key,
+					StorageItem { pallet: storage.prefix.clone(), name: entry.name, ty: entry.ty },
+				);
+			}
+		}
+
+		Ok(Self { types, items })
+	}
+
+	/// Decode the top-level storage `key`.
+	pub fn decode_key(&self, key: &[u8]) -> Result<DecodedKey, codec::Error> {
+		let item = key
+			.get(..32)
+			.and_then(|prefix| self.items.get(prefix))
+			.ok_or("No storage item with this prefix")?;
+		let mut input = &key[32..];
+
+		let (keys, value_ty) = match &item.ty {
+			StorageEntryType::Plain(value) => (Vec::new(), value.id),
+			StorageEntryType::Map { hashers, key, value } => {
+				let key_tys = if hashers.len() == 1 {
+					vec![key.id]
+				} else {
+					match &self.resolve(key.id)?.type_def {
+						TypeDef::Tuple(tuple) if tuple.fields.len() == hashers.len() =>
+							tuple.fields.iter().map(|field| field.id).collect(),
+						_ => return Err("The map key does not match its hashers".into()),
+					}
+				};
+				let keys = hashers
+					.iter()
+					.zip(key_tys)
+					.map(|(hasher, ty)| self.decode_map_key(hasher, ty, &mut input))
+					.collect::<Result<Vec<_>, _>>()?;
+				(keys, value.id)
+			},
+		};
+		if !input.is_empty() {
+			return Err("Trailing bytes after the storage key".into())
+		}
+
+		Ok(DecodedKey { item, keys, value_ty })
+	}
+
+	/// Decode the `value` stored under `key`.
+	pub fn decode_value(&self, key: &DecodedKey, value: &[u8]) -> Result<Value, codec::Error> {
+		let mut input = value;
+		let value = self.decode_type(key.value_ty, &mut input)?;
+		if !input.is_empty() {
+			return Err("Trailing bytes after the storage value".into())
+		}
+
+		Ok(value)
+	}
+
+	fn decode_map_key(
+		&self,
+		hasher: &StorageHasher,
+		ty: u32,
+		input: &mut &[u8],
+	) -> Result<Value, codec::Error> {
+		let (hash_len, transparent) = match hasher {
+			StorageHasher::Blake2_128 | StorageHasher::Twox128 => (16, false),
+			StorageHasher::Blake2_256 | StorageHasher::Twox256 => (32, false),
+			StorageHasher::Blake2_128Concat => (16, true),
+			StorageHasher::Twox64Concat => (8, true),
+			StorageHasher::Identity => (0, true),
+		};
+		let hash = take(input, hash_len)?;
+		if !transparent {
+			return Ok(to_hex(hash, false).into())
+		}
+
+		self.decode_type(ty, input)
+	}
+
+	fn resolve(&self, ty: u32) -> Result<&scale_info::Type<PortableForm>, codec::Error> {
+		self.types.resolve(ty).ok_or_else(|| "Unknown type id".into())
+	}
+
+	fn decode_type(&self, ty: u32, input: &mut &[u8]) -> Result<Value, codec::Error> {
+		let ty = self.resolve(ty)?;
+		if ty.path.segments == ["sp_core", "crypto", "AccountId32"] {
+			return Ok(AccountId32::decode(input)?.to_ss58check().into())
+		}
+
+		match &ty.type_def {
+			TypeDef::Composite(composite) => self.decode_fields(&composite.fields, input),
+			TypeDef::Variant(variant) => {
+				let index = u8::decode(input)?;
+				let variant = variant
+					.variants
+					.iter()
+					.find(|variant| variant.index == index)
+					.ok_or("Unknown variant index")?;
+				if variant.fields.is_empty() {
+					return Ok(variant.name.clone().into())
+				}
+				let mut object = serde_json::Map::new();
+				object.insert(variant.name.clone(), self.decode_fields(&variant.fields, input)?);
+				Ok(object.into())
+			},
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				self.decode_items(sequence.type_param.id, len, input)
+			},
+			TypeDef::Array(array) => self.decode_items(array.type_param.id, array.len, input),
+			TypeDef::Tuple(tuple) => tuple
+				.fields
+				.iter()
+				.map(|field| self.decode_type(field.id, input))
+				.collect::<Result<Vec<_>, _>>()
+				.map(Value::Array),
+			TypeDef::Primitive(primitive) => decode_primitive(primitive, input),
+			TypeDef::Compact(_) => Ok(unsigned(Compact::<u128>::decode(input)?.0)),
+			TypeDef::BitSequence(_) => Err("Bit sequences are not supported".into()),
+		}
+	}
+
+	fn decode_fields(
+		&self,
+		fields: &[Field<PortableForm>],
+		input: &mut &[u8],
+	) -> Result<Value, codec::Error> {
+		match fields {
+			[] => Ok(Value::Null),
+			[field] if field.name.is_none() => self.decode_type(field.ty.id, input),
+			[field, ..] if field.name.is_some() => {
+				let mut object = serde_json::Map::new();
+				for field in fields {
+					let name = field.name.clone().unwrap_or_default();
+					object.insert(name, self.decode_type(field.ty.id, input)?);
+				}
+				Ok(object.into())
+			},
+			_ => fields
+				.iter()
+				.map(|field| self.decode_type(field.ty.id, input))
+				.collect::<Result<Vec<_>, _>>()
+				.map(Value::Array),
+		}
+	}
+
+	fn decode_items(&self, ty: u32, len: u32, input: &mut &[u8]) -> Result<Value, codec::Error> {
+		if matches!(self.resolve(ty)?.type_def, TypeDef::Primitive(TypeDefPrimitive::U8)) {
+			return Ok(to_hex(take(input, len as usize)?, false).into())
+		}
+
+		// The length is not trusted, so do not allocate for it upfront.
+		let mut items = Vec::new();
+		for _ in 0..len {
+			items.push(self.decode_type(ty, input)?);
+		}
+		Ok(Value::Array(items))
+	}
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], codec::Error> {
+	if input.len() < len {
+		return Err("Not enough data to fill buffer".into())
+	}
+	let (taken, rest) = input.split_at(len);
+	*input = rest;
+	Ok(taken)
+}
+
+fn unsigned(value: u128) -> Value {
+	u64::try_from(value)
+		.map(Value::from)
+		.unwrap_or_else(|_| value.to_string().into())
+}
+
+fn signed(value: i128) -> Value {
+	i64::try_from(value)
+		.map(Value::from)
+		.unwrap_or_else(|_| value.to_string().into())
+}
+
+fn decode_primitive(
+	primitive: &TypeDefPrimitive,
+	input: &mut &[u8],
+) -> Result<Value, codec::Error> {
+	Ok(match primitive {
+		TypeDefPrimitive::Bool => bool::decode(input)?.into(),
+		TypeDefPrimitive::Char =>
+			char::from_u32(u32::decode(input)?).ok_or("Invalid char")?.to_string().into(),
+		TypeDefPrimitive::Str => String::decode(input)?.into(),
+		TypeDefPrimitive::U8 => u8::decode(input)?.into(),
+		TypeDefPrimitive::U16 => u16::decode(input)?.into(),
+		TypeDefPrimitive::U32 => u32::decode(input)?.into(),
+		TypeDefPrimitive::U64 => u64::decode(input)?.into(),
+		TypeDefPrimitive::U128 => unsigned(u128::decode(input)?),
+		TypeDefPrimitive::U256 => sp_core::U256::from_little_endian(&<[u8; 32]>::decode(input)?)
+			.to_string()
+			.into(),
+		TypeDefPrimitive::I8 => i8::decode(input)?.into(),
+		TypeDefPrimitive::I16 => i16::decode(input)?.into(),
+		TypeDefPrimitive::I32 => i32::decode(input)?.into(),
+		TypeDefPrimitive::I64 => i64::decode(input)?.into(),
+		TypeDefPrimitive::I128 => signed(i128::decode(input)?),
+		TypeDefPrimitive::I256 => to_hex(&<[u8; 32]>::decode(input)?, false).into(),
+	})
+}
+
+/// List the differences between the decoded values `old` and `new`, appending them to `changes`.
+///
+/// Objects are compared field by field, such that each changed field is listed on its own with
+/// its path, e.g. `data.free 10 → 20`, while any other changed value is listed as `old → new`.
+pub fn diff_values(path: &str, old: &Value, new: &Value, changes: &mut Vec<String>) {
+	match (old, new) {
+		(Value::Object(old_fields), Value::Object(new_fields)) => {
+			let added = new_fields.keys().filter(|key| !old_fields.contains_key(*key));
+			for key in old_fields.keys().chain(added) {
+				let path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+				let old = old_fields.get(key).unwrap_or(&Value::Null);
+				let new = new_fields.get(key).unwrap_or(&Value::Null);
+				diff_values(&path, old, new, changes);
+			}
+		},
+		(old, new) if old == new => {},
+		(old, new) if path.is_empty() => changes.push(format!("{} → {}", old, new)),
+		(old, new) => changes.push(format!("{} {} → {}", path, old, new)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use scale_info::{meta_type, Registry};
+	use serde_json::json;
+
+	fn decoder<K: scale_info::TypeInfo + 'static, V: scale_info::TypeInfo + 'static>(
+	) -> StorageDecoder {
+		let mut registry = Registry::new();
+		let key = registry.register_type(&meta_type::<K>());
+		let value = registry.register_type(&meta_type::<V>());
+
+		let mut prefix = [0; 32];
+		prefix[..16].copy_from_slice(&twox_128(b"System"));
+		prefix[16..].copy_from_slice(&twox_128(b"Account"));
+		let item = StorageItem {
+			pallet: "System".into(),
+			name: "Account".into(),
+			ty: StorageEntryType::Map {
+				hashers: vec![StorageHasher::Blake2_128Concat],
+				key,
+				value,
+			},
+		};
+
+		StorageDecoder { types: registry.into(), items: [(prefix, item)].into_iter().collect() }
+	}
+
+	#[test]
+	fn decodes_map_entry() {
+		let decoder = decoder::<AccountId32, (u64, Option<Vec<u8>>)>();
+		let account = AccountId32::new([1; 32]);
+
+		let mut key = twox_128(b"System").to_vec();
+		key.extend(twox_128(b"Account"));
+		key.extend(sp_core::hashing::blake2_128(&account.encode()));
+		key.extend(account.encode());
+		let value = (10u64, Some(vec![1u8, 2])).encode();
+
+		let decoded = decoder.decode_key(&key).unwrap();
+		assert_eq!(decoded.item.pallet, "System");
+		assert_eq!(decoded.item.name, "Account");
+		assert_eq!(decoded.keys, vec![json!(account.to_ss58check())]);
+		assert_eq!(decoded.name(), format!("System.Account[{}]", account.to_ss58check()));
+		assert_eq!(
+			decoder.decode_value(&decoded, &value).unwrap(),
+			json!([10, { "Some": "0x0102" }])
+		);
+
+		// Trailing bytes in the key or the value do not decode.
+		let mut trailing = value.clone();
+		trailing.push(0);
+		assert!(decoder.decode_value(&decoded, &trailing).is_err());
+		key.push(0);
+		assert!(decoder.decode_key(&key).is_err());
+
+		assert!(decoder.decode_key(b"unknown").is_err());
+	}
+
+	#[test]
+	fn diff_values_lists_changed_fields() {
+		let mut changes = Vec::new();
+		diff_values(
+			"",
+			&json!({ "nonce": 0, "data": { "free": 10, "reserved": 0 } }),
+			&json!({ "nonce": 0, "data": { "free": 20, "reserved": 0 }, "sufficients": 1 }),
+			&mut changes,
+		);
+		assert_eq!(changes, vec!["data.free 10 → 20", "sufficients null → 1"]);
+
+		let mut changes = Vec::new();
+		diff_values("", &json!([10]), &json!([20]), &mut changes);
+		assert_eq!(changes, vec!["[10] → [20]"]);
+	}
+}
@@ -16,8 +16,9 @@
 // limitations under the License.
 
 use crate::{
-	build_executor, full_extensions, rpc_err_handler, state_machine_call_with_proof, LiveState,
-	SharedParams, State, LOG_TARGET,
+	build_executor, full_extensions, rpc_err_handler, state_machine_call_with_proof,
+	storage_diff::StorageDiff, LiveState, LocalState, SharedParams, State, StorageDiffFormat,
+	LOG_TARGET,
 };
 use parity_scale_codec::Encode;
 use sc_executor::sp_wasm_interface::HostFunctions;
@@ -53,32 +54,45 @@ pub struct ExecuteBlockCmd {
 	/// the only sensible combination. In other words, if you have the state of block `n`, you
 	/// should execute block `n+1` on top of it.
 	///
-	/// If `state` is `Live`, this can be ignored and the same uri is used for both.
+	/// If `state` is `Live`, this can be ignored and the same uri is used for both. If `state` is
+	/// `Local`, the block is read from the same database if this is not provided. It must be
+	/// provided if `state` is `Snap`.
 	#[arg(
 		long,
 		value_parser = crate::parse::url
 	)]
 	pub block_ws_uri: Option<String>,
 
+	/// Print the storage changes made by the block, decoded through the metadata of the runtime.
+	///
+	/// `human` logs one line per changed entry, or per changed field of an entry. `json` writes a
+	/// JSON array with the pallet, storage item, key and old and new value of each changed entry
+	/// to stdout.
+	#[arg(long, value_enum)]
+	pub storage_diff: Option<StorageDiffFormat>,
+
 	/// The state type to use.
 	#[command(subcommand)]
 	pub state: State,
 }
 
 impl ExecuteBlockCmd {
-	fn block_ws_uri<Block: BlockT>(&self) -> String
+	/// The uri to fetch the block from, `None` if it is read from the local database.
+	fn block_ws_uri<Block: BlockT>(&self) -> Option<String>
 	where
 		<Block::Hash as FromStr>::Err: Debug,
 	{
 		match (&self.block_ws_uri, &self.state) {
-			(Some(block_ws_uri), State::Snap { .. }) => block_ws_uri.to_owned(),
+			(Some(block_ws_uri), State::Snap { .. } | State::Local(_)) =>
+				Some(block_ws_uri.to_owned()),
 			(Some(block_ws_uri), State::Live { .. }) => {
 				log::error!(target: LOG_TARGET, "--block-uri is provided while state type is live, Are you sure you know what you are doing?");
-				block_ws_uri.to_owned()
+				Some(block_ws_uri.to_owned())
 			},
-			(None, State::Live(LiveState { uri, .. })) => uri.clone(),
+			(None, State::Live(LiveState { uri, .. })) => Some(uri.clone()),
+			(None, State::Local(_)) => None,
 			(None, State::Snap { .. }) => {
-				panic!("either `--block-uri` must be provided, or state must be `live`");
+				panic!("either `--block-uri` must be provided, or state must be `live` or `local`");
 			},
 		}
 	}
@@ -99,21 +113,29 @@ where
 	let executor = build_executor::<HostFns>(&shared);
 	let ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
 
-	// get the block number associated with this block.
-	let block_ws_uri = command.block_ws_uri::<Block>();
-	let rpc = ws_client(&block_ws_uri).await?;
-	let next_hash = next_hash_of::<Block>(&rpc, ext.block_hash).await?;
-
-	log::info!(target: LOG_TARGET, "fetching next block: {:?} ", next_hash);
-
-	let block = ChainApi::<(), Block::Hash, Block::Header, SignedBlock<Block>>::block(
-		&rpc,
-		Some(next_hash),
-	)
-	.await
-	.map_err(rpc_err_handler)?
-	.expect("header exists, block should also exist; qed")
-	.block;
+	let block = match (command.block_ws_uri::<Block>(), &command.state) {
+		(None, State::Local(LocalState { db_path, .. })) => {
+			log::info!(target: LOG_TARGET, "reading next block from the local database");
+			remote_externalities::next_local_block::<Block>(db_path, ext.block_hash)?
+		},
+		(Some(block_ws_uri), _) => {
+			// get the block number associated with this block.
+			let rpc = ws_client(&block_ws_uri).await?;
+			let next_hash = next_hash_of::<Block>(&rpc, ext.block_hash).await?;
+
+			log::info!(target: LOG_TARGET, "fetching next block: {:?} ", next_hash);
+
+			ChainApi::<(), Block::Hash, Block::Header, SignedBlock<Block>>::block(
+				&rpc,
+				Some(next_hash),
+			)
+			.await
+			.map_err(rpc_err_handler)?
+			.expect("header exists, block should also exist; qed")
+			.block
+		},
+		(None, _) => unreachable!("only the local state has no block uri; qed"),
+	};
 
 	// A digest item gets added when the runtime is processing the block, so we need to pop
 	// the last one to be consistent with what a gossiped block would contain.
@@ -126,7 +148,7 @@ where
 	let signature_check = false;
 	let payload = (block.clone(), state_root_check, signature_check, command.try_state).encode();
 
-	let _ = state_machine_call_with_proof::<Block, HostFns>(
+	let (changes, _) = state_machine_call_with_proof::<Block, HostFns>(
 		&ext,
 		&executor,
 		"TryRuntime_execute_block",
@@ -135,6 +157,10 @@ where
 		shared.export_proof,
 	)?;
 
+	if let Some(format) = command.storage_diff {
+		StorageDiff::print::<Block, HostFns>(&ext, &executor, &changes, format)?;
+	}
+
 	Ok(())
 }
 
@@ -0,0 +1,128 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	build_executor, full_extensions, state_machine_call_with_proof,
+	storage_diff::{StorageDecoder, StorageDiff},
+	SharedParams, State, StorageDiffFormat, LOG_TARGET,
+};
+use frame_try_runtime::{CallOrigin, CallOutcome};
+use parity_scale_codec::{Decode, Encode};
+use sc_executor::sp_wasm_interface::HostFunctions;
+use serde_json::Value;
+use sp_runtime::{
+	traits::{Block as BlockT, NumberFor},
+	TryRuntimeError,
+};
+use std::{fmt::Debug, str::FromStr};
+
+/// Configurations of the [`crate::Command::ExecuteCall`].
+///
+/// This will always call into `TryRuntime_execute_call`, which dispatches the call on top of the
+/// state without any transaction checks, as it would be enacted by e.g. governance.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExecuteCallCmd {
+	/// The SCALE encoded call to dispatch, as hex.
+	#[arg(long)]
+	pub call: String,
+
+	/// The origin to dispatch the call from.
+	///
+	/// Either `root`, or the account to sign the call with, as SS58 address or as hex of its
+	/// SCALE encoding.
+	#[arg(long, default_value = "root", value_parser = crate::parse::call_origin)]
+	pub origin: CallOrigin,
+
+	/// The format to print the storage changes of the call in.
+	#[arg(long, value_enum, default_value_t = StorageDiffFormat::Human)]
+	pub storage_diff: StorageDiffFormat,
+
+	/// The state type to use.
+	#[command(subcommand)]
+	pub state: State,
+}
+
+pub(crate) async fn execute_call<Block, HostFns>(
+	shared: SharedParams,
+	command: ExecuteCallCmd,
+) -> sc_cli::Result<()>
+where
+	Block: BlockT + serde::de::DeserializeOwned,
+	<Block::Hash as FromStr>::Err: Debug,
+	Block::Header: serde::de::DeserializeOwned,
+	NumberFor<Block>: FromStr,
+	<NumberFor<Block> as FromStr>::Err: Debug,
+	HostFns: HostFunctions,
+{
+	let call = hex::decode(command.call.trim_start_matches("0x"))
+		.map_err(|e| format!("call is not valid hex: {:?}", e))?;
+
+	let executor = build_executor(&shared);
+	let ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
+
+	let (changes, encoded_result) = state_machine_call_with_proof::<Block, HostFns>(
+		&ext,
+		&executor,
+		"TryRuntime_execute_call",
+		(call, command.origin).encode().as_ref(),
+		full_extensions(executor.clone()),
+		shared.export_proof,
+	)?;
+
+	let CallOutcome { result, info, fee } =
+		<Result<CallOutcome, TryRuntimeError> as Decode>::decode(&mut &*encoded_result)
+			.map_err(|e| format!("failed to decode call outcome: {:?}", e))?
+			.map_err(|e| format!("failed to execute the call: {:?}", e))?;
+
+	let (actual_weight, result) = match result {
+		Ok(post_info) => (post_info.calc_actual_weight(&info), Ok(())),
+		Err(e) => (e.post_info.calc_actual_weight(&info), Err(e.error)),
+	};
+	log::info!(target: LOG_TARGET, "dispatch result: {:?}", result);
+	log::info!(
+		target: LOG_TARGET,
+		"weight: ({} ps, {} byte), declared: ({} ps, {} byte), class: {:?}, pays fee: {:?}",
+		actual_weight.ref_time(),
+		actual_weight.proof_size(),
+		info.weight.ref_time(),
+		info.weight.proof_size(),
+		info.class,
+		info.pays_fee,
+	);
+	log::info!(target: LOG_TARGET, "fee if submitted as a transaction: {}", fee);
+
+	let decoder = StorageDecoder::new::<Block, HostFns>(&ext, &executor)?;
+	let mut diff = StorageDiff::new::<Block>(&decoder, &ext, &changes)?;
+
+	// `TryRuntime_execute_call` clears the events first, so these are all emitted by the call.
+	let events = diff.find("System", "Events").and_then(|change| change.new.clone());
+	match events {
+		Some(Value::Array(records)) =>
+			for record in records {
+				log::info!(target: LOG_TARGET, "event: {}", record.get("event").unwrap_or(&record));
+			},
+		Some(events) => log::info!(target: LOG_TARGET, "events (undecoded): {}", events),
+		None => log::info!(target: LOG_TARGET, "no events emitted"),
+	}
+
+	// The events of the state were cleared to record the ones of the call, which is not a change
+	// made by the call.
+	diff.remove("System", &["Events", "EventCount", "EventTopics"]);
+	diff.print_as(command.storage_diff);
+
+	Ok(())
+}
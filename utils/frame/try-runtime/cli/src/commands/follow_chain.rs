@@ -17,7 +17,7 @@
 
 use crate::{
 	build_executor, full_extensions, parse, rpc_err_handler, state_machine_call_with_proof,
-	LiveState, SharedParams, State, LOG_TARGET,
+	LiveState, LocalState, SharedParams, State, LOG_TARGET,
 };
 use parity_scale_codec::{Decode, Encode};
 use sc_executor::sp_wasm_interface::HostFunctions;
@@ -27,7 +27,7 @@ use sp_runtime::{
 	generic::SignedBlock,
 	traits::{Block as BlockT, Header as HeaderT, NumberFor},
 };
-use std::{fmt::Debug, str::FromStr};
+use std::{fmt::Debug, path::PathBuf, str::FromStr};
 use substrate_rpc_client::{ws_client, ChainApi, FinalizedHeaders, Subscription, WsClient};
 
 const SUB: &str = "chain_subscribeFinalizedHeads";
@@ -59,6 +59,13 @@ pub struct FollowChainCmd {
 	/// If present, a single connection to a node will be kept and reused for fetching blocks.
 	#[arg(long)]
 	pub keep_connection: bool,
+
+	/// The path to the database of a local node of the same chain. It is opened read-only.
+	///
+	/// If present, the initial state is read from the latest finalized block of this database,
+	/// instead of from the remote node, and all the blocks finalized after it are executed.
+	#[arg(long)]
+	pub local_db: Option<PathBuf>,
 }
 
 /// Start listening for with `SUB` at `url`.
@@ -91,12 +98,22 @@ where
 	HostFns: HostFunctions,
 {
 	let (rpc, subscription) = start_subscribing::<Block::Header>(&command.uri).await?;
-	let mut finalized_headers: FinalizedHeaders<Block, _, _> =
-		FinalizedHeaders::new(&rpc, subscription);
 
 	let mut maybe_state_ext = None;
 	let executor = build_executor::<HostFns>(&shared);
 
+	// With a local database, start from its state and catch up with the remote node.
+	let mut finalized_headers: FinalizedHeaders<Block, _, _> = match command.local_db {
+		Some(ref db_path) => {
+			let state = State::Local(LocalState { db_path: db_path.clone(), at: None });
+			let ext = state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
+			let finalized_headers = FinalizedHeaders::new_after(&rpc, subscription, ext.block_hash);
+			maybe_state_ext = Some(ext);
+			finalized_headers
+		},
+		None => FinalizedHeaders::new(&rpc, subscription),
+	};
+
 	while let Some(header) = finalized_headers.next().await {
 		let hash = header.hash();
 		let number = header.number();
@@ -17,6 +17,7 @@
 
 pub mod create_snapshot;
 pub mod execute_block;
+pub mod execute_call;
 pub mod fast_forward;
 pub mod follow_chain;
 pub mod offchain_worker;
@@ -17,7 +17,7 @@
 
 use crate::{
 	build_executor, commands::execute_block::next_hash_of, full_extensions, parse, rpc_err_handler,
-	state_machine_call, LiveState, SharedParams, State, LOG_TARGET,
+	state_machine_call, LiveState, LocalState, SharedParams, State, LOG_TARGET,
 };
 use parity_scale_codec::Encode;
 use sc_executor::sp_wasm_interface::HostFunctions;
@@ -31,7 +31,8 @@ pub struct OffchainWorkerCmd {
 	/// The ws uri from which to fetch the header.
 	///
 	/// If the `live` state type is being used, then this can be omitted, and is equal to whatever
-	/// the `state::uri` is. Only use this (with care) when combined with a snapshot.
+	/// the `state::uri` is. If the `local` state type is being used, the header is read from the
+	/// same database if this is omitted. Only use this (with care) when combined with a snapshot.
 	#[arg(
 		long,
 		value_parser = parse::url
@@ -44,19 +45,24 @@ pub struct OffchainWorkerCmd {
 }
 
 impl OffchainWorkerCmd {
-	fn header_ws_uri<Block: BlockT>(&self) -> String
+	/// The uri to fetch the header from, `None` if it is read from the local database.
+	fn header_ws_uri<Block: BlockT>(&self) -> Option<String>
 	where
 		<Block::Hash as FromStr>::Err: Debug,
 	{
 		match (&self.header_ws_uri, &self.state) {
-			(Some(header_ws_uri), State::Snap { .. }) => header_ws_uri.to_owned(),
+			(Some(header_ws_uri), State::Snap { .. } | State::Local(_)) =>
+				Some(header_ws_uri.to_owned()),
 			(Some(header_ws_uri), State::Live { .. }) => {
 				log::error!(target: LOG_TARGET, "--header-uri is provided while state type is live, this will most likely lead to a nonsensical result.");
-				header_ws_uri.to_owned()
+				Some(header_ws_uri.to_owned())
 			},
-			(None, State::Live(LiveState { uri, .. })) => uri.clone(),
+			(None, State::Live(LiveState { uri, .. })) => Some(uri.clone()),
+			(None, State::Local(_)) => None,
 			(None, State::Snap { .. }) => {
-				panic!("either `--header-uri` must be provided, or state must be `live`");
+				panic!(
+					"either `--header-uri` must be provided, or state must be `live` or `local`"
+				);
 			},
 		}
 	}
@@ -78,16 +84,25 @@ where
 	// we first build the externalities with the remote code.
 	let ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
 
-	let header_ws_uri = command.header_ws_uri::<Block>();
+	let header = match (command.header_ws_uri::<Block>(), &command.state) {
+		(None, State::Local(LocalState { db_path, .. })) => {
+			log::info!(target: LOG_TARGET, "reading next header from the local database");
+			remote_externalities::next_local_block::<Block>(db_path, ext.block_hash)?
+				.deconstruct()
+				.0
+		},
+		(Some(header_ws_uri), _) => {
+			let rpc = ws_client(&header_ws_uri).await?;
+			let next_hash = next_hash_of::<Block>(&rpc, ext.block_hash).await?;
+			log::info!(target: LOG_TARGET, "fetching next header: {:?} ", next_hash);
 
-	let rpc = ws_client(&header_ws_uri).await?;
-	let next_hash = next_hash_of::<Block>(&rpc, ext.block_hash).await?;
-	log::info!(target: LOG_TARGET, "fetching next header: {:?} ", next_hash);
-
-	let header = ChainApi::<(), Block::Hash, Block::Header, ()>::header(&rpc, Some(next_hash))
-		.await
-		.map_err(rpc_err_handler)
-		.map(|maybe_header| maybe_header.ok_or("Header does not exist"))??;
+			ChainApi::<(), Block::Hash, Block::Header, ()>::header(&rpc, Some(next_hash))
+				.await
+				.map_err(rpc_err_handler)
+				.map(|maybe_header| maybe_header.ok_or("Header does not exist"))??
+		},
+		(None, _) => unreachable!("only the local state has no header uri; qed"),
+	};
 	let payload = header.encode();
 
 	let _ = state_machine_call::<Block, HostFns>(
@@ -15,7 +15,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{build_executor, state_machine_call_with_proof, SharedParams, State, LOG_TARGET};
+use crate::{
+	build_executor, state_machine_call_with_proof, storage_diff::StorageDiff, SharedParams, State,
+	StorageDiffFormat, LOG_TARGET,
+};
 use frame_try_runtime::UpgradeCheckSelect;
 use parity_scale_codec::{Decode, Encode};
 use sc_executor::sp_wasm_interface::HostFunctions;
@@ -37,6 +40,8 @@ pub struct OnRuntimeUpgradeCmd {
 	/// - `pre-and-post`: Perform pre- and post-upgrade checks (default when the arg is not
 	///   present).
 	/// - `try-state`: Perform the try-state checks.
+	/// - `decode-entire-state`: Decode every storage value into its declared type after the
+	///   upgrade, reporting the keys of the values that fail to decode.
 	///
 	/// Performing any checks will potentially invalidate the measured PoV/Weight.
 	// NOTE: The clap attributes make it backwards compatible with the previous `--checks` flag.
@@ -47,6 +52,16 @@ pub struct OnRuntimeUpgradeCmd {
 		require_equals = true,
 		verbatim_doc_comment)]
 	pub checks: UpgradeCheckSelect,
+
+	/// Print the storage changes made by the upgrade, decoded through the metadata of the new
+	/// runtime.
+	///
+	/// `human` logs one line per changed entry, or per changed field of an entry. `json` writes a
+	/// JSON array with the pallet, storage item, key and old and new value of each changed entry
+	/// to stdout. Old values that do not decode into the type declared by the new runtime are
+	/// printed as hex.
+	#[arg(long, value_enum)]
+	pub storage_diff: Option<StorageDiffFormat>,
 }
 
 pub(crate) async fn on_runtime_upgrade<Block, HostFns>(
@@ -64,7 +79,7 @@ where
 	let executor = build_executor(&shared);
 	let ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
 
-	let (_, encoded_result) = state_machine_call_with_proof::<Block, HostFns>(
+	let (changes, encoded_result) = state_machine_call_with_proof::<Block, HostFns>(
 		&ext,
 		&executor,
 		"TryRuntime_on_runtime_upgrade",
@@ -85,5 +100,9 @@ where
 		(weight.proof_size() as f64 / total_weight.proof_size().max(1) as f64) * 100.0,
 	);
 
+	if let Some(format) = command.storage_diff {
+		StorageDiff::print::<Block, HostFns>(&ext, &executor, &changes, format)?;
+	}
+
 	Ok(())
 }
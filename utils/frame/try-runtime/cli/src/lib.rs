@@ -27,7 +27,7 @@
 use crate::block_building_info::BlockBuildingInfoProvider;
 use parity_scale_codec::Decode;
 use remote_externalities::{
-	Builder, Mode, OfflineConfig, OnlineConfig, RemoteExternalities, SnapshotConfig,
+	Builder, LocalConfig, Mode, OfflineConfig, OnlineConfig, RemoteExternalities, SnapshotConfig,
 };
 use sc_cli::{
 	execution_method_from_cli, CliConfiguration, RuntimeVersion, WasmExecutionMethod,
@@ -63,6 +63,7 @@ use std::{fmt::Debug, path::PathBuf, str::FromStr};
 pub mod block_building_info;
 pub mod commands;
 pub(crate) mod parse;
+pub(crate) mod storage_diff;
 pub(crate) const LOG_TARGET: &str = "try-runtime::cli";
 
 /// Possible commands of `try-runtime`.
@@ -88,6 +89,17 @@ pub enum Command {
 	/// more information.
 	ExecuteBlock(commands::execute_block::ExecuteBlockCmd),
 
+	/// Dispatches the given call with the given origin against some state.
+	///
+	/// This uses a custom runtime api call, namely "TryRuntime_execute_call". The dispatch result,
+	/// weight, fee, emitted events and storage changes of the call are printed, decoded through the
+	/// metadata of the runtime. This allows e.g. governance proposals to be rehearsed before they
+	/// are enacted.
+	///
+	/// See [`frame_try_runtime::TryRuntime`] and [`commands::execute_call::ExecuteCallCmd`] for
+	/// more information.
+	ExecuteCall(commands::execute_call::ExecuteCallCmd),
+
 	/// Executes *the offchain worker hooks* of a given block against some state.
 	///
 	/// This executes the same runtime api as normal block import, namely
@@ -110,7 +122,9 @@ pub enum Command {
 	/// This does not support snapshot states, and can only work with a remote chain. Upon first
 	/// connections, starts listening for finalized block events. Upon first block notification, it
 	/// initializes the state from the remote node, and starts applying that block, plus all the
-	/// blocks that follow, to the same growing state.
+	/// blocks that follow, to the same growing state. Alternatively, the initial state can be read
+	/// from the database of a local node, in which case all the blocks finalized since are applied
+	/// first.
 	///
 	/// This can only work if the block format between the remote chain and the new runtime being
 	/// tested has remained the same, otherwise block decoding might fail.
@@ -154,6 +168,15 @@ impl FromStr for Runtime {
 	}
 }
 
+/// The format to print the storage changes of a command in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StorageDiffFormat {
+	/// One line per changed entry, or per changed field of an entry.
+	Human,
+	/// A JSON array with one object per changed entry, written to stdout.
+	Json,
+}
+
 /// Shared parameters of the `try-runtime` commands
 #[derive(Debug, Clone, clap::Parser)]
 #[group(skip)]
@@ -263,6 +286,26 @@ pub struct LiveState {
 	child_tree: bool,
 }
 
+/// A `Local` variant [`State`]
+#[derive(Debug, Clone, clap::Args)]
+pub struct LocalState {
+	/// The path to the database of the node, e.g. `<base-path>/chains/<chain>/db/full`.
+	///
+	/// The database is opened read-only, so the node may be running.
+	#[arg(short, long)]
+	db_path: PathBuf,
+
+	/// The block hash at which to read the state.
+	///
+	/// If non provided, then the latest finalized block of the database is used.
+	#[arg(
+		short,
+		long,
+		value_parser = parse::hash,
+	)]
+	at: Option<String>,
+}
+
 /// The source of runtime *state* to use.
 #[derive(Debug, Clone, clap::Subcommand)]
 pub enum State {
@@ -274,6 +317,9 @@ pub enum State {
 
 	/// Use a live chain as the source of runtime state.
 	Live(LiveState),
+
+	/// Use the database of a local node as the source of runtime state.
+	Local(LocalState),
 }
 
 impl State {
@@ -321,6 +367,13 @@ impl State {
 					hashed_prefixes: vec![],
 				}))
 			},
+			State::Local(LocalState { db_path, at }) => {
+				let at = match at {
+					Some(at_str) => Some(hash_of::<Block>(at_str)?),
+					None => None,
+				};
+				Builder::<Block>::new().mode(Mode::Local(LocalConfig { path: db_path.clone(), at }))
+			},
 		};
 
 		// possibly overwrite the state version, should hardly be needed.
@@ -422,6 +475,12 @@ impl TryRuntimeCmd {
 					cmd.clone(),
 				)
 				.await,
+			Command::ExecuteCall(cmd) =>
+				commands::execute_call::execute_call::<Block, HostFns>(
+					self.shared.clone(),
+					cmd.clone(),
+				)
+				.await,
 			Command::OffchainWorker(cmd) =>
 				commands::offchain_worker::offchain_worker::<Block, HostFns>(
 					self.shared.clone(),
@@ -17,6 +17,9 @@
 
 //! Utils for parsing user input
 
+use frame_try_runtime::CallOrigin;
+use parity_scale_codec::Encode;
+use sp_core::crypto::{AccountId32, Ss58Codec};
 use sp_version::StateVersion;
 
 pub(crate) fn hash(block_hash: &str) -> Result<String, String> {
@@ -51,3 +54,17 @@ pub(crate) fn state_version(s: &str) -> Result<StateVersion, &'static str> {
 		.and_then(StateVersion::try_from)
 		.map_err(|_| "Invalid state version.")
 }
+
+pub(crate) fn call_origin(s: &str) -> Result<CallOrigin, String> {
+	if s == "root" {
+		Ok(CallOrigin::Root)
+	} else if let Some(account) = s.strip_prefix("0x") {
+		hex::decode(account)
+			.map(CallOrigin::Signed)
+			.map_err(|e| format!("Invalid hex account: {}", e))
+	} else {
+		AccountId32::from_ss58check(s)
+			.map(|account| CallOrigin::Signed(account.encode()))
+			.map_err(|e| format!("Invalid SS58 account: {:?}", e))
+	}
+}
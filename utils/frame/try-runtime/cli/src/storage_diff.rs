@@ -0,0 +1,352 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage changes, decoded through the metadata of the runtime.
+
+use crate::{state_machine_call, StorageDiffFormat, LOG_TARGET};
+use frame_storage_decoder::{diff_values, entry_name};
+use sc_executor::{sp_wasm_interface::HostFunctions, WasmExecutor};
+use serde_json::{json, Value};
+use sp_core::{bytes::to_hex, storage::well_known_keys};
+use sp_runtime::traits::{Block as BlockT, HashingFor};
+use sp_state_machine::{Backend, OverlayedChanges, TestExternalities};
+use std::fmt;
+
+/// Decodes storage keys and values using the metadata of a runtime.
+pub(crate) struct StorageDecoder(frame_storage_decoder::StorageDecoder);
+
+impl StorageDecoder {
+	/// Create a new instance with the metadata of the runtime in `ext`.
+	pub(crate) fn new<Block: BlockT, HostFns: HostFunctions>(
+		ext: &TestExternalities<HashingFor<Block>>,
+		executor: &WasmExecutor<HostFns>,
+	) -> sc_cli::Result<Self> {
+		let (_, encoded) = state_machine_call::<Block, HostFns>(
+			ext,
+			executor,
+			"Metadata_metadata",
+			&[],
+			Default::default(),
+		)?;
+		Ok(Self(frame_storage_decoder::StorageDecoder::new(&encoded)?))
+	}
+
+	/// Decode the change of the top-level storage entry `key` from `old` to `new`.
+	///
+	/// Keys and values that can not be decoded are kept as hex.
+	pub(crate) fn decode_change(
+		&self,
+		key: &[u8],
+		old: Option<&[u8]>,
+		new: Option<&[u8]>,
+	) -> StorageChange {
+		let mut change = StorageChange {
+			child: None,
+			key: key.to_vec(),
+			pallet: None,
+			item: None,
+			keys: Vec::new(),
+			old: old.map(|value| to_hex(value, false).into()),
+			new: new.map(|value| to_hex(value, false).into()),
+		};
+
+		if key == well_known_keys::CODE {
+			let code =
+				|value: &[u8]| Value::from(format!("<{} bytes of runtime code>", value.len()));
+			change.old = old.map(code);
+			change.new = new.map(code);
+			return change
+		}
+
+		let Ok(decoded) = self.0.decode_key(key) else { return change };
+		let decode_value = |value: &[u8]| {
+			self.0
+				.decode_value(&decoded, value)
+				.unwrap_or_else(|_| to_hex(value, false).into())
+		};
+		change.old = old.map(decode_value);
+		change.new = new.map(decode_value);
+		change.pallet = Some(decoded.item.pallet.clone());
+		change.item = Some(decoded.item.name.clone());
+		change.keys = decoded.keys;
+		change
+	}
+}
+
+/// The change of a single storage entry.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct StorageChange {
+	/// The storage key of the child trie of the entry, `None` for the top-level storage.
+	///
+	/// Child tries are not described by the metadata, so their entries are never decoded.
+	pub child: Option<Vec<u8>>,
+	/// The raw storage key.
+	pub key: Vec<u8>,
+	/// The pallet of the storage item, if the key is declared in the metadata.
+	pub pallet: Option<String>,
+	/// The name of the storage item, if the key is declared in the metadata.
+	pub item: Option<String>,
+	/// The decoded keys of a map entry.
+	pub keys: Vec<Value>,
+	/// The value before the change, `None` if the entry did not exist.
+	pub old: Option<Value>,
+	/// The value after the change, `None` if the entry was removed.
+	pub new: Option<Value>,
+}
+
+impl StorageChange {
+	/// The change of the entry `key` of the child trie `child` from `old` to `new`.
+	fn child(child: &[u8], key: &[u8], old: Option<&[u8]>, new: Option<&[u8]>) -> Self {
+		Self {
+			child: Some(child.to_vec()),
+			key: key.to_vec(),
+			pallet: None,
+			item: None,
+			keys: Vec::new(),
+			old: old.map(|value| to_hex(value, false).into()),
+			new: new.map(|value| to_hex(value, false).into()),
+		}
+	}
+
+	/// The name of the entry, e.g.
+	/// `System.Account[5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY]`, its raw key as hex if
+	/// it is not declared in the metadata, or `0x<child>[0x<key>]` for an entry of a child trie.
+	pub(crate) fn name(&self) -> String {
+		if let Some(child) = &self.child {
+			return format!("{}[{}]", to_hex(child, false), to_hex(&self.key, false))
+		}
+		match (&self.pallet, &self.item) {
+			(Some(pallet), Some(item)) => entry_name(pallet, item, &self.keys),
+			_ => to_hex(&self.key, false),
+		}
+	}
+
+	/// The change as a JSON object, with the raw and the decoded key and the old and new value.
+	pub(crate) fn to_json(&self) -> Value {
+		json!({
+			"child": self.child.as_ref().map(|child| to_hex(child, false)),
+			"key": to_hex(&self.key, false),
+			"pallet": self.pallet,
+			"item": self.item,
+			"keys": self.keys,
+			"old": self.old,
+			"new": self.new,
+		})
+	}
+}
+
+impl fmt::Display for StorageChange {
+	/// One line per change: `+` for an added entry, `-` for a removed entry and `~` for each
+	/// changed field of an entry, e.g. `~ System.Account[..]: data.free 10 → 20`.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let name = self.name();
+		match (&self.old, &self.new) {
+			(None, None) => Ok(()),
+			(None, Some(new)) => write!(f, "+ {}: {}", name, new),
+			(Some(old), None) => write!(f, "- {}: {}", name, old),
+			(Some(old), Some(new)) => {
+				let mut fields = Vec::new();
+				diff_values("", old, new, &mut fields);
+				let lines = fields.into_iter().map(|field| format!("~ {}: {}", name, field));
+				write!(f, "{}", lines.collect::<Vec<_>>().join("\n"))
+			},
+		}
+	}
+}
+
+/// The changes made to the storage by a runtime call.
+pub(crate) struct StorageDiff {
+	/// The changed entries of the top-level storage first, ordered by their raw key, followed by
+	/// the changed entries of each child trie.
+	pub changes: Vec<StorageChange>,
+}
+
+impl StorageDiff {
+	/// Collect the storage `changes` made on top of the state of `ext`, including the changes of
+	/// child tries.
+	///
+	/// Entries that were written with their previous value are not included.
+	pub(crate) fn new<Block: BlockT>(
+		decoder: &StorageDecoder,
+		ext: &TestExternalities<HashingFor<Block>>,
+		changes: &OverlayedChanges<HashingFor<Block>>,
+	) -> sc_cli::Result<Self> {
+		let mut diff = Vec::new();
+		for (key, value) in changes.changes() {
+			let old = ext
+				.backend
+				.storage(key)
+				.map_err(|e| format!("failed to read storage key {}: {}", to_hex(key, false), e))?;
+			let new = value.value();
+			if old.as_ref() != new {
+				diff.push(decoder.decode_change(key, old.as_deref(), new.map(|v| &v[..])));
+			}
+		}
+
+		for (child_changes, child_info) in changes.children() {
+			let child = child_info.storage_key();
+			for (key, value) in child_changes {
+				let old = ext.backend.child_storage(child_info, key).map_err(|e| {
+					format!(
+						"failed to read key {} of child trie {}: {}",
+						to_hex(key, false),
+						to_hex(child, false),
+						e
+					)
+				})?;
+				let new = value.value();
+				if old.as_ref() != new {
+					diff.push(StorageChange::child(
+						child,
+						key,
+						old.as_deref(),
+						new.map(|v| &v[..]),
+					));
+				}
+			}
+		}
+
+		Ok(Self { changes: diff })
+	}
+
+	/// Collect the storage `changes` made on top of the state of `ext`, decoded through the
+	/// metadata of the runtime in `ext`, and print them in the given `format`.
+	///
+	/// Values that were written by a previous runtime in a different format do not decode and
+	/// are printed as hex.
+	pub(crate) fn print<Block: BlockT, HostFns: HostFunctions>(
+		ext: &TestExternalities<HashingFor<Block>>,
+		executor: &WasmExecutor<HostFns>,
+		changes: &OverlayedChanges<HashingFor<Block>>,
+		format: StorageDiffFormat,
+	) -> sc_cli::Result<()> {
+		let decoder = StorageDecoder::new::<Block, HostFns>(ext, executor)?;
+		Self::new::<Block>(&decoder, ext, changes)?.print_as(format);
+		Ok(())
+	}
+
+	/// Print the changes in the given `format`.
+	///
+	/// The human-readable diff is logged, while the JSON diff is written to stdout, such that it
+	/// can be piped into other tools.
+	pub(crate) fn print_as(&self, format: StorageDiffFormat) {
+		match format {
+			StorageDiffFormat::Human =>
+				log::info!(target: LOG_TARGET, "{} storage changes:\n{}", self.changes.len(), self),
+			StorageDiffFormat::Json => println!("{:#}", self.to_json()),
+		}
+	}
+
+	/// The changes as a JSON array.
+	pub(crate) fn to_json(&self) -> Value {
+		Value::Array(self.changes.iter().map(StorageChange::to_json).collect())
+	}
+
+	/// Find the change of a plain storage value.
+	pub(crate) fn find(&self, pallet: &str, item: &str) -> Option<&StorageChange> {
+		self.changes.iter().find(|change| {
+			change.pallet.as_deref() == Some(pallet) && change.item.as_deref() == Some(item)
+		})
+	}
+
+	/// Remove the changes of the given `items` of `pallet`, including all entries of maps.
+	pub(crate) fn remove(&mut self, pallet: &str, items: &[&str]) {
+		self.changes.retain(|change| {
+			change.pallet.as_deref() != Some(pallet) ||
+				!change.item.as_deref().map_or(false, |item| items.contains(&item))
+		});
+	}
+}
+
+impl fmt::Display for StorageDiff {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let lines = self.changes.iter().map(ToString::to_string).collect::<Vec<_>>();
+		write!(f, "{}", lines.join("\n"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn account_change(old: Option<Value>, new: Option<Value>) -> StorageChange {
+		StorageChange {
+			child: None,
+			key: vec![1],
+			pallet: Some("System".into()),
+			item: Some("Account".into()),
+			keys: vec![json!("alice")],
+			old,
+			new,
+		}
+	}
+
+	#[test]
+	fn displays_changes() {
+		let change = account_change(
+			Some(json!({ "nonce": 0, "data": { "free": 10 } })),
+			Some(json!({ "nonce": 1, "data": { "free": 20 } })),
+		);
+		assert_eq!(
+			change.to_string(),
+			"~ System.Account[alice]: data.free 10 → 20\n~ System.Account[alice]: nonce 0 → 1"
+		);
+
+		let change = account_change(Some(json!(1)), None);
+		assert_eq!(change.to_string(), "- System.Account[alice]: 1");
+
+		// Keys that are not declared in the metadata are named by their raw key.
+		let change = StorageChange {
+			pallet: None,
+			item: None,
+			keys: Vec::new(),
+			..account_change(None, Some(json!("0x03")))
+		};
+		assert_eq!(change.to_string(), "+ 0x01: \"0x03\"");
+	}
+
+	#[test]
+	fn child_changes_are_not_decoded() {
+		let change = StorageChange::child(b"child", &[1, 2], None, Some(&[3]));
+		assert_eq!(change.to_string(), "+ 0x6368696c64[0x0102]: \"0x03\"");
+		assert_eq!(
+			change.to_json(),
+			json!({
+				"child": "0x6368696c64",
+				"key": "0x0102",
+				"pallet": null,
+				"item": null,
+				"keys": [],
+				"old": null,
+				"new": "0x03",
+			})
+		);
+	}
+
+	#[test]
+	fn removes_changes_of_items() {
+		let known_change = account_change(None, Some(json!(1)));
+		let unknown_change = StorageChange { pallet: None, item: None, ..known_change.clone() };
+		let mut diff = StorageDiff { changes: vec![known_change, unknown_change.clone()] };
+
+		diff.remove("System", &["Events"]);
+		assert_eq!(diff.changes.len(), 2);
+
+		diff.remove("System", &["Events", "Account"]);
+		assert_eq!(diff.changes, vec![unknown_change]);
+	}
+}